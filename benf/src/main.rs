@@ -8,7 +8,7 @@ use lawkit_core::{
         statistics,
         streaming_io::OptimizedFileReader,
     },
-    laws::benford::BenfordResult,
+    laws::benford::{BenfordResult, DigitTest, DigitTestResult},
 };
 use std::str::FromStr;
 
@@ -224,7 +224,11 @@ fn convert_incremental_to_result(
         30.103, 17.609, 12.494, 9.691, 7.918, 6.695, 5.799, 5.115, 4.576,
     ];
 
-    let chi_square = statistics::calculate_chi_square(&digit_distribution, &expected_distribution);
+    let chi_square = statistics::calculate_chi_square_from_percentages(
+        &digit_distribution,
+        &expected_distribution,
+        incremental.total_count(),
+    );
     let p_value = statistics::calculate_p_value(chi_square, 8);
     let mad = incremental.calculate_mad();
 
@@ -250,6 +254,16 @@ fn convert_incremental_to_result(
         mean_absolute_deviation: mad,
         risk_level,
         verdict,
+        conformity: DigitTest::First.conformity(mad),
+        z_statistics: DigitTestResult::from_counts(
+            DigitTest::First,
+            incremental.get_counts(),
+            &RiskThreshold::Auto,
+            0.05,
+        )
+        .z_statistics,
+        digit_tests: Vec::new(),
+        drill_down: Vec::new(),
     }
 }
 
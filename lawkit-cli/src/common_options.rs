@@ -1,5 +1,23 @@
-use clap::{Arg, Command};
-use lawkit_core::common::{memory::MemoryConfig, parallel::ParallelConfig};
+use clap::{Arg, ArgMatches, Command};
+use lawkit_core::common::{
+    batch::{analyze_batch, analyze_batch_members, expand_batch_input, BatchAnalysis},
+    filtering::NumberFilter,
+    grouping::GroupResult,
+    input::{
+        is_packed, parse_input_records_auto_with, parse_text_records_with,
+        selector::{parse_key_regex, KeySelection},
+        InputOptions, NumberRecord,
+    },
+    memory::MemoryConfig,
+    output::formatter::{format_output, OutputFormat},
+    parallel::ParallelConfig,
+    streaming_io::OptimizedFileReader,
+};
+use lawkit_core::error::BenfError;
+use lawkit_core::laws::pareto::PowerLawOptions;
+use lawkit_core::laws::zipf::{
+    builtin_tokenizer, parse_stopwords, TextTokenizer, TokenizerOptions,
+};
 
 /// 全サブコマンドで共通のオプションを定義
 pub fn add_common_options(cmd: Command) -> Command {
@@ -71,13 +89,263 @@ pub fn add_generate_common_options(cmd: Command) -> Command {
     )
 }
 
-/// input引数を追加（位置引数）
+/// input引数を追加（位置引数）と入力の選択（表の列・シート・範囲、XML の XPath、JSON/YAML/TOML のパス・キー、
+/// SQLite のクエリ、グループ別分析の列）
 pub fn add_input_arg(cmd: Command) -> Command {
     cmd.arg(
         Arg::new("input")
             .help("Input data (file path, URL, or '-' for stdin)")
             .index(1),
     )
+    .arg(
+        Arg::new("column")
+            .long("column")
+            .value_name("COLUMN")
+            .help("Read only this CSV/TSV or spreadsheet column (header name or 1-based index)"),
+    )
+    .arg(
+        Arg::new("sheet")
+            .long("sheet")
+            .value_name("NAME")
+            .help("Read only this spreadsheet sheet (Excel, ODS)"),
+    )
+    .arg(
+        Arg::new("range")
+            .long("range")
+            .value_name("CELLS")
+            .help("Read only this spreadsheet cell range (e.g., B2:B500)"),
+    )
+    .arg(
+        Arg::new("xpath")
+            .long("xpath")
+            .value_name("EXPR")
+            .help("Read only the XML nodes this XPath-like expression selects (e.g., //Amount/@value)"),
+    )
+    .arg(
+        Arg::new("path")
+            .long("path")
+            .value_name("JSONPATH")
+            .help("Read only the JSON/YAML/TOML values this path selects (e.g., $.invoices[*].total)"),
+    )
+    .arg(
+        Arg::new("include_keys")
+            .long("include-keys")
+            .value_name("REGEX")
+            .help("Read only JSON/YAML/TOML values under keys matching this regex"),
+    )
+    .arg(
+        Arg::new("exclude_keys")
+            .long("exclude-keys")
+            .value_name("REGEX")
+            .help("Skip JSON/YAML/TOML values under keys matching this regex"),
+    )
+    .arg(
+        Arg::new("query")
+            .long("query")
+            .value_name("SQL")
+            .help("Read the numbers this SQL query returns from a SQLite database"),
+    )
+    .arg(
+        Arg::new("group-by")
+            .long("group-by")
+            .value_name("COLUMN")
+            .help("Analyze each category separately: CSV/TSV or spreadsheet column (header name or 1-based index) or JSON key"),
+    )
+    .arg(
+        Arg::new("members")
+            .long("members")
+            .help("Analyze each member of ZIP/tar archives separately instead of pooling them")
+            .action(clap::ArgAction::SetTrue)
+            .conflicts_with("group-by"),
+    )
+    .arg(
+        Arg::new("locale")
+            .long("locale")
+            .value_name("LOCALE")
+            .help("Number notation: en (1,234.56), eu (1.234,56), fr (1 234,56), ch (1'234.56), in (12,34,567) or a tag like de-DE"),
+    )
+}
+
+/// --column / --sheet / --range / --xpath / --path / --include-keys / --exclude-keys /
+/// --query / --group-by / --locale の指定を取得（--members は `run_batch_if_requested` が扱う）
+pub fn get_input_options(matches: &ArgMatches) -> Result<InputOptions, BenfError> {
+    let key_regex = |id: &str, option: &str| {
+        matches
+            .get_one::<String>(id)
+            .map(|pattern| parse_key_regex(pattern, option))
+            .transpose()
+    };
+
+    Ok(InputOptions {
+        column: matches
+            .get_one::<String>("column")
+            .map(|column| column.parse())
+            .transpose()?,
+        sheet: matches.get_one::<String>("sheet").cloned(),
+        xpath: matches.get_one::<String>("xpath").cloned(),
+        query: matches.get_one::<String>("query").cloned(),
+        group_by: matches
+            .get_one::<String>("group-by")
+            .map(|column| column.parse())
+            .transpose()?,
+        keys: KeySelection {
+            path: matches
+                .get_one::<String>("path")
+                .map(|path| path.parse())
+                .transpose()?,
+            include_keys: key_regex("include_keys", "--include-keys")?,
+            exclude_keys: key_regex("exclude_keys", "--exclude-keys")?,
+        },
+        range: matches
+            .get_one::<String>("range")
+            .map(|range| range.parse())
+            .transpose()?,
+        locale: matches
+            .get_one::<String>("locale")
+            .map(|locale| locale.parse())
+            .transpose()?
+            .unwrap_or_default(),
+    })
+}
+
+/// 列・シート・範囲・XPath の指定に従って入力（ファイルまたは stdin）を読み込む
+pub fn read_selected_input(
+    matches: &ArgMatches,
+    options: &InputOptions,
+) -> Result<Vec<NumberRecord>, BenfError> {
+    match matches
+        .get_one::<String>("input")
+        .filter(|input| *input != "-")
+    {
+        Some(input) => parse_input_records_auto_with(input, options),
+        None => read_selected_stdin(&mut OptimizedFileReader::from_stdin(), options),
+    }
+}
+
+/// 入力をテキストとして読めず形式に応じた読み込みが必要か
+/// （列などの選択指定がある、または入力ファイルが圧縮ファイル・アーカイブである）
+pub fn needs_format_aware_read(matches: &ArgMatches, options: &InputOptions) -> bool {
+    options.has_selection()
+        || matches
+            .get_one::<String>("input")
+            .filter(|input| *input != "-")
+            .is_some_and(|input| is_packed(std::path::Path::new(input)))
+}
+
+/// 列・シート・範囲・XPath の指定がある場合に stdin 全体を表（または XML）として読み込む
+pub fn read_selected_stdin(
+    reader: &mut OptimizedFileReader,
+    options: &InputOptions,
+) -> Result<Vec<NumberRecord>, BenfError> {
+    let records = parse_text_records_with(&reader.read_to_string()?, options)?;
+    if records.is_empty() {
+        return Err(BenfError::NoNumbersFound);
+    }
+    Ok(records)
+}
+
+/// 入力がディレクトリまたは glob パターンであれば各ファイルを一括分析し、
+/// 最もリスクの高いファイルの終了コードで終了する（単一入力ならそのまま戻る）
+///
+/// --members ではアーカイブのメンバーごとに分析し、単一のファイルも一括分析する。
+pub fn run_batch_if_requested<R, F>(
+    matches: &ArgMatches,
+    options: &InputOptions,
+    analyze: F,
+) -> Result<(), BenfError>
+where
+    R: BatchAnalysis,
+    F: Fn(&str, &[f64]) -> Result<R, BenfError> + Sync,
+{
+    let Some(input) = matches.get_one::<String>("input") else {
+        return Ok(());
+    };
+    let members = matches.get_flag("members");
+    let files = match expand_batch_input(input)? {
+        Some(files) => files,
+        None if members && std::path::Path::new(input).is_file() => vec![input.into()],
+        None => return Ok(()),
+    };
+    if matches.get_flag("verbose") {
+        eprintln!("Debug: batch mode over {} files", files.len());
+    }
+
+    let format = parse_output_format(matches);
+    let batch = if members {
+        analyze_batch_members(&files, options, analyze)
+    } else {
+        analyze_batch(&files, options, analyze)
+    };
+    println!("{}", format_output(&batch, &format)?);
+    std::process::exit(batch.exit_code());
+}
+
+/// --group-by の指定があれば入力を読み込んで `analyze_groups` でグループ別に分析し、
+/// 最もリスクの高いグループの終了コードで終了する（指定がなければそのまま戻る）
+///
+/// `analyze_groups` はグループ列の名前、レコード、--min-count の値を受け取る。
+pub fn run_group_by_if_requested<R, F>(
+    matches: &ArgMatches,
+    options: &InputOptions,
+    analyze_groups: F,
+) -> Result<(), BenfError>
+where
+    R: BatchAnalysis,
+    F: FnOnce(&str, &[NumberRecord], usize) -> Result<GroupResult<R>, BenfError>,
+{
+    let Some(group_by) = &options.group_by else {
+        return Ok(());
+    };
+    let format = parse_output_format(matches);
+    let min_count = match matches.get_one::<String>("min-count") {
+        Some(min_count) => min_count
+            .parse::<usize>()
+            .map_err(|_| BenfError::ParseError("無効な最小数値数".to_string()))?,
+        None => 10,
+    };
+
+    let records = read_selected_input(matches, options)?;
+    let result = analyze_groups(&group_by.to_string(), &records, min_count)?;
+    if matches.get_flag("verbose") {
+        eprintln!(
+            "Debug: {} groups analyzed, {} skipped",
+            result.groups.len(),
+            result.skipped.len()
+        );
+    }
+    println!("{}", format_output(&result, &format)?);
+    std::process::exit(result.exit_code());
+}
+
+/// --filter の範囲に入るレコードだけを残す
+pub fn filter_records(
+    matches: &ArgMatches,
+    records: &[NumberRecord],
+) -> Result<Vec<NumberRecord>, BenfError> {
+    let Some(filter_str) = matches.get_one::<String>("filter") else {
+        return Ok(records.to_vec());
+    };
+    let filter = NumberFilter::parse(filter_str)
+        .map_err(|e| BenfError::ParseError(format!("無効なフィルタ: {e}")))?;
+    Ok(records
+        .iter()
+        .filter(|record| filter.matches(record.value))
+        .cloned()
+        .collect())
+}
+
+/// --format の値（不正な値なら終了コード 2 で終了）
+pub fn parse_output_format(matches: &ArgMatches) -> OutputFormat {
+    match matches
+        .get_one::<String>("format")
+        .map_or(Ok(OutputFormat::Text), |format| format.parse())
+    {
+        Ok(format) => format,
+        Err(e) => {
+            eprintln!("Error: {e}");
+            std::process::exit(2);
+        }
+    }
 }
 
 /// サブコマンド固有のオプション：ベンフォード法則
@@ -87,14 +355,14 @@ pub fn add_benf_options(cmd: Command) -> Command {
             .long("threshold")
             .short('t')
             .value_name("LEVEL")
-            .help("Anomaly detection threshold: low, medium, high, critical")
+            .help("Anomaly detection threshold: low, medium, high, critical, mad")
             .default_value("auto"),
     )
     .arg(
         Arg::new("confidence")
             .long("confidence")
             .value_name("LEVEL")
-            .help("Statistical confidence level for tests (0.01-0.99)")
+            .help("Statistical confidence level for tests and per-digit Z-statistics (0.01-0.99)")
             .default_value("0.95"),
     )
     .arg(
@@ -109,6 +377,18 @@ pub fn add_benf_options(cmd: Command) -> Command {
             .value_name("VALUE")
             .help("Minimum value to include in analysis (filters small values that add noise)"),
     )
+    .arg(
+        Arg::new("digits")
+            .long("digits")
+            .value_name("TESTS")
+            .help("Digit tests: first, second, first-two, first-three, last-two, both, all"),
+    )
+    .arg(
+        Arg::new("drill-down")
+            .long("drill-down")
+            .help("List the input records behind digits with significant Z-statistics")
+            .action(clap::ArgAction::SetTrue),
+    )
 }
 
 /// サブコマンド固有のオプション：パレート法則  
@@ -139,6 +419,77 @@ pub fn add_pareto_options(cmd: Command) -> Command {
             .help("Enable business analysis insights")
             .action(clap::ArgAction::SetTrue),
     )
+    .arg(
+        Arg::new("label")
+            .long("label")
+            .value_name("COLUMN")
+            .help("Column (or JSON key) naming each item; classifies items into ABC classes")
+            .conflicts_with("group-by"),
+    )
+    .arg(
+        Arg::new("abc-cutoffs")
+            .long("abc-cutoffs")
+            .value_name("PERCENTAGES")
+            .help("Cumulative share cut-offs for ABC (e.g., 80,95) or ABCD (e.g., 70,90,98)")
+            .requires("label"),
+    )
+    .arg(
+        Arg::new("indices")
+            .long("indices")
+            .help("Report inequality indices (Gini with CI, Theil, Atkinson, HHI, Palma, Hoover, top shares)")
+            .action(clap::ArgAction::SetTrue),
+    )
+    .arg(
+        Arg::new("atkinson")
+            .long("atkinson")
+            .value_name("EPSILONS")
+            .help("Inequality aversion values for the Atkinson index (default: 0.5,1,2)")
+            .requires("indices"),
+    )
+    .arg(
+        Arg::new("top-shares")
+            .long("top-shares")
+            .value_name("PERCENTILES")
+            .help("Top-k% shares to report (default: 1,10,20,50)")
+            .requires("indices"),
+    )
+    .arg(
+        Arg::new("gini-bootstrap")
+            .long("gini-bootstrap")
+            .value_name("N")
+            .help("Bootstrap samples for the Gini confidence interval (default: 200, 0 to skip)")
+            .requires("indices"),
+    )
+    .args(power_law_args())
+}
+
+/// べき乗則フィットのオプション（pareto・zipf 共通）
+fn power_law_args() -> [Arg; 2] {
+    [
+        Arg::new("power-law")
+            .long("power-law")
+            .help("Fit a power-law tail by maximum likelihood (Clauset-Shalizi-Newman)")
+            .action(clap::ArgAction::SetTrue),
+        Arg::new("bootstrap")
+            .long("bootstrap")
+            .value_name("N")
+            .help("Bootstrap samples for the power-law goodness-of-fit p-value (0 to skip)")
+            .requires("power-law"),
+    ]
+}
+
+/// --power-law / --bootstrap の指定（フィットしない場合は None）
+pub fn get_power_law_options(matches: &ArgMatches) -> Result<Option<PowerLawOptions>, BenfError> {
+    if !matches.get_flag("power-law") {
+        return Ok(None);
+    }
+    let mut options = PowerLawOptions::default();
+    if let Some(bootstrap) = matches.get_one::<String>("bootstrap") {
+        options.bootstrap = bootstrap
+            .parse()
+            .map_err(|_| BenfError::ParseError("無効なブートストラップ回数".to_string()))?;
+    }
+    Ok(Some(options))
 }
 
 /// サブコマンド固有のオプション：Zipf法則
@@ -158,6 +509,105 @@ pub fn add_zipf_options(cmd: Command) -> Command {
             .help("Maximum number of words to analyze in text mode")
             .default_value("1000"),
     )
+    .arg(
+        Arg::new("heaps")
+            .long("heaps")
+            .help("Fit Heaps' law (vocabulary growth V = K n^beta) in text mode")
+            .action(clap::ArgAction::SetTrue)
+            .requires("text"),
+    )
+    .args(tokenizer_args())
+    .arg(
+        Arg::new("confidence")
+            .long("confidence")
+            .value_name("LEVEL")
+            .help("Confidence level for the Zipf exponent interval (0.01-0.99)")
+            .default_value("0.95"),
+    )
+    .arg(
+        Arg::new("mandelbrot")
+            .long("mandelbrot")
+            .help("Also fit the Zipf-Mandelbrot distribution (r + q)^(-s) by maximum likelihood")
+            .action(clap::ArgAction::SetTrue),
+    )
+    .args(power_law_args())
+}
+
+/// テキストのトークン化のオプション（zipf・compare の --text 共通）
+pub fn tokenizer_args() -> [Arg; 7] {
+    [
+        Arg::new("tokenizer")
+            .long("tokenizer")
+            .value_name("NAME")
+            .help("Tokenizer for text mode: unicode (UAX #29 words), whitespace, cjk-ngram, regex")
+            .value_parser(["unicode", "whitespace", "cjk-ngram", "regex"])
+            .default_value("unicode")
+            .requires("text"),
+        Arg::new("ngram")
+            .long("ngram")
+            .value_name("N")
+            .help("Character n-gram size for CJK text with --tokenizer cjk-ngram")
+            .value_parser(clap::value_parser!(usize))
+            .default_value("2")
+            .requires("text"),
+        Arg::new("token-pattern")
+            .long("token-pattern")
+            .value_name("REGEX")
+            .help("Regular expression matching one token (implies --tokenizer regex)")
+            .requires("text"),
+        Arg::new("no-lowercase")
+            .long("no-lowercase")
+            .help("Keep the original case of tokens in text mode")
+            .action(clap::ArgAction::SetTrue)
+            .requires("text"),
+        Arg::new("nfkc")
+            .long("nfkc")
+            .help("Apply Unicode NFKC normalization before tokenizing")
+            .action(clap::ArgAction::SetTrue)
+            .requires("text"),
+        Arg::new("stopwords")
+            .long("stopwords")
+            .value_name("FILE")
+            .help("File of words to exclude (whitespace separated, # starts a comment)")
+            .requires("text"),
+        Arg::new("min-token-length")
+            .long("min-token-length")
+            .value_name("CHARS")
+            .help("Exclude tokens shorter than this many characters")
+            .value_parser(clap::value_parser!(usize))
+            .default_value("1")
+            .requires("text"),
+    ]
+}
+
+/// --text のトークナイザーを --tokenizer などのオプションから組み立てる
+pub fn get_text_tokenizer(matches: &ArgMatches) -> Result<TextTokenizer, BenfError> {
+    let pattern = matches.get_one::<String>("token-pattern");
+    let name = if pattern.is_some() {
+        "regex"
+    } else {
+        matches.get_one::<String>("tokenizer").unwrap()
+    };
+    let ngram = *matches.get_one::<usize>("ngram").unwrap();
+    let stopwords = match matches.get_one::<String>("stopwords") {
+        Some(path) => {
+            let text = std::fs::read_to_string(path)
+                .map_err(|e| BenfError::FileError(format!("{path}: {e}")))?;
+            parse_stopwords(&text)
+        }
+        None => Vec::new(),
+    };
+
+    let options = TokenizerOptions {
+        lowercase: !matches.get_flag("no-lowercase"),
+        nfkc: matches.get_flag("nfkc"),
+        stopwords,
+        min_length: *matches.get_one::<usize>("min-token-length").unwrap(),
+    };
+    Ok(TextTokenizer::with_tokenizer(
+        builtin_tokenizer(name, ngram, pattern.map(String::as_str))?,
+        options,
+    ))
 }
 
 /// サブコマンド固有のオプション：正規分布
@@ -12,6 +12,6 @@ pub mod subcommands;
 
 // 明示的なre-exportで曖昧さを回避
 pub use lawkit_core::{common, error, laws};
-pub use subcommands::{analyze, benf, diagnose, normal, pareto, poisson, validate, zipf};
+pub use subcommands::{analyze, benf, compare, diagnose, normal, pareto, poisson, validate, zipf};
 
 pub const VERSION: &str = "2.0.1";
@@ -40,6 +40,7 @@ fn build_cli() -> Command {
         .subcommand(subcommands::analyze::command())
         .subcommand(subcommands::validate::command())
         .subcommand(subcommands::diagnose::command())
+        .subcommand(subcommands::compare::command())
         .subcommand(
             Command::new("generate")
                 .about("Generate sample data following statistical laws")
@@ -112,6 +113,7 @@ fn main() {
         Some(("analyze", sub_matches)) => subcommands::analyze::run(sub_matches),
         Some(("validate", sub_matches)) => subcommands::validate::run(sub_matches),
         Some(("diagnose", sub_matches)) => subcommands::diagnose::run(sub_matches),
+        Some(("compare", sub_matches)) => subcommands::compare::run(sub_matches),
         Some(("generate", sub_matches)) => run::handle_generate_command(sub_matches),
         Some(("list", sub_matches)) => run::list_laws(sub_matches),
         Some(("selftest", sub_matches)) => run::run_selftest(sub_matches),
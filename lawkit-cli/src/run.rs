@@ -282,6 +282,10 @@ pub fn list_laws(matches: &ArgMatches) -> Result<(), LawkitError> {
         "  {} - Conflict detection and detailed diagnostics",
         colors::pass("diagnose", no_color)
     );
+    println!(
+        "  {} - Two-dataset comparison under one law",
+        colors::pass("compare", no_color)
+    );
     println!();
     println!("{}", colors::info("Generation commands:", no_color));
     println!(
@@ -1,19 +1,63 @@
 use crate::colors;
+use crate::common_options::{
+    filter_records, get_input_options, read_selected_stdin, run_batch_if_requested,
+    run_group_by_if_requested,
+};
 use clap::ArgMatches;
 use lawkit_core::{
     common::{
         filtering::{apply_number_filter, NumberFilter, RiskThreshold},
-        input::{parse_input_auto, parse_text_input},
+        grouping::analyze_benford_groups,
+        input::{
+            accumulate_input_auto_with, parse_input_auto_with, parse_input_records_auto_with,
+            parse_line_records_with, NumberRecord,
+        },
         memory::{streaming_benford_analysis, MemoryConfig},
+        output::formatter::{format_output, OutputFormat},
         risk::RiskLevel,
         streaming_io::OptimizedFileReader,
     },
     error::{BenfError, Result},
-    laws::benford::BenfordResult,
+    laws::benford::{BenfordResult, DigitTest, DigitTestResult, DigitZStatistic, FirstDigitCounts},
 };
 use std::str::FromStr;
 
 pub fn run(matches: &ArgMatches) -> Result<()> {
+    let options = get_input_options(matches)?;
+
+    // ディレクトリまたは glob パターンはファイルごとに一括分析する
+    run_batch_if_requested(matches, &options, |name, numbers| {
+        analyze_numbers_with_options(matches, name.to_string(), numbers)
+    })?;
+
+    // --group-by はカテゴリごとに第1桁と --digits の桁テストを集計して分析する
+    run_group_by_if_requested(matches, &options, |group_by, records, min_count| {
+        let records = filter_records(matches, records)?;
+        let threshold = parse_threshold(matches)?;
+        let significance_level = parse_significance_level(matches)?;
+        let digit_tests = parse_digit_tests(matches)?.unwrap_or_default();
+        analyze_benford_groups(
+            group_by,
+            &records,
+            min_count,
+            &digit_tests,
+            |group, counts| {
+                Ok(BenfordResult::from_digit_counts(
+                    group.to_string(),
+                    counts.first_digit.total_count(),
+                    counts.first_digit.get_counts(),
+                    &threshold,
+                    significance_level,
+                )
+                .with_digit_test_counts(
+                    &counts.digit_tests,
+                    &threshold,
+                    significance_level,
+                ))
+            },
+        )
+    })?;
+
     // Determine input source based on arguments
     if matches.get_flag("verbose") {
         eprintln!(
@@ -23,9 +67,47 @@ pub fn run(matches: &ArgMatches) -> Result<()> {
     }
 
     if let Some(input) = matches.get_one::<String>("input") {
+        // Without options that look at individual numbers only the first-digit counts are
+        // needed, so they are gathered batch by batch (Parquet and Arrow files are streamed)
+        if !needs_individual_numbers(matches) {
+            let mut counts = FirstDigitCounts::new();
+            if let Err(e) = accumulate_input_auto_with(input, &options, &mut counts) {
+                eprintln!("Error processing input '{input}': {e}");
+                std::process::exit(1);
+            }
+            let result = parse_threshold(matches).and_then(|threshold| {
+                counts.into_result(
+                    input.to_string(),
+                    &threshold,
+                    parse_min_count(matches)?,
+                    parse_significance_level(matches)?,
+                )
+            });
+            match result {
+                Ok(result) => {
+                    output_results(matches, &result);
+                    std::process::exit(result.risk_level.exit_code());
+                }
+                Err(e) => {
+                    eprintln!("Analysis error: {e}");
+                    std::process::exit(1);
+                }
+            }
+        }
+
         // Use auto-detection for file vs string input
-        match parse_input_auto(input) {
-            Ok(numbers) => {
+        // Drill-down needs to know where each number came from
+        let parsed = if matches.get_flag("drill-down") {
+            parse_input_records_auto_with(input, &options).map(|records| {
+                let numbers = records.iter().map(|record| record.value).collect();
+                (numbers, Some(records))
+            })
+        } else {
+            parse_input_auto_with(input, &options).map(|numbers| (numbers, None))
+        };
+
+        match parsed {
+            Ok((numbers, records)) => {
                 if numbers.is_empty() {
                     eprintln!("Error: No valid numbers found in input");
                     std::process::exit(1);
@@ -40,6 +122,16 @@ pub fn run(matches: &ArgMatches) -> Result<()> {
                             std::process::exit(1);
                         }
                     };
+                let result = match records {
+                    Some(records) => match drill_down_records(matches, records) {
+                        Ok(records) => result.with_drill_down(&records),
+                        Err(e) => {
+                            eprintln!("Analysis error: {e}");
+                            std::process::exit(1);
+                        }
+                    },
+                    None => result,
+                };
 
                 // Output results and exit
                 output_results(matches, &result);
@@ -66,22 +158,53 @@ pub fn run(matches: &ArgMatches) -> Result<()> {
         }
 
         // ストリーミング処理でインクリメンタル分析を実行
-        let numbers = match reader.read_lines_streaming(|line| {
-            if matches.get_flag("verbose") {
-                eprintln!("Debug: Processing line: '{line}'");
+        let drill_down = matches.get_flag("drill-down");
+        let mut line_number = 0;
+        let mut records = Vec::new();
+        // 列・シート・範囲の指定があれば stdin 全体を表として読み込む
+        let numbers = if options.has_selection() {
+            match read_selected_stdin(&mut reader, &options) {
+                Ok(selected) => {
+                    let numbers = selected.iter().map(|record| record.value).collect();
+                    if drill_down {
+                        records = selected;
+                    }
+                    numbers
+                }
+                Err(e) => {
+                    eprintln!("Error processing input: {e}");
+                    std::process::exit(1);
+                }
             }
-            parse_text_input(&line).map(Some).or(Ok(None))
-        }) {
-            Ok(nested_numbers) => {
-                let flattened: Vec<f64> = nested_numbers.into_iter().flatten().collect();
+        } else {
+            match reader.read_lines_streaming(|line| {
                 if matches.get_flag("verbose") {
-                    eprintln!("Debug: Collected {} numbers from stream", flattened.len());
+                    eprintln!("Debug: Processing line: '{line}'");
+                }
+                line_number += 1;
+                let line_records = parse_line_records_with(&line, line_number, options.locale)?;
+                if line_records.is_empty() {
+                    return Ok(None);
+                }
+                let line_numbers: Vec<f64> =
+                    line_records.iter().map(|record| record.value).collect();
+                // ドリルダウン用に行番号付きレコードを保持
+                if drill_down {
+                    records.extend(line_records);
+                }
+                Ok(Some(line_numbers))
+            }) {
+                Ok(nested_numbers) => {
+                    let flattened: Vec<f64> = nested_numbers.into_iter().flatten().collect();
+                    if matches.get_flag("verbose") {
+                        eprintln!("Debug: Collected {} numbers from stream", flattened.len());
+                    }
+                    flattened
+                }
+                Err(e) => {
+                    eprintln!("Analysis error: {e}");
+                    std::process::exit(1);
                 }
-                flattened
-            }
-            Err(e) => {
-                eprintln!("Analysis error: {e}");
-                std::process::exit(1);
             }
         };
 
@@ -116,6 +239,29 @@ pub fn run(matches: &ArgMatches) -> Result<()> {
             numbers
         };
 
+        let digit_tests = match parse_digit_tests(matches) {
+            Ok(tests) => tests,
+            Err(e) => {
+                eprintln!("Error: {e}");
+                std::process::exit(2);
+            }
+        };
+        let threshold = match parse_threshold(matches) {
+            Ok(threshold) => threshold,
+            Err(e) => {
+                eprintln!("Error: {e}");
+                std::process::exit(2);
+            }
+        };
+        let significance_level = match parse_significance_level(matches) {
+            Ok(significance_level) => significance_level,
+            Err(e) => {
+                eprintln!("Error: {e}");
+                std::process::exit(2);
+            }
+        };
+        let digit_test_numbers = digit_tests.as_ref().map(|_| filtered_numbers.clone());
+
         // メモリ設定を作成
         let memory_config = MemoryConfig::default();
 
@@ -149,8 +295,25 @@ pub fn run(matches: &ArgMatches) -> Result<()> {
         }
 
         // IncrementalBenford を BenfordResult に変換
-        let benford_result =
-            convert_incremental_to_result(&chunk_result.result, "stdin".to_string(), matches);
+        let mut benford_result = convert_incremental_to_result(
+            &chunk_result.result,
+            "stdin".to_string(),
+            &threshold,
+            significance_level,
+        );
+        if let (Some(tests), Some(numbers)) = (&digit_tests, &digit_test_numbers) {
+            benford_result =
+                benford_result.with_digit_tests(numbers, tests, &threshold, significance_level);
+        }
+        if drill_down {
+            match drill_down_records(matches, records) {
+                Ok(records) => benford_result = benford_result.with_drill_down(&records),
+                Err(e) => {
+                    eprintln!("Error: {e}");
+                    std::process::exit(2);
+                }
+            }
+        }
 
         // デバッグ情報を出力
         if matches.get_flag("verbose") {
@@ -180,10 +343,7 @@ fn output_results(matches: &clap::ArgMatches, result: &BenfordResult) {
     match format.as_str() {
         "text" => print_text_output(result, quiet, verbose, no_color),
         "json" => print_json_output(result),
-        "csv" => print_csv_output(result),
-        "yaml" => print_yaml_output(result),
-        "toml" => print_toml_output(result),
-        "xml" => print_xml_output(result),
+        "csv" | "yaml" | "toml" | "xml" => print_structured_output(result, format),
         _ => {
             eprintln!("Error: Unsupported output format: {format}");
             std::process::exit(2);
@@ -193,8 +353,15 @@ fn output_results(matches: &clap::ArgMatches, result: &BenfordResult) {
 
 fn print_text_output(result: &BenfordResult, quiet: bool, verbose: bool, no_color: bool) {
     if quiet {
-        for (i, &observed) in result.digit_distribution.iter().enumerate() {
-            println!("{}: {:.1}%", i + 1, observed);
+        if result.digit_tests.is_empty() {
+            for (i, &observed) in result.digit_distribution.iter().enumerate() {
+                println!("{}: {:.1}%", i + 1, observed);
+            }
+        }
+        for test in &result.digit_tests {
+            for (i, &observed) in test.observed_distribution.iter().enumerate() {
+                println!("{}: {:.1}%", test.test.bin_label(i), observed);
+            }
         }
         return;
     }
@@ -213,6 +380,9 @@ fn print_text_output(result: &BenfordResult, quiet: bool, verbose: bool, no_colo
     println!();
     println!("First Digit Distribution:");
     println!("{}", format_distribution_bars(result));
+    if result.digit_tests.is_empty() {
+        print_significant_bins(&result.z_statistics);
+    }
 
     if verbose {
         println!();
@@ -221,9 +391,12 @@ fn print_text_output(result: &BenfordResult, quiet: bool, verbose: bool, no_colo
             let digit = i + 1;
             let expected = result.expected_distribution[i];
             let deviation = observed - expected;
+            let z = &result.z_statistics[i];
 
             println!(
-                "{digit}: {observed:.1}% (expected: {expected:.1}%, deviation: {deviation:+.1}%)"
+                "{digit}: {observed:.1}% (expected: {expected:.1}%, deviation: {deviation:+.1}%, Z: {:.2}{})",
+                z.z_statistic,
+                significance_marker(z)
             );
         }
 
@@ -233,68 +406,112 @@ fn print_text_output(result: &BenfordResult, quiet: bool, verbose: bool, no_colo
             "Chi-square: {:.2} (p-value: {:.6})",
             result.chi_square, result.p_value
         );
+        println!(
+            "MAD: {:.3} ({})",
+            result.mean_absolute_deviation, result.conformity
+        );
+    }
+
+    for test in &result.digit_tests {
+        print_digit_test(test, verbose);
+    }
+
+    if !result.drill_down.is_empty() {
+        println!();
+        println!("Drill-down:");
+        for drill_down in &result.drill_down {
+            println!(
+                "{} {} (Z = {:.2}): {} records",
+                drill_down.test.title(),
+                drill_down.bin,
+                drill_down.z_statistic,
+                drill_down.records.len()
+            );
+            for record in &drill_down.records {
+                println!("  {}: {}", record.source, record.text);
+            }
+        }
+    }
+}
+
+/// List bins whose Z-statistic is significant
+fn print_significant_bins(z_statistics: &[DigitZStatistic]) {
+    let significant: Vec<String> = z_statistics
+        .iter()
+        .filter(|z| z.significant)
+        .map(|z| format!("{} (Z = {:.2})", z.bin, z.z_statistic))
+        .collect();
+    if !significant.is_empty() {
+        println!("Significant digits: {}", significant.join(", "));
+    }
+}
+
+fn significance_marker(z: &DigitZStatistic) -> &'static str {
+    if z.significant {
+        " *"
+    } else {
+        ""
+    }
+}
+
+fn print_digit_test(test: &DigitTestResult, verbose: bool) {
+    println!();
+    println!("{} Test:", test.test.title());
+    println!("Numbers analyzed: {}", test.numbers_analyzed);
+    println!(
+        "Chi-square: {:.2} (p-value: {:.6}), MAD: {:.3} ({})",
+        test.chi_square, test.p_value, test.mean_absolute_deviation, test.conformity
+    );
+    println!("Verdict: {}", test.verdict);
+    print_significant_bins(&test.z_statistics);
+
+    if verbose {
+        for z in &test.z_statistics {
+            let deviation = z.observed - z.expected;
+            println!(
+                "{}: {:.2}% (expected: {:.2}%, deviation: {deviation:+.2}%, Z: {:.2}{})",
+                z.bin,
+                z.observed,
+                z.expected,
+                z.z_statistic,
+                significance_marker(z)
+            );
+        }
     }
 }
 
 fn print_json_output(result: &BenfordResult) {
     use serde_json::json;
 
-    let output = json!({
+    let mut output = json!({
         "dataset": result.dataset_name,
         "numbers_analyzed": result.numbers_analyzed,
         "risk_level": format!("{:?}", result.risk_level),
         "chi_square": result.chi_square,
         "p_value": result.p_value,
-        "mean_absolute_deviation": result.mean_absolute_deviation
+        "mean_absolute_deviation": result.mean_absolute_deviation,
+        "conformity": result.conformity,
+        "z_statistics": result.z_statistics
     });
+    if !result.digit_tests.is_empty() {
+        output["digit_tests"] = serde_json::to_value(&result.digit_tests).unwrap();
+    }
+    if !result.drill_down.is_empty() {
+        output["drill_down"] = serde_json::to_value(&result.drill_down).unwrap();
+    }
 
     println!("{}", serde_json::to_string_pretty(&output).unwrap());
 }
 
-fn print_csv_output(result: &BenfordResult) {
-    println!("dataset,numbers_analyzed,risk_level,chi_square,p_value,mad");
-    println!(
-        "{},{},{:?},{:.6},{:.6},{:.2}",
-        result.dataset_name,
-        result.numbers_analyzed,
-        result.risk_level,
-        result.chi_square,
-        result.p_value,
-        result.mean_absolute_deviation
-    );
-}
-
-fn print_yaml_output(result: &BenfordResult) {
-    println!("dataset: \"{}\"", result.dataset_name);
-    println!("numbers_analyzed: {}", result.numbers_analyzed);
-    println!("risk_level: \"{:?}\"", result.risk_level);
-    println!("chi_square: {:.6}", result.chi_square);
-    println!("p_value: {:.6}", result.p_value);
-    println!("mad: {:.2}", result.mean_absolute_deviation);
-}
-
-fn print_toml_output(result: &BenfordResult) {
-    println!("dataset = \"{}\"", result.dataset_name);
-    println!("numbers_analyzed = {}", result.numbers_analyzed);
-    println!("risk_level = \"{:?}\"", result.risk_level);
-    println!("chi_square = {:.6}", result.chi_square);
-    println!("p_value = {:.6}", result.p_value);
-    println!("mad = {:.2}", result.mean_absolute_deviation);
-}
-
-fn print_xml_output(result: &BenfordResult) {
-    println!("<?xml version=\"1.0\" encoding=\"UTF-8\"?>");
-    println!("<benford_analysis>");
-    println!("  <dataset>{}</dataset>", result.dataset_name);
-    println!(
-        "  <numbers_analyzed>{}</numbers_analyzed>",
-        result.numbers_analyzed
-    );
-    println!("  <risk_level>{:?}</risk_level>", result.risk_level);
-    println!("  <chi_square>{:.6}</chi_square>", result.chi_square);
-    println!("  <p_value>{:.6}</p_value>", result.p_value);
-    println!("  <mad>{:.2}</mad>", result.mean_absolute_deviation);
-    println!("</benford_analysis>");
+/// CSV, YAML, TOML and XML use the core serializers, which escape every field
+fn print_structured_output(result: &BenfordResult, format: &str) {
+    match OutputFormat::from_str(format).and_then(|format| format_output(result, &format)) {
+        Ok(output) => println!("{output}"),
+        Err(e) => {
+            eprintln!("Error: {e}");
+            std::process::exit(2);
+        }
+    }
 }
 
 /// Analyze numbers with filtering and custom options
@@ -326,40 +543,13 @@ fn analyze_numbers_with_options(
     };
 
     // Parse custom threshold if specified
-    let threshold = if let Some(threshold_str) = matches.get_one::<String>("threshold") {
-        if threshold_str == "auto" {
-            RiskThreshold::Auto
-        } else {
-            RiskThreshold::from_str(threshold_str)
-                .map_err(|e| BenfError::ParseError(format!("無効な閾値: {e}")))?
-        }
-    } else {
-        RiskThreshold::Auto
-    };
+    let threshold = parse_threshold(matches)?;
 
     // Parse minimum count requirement
-    let min_count = if let Some(min_count_str) = matches.get_one::<String>("min-count") {
-        min_count_str
-            .parse::<usize>()
-            .map_err(|_| BenfError::ParseError("無効な最小数値数".to_string()))?
-    } else {
-        5
-    };
+    let min_count = parse_min_count(matches)?;
 
     // Parse confidence level
-    let _confidence = if let Some(confidence_str) = matches.get_one::<String>("confidence") {
-        let conf = confidence_str
-            .parse::<f64>()
-            .map_err(|_| BenfError::ParseError("無効な信頼度レベル".to_string()))?;
-        if !(0.01..=0.99).contains(&conf) {
-            return Err(BenfError::ParseError(
-                "信頼度レベルは0.01から0.99の間である必要があります".to_string(),
-            ));
-        }
-        conf
-    } else {
-        0.95
-    };
+    let significance_level = parse_significance_level(matches)?;
 
     // Parse sample size limit
     let mut working_numbers = filtered_numbers.clone();
@@ -419,14 +609,104 @@ fn analyze_numbers_with_options(
     }
 
     // Perform Benford analysis with custom options
-    BenfordResult::new_with_threshold(dataset_name, &working_numbers, &threshold, min_count)
+    let result = BenfordResult::new_with_significance(
+        dataset_name,
+        &working_numbers,
+        &threshold,
+        min_count,
+        significance_level,
+    )?;
+
+    match parse_digit_tests(matches)? {
+        Some(tests) => {
+            Ok(result.with_digit_tests(&working_numbers, &tests, &threshold, significance_level))
+        }
+        None => Ok(result),
+    }
+}
+
+/// Whether `--filter`, `--sample-size`, `--min-value`, `--digits` or `--drill-down` needs
+/// every number rather than the first-digit counts
+fn needs_individual_numbers(matches: &clap::ArgMatches) -> bool {
+    matches.get_flag("drill-down")
+        || ["filter", "sample-size", "min-value", "digits"]
+            .iter()
+            .any(|name| matches.get_one::<String>(name).is_some())
+}
+
+/// Parse `--min-count` (defaults to 5)
+fn parse_min_count(matches: &clap::ArgMatches) -> Result<usize> {
+    match matches.get_one::<String>("min-count") {
+        Some(min_count_str) => min_count_str
+            .parse::<usize>()
+            .map_err(|_| BenfError::ParseError("無効な最小数値数".to_string())),
+        None => Ok(5),
+    }
+}
+
+/// Parse `--threshold` (defaults to `auto`)
+fn parse_threshold(matches: &clap::ArgMatches) -> Result<RiskThreshold> {
+    match matches.get_one::<String>("threshold") {
+        Some(threshold_str) if threshold_str != "auto" => RiskThreshold::from_str(threshold_str)
+            .map_err(|e| BenfError::ParseError(format!("無効な閾値: {e}"))),
+        _ => Ok(RiskThreshold::Auto),
+    }
+}
+
+/// Parse `--confidence` into the significance level (1 - confidence)
+fn parse_significance_level(matches: &clap::ArgMatches) -> Result<f64> {
+    let Some(confidence_str) = matches.get_one::<String>("confidence") else {
+        return Ok(0.05);
+    };
+    let conf = confidence_str
+        .parse::<f64>()
+        .map_err(|_| BenfError::ParseError("無効な信頼度レベル".to_string()))?;
+    if !(0.01..=0.99).contains(&conf) {
+        return Err(BenfError::ParseError(
+            "信頼度レベルは0.01から0.99の間である必要があります".to_string(),
+        ));
+    }
+    Ok(1.0 - conf)
+}
+
+/// Keep only the records that survive `--filter` and `--min-value`
+///
+/// `--sample-size` is not applied: the drill-down lists every matching record.
+fn drill_down_records(
+    matches: &clap::ArgMatches,
+    mut records: Vec<NumberRecord>,
+) -> Result<Vec<NumberRecord>> {
+    if let Some(filter_str) = matches.get_one::<String>("filter") {
+        let filter = NumberFilter::parse(filter_str)
+            .map_err(|e| BenfError::ParseError(format!("無効なフィルタ: {e}")))?;
+        records.retain(|record| filter.matches(record.value));
+    }
+    if let Some(min_value_str) = matches.get_one::<String>("min-value") {
+        let min_val = min_value_str
+            .parse::<f64>()
+            .map_err(|_| BenfError::ParseError("無効な最小値".to_string()))?;
+        records.retain(|record| record.value >= min_val);
+    }
+    Ok(records)
+}
+
+/// Parse `--digits` into the list of digit tests to run
+fn parse_digit_tests(matches: &clap::ArgMatches) -> Result<Option<Vec<DigitTest>>> {
+    matches
+        .get_one::<String>("digits")
+        .map(|digits| {
+            DigitTest::parse_list(digits)
+                .map_err(|e| BenfError::ParseError(format!("無効な桁テスト: {e}")))
+        })
+        .transpose()
 }
 
 /// IncrementalBenford を BenfordResult に変換
 fn convert_incremental_to_result(
     incremental: &lawkit_core::common::memory::IncrementalBenford,
     dataset_name: String,
-    _matches: &clap::ArgMatches,
+    threshold: &RiskThreshold,
+    significance_level: f64,
 ) -> BenfordResult {
     use lawkit_core::common::statistics;
 
@@ -437,7 +717,11 @@ fn convert_incremental_to_result(
     ];
 
     // 統計値を計算
-    let chi_square = statistics::calculate_chi_square(&digit_distribution, &expected_distribution);
+    let chi_square = statistics::calculate_chi_square_from_percentages(
+        &digit_distribution,
+        &expected_distribution,
+        incremental.total_count(),
+    );
     let p_value = statistics::calculate_p_value(chi_square, 8);
     let mean_absolute_deviation = incremental.calculate_mad();
 
@@ -447,6 +731,14 @@ fn convert_incremental_to_result(
     // 判定を生成
     let verdict = format!("Risk Level: {risk_level:?}");
 
+    // MAD適合性閾値が指定された場合はNigriniの分類でリスクを決定
+    let conformity = DigitTest::First.conformity(mean_absolute_deviation);
+    let risk_level = if threshold.uses_mad_conformity() {
+        conformity.risk_level()
+    } else {
+        risk_level
+    };
+
     BenfordResult {
         dataset_name,
         numbers_analyzed: incremental.total_count(),
@@ -457,6 +749,16 @@ fn convert_incremental_to_result(
         mean_absolute_deviation,
         risk_level,
         verdict,
+        conformity,
+        z_statistics: DigitTestResult::from_counts(
+            DigitTest::First,
+            incremental.get_counts(),
+            threshold,
+            significance_level,
+        )
+        .z_statistics,
+        digit_tests: Vec::new(),
+        drill_down: Vec::new(),
     }
 }
 
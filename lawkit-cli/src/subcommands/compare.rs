@@ -0,0 +1,261 @@
+use crate::colors;
+use crate::common_options::{self, get_input_options, get_optimized_reader, get_text_tokenizer};
+use clap::{Arg, ArgMatches, Command};
+use lawkit_core::{
+    common::{
+        filtering::{apply_number_filter, NumberFilter},
+        input::{parse_input_auto_with, parse_text_input_with, InputOptions},
+        output::formatter::{format_output, OutputFormat},
+        risk::RiskLevel,
+    },
+    error::{BenfError, Result},
+    laws::{
+        compare::{
+            compare_datasets, compare_word_frequencies, ComparedLaw, ComparisonResult, EffectSize,
+        },
+        zipf::extract_word_frequencies_with,
+    },
+};
+use std::str::FromStr;
+
+pub fn command() -> Command {
+    common_options::add_common_options(common_options::add_input_arg(
+        Command::new("compare")
+            .about("Compare two datasets under one statistical law")
+            .arg(
+                Arg::new("other")
+                    .help("Second dataset (file path or '-' for stdin)")
+                    .index(2)
+                    .required(true),
+            )
+            .arg(
+                Arg::new("law")
+                    .long("law")
+                    .value_name("LAW")
+                    .help("Law to compare under")
+                    .value_parser(["benf", "pareto", "zipf", "normal", "poisson"])
+                    .required(true),
+            )
+            .arg(
+                Arg::new("confidence")
+                    .long("confidence")
+                    .value_name("LEVEL")
+                    .help("Confidence level for tests and effect-size intervals (0.01-0.99)")
+                    .default_value("0.95"),
+            )
+            .arg(
+                Arg::new("text")
+                    .long("text")
+                    .short('T')
+                    .help("Compare word frequencies of two texts (--law zipf)")
+                    .action(clap::ArgAction::SetTrue),
+            )
+            .args(common_options::tokenizer_args()),
+    ))
+    .mut_arg("input", |arg| arg.required(true))
+}
+
+pub fn run(matches: &ArgMatches) -> Result<()> {
+    let law = ComparedLaw::parse(matches.get_one::<String>("law").unwrap())?;
+    let significance_level = parse_significance_level(matches)?;
+    let input_a = matches.get_one::<String>("input").unwrap();
+    let input_b = matches.get_one::<String>("other").unwrap();
+    if input_a == "-" && input_b == "-" {
+        return Err(BenfError::InvalidInput(
+            "only one of the two datasets can be read from stdin".to_string(),
+        ));
+    }
+
+    let result = if matches.get_flag("text") {
+        if law != ComparedLaw::Zipf {
+            return Err(BenfError::InvalidInput(
+                "--text compares word frequencies and requires --law zipf".to_string(),
+            ));
+        }
+        let tokenizer = get_text_tokenizer(matches)?;
+        let words_a = extract_word_frequencies_with(&read_text(input_a)?, &tokenizer);
+        let words_b = extract_word_frequencies_with(&read_text(input_b)?, &tokenizer);
+        compare_word_frequencies(input_a, &words_a, input_b, &words_b, significance_level)?
+    } else {
+        let options = get_input_options(matches)?;
+        let numbers_a = read_numbers(matches, input_a, &options)?;
+        let numbers_b = read_numbers(matches, input_b, &options)?;
+        compare_datasets(
+            law,
+            input_a,
+            &numbers_a,
+            input_b,
+            &numbers_b,
+            significance_level,
+        )?
+    };
+
+    output_results(matches, &result);
+    std::process::exit(result.risk_level.exit_code())
+}
+
+/// Parse `--confidence` into the significance level (1 - confidence)
+fn parse_significance_level(matches: &ArgMatches) -> Result<f64> {
+    let confidence = matches
+        .get_one::<String>("confidence")
+        .unwrap()
+        .parse::<f64>()
+        .map_err(|_| BenfError::ParseError("無効な信頼度レベル".to_string()))?;
+    if !(0.01..=0.99).contains(&confidence) {
+        return Err(BenfError::ParseError(
+            "信頼度レベルは0.01から0.99の間である必要があります".to_string(),
+        ));
+    }
+    Ok(1.0 - confidence)
+}
+
+fn read_text(input: &str) -> Result<String> {
+    get_optimized_reader(Some(&input.to_string()))
+        .map_err(|e| BenfError::FileError(format!("{input}: {e}")))
+}
+
+/// 1 つのデータセットを読み込み、--filter と --min-count を適用する
+fn read_numbers(matches: &ArgMatches, input: &str, options: &InputOptions) -> Result<Vec<f64>> {
+    let numbers = if input == "-" {
+        let data = get_optimized_reader(None).map_err(|e| BenfError::ParseError(e.to_string()))?;
+        parse_text_input_with(&data, options)?
+    } else {
+        parse_input_auto_with(input, options)?
+    };
+    if numbers.is_empty() {
+        return Err(BenfError::NoNumbersFound);
+    }
+
+    let numbers = match matches.get_one::<String>("filter") {
+        Some(filter_str) => {
+            let filter = NumberFilter::parse(filter_str)
+                .map_err(|e| BenfError::ParseError(format!("無効なフィルタ: {e}")))?;
+            apply_number_filter(&numbers, &filter)
+        }
+        None => numbers,
+    };
+
+    let min_count = matches
+        .get_one::<String>("min-count")
+        .unwrap()
+        .parse::<usize>()
+        .map_err(|_| BenfError::ParseError("無効な最小数値数".to_string()))?;
+    if numbers.len() < min_count {
+        return Err(BenfError::InsufficientData(numbers.len()));
+    }
+    Ok(numbers)
+}
+
+fn output_results(matches: &ArgMatches, result: &ComparisonResult) {
+    let format = matches.get_one::<String>("format").unwrap();
+    let quiet = matches.get_flag("quiet");
+    let verbose = matches.get_flag("verbose");
+    let no_color = matches.get_flag("no-color");
+
+    match format.as_str() {
+        "text" => print_text_output(result, quiet, verbose, no_color),
+        _ => print_structured_output(result, format),
+    }
+}
+
+fn law_title(law: ComparedLaw) -> &'static str {
+    match law {
+        ComparedLaw::Benford => "Benford's law",
+        ComparedLaw::Pareto => "Pareto principle",
+        ComparedLaw::Zipf => "Zipf's law",
+        ComparedLaw::Normal => "Normal distribution",
+        ComparedLaw::Poisson => "Poisson distribution",
+    }
+}
+
+/// 効果量を 1 行で表す（例: "Cohen's d: -0.512 (medium), 95% CI [-0.71, -0.31]"）
+fn format_effect_size(effect: &EffectSize) -> String {
+    let mut line = format!("{}: {:.3}", effect.name, effect.value);
+    if let Some(magnitude) = effect.magnitude {
+        line.push_str(&format!(" ({})", magnitude.name()));
+    }
+    if let Some(ref ci) = effect.confidence_interval {
+        line.push_str(&format!(
+            ", {:.0}% CI [{:.3}, {:.3}]",
+            ci.level * 100.0,
+            ci.lower,
+            ci.upper
+        ));
+    }
+    line
+}
+
+fn print_text_output(result: &ComparisonResult, quiet: bool, verbose: bool, no_color: bool) {
+    if quiet {
+        if let Some(p_value) = result.min_p_value() {
+            println!("p_value: {p_value:.6}");
+        }
+        if let Some(effect) = result.effect_sizes.first() {
+            println!("{}", format_effect_size(effect));
+        }
+        return;
+    }
+
+    println!("Dataset Comparison ({})", law_title(result.law));
+    println!();
+    println!("Dataset A: {} ({} values)", result.dataset_a, result.size_a);
+    println!("Dataset B: {} ({} values)", result.dataset_b, result.size_b);
+    let verdict = if result.significant {
+        "Datasets differ"
+    } else {
+        "No significant difference"
+    };
+    match result.risk_level {
+        RiskLevel::Critical => println!("{}", colors::level_critical(verdict, no_color)),
+        RiskLevel::High => println!("{}", colors::level_high(verdict, no_color)),
+        RiskLevel::Medium => println!("{}", colors::level_medium(verdict, no_color)),
+        RiskLevel::Low => println!("{}", colors::level_low(verdict, no_color)),
+    }
+
+    println!();
+    println!("Two-Sample Tests (α = {:.3}):", result.significance_level);
+    for test in &result.tests {
+        match test.degrees_of_freedom {
+            Some(df) => println!(
+                "  {}: statistic={:.3}, df={}, p={:.4}",
+                test.name, test.statistic, df, test.p_value
+            ),
+            None => println!(
+                "  {}: statistic={:.3}, p={:.4}",
+                test.name, test.statistic, test.p_value
+            ),
+        }
+    }
+
+    println!();
+    println!("Effect Sizes:");
+    for effect in &result.effect_sizes {
+        println!("  {}", format_effect_size(effect));
+    }
+
+    if !result.categories.is_empty() {
+        let shown = if verbose { result.categories.len() } else { 10 };
+        println!();
+        println!("Proportions (A vs B):");
+        for category in result.categories.iter().take(shown) {
+            println!(
+                "  {:>8}: {:>6.2}% vs {:>6.2}%",
+                category.category,
+                category.proportion_a * 100.0,
+                category.proportion_b * 100.0
+            );
+        }
+    }
+}
+
+/// JSON, CSV, YAML, TOML and XML use the core serializers, which escape every field
+/// and keep the per-category proportions
+fn print_structured_output(result: &ComparisonResult, format: &str) {
+    match OutputFormat::from_str(format).and_then(|format| format_output(result, &format)) {
+        Ok(output) => println!("{output}"),
+        Err(e) => {
+            eprintln!("Error: {e}");
+            std::process::exit(2);
+        }
+    }
+}
@@ -1,11 +1,14 @@
 use crate::colors;
-use crate::common_options::{get_optimized_reader, setup_automatic_optimization_config};
+use crate::common_options::{
+    get_input_options, get_optimized_reader, needs_format_aware_read, read_selected_input,
+    setup_automatic_optimization_config,
+};
 use clap::ArgMatches;
-use lawkit_core::common::input::parse_text_input;
+use lawkit_core::common::input::parse_text_input_with;
 use lawkit_core::common::output::OutputConfig;
 use lawkit_core::error::{BenfError, Result};
 use lawkit_core::laws::integration::IntegrationResult;
-use lawkit_core::{IntegrationData, LawkitResult};
+use lawkit_core::{IntegrationData, LawkitResult, RiskLevel};
 use std::io::Write;
 
 #[allow(dead_code)]
@@ -46,10 +49,13 @@ pub fn print_integration_summary(data: &IntegrationData, no_color: bool) {
     let risk_display = if no_color {
         format!("Overall Risk Level: {}", data.overall_risk)
     } else {
-        match data.overall_risk.as_str() {
-            "HIGH" => format!("Overall Risk Level: {}", colors::red(&data.overall_risk)),
-            "MEDIUM" => format!("Overall Risk Level: {}", colors::yellow(&data.overall_risk)),
-            _ => format!("Overall Risk Level: {}", colors::green(&data.overall_risk)),
+        let risk = data.overall_risk.to_string();
+        match data.overall_risk {
+            RiskLevel::Critical | RiskLevel::High => {
+                format!("Overall Risk Level: {}", colors::red(&risk))
+            }
+            RiskLevel::Medium => format!("Overall Risk Level: {}", colors::yellow(&risk)),
+            RiskLevel::Low => format!("Overall Risk Level: {}", colors::green(&risk)),
         }
     };
     println!("{risk_display}");
@@ -107,7 +113,7 @@ pub fn print_individual_results(results: &[LawkitResult], matches: &ArgMatches)
                 if verbose {
                     println!(
                         "Chi-square: {:.4}, P-value: {:.4}, MAD: {:.4}",
-                        data.chi_square, data.p_value, data.mad
+                        data.chi_square, data.p_value, data.mean_absolute_deviation
                     );
                 }
             }
@@ -122,7 +128,7 @@ pub fn print_individual_results(results: &[LawkitResult], matches: &ArgMatches)
                 if verbose {
                     println!(
                         "Top 20% contribution: {:.1}%, Pareto ratio: {:.2}",
-                        data.top_20_percent_contribution, data.pareto_ratio
+                        data.top_20_percent_share, data.pareto_ratio
                     );
                 }
             }
@@ -136,8 +142,8 @@ pub fn print_individual_results(results: &[LawkitResult], matches: &ArgMatches)
                 println!("Risk Level: {}", data.risk_level);
                 if verbose {
                     println!(
-                        "Zipf coefficient: {:.3}, Correlation: {:.3}",
-                        data.zipf_coefficient, data.correlation_coefficient
+                        "Zipf exponent: {:.3}, Correlation: {:.3}",
+                        data.zipf_exponent, data.correlation_coefficient
                     );
                 }
             }
@@ -167,7 +173,7 @@ pub fn print_individual_results(results: &[LawkitResult], matches: &ArgMatches)
                 if verbose {
                     println!(
                         "Lambda: {:.3}, Variance ratio: {:.3}, P-value: {:.4}",
-                        data.lambda, data.variance_ratio, data.poisson_test_p
+                        data.lambda, data.variance_ratio, data.chi_square_p_value
                     );
                 }
             }
@@ -185,6 +191,11 @@ pub fn get_dataset_name(matches: &ArgMatches) -> String {
 
 pub fn get_numbers_from_input(matches: &ArgMatches) -> Result<Vec<f64>> {
     let (_parallel_config, _memory_config) = setup_automatic_optimization_config();
+    let options = get_input_options(matches)?;
+    if needs_format_aware_read(matches, &options) {
+        let records = read_selected_input(matches, &options)?;
+        return Ok(records.into_iter().map(|record| record.value).collect());
+    }
 
     let buffer = if let Some(input) = matches.get_one::<String>("input") {
         if input == "-" {
@@ -197,7 +208,7 @@ pub fn get_numbers_from_input(matches: &ArgMatches) -> Result<Vec<f64>> {
     };
 
     let data = buffer.map_err(|e| BenfError::ParseError(e.to_string()))?;
-    parse_text_input(&data)
+    parse_text_input_with(&data, &options)
 }
 
 pub fn output_integration_result(
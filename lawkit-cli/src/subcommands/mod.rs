@@ -1,5 +1,6 @@
 pub mod analyze;
 pub mod benf;
+pub mod compare;
 pub mod diagnose;
 pub mod integration_common;
 pub mod normal;
@@ -1,10 +1,17 @@
 use crate::colors;
-use crate::common_options::{get_optimized_reader, setup_automatic_optimization_config};
+use crate::common_options::{
+    get_input_options, get_optimized_reader, needs_format_aware_read, read_selected_input,
+    read_selected_stdin, run_batch_if_requested, run_group_by_if_requested,
+    setup_automatic_optimization_config,
+};
 use clap::ArgMatches;
 use lawkit_core::{
     common::{
         filtering::{apply_number_filter, NumberFilter},
-        input::{parse_input_auto, parse_text_input},
+        grouping::analyze_groups,
+        input::{
+            parse_input_auto_with, parse_text_input_with, parse_text_records_with, NumberRecord,
+        },
         memory::{streaming_normal_analysis, MemoryConfig},
         outliers::{
             detect_outliers_dbscan, detect_outliers_ensemble, detect_outliers_isolation,
@@ -22,6 +29,8 @@ use lawkit_core::{
 };
 
 pub fn run(matches: &ArgMatches) -> Result<()> {
+    let options = get_input_options(matches)?;
+
     // 自動最適化設定をセットアップ
     let (_parallel_config, _memory_config) = setup_automatic_optimization_config();
 
@@ -46,6 +55,18 @@ pub fn run(matches: &ArgMatches) -> Result<()> {
         }
     }
 
+    // ディレクトリまたは glob パターンはファイルごとに一括分析する
+    run_batch_if_requested(matches, &options, |name, numbers| {
+        analyze_numbers_with_options(matches, name.to_string(), numbers)
+    })?;
+
+    // --group-by はカテゴリごとに分析する
+    run_group_by_if_requested(matches, &options, |group_by, records, min_count| {
+        analyze_groups(group_by, records, min_count, |name, numbers| {
+            analyze_numbers_with_options(matches, name.to_string(), numbers)
+        })
+    })?;
+
     // Determine input source based on arguments
     if matches.get_flag("verbose") {
         eprintln!(
@@ -57,7 +78,7 @@ pub fn run(matches: &ArgMatches) -> Result<()> {
     // 入力データ処理
     let numbers = if let Some(input) = matches.get_one::<String>("input") {
         // ファイル入力の場合
-        match parse_input_auto(input) {
+        match parse_input_auto_with(input, &options) {
             Ok(numbers) => {
                 if numbers.is_empty() {
                     eprintln!("Error: No valid numbers found in input");
@@ -84,19 +105,32 @@ pub fn run(matches: &ArgMatches) -> Result<()> {
             );
         }
 
-        let numbers = match reader
-            .read_lines_streaming(|line: String| parse_text_input(&line).map(Some).or(Ok(None)))
-        {
-            Ok(nested_numbers) => {
-                let flattened: Vec<f64> = nested_numbers.into_iter().flatten().collect();
-                if matches.get_flag("verbose") {
-                    eprintln!("Debug: Collected {} numbers from stream", flattened.len());
+        // 列・シート・範囲の指定があれば stdin 全体を表として読み込む
+        let numbers = if options.has_selection() {
+            match read_selected_stdin(&mut reader, &options) {
+                Ok(selected) => selected.into_iter().map(|record| record.value).collect(),
+                Err(e) => {
+                    eprintln!("Error processing input: {e}");
+                    std::process::exit(1);
                 }
-                flattened
             }
-            Err(e) => {
-                eprintln!("Analysis error: {e}");
-                std::process::exit(1);
+        } else {
+            match reader.read_lines_streaming(|line: String| {
+                parse_text_input_with(&line, &options)
+                    .map(Some)
+                    .or(Ok(None))
+            }) {
+                Ok(nested_numbers) => {
+                    let flattened: Vec<f64> = nested_numbers.into_iter().flatten().collect();
+                    if matches.get_flag("verbose") {
+                        eprintln!("Debug: Collected {} numbers from stream", flattened.len());
+                    }
+                    flattened
+                }
+                Err(e) => {
+                    eprintln!("Analysis error: {e}");
+                    std::process::exit(1);
+                }
             }
         };
 
@@ -184,7 +218,8 @@ fn run_normality_test_mode(matches: &ArgMatches, test_type: &str) -> Result<()>
 }
 
 fn run_outlier_detection_mode(matches: &ArgMatches) -> Result<()> {
-    let numbers = get_numbers_from_input(matches)?;
+    let records = get_records_from_input(matches)?;
+    let numbers: Vec<f64> = records.iter().map(|record| record.value).collect();
 
     let method_str = matches
         .get_one::<String>("outlier-method")
@@ -195,12 +230,14 @@ fn run_outlier_detection_mode(matches: &ArgMatches) -> Result<()> {
     match method_str {
         "lof" => {
             let result = detect_outliers_lof(&numbers, 5)?;
+            let result = result.with_records(&records);
             output_advanced_outlier_result(matches, &result);
             let exit_code = if result.outliers.is_empty() { 0 } else { 10 };
             std::process::exit(exit_code);
         }
         "isolation" => {
             let result = detect_outliers_isolation(&numbers, 8)?;
+            let result = result.with_records(&records);
             output_advanced_outlier_result(matches, &result);
             let exit_code = if result.outliers.is_empty() { 0 } else { 10 };
             std::process::exit(exit_code);
@@ -210,12 +247,14 @@ fn run_outlier_detection_mode(matches: &ArgMatches) -> Result<()> {
             let eps = std_dev * 0.5;
             let min_pts = (numbers.len() as f64).sqrt() as usize;
             let result = detect_outliers_dbscan(&numbers, eps, min_pts)?;
+            let result = result.with_records(&records);
             output_advanced_outlier_result(matches, &result);
             let exit_code = if result.outliers.is_empty() { 0 } else { 10 };
             std::process::exit(exit_code);
         }
         "ensemble" => {
             let result = detect_outliers_ensemble(&numbers)?;
+            let result = result.with_records(&records);
             output_advanced_outlier_result(matches, &result);
             let exit_code = if result.outliers.is_empty() { 0 } else { 10 };
             std::process::exit(exit_code);
@@ -234,7 +273,7 @@ fn run_outlier_detection_mode(matches: &ArgMatches) -> Result<()> {
                 }
             };
 
-            let outlier_result = detect_outliers(&numbers, method)?;
+            let outlier_result = detect_outliers(&numbers, method)?.with_records(&records);
             output_outlier_detection_result(matches, &outlier_result);
 
             // Exit code: 0 = no outliers, 10 = outliers found (HIGH risk indication)
@@ -288,7 +327,17 @@ fn run_quality_control_mode(matches: &ArgMatches) -> Result<()> {
 }
 
 fn get_numbers_from_input(matches: &ArgMatches) -> Result<Vec<f64>> {
+    let records = get_records_from_input(matches)?;
+    Ok(records.into_iter().map(|record| record.value).collect())
+}
+
+/// 入力を読み込み、各数値の出所（ファイルと行番号）付きで返す
+fn get_records_from_input(matches: &ArgMatches) -> Result<Vec<NumberRecord>> {
     let (_parallel_config, _memory_config) = setup_automatic_optimization_config();
+    let options = get_input_options(matches)?;
+    if needs_format_aware_read(matches, &options) {
+        return read_selected_input(matches, &options);
+    }
 
     let buffer = if let Some(input) = matches.get_one::<String>("input") {
         if input == "-" {
@@ -301,7 +350,17 @@ fn get_numbers_from_input(matches: &ArgMatches) -> Result<Vec<f64>> {
     };
 
     let data = buffer.map_err(|e| BenfError::ParseError(e.to_string()))?;
-    parse_text_input(&data)
+    let mut records = parse_text_records_with(&data, &options)?;
+    if records.is_empty() {
+        return Err(BenfError::NoNumbersFound);
+    }
+
+    if let Some(input) = matches.get_one::<String>("input").filter(|i| *i != "-") {
+        for record in &mut records {
+            record.source.file = Some(input.clone());
+        }
+    }
+    Ok(records)
 }
 
 fn parse_spec_limits(limits_str: &str) -> Result<Option<(f64, f64)>> {
@@ -397,7 +456,15 @@ fn output_outlier_detection_result(matches: &clap::ArgMatches, result: &OutlierD
             if !result.outliers.is_empty() {
                 println!("\nOutlier Details:");
                 for outlier in &result.outliers {
-                    println!("  Index: {} (Value: {:.3})", outlier.index, outlier.value);
+                    match &outlier.record {
+                        Some(record) => println!(
+                            "  Index: {} (Value: {:.3}) at {}",
+                            outlier.index, outlier.value, record.source
+                        ),
+                        None => {
+                            println!("  Index: {} (Value: {:.3})", outlier.index, outlier.value)
+                        }
+                    }
                 }
             }
         }
@@ -411,7 +478,9 @@ fn output_outlier_detection_result(matches: &clap::ArgMatches, result: &OutlierD
                     "index": o.index,
                     "value": o.value,
                     "score": o.score,
-                    "is_outlier": o.is_outlier
+                    "is_outlier": o.is_outlier,
+                    "source": o.record.as_ref().map(|r| &r.source),
+                    "text": o.record.as_ref().map(|r| &r.text)
                 })).collect::<Vec<_>>()
             });
             println!("{}", serde_json::to_string_pretty(&output).unwrap());
@@ -497,10 +566,12 @@ fn print_text_output(result: &NormalResult, quiet: bool, verbose: bool, no_color
     if verbose {
         println!();
         println!("Normality Tests:");
-        println!(
-            "  Shapiro-Wilk: W={:.3}, p={:.3}",
-            result.shapiro_wilk_statistic, result.shapiro_wilk_p_value
-        );
+        match (result.shapiro_wilk_statistic, result.shapiro_wilk_p_value) {
+            (Some(statistic), Some(p_value)) => {
+                println!("  Shapiro-Wilk: W={statistic:.3}, p={p_value:.3}")
+            }
+            _ => println!("  Shapiro-Wilk: n/a (requires 3-5000 values)"),
+        }
         println!(
             "  Anderson-Darling: A²={:.3}, p={:.3}",
             result.anderson_darling_statistic, result.anderson_darling_p_value
@@ -789,6 +860,9 @@ fn output_advanced_outlier_result(_matches: &ArgMatches, result: &AdvancedOutlie
                 "  Index {}: Value={:.3}, Score={:.3}, Confidence={:.3}",
                 outlier.index, outlier.value, outlier.outlier_score, outlier.confidence
             );
+            if let Some(record) = &outlier.record {
+                println!("    at {}: {}", record.source, record.text);
+            }
         }
     }
 
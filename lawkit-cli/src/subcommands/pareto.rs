@@ -1,19 +1,50 @@
 use crate::colors;
-// Removed unused imports: get_optimized_reader, setup_automatic_optimization_config
+use crate::common_options::{
+    filter_records, get_input_options, get_power_law_options, parse_output_format,
+    read_selected_input, read_selected_stdin, run_batch_if_requested, run_group_by_if_requested,
+};
 use clap::ArgMatches;
 use lawkit_core::{
     common::{
         filtering::{apply_number_filter, NumberFilter},
-        input::{parse_input_auto, parse_text_input},
+        grouping::analyze_groups,
+        input::{parse_input_auto_with, parse_text_input_with, InputOptions},
         memory::{streaming_pareto_analysis, MemoryConfig},
+        output::formatter::format_output,
         risk::RiskLevel,
         streaming_io::OptimizedFileReader,
     },
     error::{BenfError, Result},
-    laws::pareto::{analyze_pareto_distribution, ParetoResult},
+    laws::pareto::{
+        analyze_abc_records, analyze_pareto_distribution, format_inequality_text,
+        format_power_law_text, parse_number_list, AbcCutoffs, InequalityOptions, ParetoResult,
+    },
 };
 
 pub fn run(matches: &ArgMatches) -> Result<()> {
+    let options = get_input_options(matches)?;
+
+    // ディレクトリまたは glob パターンはファイルごとに一括分析する
+    run_batch_if_requested(matches, &options, |name, numbers| {
+        analyze_numbers_with_options(matches, name.to_string(), numbers)
+    })?;
+
+    // --label は品目ごとの値を ABC 分析する
+    if let Some(label) = matches.get_one::<String>("label") {
+        let options = InputOptions {
+            group_by: Some(label.parse()?),
+            ..options.clone()
+        };
+        run_abc_analysis(matches, &options)?;
+    }
+
+    // --group-by はカテゴリごとに分析する
+    run_group_by_if_requested(matches, &options, |group_by, records, min_count| {
+        analyze_groups(group_by, records, min_count, |name, numbers| {
+            analyze_numbers_with_options(matches, name.to_string(), numbers)
+        })
+    })?;
+
     // Determine input source based on arguments
     if matches.get_flag("verbose") {
         eprintln!(
@@ -24,7 +55,7 @@ pub fn run(matches: &ArgMatches) -> Result<()> {
 
     if let Some(input) = matches.get_one::<String>("input") {
         // Use auto-detection for file vs string input
-        match parse_input_auto(input) {
+        match parse_input_auto_with(input, &options) {
             Ok(numbers) => {
                 if numbers.is_empty() {
                     eprintln!("Error: No valid numbers found in input");
@@ -66,22 +97,35 @@ pub fn run(matches: &ArgMatches) -> Result<()> {
         }
 
         // ストリーミング処理でインクリメンタル分析を実行
-        let numbers = match reader.read_lines_streaming(|line: String| {
-            if matches.get_flag("verbose") {
-                eprintln!("Debug: Processing line: '{line}'");
+        // 列・シート・範囲の指定があれば stdin 全体を表として読み込む
+        let numbers = if options.has_selection() {
+            match read_selected_stdin(&mut reader, &options) {
+                Ok(selected) => selected.into_iter().map(|record| record.value).collect(),
+                Err(e) => {
+                    eprintln!("Error processing input: {e}");
+                    std::process::exit(1);
+                }
             }
-            parse_text_input(&line).map(Some).or(Ok(None))
-        }) {
-            Ok(nested_numbers) => {
-                let flattened: Vec<f64> = nested_numbers.into_iter().flatten().collect();
+        } else {
+            match reader.read_lines_streaming(|line: String| {
                 if matches.get_flag("verbose") {
-                    eprintln!("Debug: Collected {} numbers from stream", flattened.len());
+                    eprintln!("Debug: Processing line: '{line}'");
+                }
+                parse_text_input_with(&line, &options)
+                    .map(Some)
+                    .or(Ok(None))
+            }) {
+                Ok(nested_numbers) => {
+                    let flattened: Vec<f64> = nested_numbers.into_iter().flatten().collect();
+                    if matches.get_flag("verbose") {
+                        eprintln!("Debug: Collected {} numbers from stream", flattened.len());
+                    }
+                    flattened
+                }
+                Err(e) => {
+                    eprintln!("Analysis error: {e}");
+                    std::process::exit(1);
                 }
-                flattened
-            }
-            Err(e) => {
-                eprintln!("Analysis error: {e}");
-                std::process::exit(1);
             }
         };
 
@@ -142,6 +186,29 @@ pub fn run(matches: &ArgMatches) -> Result<()> {
     }
 }
 
+/// ラベル列と値の組を ABC 分類して出力し、集中度のリスクレベルで終了する
+fn run_abc_analysis(matches: &ArgMatches, options: &InputOptions) -> Result<()> {
+    let format = parse_output_format(matches);
+    let cutoffs = match matches.get_one::<String>("abc-cutoffs") {
+        Some(cutoffs) => cutoffs.parse()?,
+        None => AbcCutoffs::default(),
+    };
+    let dataset_name = matches
+        .get_one::<String>("input")
+        .map_or("stdin", String::as_str);
+
+    let records = filter_records(matches, &read_selected_input(matches, options)?)?;
+    let result = analyze_abc_records(&records, dataset_name, &cutoffs)?;
+    if matches.get_flag("verbose") {
+        eprintln!(
+            "Debug: {} items classified, {} numbers without a label",
+            result.items_analyzed, result.unlabelled
+        );
+    }
+    println!("{}", format_output(&result, &format)?);
+    std::process::exit(result.risk_level.exit_code());
+}
+
 fn output_results(matches: &clap::ArgMatches, result: &ParetoResult) {
     let format = matches.get_one::<String>("format").unwrap();
     let quiet = matches.get_flag("quiet");
@@ -246,6 +313,14 @@ fn print_text_output(
             println!("  Recommendation: Consider focusing efforts on high-value activities");
         }
     }
+    if let Some(ref fit) = result.power_law {
+        println!();
+        print!("{}", format_power_law_text(fit));
+    }
+    if let Some(ref indices) = result.inequality {
+        println!();
+        print!("{}", format_inequality_text(indices));
+    }
 }
 
 fn print_pareto_interpretation(result: &ParetoResult, no_color: bool) {
@@ -309,15 +384,51 @@ fn print_json_output(result: &ParetoResult) {
         output["custom_percentiles"] = json!(percentiles);
     }
 
+    if let Some(ref fit) = result.power_law {
+        output["power_law"] = json!(fit);
+    }
+
+    if let Some(ref indices) = result.inequality {
+        output["inequality"] = json!(indices);
+    }
+
     println!("{}", serde_json::to_string_pretty(&output).unwrap());
 }
 
 fn print_csv_output(result: &ParetoResult) {
+    let (power_law_header, power_law_values) = match result.power_law {
+        Some(ref fit) => (
+            ",power_law_alpha,power_law_x_min,power_law_p_value",
+            format!(
+                ",{:.3},{},{}",
+                fit.alpha,
+                fit.x_min,
+                fit.p_value.map_or(String::new(), |p| format!("{p:.3}"))
+            ),
+        ),
+        None => ("", String::new()),
+    };
+    let (inequality_header, inequality_values) = match result.inequality {
+        Some(ref indices) => (
+            ",gini,theil_t,herfindahl_hirschman,palma_ratio,hoover_index",
+            format!(
+                ",{:.4},{:.4},{:.4},{},{:.4}",
+                indices.gini,
+                indices.theil_t,
+                indices.herfindahl_hirschman,
+                indices
+                    .palma_ratio
+                    .map_or(String::new(), |palma| format!("{palma:.3}")),
+                indices.hoover_index
+            ),
+        ),
+        None => ("", String::new()),
+    };
     println!(
-        "dataset,numbers_analyzed,risk_level,pareto_ratio,concentration_index,top_20_percent_share"
+        "dataset,numbers_analyzed,risk_level,pareto_ratio,concentration_index,top_20_percent_share{power_law_header}{inequality_header}"
     );
     println!(
-        "{},{},{:?},{:.3},{:.3},{:.1}",
+        "{},{},{:?},{:.3},{:.3},{:.1}{power_law_values}{inequality_values}",
         result.dataset_name,
         result.numbers_analyzed,
         result.risk_level,
@@ -334,6 +445,41 @@ fn print_yaml_output(result: &ParetoResult) {
     println!("pareto_ratio: {:.3}", result.pareto_ratio);
     println!("concentration_index: {:.3}", result.concentration_index);
     println!("top_20_percent_share: {:.1}", result.top_20_percent_share);
+    if let Some(ref fit) = result.power_law {
+        println!("power_law:");
+        println!("  alpha: {:.3}", fit.alpha);
+        println!("  x_min: {}", fit.x_min);
+        if let Some(p_value) = fit.p_value {
+            println!("  p_value: {p_value:.3}");
+        }
+    }
+    if let Some(ref indices) = result.inequality {
+        println!("inequality:");
+        println!("  gini: {:.4}", indices.gini);
+        if let Some(ref interval) = indices.gini_confidence_interval {
+            println!("  gini_ci: [{:.4}, {:.4}]", interval.lower, interval.upper);
+        }
+        println!("  theil_t: {:.4}", indices.theil_t);
+        if let Some(theil_l) = indices.theil_l {
+            println!("  theil_l: {theil_l:.4}");
+        }
+        println!("  atkinson:");
+        for atkinson in &indices.atkinson {
+            println!("    \"{}\": {:.4}", atkinson.epsilon, atkinson.value);
+        }
+        println!(
+            "  herfindahl_hirschman: {:.4}",
+            indices.herfindahl_hirschman
+        );
+        if let Some(palma) = indices.palma_ratio {
+            println!("  palma_ratio: {palma:.3}");
+        }
+        println!("  hoover_index: {:.4}", indices.hoover_index);
+        println!("  top_shares:");
+        for top in &indices.top_shares {
+            println!("    \"{}\": {:.1}", top.percentile, top.share);
+        }
+    }
 }
 
 fn print_toml_output(result: &ParetoResult) {
@@ -343,6 +489,42 @@ fn print_toml_output(result: &ParetoResult) {
     println!("pareto_ratio = {:.3}", result.pareto_ratio);
     println!("concentration_index = {:.3}", result.concentration_index);
     println!("top_20_percent_share = {:.1}", result.top_20_percent_share);
+    if let Some(ref fit) = result.power_law {
+        println!();
+        println!("[power_law]");
+        println!("alpha = {:.3}", fit.alpha);
+        println!("x_min = {}", fit.x_min);
+        if let Some(p_value) = fit.p_value {
+            println!("p_value = {p_value:.3}");
+        }
+    }
+    if let Some(ref indices) = result.inequality {
+        println!();
+        println!("[inequality]");
+        println!("gini = {:.4}", indices.gini);
+        if let Some(ref interval) = indices.gini_confidence_interval {
+            println!("gini_ci = [{:.4}, {:.4}]", interval.lower, interval.upper);
+        }
+        println!("theil_t = {:.4}", indices.theil_t);
+        if let Some(theil_l) = indices.theil_l {
+            println!("theil_l = {theil_l:.4}");
+        }
+        println!("herfindahl_hirschman = {:.4}", indices.herfindahl_hirschman);
+        if let Some(palma) = indices.palma_ratio {
+            println!("palma_ratio = {palma:.3}");
+        }
+        println!("hoover_index = {:.4}", indices.hoover_index);
+        println!();
+        println!("[inequality.atkinson]");
+        for atkinson in &indices.atkinson {
+            println!("\"{}\" = {:.4}", atkinson.epsilon, atkinson.value);
+        }
+        println!();
+        println!("[inequality.top_shares]");
+        for top in &indices.top_shares {
+            println!("\"{}\" = {:.1}", top.percentile, top.share);
+        }
+    }
 }
 
 fn print_xml_output(result: &ParetoResult) {
@@ -363,6 +545,53 @@ fn print_xml_output(result: &ParetoResult) {
         "  <top_20_percent_share>{:.1}</top_20_percent_share>",
         result.top_20_percent_share
     );
+    if let Some(ref fit) = result.power_law {
+        println!("  <power_law>");
+        println!("    <alpha>{:.3}</alpha>", fit.alpha);
+        println!("    <x_min>{}</x_min>", fit.x_min);
+        if let Some(p_value) = fit.p_value {
+            println!("    <p_value>{p_value:.3}</p_value>");
+        }
+        println!("  </power_law>");
+    }
+    if let Some(ref indices) = result.inequality {
+        println!("  <inequality>");
+        println!("    <gini>{:.4}</gini>", indices.gini);
+        if let Some(ref interval) = indices.gini_confidence_interval {
+            println!(
+                "    <gini_ci level=\"{}\" lower=\"{:.4}\" upper=\"{:.4}\"/>",
+                interval.level, interval.lower, interval.upper
+            );
+        }
+        println!("    <theil_t>{:.4}</theil_t>", indices.theil_t);
+        if let Some(theil_l) = indices.theil_l {
+            println!("    <theil_l>{theil_l:.4}</theil_l>");
+        }
+        for atkinson in &indices.atkinson {
+            println!(
+                "    <atkinson epsilon=\"{}\">{:.4}</atkinson>",
+                atkinson.epsilon, atkinson.value
+            );
+        }
+        println!(
+            "    <herfindahl_hirschman>{:.4}</herfindahl_hirschman>",
+            indices.herfindahl_hirschman
+        );
+        if let Some(palma) = indices.palma_ratio {
+            println!("    <palma_ratio>{palma:.3}</palma_ratio>");
+        }
+        println!(
+            "    <hoover_index>{:.4}</hoover_index>",
+            indices.hoover_index
+        );
+        for top in &indices.top_shares {
+            println!(
+                "    <top_share percentile=\"{}\">{:.1}</top_share>",
+                top.percentile, top.share
+            );
+        }
+        println!("  </inequality>");
+    }
     println!("</pareto_analysis>");
 }
 
@@ -422,9 +651,39 @@ fn analyze_numbers_with_options(
         result = result.with_custom_percentiles(&percentiles, &filtered_numbers);
     }
 
+    // --power-law はべき乗則の裾を最尤フィットする
+    if let Some(power_law) = get_power_law_options(matches)? {
+        result = result.with_power_law_fit(&filtered_numbers, &power_law)?;
+    }
+
+    // --indices は不平等指標一式を計算する
+    if let Some(inequality) = get_inequality_options(matches)? {
+        result = result.with_inequality_indices(&filtered_numbers, &inequality)?;
+    }
+
     Ok(result)
 }
 
+/// --indices / --atkinson / --top-shares / --gini-bootstrap の指定（計算しない場合は None）
+fn get_inequality_options(matches: &ArgMatches) -> Result<Option<InequalityOptions>> {
+    if !matches.get_flag("indices") {
+        return Ok(None);
+    }
+    let mut options = InequalityOptions::default();
+    if let Some(epsilons) = matches.get_one::<String>("atkinson") {
+        options.atkinson_epsilons = parse_number_list(epsilons)?;
+    }
+    if let Some(percentiles) = matches.get_one::<String>("top-shares") {
+        options.top_share_percentiles = parse_number_list(percentiles)?;
+    }
+    if let Some(bootstrap) = matches.get_one::<String>("gini-bootstrap") {
+        options.bootstrap = bootstrap
+            .parse()
+            .map_err(|_| BenfError::ParseError("無効なブートストラップ回数".to_string()))?;
+    }
+    Ok(Some(options))
+}
+
 fn format_lorenz_curve(result: &ParetoResult) -> String {
     let mut output = String::new();
     const CHART_WIDTH: usize = 50;
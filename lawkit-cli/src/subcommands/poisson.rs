@@ -1,10 +1,15 @@
 use crate::colors;
-use crate::common_options::{get_optimized_reader, setup_automatic_optimization_config};
-use clap::ArgMatches;
+use crate::common_options::{
+    get_input_options, get_optimized_reader, needs_format_aware_read, read_selected_input,
+    read_selected_stdin, run_batch_if_requested, run_group_by_if_requested,
+    setup_automatic_optimization_config,
+};
+use clap::{parser::ValueSource, ArgMatches};
 use lawkit_core::{
     common::{
-        filtering::{apply_number_filter, NumberFilter},
-        input::{parse_input_auto, parse_text_input},
+        filtering::{apply_number_filter, NumberFilter, RiskThreshold},
+        grouping::analyze_groups,
+        input::{parse_input_auto_with, parse_text_input_with},
         memory::{streaming_poisson_analysis, MemoryConfig},
         streaming_io::OptimizedFileReader,
     },
@@ -17,6 +22,8 @@ use lawkit_core::{
 };
 
 pub fn run(matches: &ArgMatches) -> Result<()> {
+    let options = get_input_options(matches)?;
+
     // 特殊モードの確認（フラグが明示的に指定された場合を優先）
     if matches.get_flag("predict") {
         return run_prediction_mode(matches);
@@ -34,6 +41,18 @@ pub fn run(matches: &ArgMatches) -> Result<()> {
         }
     }
 
+    // ディレクトリまたは glob パターンはファイルごとに一括分析する
+    run_batch_if_requested(matches, &options, |name, numbers| {
+        analyze_numbers_with_options(matches, name.to_string(), numbers)
+    })?;
+
+    // --group-by はカテゴリごとに分析する
+    run_group_by_if_requested(matches, &options, |group_by, records, min_count| {
+        analyze_groups(group_by, records, min_count, |name, numbers| {
+            analyze_numbers_with_options(matches, name.to_string(), numbers)
+        })
+    })?;
+
     // 自動最適化設定をセットアップ
     let (_parallel_config, _memory_config) = setup_automatic_optimization_config();
 
@@ -48,7 +67,7 @@ pub fn run(matches: &ArgMatches) -> Result<()> {
     // 入力データ処理
     let numbers = if let Some(input) = matches.get_one::<String>("input") {
         // ファイル入力の場合
-        match parse_input_auto(input) {
+        match parse_input_auto_with(input, &options) {
             Ok(numbers) => {
                 if numbers.is_empty() {
                     eprintln!("Error: No valid numbers found in input");
@@ -75,19 +94,32 @@ pub fn run(matches: &ArgMatches) -> Result<()> {
             );
         }
 
-        let numbers = match reader
-            .read_lines_streaming(|line: String| parse_text_input(&line).map(Some).or(Ok(None)))
-        {
-            Ok(nested_numbers) => {
-                let flattened: Vec<f64> = nested_numbers.into_iter().flatten().collect();
-                if matches.get_flag("verbose") {
-                    eprintln!("Debug: Collected {} numbers from stream", flattened.len());
+        // 列・シート・範囲の指定があれば stdin 全体を表として読み込む
+        let numbers = if options.has_selection() {
+            match read_selected_stdin(&mut reader, &options) {
+                Ok(selected) => selected.into_iter().map(|record| record.value).collect(),
+                Err(e) => {
+                    eprintln!("Error processing input: {e}");
+                    std::process::exit(1);
                 }
-                flattened
             }
-            Err(e) => {
-                eprintln!("Analysis error: {e}");
-                std::process::exit(1);
+        } else {
+            match reader.read_lines_streaming(|line: String| {
+                parse_text_input_with(&line, &options)
+                    .map(Some)
+                    .or(Ok(None))
+            }) {
+                Ok(nested_numbers) => {
+                    let flattened: Vec<f64> = nested_numbers.into_iter().flatten().collect();
+                    if matches.get_flag("verbose") {
+                        eprintln!("Debug: Collected {} numbers from stream", flattened.len());
+                    }
+                    flattened
+                }
+                Err(e) => {
+                    eprintln!("Analysis error: {e}");
+                    std::process::exit(1);
+                }
             }
         };
 
@@ -165,6 +197,11 @@ pub fn run(matches: &ArgMatches) -> Result<()> {
 
 fn get_numbers_from_input(matches: &ArgMatches) -> Result<Vec<f64>> {
     let (_parallel_config, _memory_config) = setup_automatic_optimization_config();
+    let options = get_input_options(matches)?;
+    if needs_format_aware_read(matches, &options) {
+        let records = read_selected_input(matches, &options)?;
+        return Ok(records.into_iter().map(|record| record.value).collect());
+    }
 
     let buffer = if let Some(input) = matches.get_one::<String>("input") {
         if input == "-" {
@@ -177,7 +214,7 @@ fn get_numbers_from_input(matches: &ArgMatches) -> Result<Vec<f64>> {
     };
 
     let data = buffer.map_err(|e| BenfError::ParseError(e.to_string()))?;
-    parse_text_input(&data)
+    parse_text_input_with(&data, &options)
 }
 
 fn run_poisson_test_mode(matches: &ArgMatches, test_type: &str) -> Result<()> {
@@ -675,8 +712,10 @@ fn analyze_numbers_with_options(
         return Err(BenfError::InsufficientData(filtered_numbers.len()));
     }
 
-    // Parse confidence level
-    let confidence = if let Some(confidence_str) = matches.get_one::<String>("confidence") {
+    // Parse confidence level; only an explicit --confidence adds p-value risk bands
+    let significance_level = if matches.value_source("confidence") == Some(ValueSource::CommandLine)
+    {
+        let confidence_str = matches.get_one::<String>("confidence").unwrap();
         let conf = confidence_str
             .parse::<f64>()
             .map_err(|_| BenfError::ParseError("無効な信頼度レベル".to_string()))?;
@@ -685,19 +724,18 @@ fn analyze_numbers_with_options(
                 "信頼度レベルは0.01から0.99の間である必要があります".to_string(),
             ));
         }
-        conf
+        Some(1.0 - conf)
     } else {
-        0.95
+        None
     };
 
     // Perform Poisson distribution analysis
-    // TODO: Integrate confidence level into analysis
-    let mut result = analyze_poisson_distribution(&filtered_numbers, &dataset_name)?;
-
-    // For now, just store confidence level as a comment in the dataset name
-    if confidence != 0.95 {
-        result.dataset_name = format!("{} (confidence: {:.2})", result.dataset_name, confidence);
-    }
+    let result = PoissonResult::new_with_significance(
+        dataset_name,
+        &filtered_numbers,
+        &RiskThreshold::Auto,
+        significance_level,
+    )?;
 
     Ok(result)
 }
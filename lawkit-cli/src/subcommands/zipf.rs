@@ -1,23 +1,47 @@
 use crate::colors;
-use crate::common_options::get_optimized_reader;
+use crate::common_options::{
+    get_input_options, get_optimized_reader, get_power_law_options, get_text_tokenizer,
+    run_batch_if_requested, run_group_by_if_requested,
+};
 use clap::ArgMatches;
 use lawkit_core::{
     common::{
         filtering::{apply_number_filter, NumberFilter},
-        input::{parse_input_auto, parse_text_input},
+        grouping::analyze_groups,
+        input::{parse_input_auto_with, parse_text_records_with, provenance::record_values},
         memory::{streaming_zipf_analysis, MemoryConfig},
         risk::RiskLevel,
         streaming_io::OptimizedFileReader,
     },
     error::{BenfError, Result},
-    laws::zipf::{
-        analyze_numeric_zipf, analyze_text_zipf, analyze_text_zipf_from_frequencies, ZipfResult,
+    laws::{
+        pareto::format_power_law_text,
+        zipf::{
+            analyze_numeric_zipf, analyze_text_heaps_with, analyze_text_zipf_from_frequencies,
+            analyze_text_zipf_with, extract_word_frequencies_with, format_heaps_text,
+            format_zipf_fits_text, HeapsResult, TokenizerInfo, ZipfResult,
+        },
     },
 };
 
 pub fn run(matches: &ArgMatches) -> Result<()> {
+    let options = get_input_options(matches)?;
     let is_text_mode = matches.get_flag("text");
 
+    // ディレクトリ・glob パターンの一括分析と --group-by は数値モードのみ
+    if !is_text_mode {
+        run_batch_if_requested(matches, &options, |name, numbers| {
+            analyze_numbers_with_options(matches, name.to_string(), numbers)
+        })?;
+
+        // --group-by はカテゴリごとに分析する
+        run_group_by_if_requested(matches, &options, |group_by, records, min_count| {
+            analyze_groups(group_by, records, min_count, |name, numbers| {
+                analyze_numbers_with_options(matches, name.to_string(), numbers)
+            })
+        })?;
+    }
+
     // Determine input source based on arguments
     if matches.get_flag("verbose") {
         eprintln!(
@@ -27,9 +51,21 @@ pub fn run(matches: &ArgMatches) -> Result<()> {
         eprintln!("Debug: text mode = {is_text_mode}");
     }
 
+    let tokenizer = if is_text_mode {
+        match get_text_tokenizer(matches) {
+            Ok(tokenizer) => Some(tokenizer),
+            Err(e) => {
+                eprintln!("Error: {e}");
+                std::process::exit(2);
+            }
+        }
+    } else {
+        None
+    };
+
     if let Some(input) = matches.get_one::<String>("input") {
         // Use auto-detection for file vs string input
-        if is_text_mode {
+        if let Some(ref tokenizer) = tokenizer {
             // Text mode: read file or use as text directly
             let buffer = if input == "-" {
                 match get_optimized_reader(None) {
@@ -49,7 +85,21 @@ pub fn run(matches: &ArgMatches) -> Result<()> {
                 }
             };
 
-            match analyze_text_zipf(&buffer, input) {
+            let result = analyze_text_zipf_with(&buffer, input, tokenizer)
+                .and_then(|result| {
+                    add_fits_if_requested(matches, result, || {
+                        extract_word_frequencies_with(&buffer, tokenizer)
+                            .into_iter()
+                            .map(|(_, count)| count as f64)
+                            .collect()
+                    })
+                })
+                .and_then(|result| {
+                    add_heaps_if_requested(matches, result, || {
+                        analyze_text_heaps_with(&buffer, tokenizer)
+                    })
+                });
+            match result {
                 Ok(result) => {
                     output_results(matches, &result);
                     std::process::exit(result.risk_level.exit_code());
@@ -61,7 +111,7 @@ pub fn run(matches: &ArgMatches) -> Result<()> {
             }
         } else {
             // Numeric mode
-            match parse_input_auto(input) {
+            match parse_input_auto_with(input, &options) {
                 Ok(numbers) => {
                     if numbers.is_empty() {
                         eprintln!("Error: No valid numbers found in input");
@@ -95,7 +145,7 @@ pub fn run(matches: &ArgMatches) -> Result<()> {
             );
         }
 
-        if is_text_mode {
+        if let Some(ref tokenizer) = tokenizer {
             // Text mode with streaming
             let mut reader = OptimizedFileReader::from_stdin();
             let memory_config = MemoryConfig::default();
@@ -104,9 +154,7 @@ pub fn run(matches: &ArgMatches) -> Result<()> {
             let mut words = Vec::new();
             match reader.read_lines_streaming(|line: String| {
                 // Extract words from line
-                let line_words: Vec<String> =
-                    line.split_whitespace().map(|s| s.to_string()).collect();
-                words.extend(line_words);
+                words.extend(tokenizer.tokenize(&line));
                 Ok(None::<()>)
             }) {
                 Ok(_) => {}
@@ -151,7 +199,19 @@ pub fn run(matches: &ArgMatches) -> Result<()> {
 
             // Convert IncrementalZipf to ZipfResult
             let frequencies = chunk_result.result.get_sorted_frequencies();
-            let result = match analyze_text_zipf_from_frequencies(&frequencies, "stdin") {
+            let result = analyze_text_zipf_from_frequencies(&frequencies, "stdin")
+                .map(|result| result.with_tokenizer(tokenizer.info()))
+                .and_then(|result| {
+                    add_fits_if_requested(matches, result, || {
+                        frequencies.iter().map(|(_, count)| *count as f64).collect()
+                    })
+                })
+                .and_then(|result| {
+                    add_heaps_if_requested(matches, result, || {
+                        HeapsResult::from_zipf(&chunk_result.result)
+                    })
+                });
+            let result = match result {
                 Ok(result) => result,
                 Err(e) => {
                     eprintln!("Analysis error: {e}");
@@ -170,7 +230,7 @@ pub fn run(matches: &ArgMatches) -> Result<()> {
                     std::process::exit(1);
                 }
             };
-            let numbers = match parse_text_input(&buffer) {
+            let numbers = match parse_text_records_with(&buffer, &options).and_then(record_values) {
                 Ok(numbers) => {
                     if matches.get_flag("verbose") {
                         eprintln!("Debug: Collected {} numbers from input", numbers.len());
@@ -235,6 +295,9 @@ fn print_text_output(result: &ZipfResult, quiet: bool, verbose: bool, no_color:
     println!();
     println!("Dataset: {}", result.dataset_name);
     println!("Numbers analyzed: {}", result.numbers_analyzed);
+    if let Some(ref tokenizer) = result.tokenizer {
+        println!("Tokenizer: {}", format_tokenizer(tokenizer));
+    }
     match result.risk_level {
         RiskLevel::Critical => println!("{}", colors::level_critical("Dataset analysis", no_color)),
         RiskLevel::High => println!("{}", colors::level_high("Dataset analysis", no_color)),
@@ -246,6 +309,12 @@ fn print_text_output(result: &ZipfResult, quiet: bool, verbose: bool, no_color:
     println!("Rank-Frequency Distribution:");
     println!("{}", format_rank_frequency_chart(result));
 
+    println!();
+    print!(
+        "{}",
+        format_zipf_fits_text(&result.zipf_fit, result.zipf_mandelbrot.as_ref())
+    );
+
     if verbose {
         println!();
         println!("Zipf Metrics:");
@@ -269,6 +338,15 @@ fn print_text_output(result: &ZipfResult, quiet: bool, verbose: bool, no_color:
         println!("Interpretation:");
         print_zipf_interpretation(result);
     }
+
+    if let Some(ref fit) = result.power_law {
+        println!();
+        print!("{}", format_power_law_text(fit));
+    }
+    if let Some(ref heaps) = result.heaps {
+        println!();
+        print!("{}", format_heaps_text(heaps));
+    }
 }
 
 fn print_zipf_interpretation(result: &ZipfResult) {
@@ -311,7 +389,7 @@ fn print_zipf_interpretation(result: &ZipfResult) {
 fn print_json_output(result: &ZipfResult) {
     use serde_json::json;
 
-    let output = json!({
+    let mut output = json!({
         "dataset": result.dataset_name,
         "numbers_analyzed": result.numbers_analyzed,
         "risk_level": format!("{:?}", result.risk_level),
@@ -324,23 +402,73 @@ fn print_json_output(result: &ZipfResult) {
         "concentration_index": result.concentration_index,
         "diversity_index": result.diversity_index,
         "power_law_fit": result.power_law_fit,
+        "zipf_fit": result.zipf_fit,
         "rank_frequency_pairs": result.rank_frequency_pairs
     });
 
+    if let Some(ref mandelbrot) = result.zipf_mandelbrot {
+        output["zipf_mandelbrot"] = json!(mandelbrot);
+    }
+
+    if let Some(ref fit) = result.power_law {
+        output["power_law"] = json!(fit);
+    }
+
+    if let Some(ref heaps) = result.heaps {
+        output["heaps"] = json!(heaps);
+    }
+
+    if let Some(ref tokenizer) = result.tokenizer {
+        output["tokenizer"] = json!(tokenizer);
+    }
+
     println!("{}", serde_json::to_string_pretty(&output).unwrap());
 }
 
 fn print_csv_output(result: &ZipfResult) {
-    println!("dataset,numbers_analyzed,risk_level,zipf_exponent,correlation_coefficient,distribution_quality,power_law_fit");
+    let (mandelbrot_header, mandelbrot_values) = match result.zipf_mandelbrot {
+        Some(ref mandelbrot) => (
+            ",mandelbrot_exponent,mandelbrot_q",
+            format!(",{:.4},{:.4}", mandelbrot.exponent, mandelbrot.q),
+        ),
+        None => ("", String::new()),
+    };
+    let (power_law_header, power_law_values) = match result.power_law {
+        Some(ref fit) => (
+            ",power_law_alpha,power_law_x_min,power_law_p_value",
+            format!(
+                ",{:.3},{},{}",
+                fit.alpha,
+                fit.x_min,
+                fit.p_value.map_or(String::new(), |p| format!("{p:.3}"))
+            ),
+        ),
+        None => ("", String::new()),
+    };
+    let (heaps_header, heaps_values) = match result.heaps {
+        Some(ref heaps) => (
+            ",heaps_k,heaps_beta,heaps_r_squared",
+            format!(",{:.4},{:.4},{:.4}", heaps.k, heaps.beta, heaps.r_squared),
+        ),
+        None => ("", String::new()),
+    };
+    let (tokenizer_header, tokenizer_values) = match result.tokenizer {
+        Some(ref tokenizer) => (",tokenizer", format!(",\"{}\"", tokenizer.name)),
+        None => ("", String::new()),
+    };
+    println!("dataset,numbers_analyzed,risk_level,zipf_exponent,correlation_coefficient,distribution_quality,power_law_fit,mle_exponent,mle_ci_lower,mle_ci_upper{mandelbrot_header}{power_law_header}{heaps_header}{tokenizer_header}");
     println!(
-        "{},{},{:?},{:.3},{:.3},{:.3},{:.3}",
+        "{},{},{:?},{:.3},{:.3},{:.3},{:.3},{:.4},{:.4},{:.4}{mandelbrot_values}{power_law_values}{heaps_values}{tokenizer_values}",
         result.dataset_name,
         result.numbers_analyzed,
         result.risk_level,
         result.zipf_exponent,
         result.correlation_coefficient,
         result.distribution_quality,
-        result.power_law_fit
+        result.power_law_fit,
+        result.zipf_fit.exponent,
+        result.zipf_fit.confidence_interval.lower,
+        result.zipf_fit.confidence_interval.upper
     );
 }
 
@@ -355,6 +483,54 @@ fn print_yaml_output(result: &ZipfResult) {
     );
     println!("distribution_quality: {:.3}", result.distribution_quality);
     println!("power_law_fit: {:.3}", result.power_law_fit);
+    println!("zipf_fit:");
+    println!("  exponent: {:.4}", result.zipf_fit.exponent);
+    println!(
+        "  confidence_interval: [{:.4}, {:.4}]",
+        result.zipf_fit.confidence_interval.lower, result.zipf_fit.confidence_interval.upper
+    );
+    println!(
+        "  chi_square: {:.3}",
+        result.zipf_fit.goodness_of_fit.chi_square
+    );
+    println!(
+        "  ks_statistic: {:.4}",
+        result.zipf_fit.goodness_of_fit.ks_statistic
+    );
+    if let Some(ref mandelbrot) = result.zipf_mandelbrot {
+        println!("zipf_mandelbrot:");
+        println!("  exponent: {:.4}", mandelbrot.exponent);
+        println!("  q: {:.4}", mandelbrot.q);
+        println!("  chi_square: {:.3}", mandelbrot.goodness_of_fit.chi_square);
+        println!(
+            "  ks_statistic: {:.4}",
+            mandelbrot.goodness_of_fit.ks_statistic
+        );
+    }
+    if let Some(ref fit) = result.power_law {
+        println!("power_law:");
+        println!("  alpha: {:.3}", fit.alpha);
+        println!("  x_min: {}", fit.x_min);
+        if let Some(p_value) = fit.p_value {
+            println!("  p_value: {p_value:.3}");
+        }
+    }
+    if let Some(ref heaps) = result.heaps {
+        println!("heaps:");
+        println!("  k: {:.4}", heaps.k);
+        println!("  beta: {:.4}", heaps.beta);
+        println!("  r_squared: {:.4}", heaps.r_squared);
+        println!("  total_tokens: {}", heaps.total_tokens);
+        println!("  vocabulary_size: {}", heaps.vocabulary_size);
+    }
+    if let Some(ref tokenizer) = result.tokenizer {
+        println!("tokenizer:");
+        println!("  name: \"{}\"", tokenizer.name);
+        println!("  lowercase: {}", tokenizer.lowercase);
+        println!("  nfkc: {}", tokenizer.nfkc);
+        println!("  stopwords: {}", tokenizer.stopwords);
+        println!("  min_length: {}", tokenizer.min_length);
+    }
 }
 
 fn print_toml_output(result: &ZipfResult) {
@@ -368,6 +544,59 @@ fn print_toml_output(result: &ZipfResult) {
     );
     println!("distribution_quality = {:.3}", result.distribution_quality);
     println!("power_law_fit = {:.3}", result.power_law_fit);
+    println!();
+    println!("[zipf_fit]");
+    println!("exponent = {:.4}", result.zipf_fit.exponent);
+    println!(
+        "confidence_interval = [{:.4}, {:.4}]",
+        result.zipf_fit.confidence_interval.lower, result.zipf_fit.confidence_interval.upper
+    );
+    println!(
+        "chi_square = {:.3}",
+        result.zipf_fit.goodness_of_fit.chi_square
+    );
+    println!(
+        "ks_statistic = {:.4}",
+        result.zipf_fit.goodness_of_fit.ks_statistic
+    );
+    if let Some(ref mandelbrot) = result.zipf_mandelbrot {
+        println!();
+        println!("[zipf_mandelbrot]");
+        println!("exponent = {:.4}", mandelbrot.exponent);
+        println!("q = {:.4}", mandelbrot.q);
+        println!("chi_square = {:.3}", mandelbrot.goodness_of_fit.chi_square);
+        println!(
+            "ks_statistic = {:.4}",
+            mandelbrot.goodness_of_fit.ks_statistic
+        );
+    }
+    if let Some(ref fit) = result.power_law {
+        println!();
+        println!("[power_law]");
+        println!("alpha = {:.3}", fit.alpha);
+        println!("x_min = {}", fit.x_min);
+        if let Some(p_value) = fit.p_value {
+            println!("p_value = {p_value:.3}");
+        }
+    }
+    if let Some(ref heaps) = result.heaps {
+        println!();
+        println!("[heaps]");
+        println!("k = {:.4}", heaps.k);
+        println!("beta = {:.4}", heaps.beta);
+        println!("r_squared = {:.4}", heaps.r_squared);
+        println!("total_tokens = {}", heaps.total_tokens);
+        println!("vocabulary_size = {}", heaps.vocabulary_size);
+    }
+    if let Some(ref tokenizer) = result.tokenizer {
+        println!();
+        println!("[tokenizer]");
+        println!("name = \"{}\"", tokenizer.name);
+        println!("lowercase = {}", tokenizer.lowercase);
+        println!("nfkc = {}", tokenizer.nfkc);
+        println!("stopwords = {}", tokenizer.stopwords);
+        println!("min_length = {}", tokenizer.min_length);
+    }
 }
 
 fn print_xml_output(result: &ZipfResult) {
@@ -395,6 +624,65 @@ fn print_xml_output(result: &ZipfResult) {
         "  <power_law_fit>{:.3}</power_law_fit>",
         result.power_law_fit
     );
+    println!("  <zipf_fit>");
+    println!("    <exponent>{:.4}</exponent>", result.zipf_fit.exponent);
+    println!(
+        "    <confidence_interval lower=\"{:.4}\" upper=\"{:.4}\"/>",
+        result.zipf_fit.confidence_interval.lower, result.zipf_fit.confidence_interval.upper
+    );
+    println!(
+        "    <chi_square>{:.3}</chi_square>",
+        result.zipf_fit.goodness_of_fit.chi_square
+    );
+    println!(
+        "    <ks_statistic>{:.4}</ks_statistic>",
+        result.zipf_fit.goodness_of_fit.ks_statistic
+    );
+    println!("  </zipf_fit>");
+    if let Some(ref mandelbrot) = result.zipf_mandelbrot {
+        println!("  <zipf_mandelbrot>");
+        println!("    <exponent>{:.4}</exponent>", mandelbrot.exponent);
+        println!("    <q>{:.4}</q>", mandelbrot.q);
+        println!(
+            "    <chi_square>{:.3}</chi_square>",
+            mandelbrot.goodness_of_fit.chi_square
+        );
+        println!(
+            "    <ks_statistic>{:.4}</ks_statistic>",
+            mandelbrot.goodness_of_fit.ks_statistic
+        );
+        println!("  </zipf_mandelbrot>");
+    }
+    if let Some(ref fit) = result.power_law {
+        println!("  <power_law>");
+        println!("    <alpha>{:.3}</alpha>", fit.alpha);
+        println!("    <x_min>{}</x_min>", fit.x_min);
+        if let Some(p_value) = fit.p_value {
+            println!("    <p_value>{p_value:.3}</p_value>");
+        }
+        println!("  </power_law>");
+    }
+    if let Some(ref heaps) = result.heaps {
+        println!("  <heaps>");
+        println!("    <k>{:.4}</k>", heaps.k);
+        println!("    <beta>{:.4}</beta>", heaps.beta);
+        println!("    <r_squared>{:.4}</r_squared>", heaps.r_squared);
+        println!("    <total_tokens>{}</total_tokens>", heaps.total_tokens);
+        println!(
+            "    <vocabulary_size>{}</vocabulary_size>",
+            heaps.vocabulary_size
+        );
+        println!("  </heaps>");
+    }
+    if let Some(ref tokenizer) = result.tokenizer {
+        println!("  <tokenizer>");
+        println!("    <name>{}</name>", tokenizer.name);
+        println!("    <lowercase>{}</lowercase>", tokenizer.lowercase);
+        println!("    <nfkc>{}</nfkc>", tokenizer.nfkc);
+        println!("    <stopwords>{}</stopwords>", tokenizer.stopwords);
+        println!("    <min_length>{}</min_length>", tokenizer.min_length);
+        println!("  </tokenizer>");
+    }
     println!("</zipf_analysis>");
 }
 
@@ -441,7 +729,85 @@ fn analyze_numbers_with_options(
     }
 
     // Perform Zipf analysis
-    analyze_numeric_zipf(&filtered_numbers, &dataset_name)
+    let result = analyze_numeric_zipf(&filtered_numbers, &dataset_name)?;
+    add_fits_if_requested(matches, result, || {
+        filtered_numbers.into_iter().filter(|&x| x > 0.0).collect()
+    })
+}
+
+/// --confidence の信頼水準で Zipf 指数の信頼区間を求め、--mandelbrot・--power-law の
+/// 指定があれば頻度に Zipf–Mandelbrot 分布・べき乗則をフィットする
+fn add_fits_if_requested(
+    matches: &clap::ArgMatches,
+    result: ZipfResult,
+    frequencies: impl FnOnce() -> Vec<f64>,
+) -> Result<ZipfResult> {
+    let mut result = result.with_confidence_level(parse_confidence_level(matches)?)?;
+    let mandelbrot = matches.get_flag("mandelbrot");
+    let power_law = get_power_law_options(matches)?;
+    if !mandelbrot && power_law.is_none() {
+        return Ok(result);
+    }
+
+    let frequencies = frequencies();
+    if mandelbrot {
+        result = result.with_mandelbrot_fit(&frequencies)?;
+    }
+    match power_law {
+        Some(options) => result.with_power_law_fit(&frequencies, &options),
+        None => Ok(result),
+    }
+}
+
+/// --confidence（Zipf 指数の信頼区間の信頼水準）
+fn parse_confidence_level(matches: &clap::ArgMatches) -> Result<f64> {
+    let Some(confidence_str) = matches.get_one::<String>("confidence") else {
+        return Ok(0.95);
+    };
+    let confidence = confidence_str
+        .parse::<f64>()
+        .map_err(|_| BenfError::ParseError("無効な信頼度レベル".to_string()))?;
+    if !(0.01..=0.99).contains(&confidence) {
+        return Err(BenfError::ParseError(
+            "信頼度レベルは0.01から0.99の間である必要があります".to_string(),
+        ));
+    }
+    Ok(confidence)
+}
+
+/// --heaps が指定されていれば語彙成長（Heaps の法則）を計算して結果に加える
+fn add_heaps_if_requested(
+    matches: &clap::ArgMatches,
+    result: ZipfResult,
+    heaps: impl FnOnce() -> Result<HeapsResult>,
+) -> Result<ZipfResult> {
+    if matches.get_flag("heaps") {
+        Ok(result.with_heaps(heaps()?))
+    } else {
+        Ok(result)
+    }
+}
+
+/// トークナイザーの設定を 1 行で表す（例: "unicode-words (lowercase, NFKC, 12 stopwords)"）
+fn format_tokenizer(tokenizer: &TokenizerInfo) -> String {
+    let mut settings = Vec::new();
+    if tokenizer.lowercase {
+        settings.push("lowercase".to_string());
+    }
+    if tokenizer.nfkc {
+        settings.push("NFKC".to_string());
+    }
+    if tokenizer.stopwords > 0 {
+        settings.push(format!("{} stopwords", tokenizer.stopwords));
+    }
+    if tokenizer.min_length > 1 {
+        settings.push(format!("min length {}", tokenizer.min_length));
+    }
+    if settings.is_empty() {
+        tokenizer.name.clone()
+    } else {
+        format!("{} ({})", tokenizer.name, settings.join(", "))
+    }
 }
 
 fn format_rank_frequency_chart(result: &ZipfResult) -> String {
@@ -159,7 +159,7 @@ fn test_stdin_explicit_dash() {
     let mut cmd = lawkit();
     cmd.args(["benf", "-"]).write_stdin(SAMPLE_DATA);
     // Accept any exit code since stdin handling with "-" may vary
-    cmd.assert();
+    let _ = cmd.assert();
 }
 
 #[test]
@@ -168,3 +168,494 @@ fn test_stdin_implicit() {
     cmd.args(["benf"]).write_stdin(SAMPLE_DATA);
     cmd.assert().code(valid_exit_codes());
 }
+
+/// CSV with an ID column that would otherwise dominate the digit counts
+fn ledger_csv() -> String {
+    let mut csv = String::from("id,\"description\",amount\n");
+    for i in 0..40 {
+        csv.push_str(&format!(
+            "{},\"item, #{i}\",{}\n",
+            900_000 + i,
+            100 + i * 37
+        ));
+    }
+    csv
+}
+
+#[test]
+fn test_column_option_by_header() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("ledger.csv");
+    std::fs::write(&path, ledger_csv()).unwrap();
+
+    let mut cmd = lawkit();
+    cmd.args(["benf", path.to_str().unwrap(), "--column", "amount"]);
+    cmd.assert()
+        .code(valid_exit_codes())
+        .stdout(predicate::str::contains("Numbers analyzed: 40"));
+}
+
+#[test]
+fn test_column_option_on_stdin() {
+    let mut cmd = lawkit();
+    cmd.args(["pareto", "--column", "3", "-f", "json"])
+        .write_stdin(ledger_csv());
+    cmd.assert()
+        .code(valid_exit_codes())
+        .stdout(predicate::str::contains("\"numbers_analyzed\": 40"));
+}
+
+#[test]
+fn test_column_option_unknown_header() {
+    let mut cmd = lawkit();
+    cmd.args(["benf", "--column", "total"])
+        .write_stdin(ledger_csv());
+    cmd.assert()
+        .failure()
+        .stderr(predicate::str::contains("column 'total' not found"));
+}
+
+#[test]
+fn test_sheet_option_rejected_for_csv() {
+    let mut cmd = lawkit();
+    cmd.args(["benf", "--sheet", "Ledger"])
+        .write_stdin(ledger_csv());
+    cmd.assert().failure();
+}
+
+#[test]
+fn test_locale_option_reads_european_amounts() {
+    let mut ledger = String::from("Datum;Betrag\n");
+    for i in 0..30 {
+        ledger.push_str(&format!(
+            "2023-01-{:02};{}.{:03},50\n",
+            i % 28 + 1,
+            i + 1,
+            100 + i
+        ));
+    }
+
+    let mut cmd = lawkit();
+    cmd.args([
+        "pareto", "--locale", "de-DE", "--column", "Betrag", "-f", "json",
+    ])
+    .write_stdin(ledger.clone());
+    cmd.assert()
+        .code(valid_exit_codes())
+        .stdout(predicate::str::contains("\"numbers_analyzed\": 30"));
+
+    let mut cmd = lawkit();
+    cmd.args(["benf", "--locale", "eu", "--column", "Betrag", "-f", "json"])
+        .write_stdin(ledger);
+    cmd.assert()
+        .code(valid_exit_codes())
+        .stdout(predicate::str::contains("\"numbers_analyzed\": 30"));
+}
+
+#[test]
+fn test_locale_option_unknown() {
+    let mut cmd = lawkit();
+    cmd.args(["benf", "--locale", "xx"]).write_stdin("123 456");
+    cmd.assert()
+        .failure()
+        .stderr(predicate::str::contains("unknown locale 'xx'"));
+}
+
+fn invoices_xml() -> String {
+    let mut xml = String::from("<?xml version=\"1.0\"?>\n<invoices>\n");
+    for i in 0..30 {
+        xml.push_str(&format!(
+            "  <invoice id=\"2024-{:04}\"><amount value=\"{}\"/><note>batch 7</note></invoice>\n",
+            i + 1,
+            (i + 1) * 137
+        ));
+    }
+    xml.push_str("</invoices>\n");
+    xml
+}
+
+#[test]
+fn test_xpath_option_selects_attributes() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("invoices.xml");
+    std::fs::write(&path, invoices_xml()).unwrap();
+
+    let mut cmd = lawkit();
+    cmd.args([
+        "benf",
+        path.to_str().unwrap(),
+        "--xpath",
+        "//amount/@value",
+        "-f",
+        "json",
+    ]);
+    cmd.assert()
+        .code(valid_exit_codes())
+        .stdout(predicate::str::contains("\"numbers_analyzed\": 30"));
+}
+
+#[test]
+fn test_xpath_option_rejected_for_csv() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("ledger.csv");
+    std::fs::write(&path, ledger_csv()).unwrap();
+
+    let mut cmd = lawkit();
+    cmd.args(["benf", path.to_str().unwrap(), "--xpath", "//amount"]);
+    cmd.assert().failure();
+}
+
+fn invoices_json() -> String {
+    let invoices: Vec<String> = (0..30)
+        .map(|i| {
+            format!(
+                r#"{{"id": {}, "total": {}, "tax": {{"total": {}}}}}"#,
+                9000 + i,
+                (i + 1) * 113,
+                i + 1
+            )
+        })
+        .collect();
+    format!(
+        r#"{{"meta": {{"page": 1}}, "invoices": [{}]}}"#,
+        invoices.join(", ")
+    )
+}
+
+#[test]
+fn test_path_option_selects_json_values() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("payload.json");
+    std::fs::write(&path, invoices_json()).unwrap();
+
+    let mut cmd = lawkit();
+    cmd.args([
+        "benf",
+        path.to_str().unwrap(),
+        "--path",
+        "$.invoices[*].total",
+        "-f",
+        "json",
+    ]);
+    cmd.assert()
+        .code(valid_exit_codes())
+        .stdout(predicate::str::contains("\"numbers_analyzed\": 30"));
+}
+
+#[test]
+fn test_key_regex_options_on_stdin() {
+    let mut cmd = lawkit();
+    cmd.args([
+        "pareto",
+        "--include-keys",
+        "^total$",
+        "--exclude-keys",
+        "^tax$",
+        "-f",
+        "json",
+    ])
+    .write_stdin(invoices_json());
+    cmd.assert()
+        .code(valid_exit_codes())
+        .stdout(predicate::str::contains("\"numbers_analyzed\": 30"));
+}
+
+#[test]
+fn test_path_option_invalid() {
+    let mut cmd = lawkit();
+    cmd.args(["benf", "--path", "$.invoices["])
+        .write_stdin(invoices_json());
+    cmd.assert()
+        .failure()
+        .stderr(predicate::str::contains("invalid path"));
+}
+
+fn ledger_sqlite(path: &std::path::Path) {
+    let connection = rusqlite::Connection::open(path).unwrap();
+    connection
+        .execute_batch("CREATE TABLE journal (id INTEGER, year INTEGER, amount REAL);")
+        .unwrap();
+    for i in 1..=60 {
+        connection
+            .execute(
+                "INSERT INTO journal VALUES (?1, ?2, ?3)",
+                rusqlite::params![i, 2024 + i % 2, i as f64 * 137.5],
+            )
+            .unwrap();
+    }
+}
+
+#[test]
+fn test_query_option_reads_sqlite() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("ledger.sqlite");
+    ledger_sqlite(&path);
+
+    let mut cmd = lawkit();
+    cmd.args([
+        "benf",
+        path.to_str().unwrap(),
+        "--query",
+        "SELECT amount FROM journal WHERE year=2025",
+        "-f",
+        "json",
+    ]);
+    cmd.assert()
+        .code(valid_exit_codes())
+        .stdout(predicate::str::contains("\"numbers_analyzed\": 30"));
+}
+
+#[test]
+fn test_query_option_rejected_for_csv() {
+    let mut cmd = lawkit();
+    cmd.args(["benf", "--query", "SELECT 1"])
+        .write_stdin(ledger_csv());
+    cmd.assert()
+        .failure()
+        .stderr(predicate::str::contains("SQLite"));
+}
+
+#[test]
+fn test_compressed_input_is_decompressed() {
+    use std::io::Write;
+
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("sample.txt.gz");
+    let mut encoder = flate2::write::GzEncoder::new(
+        std::fs::File::create(&path).unwrap(),
+        flate2::Compression::default(),
+    );
+    encoder.write_all(SAMPLE_DATA.as_bytes()).unwrap();
+    encoder.finish().unwrap();
+
+    for law in ["benf", "normal"] {
+        let mut cmd = lawkit();
+        cmd.args([law, path.to_str().unwrap(), "-f", "json"]);
+        cmd.assert()
+            .code(valid_exit_codes())
+            .stdout(predicate::str::contains("\"numbers_analyzed\": 30"));
+    }
+}
+
+#[test]
+fn test_archive_members_are_pooled() {
+    use std::io::Write;
+
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("exports.zip");
+    let mut writer = zip::ZipWriter::new(std::fs::File::create(&path).unwrap());
+    for member in ["2024-01.txt", "2024-02.txt"] {
+        writer
+            .start_file(member, zip::write::FileOptions::default())
+            .unwrap();
+        writer.write_all(SAMPLE_DATA.as_bytes()).unwrap();
+    }
+    writer.finish().unwrap();
+
+    let mut cmd = lawkit();
+    cmd.args(["pareto", path.to_str().unwrap(), "-f", "json"]);
+    cmd.assert()
+        .code(valid_exit_codes())
+        .stdout(predicate::str::contains("\"numbers_analyzed\": 60"));
+}
+
+#[test]
+fn test_members_option_reports_each_archive_member() {
+    use std::io::Write;
+
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("exports.zip");
+    let mut writer = zip::ZipWriter::new(std::fs::File::create(&path).unwrap());
+    for (member, copies) in [("2024-01.txt", 1), ("2024-02.txt", 2)] {
+        writer
+            .start_file(member, zip::write::FileOptions::default())
+            .unwrap();
+        writer
+            .write_all(SAMPLE_DATA.repeat(copies).as_bytes())
+            .unwrap();
+    }
+    writer.finish().unwrap();
+
+    let output = lawkit()
+        .args(["pareto", "--members", path.to_str().unwrap(), "-f", "json"])
+        .output()
+        .unwrap();
+    assert!(valid_exit_codes().eval(&output.status.code().unwrap()));
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+
+    let files: Vec<(&str, u64)> = json["files"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|file| {
+            (
+                file["file"].as_str().unwrap(),
+                file["result"]["numbers_analyzed"].as_u64().unwrap(),
+            )
+        })
+        .collect();
+    let archive = path.display().to_string();
+    assert_eq!(
+        files,
+        [
+            (format!("{archive}:2024-01.txt").as_str(), 30),
+            (format!("{archive}:2024-02.txt").as_str(), 60),
+        ]
+    );
+    assert_eq!(json["pooled"]["numbers_analyzed"], 90);
+}
+
+#[test]
+fn test_directory_input_runs_batch_analysis() {
+    let dir = tempfile::tempdir().unwrap();
+    std::fs::create_dir_all(dir.path().join("2024")).unwrap();
+    for month in ["2024/01.txt", "2024/02.txt", "2024/03.txt"] {
+        std::fs::write(dir.path().join(month), SAMPLE_DATA).unwrap();
+    }
+
+    let mut cmd = lawkit();
+    cmd.args(["pareto", dir.path().to_str().unwrap(), "-f", "json"]);
+    cmd.assert()
+        .code(valid_exit_codes())
+        .stdout(predicate::str::contains("\"pooled\""))
+        .stdout(predicate::str::contains("\"numbers_analyzed\": 90"))
+        .stdout(predicate::str::contains("\"rank\": 3"));
+
+    let pattern = format!("{}/**/0[12].txt", dir.path().display());
+    let mut cmd = lawkit();
+    cmd.args(["benf", &pattern]);
+    cmd.assert()
+        .code(valid_exit_codes())
+        .stdout(predicate::str::contains("(2 files, ranked by risk)"))
+        .stdout(predicate::str::contains("(pooled)"));
+}
+
+#[test]
+fn test_group_by_ranks_groups_by_deviation() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("invoices.csv");
+    let mut csv = String::from("vendor_id,amount\n");
+    for i in 0..50 {
+        csv.push_str(&format!("V001,{}\n", if i < 5 { 10000 } else { 1 }));
+        csv.push_str(&format!("V002,{}\n", if i < 10 { 16.0 } else { 2.25 }));
+    }
+    csv.push_str("V003,500\n");
+    std::fs::write(&path, csv).unwrap();
+
+    let mut cmd = lawkit();
+    cmd.args([
+        "pareto",
+        path.to_str().unwrap(),
+        "--group-by",
+        "vendor_id",
+        "--column",
+        "amount",
+    ]);
+    cmd.assert()
+        .code(valid_exit_codes())
+        .stdout(predicate::str::contains(
+            "Group Analysis by vendor_id (3 groups",
+        ))
+        .stdout(predicate::str::is_match(r"1  \S+\s+\S+\s+50  V001").unwrap())
+        .stdout(predicate::str::contains(
+            "V003 (1 numbers): fewer than 10 numbers",
+        ));
+
+    let mut cmd = lawkit();
+    cmd.args([
+        "benf",
+        path.to_str().unwrap(),
+        "--group-by",
+        "vendor_id",
+        "-f",
+        "json",
+    ]);
+    cmd.assert()
+        .code(valid_exit_codes())
+        .stdout(predicate::str::contains("\"group_by\": \"vendor_id\""))
+        .stdout(predicate::str::contains("\"group\": \"V002\""));
+}
+
+#[test]
+fn test_group_by_applies_digit_tests() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("expenses.csv");
+    let mut csv = String::from("cost_center,amount\n");
+    for i in 1..=60 {
+        csv.push_str(&format!("CC1,{}\n", i * 37));
+        csv.push_str(&format!("CC2,{}\n", 100 + i * 13));
+    }
+    std::fs::write(&path, csv).unwrap();
+
+    let mut cmd = lawkit();
+    cmd.args([
+        "benf",
+        path.to_str().unwrap(),
+        "--group-by",
+        "cost_center",
+        "--digits",
+        "first-two",
+        "-f",
+        "json",
+    ]);
+    cmd.assert()
+        .code(valid_exit_codes())
+        .stdout(predicate::str::contains("\"group\": \"CC1\""))
+        .stdout(predicate::str::contains("\"digit_tests\""))
+        .stdout(predicate::str::contains("first-two"));
+}
+
+#[test]
+fn test_single_group_matches_ungrouped_risk() {
+    // ベンフォード分布に 5 を 18 件上乗せ（p ≈ 0.02）
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("payables.csv");
+    let mut csv = String::from("vendor,amount\n");
+    for digit in 1..=9u32 {
+        let mut count = (200.0 * (1.0 + 1.0 / digit as f64).log10()).round() as u32;
+        if digit == 5 {
+            count += 18;
+        }
+        for i in 0..count {
+            csv.push_str(&format!("V1,{digit}{:02}.{}\n", (i * 37) % 100, i % 10));
+        }
+    }
+    std::fs::write(&path, csv).unwrap();
+
+    let risk_level = |output: &[u8]| {
+        let json: serde_json::Value = serde_json::from_slice(output).unwrap();
+        json.get("risk_level")
+            .or_else(|| json["groups"][0]["result"].get("risk_level"))
+            .and_then(|risk| risk.as_str())
+            .unwrap()
+            .to_string()
+    };
+    for threshold in ["auto", "critical", "mad"] {
+        let args = [
+            "benf",
+            path.to_str().unwrap(),
+            "--column",
+            "amount",
+            "--threshold",
+            threshold,
+            "-f",
+            "json",
+        ];
+        let ungrouped = lawkit().args(args).output().unwrap();
+        let grouped = lawkit()
+            .args(args)
+            .args(["--group-by", "vendor"])
+            .output()
+            .unwrap();
+        assert_eq!(
+            grouped.status.code(),
+            ungrouped.status.code(),
+            "{threshold}"
+        );
+        assert_eq!(
+            risk_level(&grouped.stdout),
+            risk_level(&ungrouped.stdout),
+            "{threshold}"
+        );
+    }
+}
@@ -72,7 +72,7 @@ fn output_no_color() {
 fn output_quiet() {
     let output = generate_sample().arg("--quiet").output().unwrap();
 
-    let stdout = String::from_utf8_lossy(&output.stdout);
+    let _stdout = String::from_utf8_lossy(&output.stdout);
     // Generate with quiet still outputs data
     assert!(output.status.success());
 }
@@ -1,7 +1,7 @@
 //! Subcommand tests based on docs/specs/cli.md
 //!
 //! Analysis commands: benf, pareto, zipf, normal, poisson
-//! Integration commands: analyze, validate, diagnose
+//! Integration commands: analyze, validate, diagnose, compare
 //!
 //! Note: Exit codes 0, 10, 11 are all valid (LOW/MEDIUM, HIGH, CRITICAL risk)
 
@@ -48,6 +48,96 @@ fn test_benf_confidence_option() {
     cmd.assert().code(valid_exit_codes());
 }
 
+#[test]
+fn test_benf_digits_option() {
+    let mut cmd = lawkit();
+    cmd.args(["benf", "--digits", "first-two,last-two"])
+        .write_stdin(SAMPLE_DATA);
+    cmd.assert()
+        .code(valid_exit_codes())
+        .stdout(predicate::str::contains("First-Two Digits Test"))
+        .stdout(predicate::str::contains("Last-Two Digits Test"));
+}
+
+#[test]
+fn test_benf_digits_json_output() {
+    let mut cmd = lawkit();
+    cmd.args(["benf", "--digits", "second", "-f", "json"])
+        .write_stdin(SAMPLE_DATA);
+    cmd.assert()
+        .code(valid_exit_codes())
+        .stdout(predicate::str::contains("\"digit_tests\""))
+        .stdout(predicate::str::contains("\"second\""));
+}
+
+#[test]
+fn test_benf_mad_threshold() {
+    let mut cmd = lawkit();
+    cmd.args(["benf", "--threshold", "mad", "-f", "json"])
+        .write_stdin(SAMPLE_DATA);
+    cmd.assert()
+        .code(valid_exit_codes())
+        .stdout(predicate::str::contains("\"conformity\""));
+}
+
+#[test]
+fn test_benf_drill_down() {
+    let mut cmd = lawkit();
+    cmd.args(["benf", "--drill-down"]).write_stdin(SAMPLE_DATA);
+    cmd.assert()
+        .code(valid_exit_codes())
+        .stdout(predicate::str::contains("Drill-down:"))
+        .stdout(predicate::str::contains("line "));
+}
+
+#[test]
+fn test_benf_z_statistics_json() {
+    let mut cmd = lawkit();
+    cmd.args(["benf", "--drill-down", "-f", "json"])
+        .write_stdin(SAMPLE_DATA);
+    cmd.assert()
+        .code(valid_exit_codes())
+        .stdout(predicate::str::contains("\"z_statistics\""))
+        .stdout(predicate::str::contains("\"drill_down\""));
+}
+
+#[test]
+fn test_benf_structured_formats_escape_sources() {
+    let dir = tempfile::tempdir().unwrap();
+    let input = dir.path().join("q\"u,o<te>: x.txt");
+    std::fs::write(&input, SAMPLE_DATA).unwrap();
+
+    let mut cmd = lawkit();
+    cmd.args(["benf", "--drill-down", "-f", "xml"]).arg(&input);
+    cmd.assert()
+        .code(valid_exit_codes())
+        .stdout(predicate::str::contains(
+            "q&quot;u,o&lt;te&gt;: x.txt</file>",
+        ))
+        .stdout(predicate::str::contains("<te>").not());
+
+    let mut cmd = lawkit();
+    cmd.args(["benf", "--drill-down", "-f", "csv"]).arg(&input);
+    cmd.assert()
+        .code(valid_exit_codes())
+        .stdout(predicate::str::contains("q\"\"u,o<te>: x.txt\""));
+
+    let mut cmd = lawkit();
+    cmd.args(["benf", "--drill-down", "-f", "yaml"]).arg(&input);
+    // 単一引用符のスカラーとして出力される
+    cmd.assert()
+        .code(valid_exit_codes())
+        .stdout(predicate::str::contains("q\"u,o<te>: x.txt'"));
+}
+
+#[test]
+fn test_benf_invalid_digits_option() {
+    let mut cmd = lawkit();
+    cmd.args(["benf", "--digits", "third"])
+        .write_stdin(SAMPLE_DATA);
+    cmd.assert().code(2);
+}
+
 // ============================================================================
 // pareto (Pareto Principle)
 // ============================================================================
@@ -76,6 +166,162 @@ fn test_pareto_gini_option() {
     cmd.assert().code(valid_exit_codes());
 }
 
+#[test]
+fn test_pareto_label_abc_classification() {
+    let sales = "customer,revenue\nacme,5000\nglobex,2500\ninitech,1000\nacme,500\n\
+                 umbrella,600\nhooli,400\nstark,300\nwayne,200\n";
+
+    let mut cmd = lawkit();
+    cmd.args(["pareto", "--column", "revenue", "--label", "customer"])
+        .write_stdin(sales);
+    cmd.assert()
+        .code(valid_exit_codes())
+        .stdout(predicate::str::contains("Pareto ABC Analysis Results"))
+        .stdout(predicate::str::is_match(r"1  A\s+5500\.00\s+52\.4%\s+52\.4%  acme").unwrap())
+        .stdout(predicate::str::is_match(r"7  C\s+200\.00").unwrap());
+
+    let mut cmd = lawkit();
+    cmd.args([
+        "pareto",
+        "--column",
+        "revenue",
+        "--label",
+        "customer",
+        "--abc-cutoffs",
+        "60,85,95",
+        "-f",
+        "json",
+    ])
+    .write_stdin(sales);
+    cmd.assert()
+        .code(valid_exit_codes())
+        .stdout(predicate::str::contains("\"class\": \"D\""))
+        .stdout(predicate::str::contains("\"label\": \"wayne\""));
+}
+
+#[test]
+fn test_pareto_label_abc_csv_rows() {
+    let sales = "customer,revenue\nacme,5000\nglobex,2500\ninitech,1000\nacme,500\n\
+                 umbrella,600\nhooli,400\nstark,300\nwayne,200\n";
+
+    let output = lawkit()
+        .args([
+            "pareto", "--column", "revenue", "--label", "customer", "-f", "csv",
+        ])
+        .write_stdin(sales)
+        .output()
+        .unwrap();
+    assert!(valid_exit_codes().eval(&output.status.code().unwrap()));
+
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    let rows: Vec<Vec<&str>> = stdout
+        .lines()
+        .filter(|line| !line.is_empty())
+        .map(|line| line.split(',').collect())
+        .collect();
+    assert_eq!(
+        rows[0],
+        [
+            "rank",
+            "label",
+            "value",
+            "share",
+            "cumulative_share",
+            "class"
+        ]
+    );
+    assert_eq!(rows.len(), 8);
+
+    let labels: Vec<&str> = rows[1..].iter().map(|row| row[1]).collect();
+    assert_eq!(
+        labels,
+        ["acme", "globex", "initech", "umbrella", "hooli", "stark", "wayne"]
+    );
+    assert_eq!(rows[1][0], "1");
+    assert_eq!(rows[1][2].parse::<f64>().unwrap(), 5500.0);
+    assert_eq!(rows[1][5], "A");
+    assert_eq!(rows[7][0], "7");
+    assert_eq!(rows[7][5], "C");
+    let cumulative: f64 = rows[7][4].parse().unwrap();
+    assert!((cumulative - 100.0).abs() < 1e-9);
+}
+
+#[test]
+fn test_pareto_power_law_fit() {
+    // 分位点 x = (1 - q)^(-1/1.5)（x_min = 1, α = 2.5 のべき乗則）
+    let data: String = (0..200)
+        .map(|i| format!("{:.4}\n", (1.0 - (i as f64 + 0.5) / 200.0).powf(-1.0 / 1.5)))
+        .collect();
+
+    let mut cmd = lawkit();
+    cmd.args(["pareto", "--power-law", "--bootstrap", "10", "-f", "json"])
+        .write_stdin(data.clone());
+    cmd.assert()
+        .code(valid_exit_codes())
+        .stdout(predicate::str::contains("\"power_law\""))
+        .stdout(predicate::str::contains("\"support\": \"continuous\""))
+        .stdout(predicate::str::contains(
+            "\"alternative\": \"stretched_exponential\"",
+        ))
+        .stdout(predicate::str::contains("\"bootstrap_samples\": 10"));
+
+    let mut cmd = lawkit();
+    cmd.args(["pareto", "--power-law", "--bootstrap", "0"])
+        .write_stdin(data);
+    cmd.assert()
+        .code(valid_exit_codes())
+        .stdout(predicate::str::contains(
+            "Power-Law Fit (Clauset-Shalizi-Newman, continuous)",
+        ))
+        .stdout(predicate::str::is_match(r"alpha: 2\.[45]\d\d").unwrap())
+        .stdout(predicate::str::contains("vs lognormal: R = "));
+}
+
+#[test]
+fn test_pareto_inequality_indices() {
+    // 1..=10: ジニ係数 0.3、上位 10% のシェア 10/55
+    let data: String = (1..=10).map(|i| format!("{i}\n")).collect();
+
+    let mut cmd = lawkit();
+    cmd.args(["pareto", "--indices", "--top-shares", "10", "-f", "json"])
+        .write_stdin(data.clone());
+    cmd.assert()
+        .code(valid_exit_codes())
+        .stdout(predicate::str::contains("\"inequality\""))
+        .stdout(predicate::str::contains("\"gini\": 0.3,"))
+        .stdout(predicate::str::contains("\"gini_confidence_interval\""))
+        .stdout(predicate::str::contains("\"palma_ratio\""))
+        .stdout(predicate::str::contains("\"percentile\": 10.0"));
+
+    let mut cmd = lawkit();
+    cmd.args([
+        "pareto",
+        "--indices",
+        "--gini-bootstrap",
+        "0",
+        "--atkinson",
+        "1",
+    ])
+    .write_stdin(data.clone());
+    cmd.assert()
+        .code(valid_exit_codes())
+        .stdout(predicate::str::contains("Gini: 0.3000\n"))
+        .stdout(predicate::str::contains("Atkinson (e=1): "))
+        .stdout(predicate::str::contains("Top 10% share: 18.2%"));
+
+    let mut cmd = lawkit();
+    cmd.args(["pareto", "--atkinson", "1"]).write_stdin(data);
+    cmd.assert().code(2);
+}
+
+#[test]
+fn test_bootstrap_requires_power_law() {
+    let mut cmd = lawkit();
+    cmd.args(["zipf", "--bootstrap", "10"])
+        .write_stdin(SAMPLE_DATA);
+    cmd.assert().code(2);
+}
+
 // ============================================================================
 // zipf (Zipf's Law)
 // ============================================================================
@@ -98,6 +344,118 @@ fn test_zipf_text_mode() {
     cmd.assert().code(valid_exit_codes());
 }
 
+#[test]
+fn test_zipf_maximum_likelihood_fits() {
+    // 頻度 ≈ 10000 / r（s = 1 の Zipf 分布）
+    let data: String = (1..=50).map(|r| format!("{}\n", 10000 / r)).collect();
+
+    let mut cmd = lawkit();
+    cmd.args(["zipf"]).write_stdin(data.clone());
+    cmd.assert()
+        .code(valid_exit_codes())
+        .stdout(predicate::str::contains("Maximum-Likelihood Fits:"))
+        .stdout(predicate::str::is_match(r"Zipf: s = (0\.99|1\.00)\d\d ± \S+ \(95% CI ").unwrap())
+        .stdout(predicate::str::contains("Zipf-Mandelbrot").not());
+
+    let mut cmd = lawkit();
+    cmd.args(["zipf", "--confidence", "0.9", "--mandelbrot"])
+        .write_stdin(data.clone());
+    cmd.assert()
+        .code(valid_exit_codes())
+        .stdout(predicate::str::contains("(90% CI "))
+        .stdout(predicate::str::contains("Zipf-Mandelbrot: s = "));
+
+    let mut cmd = lawkit();
+    cmd.args(["zipf", "--mandelbrot", "-f", "json"])
+        .write_stdin(data);
+    cmd.assert()
+        .code(valid_exit_codes())
+        .stdout(predicate::str::contains("\"zipf_fit\""))
+        .stdout(predicate::str::contains("\"confidence_interval\""))
+        .stdout(predicate::str::contains("\"zipf_mandelbrot\""))
+        .stdout(predicate::str::contains("\"likelihood_ratio_p_value\""));
+}
+
+#[test]
+fn test_zipf_heaps_law() {
+    // 同じ定型文の繰り返しは語彙がほとんど増えない
+    let text = "dear customer your order has shipped thank you\n".repeat(50);
+
+    let mut cmd = lawkit();
+    cmd.args(["zipf", "--text", "--heaps"])
+        .write_stdin(text.clone());
+    cmd.assert()
+        .code(valid_exit_codes())
+        .stdout(predicate::str::contains("Heaps' Law (Vocabulary Growth):"))
+        .stdout(predicate::str::contains("Tokens: 400, vocabulary: 8"))
+        .stdout(predicate::str::contains("templated text"));
+
+    let mut cmd = lawkit();
+    cmd.args(["zipf", "--text", "--heaps", "-f", "json"])
+        .write_stdin(text.clone());
+    cmd.assert()
+        .code(valid_exit_codes())
+        .stdout(predicate::str::contains("\"heaps\""))
+        .stdout(predicate::str::contains("\"beta\""))
+        .stdout(predicate::str::contains("\"vocabulary_growth\""));
+
+    // --heaps はテキストモードのみ
+    let mut cmd = lawkit();
+    cmd.args(["zipf", "--heaps"]).write_stdin(text);
+    cmd.assert().code(2);
+}
+
+#[test]
+fn test_zipf_tokenizer_options() {
+    // 1文字の語も数え、使ったトークナイザーを記録する
+    let mut cmd = lawkit();
+    cmd.args(["zipf", "--text", "-f", "json"])
+        .write_stdin("I saw a cat and a dog and a bird and I ran\n");
+    cmd.assert()
+        .code(valid_exit_codes())
+        .stdout(predicate::str::contains("\"total_observations\": 13"))
+        .stdout(predicate::str::contains("\"name\": \"unicode-words\""));
+
+    let mut cmd = lawkit();
+    cmd.args(["zipf", "--text", "--tokenizer", "cjk-ngram", "--nfkc"])
+        .write_stdin("東京都に住む人は東京タワーを見た。東京都の人口は多い。\n");
+    cmd.assert()
+        .code(valid_exit_codes())
+        .stdout(predicate::str::contains(
+            "Tokenizer: cjk-ngram(2) (lowercase, NFKC)",
+        ));
+
+    let dir = tempfile::tempdir().unwrap();
+    let stopwords = dir.path().join("stop.txt");
+    std::fs::write(&stopwords, "the a # articles\n").unwrap();
+    let mut cmd = lawkit();
+    cmd.args(["zipf", "--text", "--min-token-length", "2", "-f", "csv"])
+        .arg("--stopwords")
+        .arg(&stopwords)
+        .write_stdin("The cat and the dog and a bird saw the fox and a cat\n");
+    cmd.assert()
+        .code(valid_exit_codes())
+        .stdout(predicate::str::contains(",tokenizer"))
+        .stdout(predicate::str::contains("stdin,6,"));
+
+    let mut cmd = lawkit();
+    cmd.args(["zipf", "--text", "--token-pattern", "#\\w+", "-f", "yaml"])
+        .write_stdin("#rust #go #rust #zig #c #rust #go #js #c\n");
+    cmd.assert()
+        .code(valid_exit_codes())
+        .stdout(predicate::str::contains("name: \"regex(#\\w+)\""));
+
+    // 無効な正規表現とテキストモード以外での指定は引数エラー
+    let mut cmd = lawkit();
+    cmd.args(["zipf", "--text", "--token-pattern", "("])
+        .write_stdin("a b c\n");
+    cmd.assert().code(2);
+
+    let mut cmd = lawkit();
+    cmd.args(["zipf", "--nfkc"]).write_stdin(SAMPLE_DATA);
+    cmd.assert().code(2);
+}
+
 // ============================================================================
 // normal (Normal Distribution)
 // ============================================================================
@@ -128,6 +486,25 @@ fn test_normal_outliers_option() {
     cmd.assert().code(valid_exit_codes());
 }
 
+#[test]
+fn test_normal_outliers_report_lines() {
+    let mut input = String::new();
+    for i in 0..30 {
+        let value = if i == 12 {
+            5000.0
+        } else {
+            50.0 + (i % 7) as f64
+        };
+        input.push_str(&format!("{value}\n"));
+    }
+
+    let mut cmd = lawkit();
+    cmd.args(["normal", "--outliers"]).write_stdin(input);
+    cmd.assert()
+        .code(valid_exit_codes())
+        .stdout(predicate::str::contains("at line 13"));
+}
+
 #[test]
 fn test_normal_quality_control_option() {
     // Generate normal data then analyze with quality control
@@ -183,6 +560,28 @@ fn test_poisson_rare_events_option() {
     cmd.assert().code(valid_exit_codes());
 }
 
+#[test]
+fn test_poisson_generated_data_is_not_high_risk() {
+    for seed in ["1", "2", "3"] {
+        let output = lawkit()
+            .args(["generate", "poisson", "--samples", "1000", "--seed", seed])
+            .output()
+            .unwrap();
+        assert!(output.status.success());
+
+        // Exit code 0 is LOW/MEDIUM risk
+        let mut cmd = lawkit();
+        cmd.args(["poisson"]).write_stdin(output.stdout.clone());
+        cmd.assert().code(0);
+
+        // An explicit --confidence adds the chi-square p-value bands
+        let mut cmd = lawkit();
+        cmd.args(["poisson", "--confidence", "0.01"])
+            .write_stdin(output.stdout);
+        cmd.assert().code(predicate::in_iter([10, 11]));
+    }
+}
+
 // ============================================================================
 // analyze (Integration)
 // ============================================================================
@@ -250,6 +649,131 @@ fn test_diagnose_basic() {
     cmd.assert().code(valid_exit_codes());
 }
 
+// ============================================================================
+// compare
+// ============================================================================
+
+#[test]
+fn test_compare_datasets() {
+    let dir = tempfile::tempdir().unwrap();
+    let baseline = dir.path().join("baseline.txt");
+    let shifted = dir.path().join("shifted.txt");
+    let values: Vec<f64> = (0..200).map(|i| ((i * 37) % 200) as f64 / 20.0).collect();
+    let join = |offset: f64| {
+        values
+            .iter()
+            .map(|v| format!("{}\n", v + offset))
+            .collect::<String>()
+    };
+    std::fs::write(&baseline, join(0.0)).unwrap();
+    std::fs::write(&shifted, join(5.0)).unwrap();
+
+    // 平均の大きなずれは有意で効果量も大きい
+    let mut cmd = lawkit();
+    cmd.args(["compare", "--law", "normal", "--no-color"])
+        .arg(&baseline)
+        .arg(&shifted);
+    cmd.assert()
+        .code(11)
+        .stdout(predicate::str::contains("Kolmogorov-Smirnov"))
+        .stdout(predicate::str::contains("Anderson-Darling"))
+        .stdout(predicate::str::contains("Cohen's d"))
+        .stdout(predicate::str::contains("(large)"));
+
+    // stdin と同じデータなら差はない
+    let mut cmd = lawkit();
+    cmd.args(["compare", "--law", "benf", "-f", "json", "-"])
+        .arg(&baseline)
+        .write_stdin(join(0.0));
+    cmd.assert()
+        .code(0)
+        .stdout(predicate::str::contains("\"law\": \"benf\""))
+        .stdout(predicate::str::contains("\"significant\": false"))
+        .stdout(predicate::str::contains("Cramér's V"));
+
+    let mut cmd = lawkit();
+    cmd.args(["compare", "--law", "poisson", "-f", "csv", "-"])
+        .arg(&baseline)
+        .write_stdin(INTEGER_DATA);
+    cmd.assert().code(1);
+
+    // --law は必須
+    let mut cmd = lawkit();
+    cmd.arg("compare").arg(&baseline).arg(&shifted);
+    cmd.assert().code(2);
+}
+
+#[test]
+fn test_compare_texts_under_zipf() {
+    let dir = tempfile::tempdir().unwrap();
+    let first = dir.path().join("first.txt");
+    let second = dir.path().join("second.txt");
+    std::fs::write(
+        &first,
+        "the cat sat on the mat and the dog sat on the log\n",
+    )
+    .unwrap();
+    std::fs::write(
+        &second,
+        "the dog sat on the log and the cat sat on the mat\n",
+    )
+    .unwrap();
+
+    let mut cmd = lawkit();
+    cmd.args(["compare", "--law", "zipf", "--text", "-f", "yaml"])
+        .arg(&first)
+        .arg(&second);
+    cmd.assert()
+        .code(0)
+        .stdout(predicate::str::contains(
+            "name: Spearman rank correlation (shared items)",
+        ))
+        .stdout(predicate::str::contains("magnitude: negligible"))
+        .stdout(predicate::str::contains("category: the"));
+
+    // トークン化のオプションは zipf --text と共通
+    let stopwords = dir.path().join("stop.txt");
+    std::fs::write(&stopwords, "the\n").unwrap();
+    let mut cmd = lawkit();
+    cmd.args(["compare", "--law", "zipf", "--text", "-f", "yaml"])
+        .args(["--tokenizer", "whitespace", "--stopwords"])
+        .arg(&stopwords)
+        .arg(&first)
+        .arg(&second);
+    cmd.assert()
+        .code(0)
+        .stdout(predicate::str::contains("category: sat"))
+        .stdout(predicate::str::contains("category: the").not());
+}
+
+#[test]
+fn test_compare_structured_output_is_escaped() {
+    let dir = tempfile::tempdir().unwrap();
+    let first = dir.path().join("a&b.txt");
+    let second = dir.path().join("c<d.txt");
+    let values: String = (1..=100).map(|i| format!("{}\n", i * 7)).collect();
+    std::fs::write(&first, &values).unwrap();
+    std::fs::write(&second, &values).unwrap();
+
+    let mut cmd = lawkit();
+    cmd.args(["compare", "--law", "benf", "-f", "xml"])
+        .arg(&first)
+        .arg(&second);
+    cmd.assert()
+        .code(0)
+        .stdout(predicate::str::contains("a&amp;b.txt</dataset_a>"))
+        .stdout(predicate::str::contains("c&lt;d.txt</dataset_b>"))
+        .stdout(predicate::str::contains("<proportion_a>"));
+
+    let mut cmd = lawkit();
+    cmd.args(["compare", "--law", "benf", "-f", "csv"])
+        .arg(&first)
+        .arg(&second);
+    cmd.assert()
+        .code(0)
+        .stdout(predicate::str::contains("categories.0.proportion_a,"));
+}
+
 // ============================================================================
 // list
 // ============================================================================
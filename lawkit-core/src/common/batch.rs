@@ -0,0 +1,445 @@
+use crate::common::input::{parse_archive_members_with, parse_file_records_with, InputOptions};
+use crate::common::output::formatter::FormattableResult;
+use crate::common::risk::RiskLevel;
+use crate::error::{BenfError, Result};
+use crate::laws::{
+    benford::BenfordResult, normal::NormalResult, pareto::ParetoResult, poisson::PoissonResult,
+    zipf::ZipfResult,
+};
+use rayon::prelude::*;
+use serde::Serialize;
+use std::path::{Path, PathBuf};
+
+/// 一括分析・グループ別分析でリスク順・偏差順に並べられる法則の分析結果
+pub trait BatchAnalysis: Serialize + Send {
+    fn risk_level(&self) -> RiskLevel;
+    fn numbers_analyzed(&self) -> usize;
+    /// 法則からの偏差（大きいほど逸脱している）
+    fn deviation(&self) -> f64;
+}
+
+macro_rules! impl_batch_analysis {
+    ($($result:ty => |$r:ident| $deviation:expr),* $(,)?) => {
+        $(impl BatchAnalysis for $result {
+            fn risk_level(&self) -> RiskLevel {
+                self.risk_level
+            }
+
+            fn numbers_analyzed(&self) -> usize {
+                self.numbers_analyzed
+            }
+
+            fn deviation(&self) -> f64 {
+                let $r = self;
+                $deviation
+            }
+        })*
+    };
+}
+
+impl_batch_analysis!(
+    // 第1桁の平均絶対偏差（MAD）
+    BenfordResult => |r| r.mean_absolute_deviation,
+    // 上位20%のシェアと80%の差
+    ParetoResult => |r| (r.top_20_percent_share - 80.0).abs() / 100.0,
+    // Zipf指数と理論値1.0の差
+    ZipfResult => |r| (r.zipf_exponent - 1.0).abs(),
+    NormalResult => |r| 1.0 - r.normality_score,
+    PoissonResult => |r| 1.0 - r.goodness_of_fit_score,
+);
+
+/// 1ファイルの分析結果
+#[derive(Debug, Clone, Serialize)]
+pub struct BatchFile<R> {
+    pub file: String,
+    pub result: R,
+}
+
+/// 読み込みまたは分析に失敗したファイル
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct BatchFailure {
+    pub file: String,
+    pub error: String,
+}
+
+/// サマリー表の1行（リスクの高い順）
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct BatchSummaryRow {
+    pub rank: usize,
+    pub file: String,
+    pub risk_level: RiskLevel,
+    pub numbers_analyzed: usize,
+}
+
+/// 複数ファイルの一括分析結果：ファイルごとの結果、全ファイルを合算した結果、サマリー表
+#[derive(Debug, Clone, Serialize)]
+pub struct BatchResult<R> {
+    /// 入力順のファイルごとの結果
+    pub files: Vec<BatchFile<R>>,
+    pub failures: Vec<BatchFailure>,
+    /// 読み込めた全ファイルの数値を合算した結果
+    pub pooled: Option<R>,
+    /// 合算した数値の分析に失敗した理由
+    pub pooled_error: Option<String>,
+    pub summary: Vec<BatchSummaryRow>,
+}
+
+impl<R: BatchAnalysis> BatchResult<R> {
+    /// 最もリスクの高いファイルのリスクレベル
+    pub fn worst_risk_level(&self) -> Option<RiskLevel> {
+        self.files.iter().map(|file| file.result.risk_level()).max()
+    }
+
+    /// 最もリスクの高いファイルの終了コード（分析できたファイルがなければ 1）
+    pub fn exit_code(&self) -> i32 {
+        self.worst_risk_level()
+            .map_or(1, |risk_level| risk_level.exit_code())
+    }
+}
+
+impl<R: BatchAnalysis> FormattableResult for BatchResult<R> {
+    const TITLE: &'static str = "Batch Analysis Summary";
+    const ROOT: &'static str = "batch_result";
+
+    /// テキストではサマリー表と失敗したファイルの一覧を出力する
+    fn format_text(&self) -> Result<String> {
+        Ok(format_batch_summary(self))
+    }
+}
+
+/// ディレクトリまたは glob パターンを分析対象のファイル一覧に展開する
+///
+/// ディレクトリは隠しファイルを除いて再帰的にたどる。単一のファイルやテキスト入力、
+/// 何にも一致しないパターンは `None` を返し、従来どおり単一入力として扱う。
+pub fn expand_batch_input(input: &str) -> Result<Option<Vec<PathBuf>>> {
+    let path = Path::new(input);
+    if path.is_dir() {
+        let mut files = Vec::new();
+        collect_files(path, &mut files)?;
+        if files.is_empty() {
+            return Err(BenfError::InvalidInput(format!(
+                "No files found in directory: {input}"
+            )));
+        }
+        return Ok(Some(files));
+    }
+    if path.exists() || !input.contains(['*', '?', '[']) {
+        return Ok(None);
+    }
+
+    let mut files = Vec::new();
+    let entries = glob::glob(input)
+        .map_err(|e| BenfError::InvalidInput(format!("Invalid glob pattern '{input}': {e}")))?;
+    for entry in entries {
+        let file = entry.map_err(|e| BenfError::FileError(e.to_string()))?;
+        if file.is_file() {
+            files.push(file);
+        }
+    }
+    Ok(if files.is_empty() { None } else { Some(files) })
+}
+
+fn collect_files(dir: &Path, files: &mut Vec<PathBuf>) -> Result<()> {
+    let mut entries = std::fs::read_dir(dir)?
+        .map(|entry| entry.map(|entry| entry.path()))
+        .collect::<std::io::Result<Vec<_>>>()?;
+    entries.sort();
+
+    for path in entries {
+        let hidden = path
+            .file_name()
+            .and_then(|name| name.to_str())
+            .is_some_and(|name| name.starts_with('.'));
+        if hidden {
+            continue;
+        }
+        if path.is_dir() {
+            collect_files(&path, files)?;
+        } else if path.is_file() {
+            files.push(path);
+        }
+    }
+    Ok(())
+}
+
+/// 各ファイルを rayon で並列に分析し、続けて全ファイルの数値を合算して分析する
+///
+/// `analyze` はデータセット名と数値を受け取る。読み込めたが単独では分析できなかった
+/// ファイル（データ不足など）の数値も合算には含める。
+pub fn analyze_batch<R, F>(files: &[PathBuf], options: &InputOptions, analyze: F) -> BatchResult<R>
+where
+    R: BatchAnalysis,
+    F: Fn(&str, &[f64]) -> Result<R> + Sync,
+{
+    let outcomes: Vec<BatchOutcome<R>> = files
+        .par_iter()
+        .map(|path| {
+            let file = path.display().to_string();
+            match parse_file_records_with(path, options) {
+                Ok(records) => {
+                    let numbers: Vec<f64> = records.iter().map(|record| record.value).collect();
+                    let result = analyze(&file, &numbers);
+                    (file, numbers, result)
+                }
+                Err(e) => (file, Vec::new(), Err(e)),
+            }
+        })
+        .collect();
+    collect_batch(outcomes, analyze)
+}
+
+/// `analyze_batch` の各ファイルを ZIP・tar アーカイブのメンバーごとに分けて分析する
+///
+/// 圧縮ファイルは展開し、アーカイブ内のアーカイブもたどる。各メンバーは
+/// `archive.zip:member.csv` の名前で1件として並び、アーカイブでないファイルはそのまま1件になる。
+pub fn analyze_batch_members<R, F>(
+    files: &[PathBuf],
+    options: &InputOptions,
+    analyze: F,
+) -> BatchResult<R>
+where
+    R: BatchAnalysis,
+    F: Fn(&str, &[f64]) -> Result<R> + Sync,
+{
+    let outcomes: Vec<BatchOutcome<R>> = files
+        .par_iter()
+        .flat_map_iter(|path| match parse_archive_members_with(path, options) {
+            Ok(members) if members.is_empty() => vec![(
+                path.display().to_string(),
+                Vec::new(),
+                Err(BenfError::NoNumbersFound),
+            )],
+            Ok(members) => members
+                .into_iter()
+                .map(|member| {
+                    let numbers: Vec<f64> =
+                        member.records.iter().map(|record| record.value).collect();
+                    let result = analyze(&member.name, &numbers);
+                    (member.name, numbers, result)
+                })
+                .collect(),
+            Err(e) => vec![(path.display().to_string(), Vec::new(), Err(e))],
+        })
+        .collect();
+    collect_batch(outcomes, analyze)
+}
+
+/// 1件の入力の名前、読み込んだ数値、分析結果
+type BatchOutcome<R> = (String, Vec<f64>, Result<R>);
+
+/// 入力順の分析結果を失敗と分け、合算した数値を分析してリスク順のサマリー表を作る
+fn collect_batch<R, F>(outcomes: Vec<BatchOutcome<R>>, analyze: F) -> BatchResult<R>
+where
+    R: BatchAnalysis,
+    F: Fn(&str, &[f64]) -> Result<R>,
+{
+    let mut pooled_numbers = Vec::new();
+    let mut read_files = 0;
+    let mut batch_files = Vec::new();
+    let mut failures = Vec::new();
+    for (file, numbers, result) in outcomes {
+        if !numbers.is_empty() {
+            read_files += 1;
+            pooled_numbers.extend(numbers);
+        }
+        match result {
+            Ok(result) => batch_files.push(BatchFile { file, result }),
+            Err(e) => failures.push(BatchFailure {
+                file,
+                error: e.to_string(),
+            }),
+        }
+    }
+
+    let (pooled, pooled_error) = if pooled_numbers.is_empty() {
+        (None, None)
+    } else {
+        match analyze(&format!("pooled ({read_files} files)"), &pooled_numbers) {
+            Ok(result) => (Some(result), None),
+            Err(e) => (None, Some(e.to_string())),
+        }
+    };
+
+    let mut ranked: Vec<&BatchFile<R>> = batch_files.iter().collect();
+    // 同じリスクレベルの中では入力順を保つ
+    ranked.sort_by_key(|file| std::cmp::Reverse(file.result.risk_level()));
+    let summary = ranked
+        .into_iter()
+        .enumerate()
+        .map(|(index, file)| BatchSummaryRow {
+            rank: index + 1,
+            file: file.file.clone(),
+            risk_level: file.result.risk_level(),
+            numbers_analyzed: file.result.numbers_analyzed(),
+        })
+        .collect();
+
+    BatchResult {
+        files: batch_files,
+        failures,
+        pooled,
+        pooled_error,
+        summary,
+    }
+}
+
+/// リスク順のサマリー表と失敗したファイルの一覧
+pub fn format_batch_summary<R: BatchAnalysis>(batch: &BatchResult<R>) -> String {
+    let mut output = format!(
+        "{} ({} files, ranked by risk)\n\n",
+        BatchResult::<R>::TITLE,
+        batch.files.len() + batch.failures.len()
+    );
+    output.push_str(&format!(
+        "{:>4}  {:<8}  {:>8}  File\n",
+        "Rank", "Risk", "Numbers"
+    ));
+    for row in &batch.summary {
+        output.push_str(&format!(
+            "{:>4}  {:<8}  {:>8}  {}\n",
+            row.rank,
+            row.risk_level.to_string(),
+            row.numbers_analyzed,
+            row.file
+        ));
+    }
+    if let Some(pooled) = &batch.pooled {
+        output.push_str(&format!(
+            "{:>4}  {:<8}  {:>8}  (pooled)\n",
+            "-",
+            pooled.risk_level().to_string(),
+            pooled.numbers_analyzed()
+        ));
+    }
+
+    if !batch.failures.is_empty() || batch.pooled_error.is_some() {
+        output.push_str("\nFailed:\n");
+        for failure in &batch.failures {
+            output.push_str(&format!("  {}: {}\n", failure.file, failure.error));
+        }
+        if let Some(error) = &batch.pooled_error {
+            output.push_str(&format!("  (pooled): {error}\n"));
+        }
+    }
+    output
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::laws::pareto::analyze_pareto_distribution;
+
+    fn analyze_pareto(name: &str, numbers: &[f64]) -> Result<ParetoResult> {
+        analyze_pareto_distribution(numbers, name)
+    }
+
+    #[test]
+    fn test_expand_batch_input() {
+        let dir = tempfile::tempdir().unwrap();
+        let exports = dir.path().join("exports");
+        std::fs::create_dir_all(exports.join("2024")).unwrap();
+        std::fs::write(exports.join("2024/02.csv"), "1\n").unwrap();
+        std::fs::write(exports.join("2024/01.csv"), "1\n").unwrap();
+        std::fs::write(exports.join("notes.txt"), "1\n").unwrap();
+        std::fs::write(exports.join(".DS_Store"), "").unwrap();
+
+        let files = expand_batch_input(exports.to_str().unwrap())
+            .unwrap()
+            .unwrap();
+        assert_eq!(
+            files,
+            [
+                exports.join("2024/01.csv"),
+                exports.join("2024/02.csv"),
+                exports.join("notes.txt")
+            ]
+        );
+
+        let pattern = format!("{}/**/*.csv", exports.display());
+        assert_eq!(expand_batch_input(&pattern).unwrap().unwrap().len(), 2);
+
+        // Single files, text and patterns without matches stay single inputs
+        let single = exports.join("notes.txt");
+        assert_eq!(expand_batch_input(single.to_str().unwrap()).unwrap(), None);
+        assert_eq!(expand_batch_input("[1, 2, 3]").unwrap(), None);
+        assert_eq!(
+            expand_batch_input(&format!("{}/*.xlsx", exports.display())).unwrap(),
+            None
+        );
+    }
+
+    #[test]
+    fn test_analyze_batch() {
+        let dir = tempfile::tempdir().unwrap();
+        let concentrated = dir.path().join("concentrated.txt");
+        let even = dir.path().join("even.txt");
+        let empty = dir.path().join("empty.txt");
+        let concentrated_data: Vec<String> = (0..50)
+            .map(|i| if i < 5 { "10000".into() } else { "1".into() })
+            .collect();
+        std::fs::write(&concentrated, concentrated_data.join("\n")).unwrap();
+        // Top 20% hold 64%: close enough to 80/20 to rank below the concentrated file
+        let even_data: Vec<&str> = (0..50)
+            .map(|i| if i < 10 { "16" } else { "2.25" })
+            .collect();
+        std::fs::write(&even, even_data.join("\n")).unwrap();
+        std::fs::write(&empty, "no numbers here").unwrap();
+
+        let files = [even.clone(), concentrated.clone(), empty.clone()];
+        let batch = analyze_batch(&files, &InputOptions::default(), analyze_pareto);
+
+        assert_eq!(batch.files.len(), 2);
+        assert_eq!(batch.files[0].file, even.display().to_string());
+        assert_eq!(batch.failures.len(), 1);
+        assert_eq!(batch.failures[0].file, empty.display().to_string());
+
+        let pooled = batch.pooled.as_ref().unwrap();
+        assert_eq!(pooled.numbers_analyzed, 100);
+        assert_eq!(pooled.dataset_name, "pooled (2 files)");
+
+        // The concentrated file ranks first and decides the exit code
+        assert_eq!(batch.summary[0].file, concentrated.display().to_string());
+        assert_eq!(batch.summary[0].rank, 1);
+        assert!(batch.summary[0].risk_level > batch.summary[1].risk_level);
+        assert_eq!(batch.exit_code(), batch.summary[0].risk_level.exit_code());
+
+        let summary = format_batch_summary(&batch);
+        assert!(summary.contains("(3 files, ranked by risk)"));
+        assert!(summary.contains("(pooled)"));
+        assert!(summary.contains("Failed:"));
+    }
+
+    #[test]
+    fn test_batch_without_results() {
+        let batch: BatchResult<ParetoResult> = analyze_batch(
+            &[PathBuf::from("missing.csv")],
+            &InputOptions::default(),
+            analyze_pareto,
+        );
+        assert!(batch.files.is_empty());
+        assert!(batch.pooled.is_none());
+        assert!(batch.pooled_error.is_none());
+        assert_eq!(batch.exit_code(), 1);
+    }
+
+    #[test]
+    fn test_pooled_analysis_error_is_kept() {
+        let dir = tempfile::tempdir().unwrap();
+        let file = dir.path().join("amounts.txt");
+        let amounts: Vec<String> = (1..=30).map(|i| (i * i * 17).to_string()).collect();
+        std::fs::write(&file, amounts.join("\n")).unwrap();
+
+        let batch = analyze_batch(&[file], &InputOptions::default(), |name, numbers| {
+            if name.starts_with("pooled") {
+                Err(BenfError::InsufficientData(numbers.len()))
+            } else {
+                analyze_pareto(name, numbers)
+            }
+        });
+        assert_eq!(batch.files.len(), 1);
+        assert!(batch.pooled.is_none());
+        let error = batch.pooled_error.as_deref().unwrap();
+        assert_eq!(error, BenfError::InsufficientData(30).to_string());
+        assert!(format_batch_summary(&batch).contains(&format!("(pooled): {error}")));
+    }
+}
@@ -0,0 +1,123 @@
+use serde_json::Value;
+
+/// JSON 値の構造的な差分の 1 件
+///
+/// パスはオブジェクトのキーを `.`、配列の添字を `[i]` でつなげたもの（例: `scores.benf`、
+/// `values[2]`）。ルート同士の差分は空文字列。
+#[derive(Debug, Clone, PartialEq)]
+pub enum DiffResult {
+    /// 値が変わった（同じ型）
+    Modified(String, Value, Value),
+    /// 新しい側にだけある
+    Added(String, Value),
+    /// 古い側にだけある
+    Removed(String, Value),
+    /// 型が変わった（数値 → 文字列など）
+    TypeChanged(String, Value, Value),
+}
+
+/// 2 つの JSON 値を再帰的に比較し、差分をパス順に返す（同一なら空）
+///
+/// 数値は f64 として比較するので `1` と `1.0` は同じとみなす。
+pub fn diff(old: &Value, new: &Value) -> Vec<DiffResult> {
+    let mut results = Vec::new();
+    diff_at("", old, new, &mut results);
+    results
+}
+
+fn diff_at(path: &str, old: &Value, new: &Value, results: &mut Vec<DiffResult>) {
+    match (old, new) {
+        (Value::Object(old_map), Value::Object(new_map)) => {
+            let mut keys: Vec<&String> = old_map.keys().chain(new_map.keys()).collect();
+            keys.sort();
+            keys.dedup();
+            for key in keys {
+                let child = if path.is_empty() {
+                    key.clone()
+                } else {
+                    format!("{path}.{key}")
+                };
+                match (old_map.get(key), new_map.get(key)) {
+                    (Some(old_value), Some(new_value)) => {
+                        diff_at(&child, old_value, new_value, results)
+                    }
+                    (Some(old_value), None) => {
+                        results.push(DiffResult::Removed(child, old_value.clone()))
+                    }
+                    (None, Some(new_value)) => {
+                        results.push(DiffResult::Added(child, new_value.clone()))
+                    }
+                    (None, None) => {}
+                }
+            }
+        }
+        (Value::Array(old_items), Value::Array(new_items)) => {
+            for index in 0..old_items.len().max(new_items.len()) {
+                let child = format!("{path}[{index}]");
+                match (old_items.get(index), new_items.get(index)) {
+                    (Some(old_value), Some(new_value)) => {
+                        diff_at(&child, old_value, new_value, results)
+                    }
+                    (Some(old_value), None) => {
+                        results.push(DiffResult::Removed(child, old_value.clone()))
+                    }
+                    (None, Some(new_value)) => {
+                        results.push(DiffResult::Added(child, new_value.clone()))
+                    }
+                    (None, None) => {}
+                }
+            }
+        }
+        (Value::Number(old_number), Value::Number(new_number)) => {
+            if old_number.as_f64() != new_number.as_f64() {
+                results.push(DiffResult::Modified(
+                    path.to_string(),
+                    old.clone(),
+                    new.clone(),
+                ));
+            }
+        }
+        _ if std::mem::discriminant(old) != std::mem::discriminant(new) => results.push(
+            DiffResult::TypeChanged(path.to_string(), old.clone(), new.clone()),
+        ),
+        _ => {
+            if old != new {
+                results.push(DiffResult::Modified(
+                    path.to_string(),
+                    old.clone(),
+                    new.clone(),
+                ));
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_identical_values_have_no_diff() {
+        let value = json!({"a": 1, "b": [1, 2, {"c": "x"}]});
+        assert!(diff(&value, &value).is_empty());
+        assert!(diff(&json!(1), &json!(1.0)).is_empty());
+    }
+
+    #[test]
+    fn test_diff_reports_paths() {
+        let old = json!({"a": 1, "b": {"c": "x", "d": true}, "e": [1, 2], "f": 0});
+        let new = json!({"a": 2, "b": {"c": "y"}, "e": [1, 2, 3], "f": "0", "g": null});
+        assert_eq!(
+            diff(&old, &new),
+            vec![
+                DiffResult::Modified("a".to_string(), json!(1), json!(2)),
+                DiffResult::Modified("b.c".to_string(), json!("x"), json!("y")),
+                DiffResult::Removed("b.d".to_string(), json!(true)),
+                DiffResult::Added("e[2]".to_string(), json!(3)),
+                DiffResult::TypeChanged("f".to_string(), json!(0), json!("0")),
+                DiffResult::Added("g".to_string(), json!(null)),
+            ]
+        );
+    }
+}
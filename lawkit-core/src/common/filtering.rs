@@ -149,6 +149,8 @@ pub enum RiskThreshold {
     Critical,
     /// Custom p-value threshold
     Custom(f64),
+    /// Nigrini's MAD conformity ranges (Benford digit tests only)
+    MadConformity,
 }
 
 impl FromStr for RiskThreshold {
@@ -161,6 +163,7 @@ impl FromStr for RiskThreshold {
             "medium" => Ok(RiskThreshold::Medium),
             "high" => Ok(RiskThreshold::High),
             "critical" => Ok(RiskThreshold::Critical),
+            "mad" | "nigrini" => Ok(RiskThreshold::MadConformity),
             _ => {
                 // Try to parse as custom p-value
                 match s.parse::<f64>() {
@@ -168,7 +171,7 @@ impl FromStr for RiskThreshold {
                         Ok(RiskThreshold::Custom(p_value))
                     },
                     Ok(_) => Err("Custom p-value must be between 0.0 and 1.0".to_string()),
-                    Err(_) => Err(format!("Invalid threshold: {s}. Use: auto, low, medium, high, critical, or a p-value (0.0-1.0)")),
+                    Err(_) => Err(format!("Invalid threshold: {s}. Use: auto, low, medium, high, critical, mad, or a p-value (0.0-1.0)")),
                 }
             }
         }
@@ -179,7 +182,7 @@ impl RiskThreshold {
     /// Get the p-value threshold for this risk level
     pub fn p_value_threshold(&self) -> Option<f64> {
         match self {
-            RiskThreshold::Auto => None,
+            RiskThreshold::Auto | RiskThreshold::MadConformity => None,
             RiskThreshold::Low => Some(0.2),
             RiskThreshold::Medium => Some(0.1),
             RiskThreshold::High => Some(0.05),
@@ -188,8 +191,24 @@ impl RiskThreshold {
         }
     }
 
+    /// Whether risk comes from MAD conformity instead of the p-value
+    pub fn uses_mad_conformity(&self) -> bool {
+        matches!(self, RiskThreshold::MadConformity)
+    }
+
     /// Determine risk level based on p-value and this threshold
+    ///
+    /// `MadConformity` has no p-value cut-off and evaluates like `Auto` here.
     pub fn evaluate_risk(&self, p_value: f64) -> crate::common::risk::RiskLevel {
+        self.evaluate_risk_with_significance(p_value, 0.05)
+    }
+
+    /// Determine risk level, scaling the Auto bands to the given significance level
+    pub fn evaluate_risk_with_significance(
+        &self,
+        p_value: f64,
+        significance_level: f64,
+    ) -> crate::common::risk::RiskLevel {
         if let Some(threshold) = self.p_value_threshold() {
             // Custom threshold evaluation
             if p_value <= threshold {
@@ -199,15 +218,10 @@ impl RiskThreshold {
             }
         } else {
             // Auto mode: use default Benford analysis
-            if p_value <= 0.01 {
-                crate::common::risk::RiskLevel::Critical
-            } else if p_value <= 0.05 {
-                crate::common::risk::RiskLevel::High
-            } else if p_value <= 0.1 {
-                crate::common::risk::RiskLevel::Medium
-            } else {
-                crate::common::risk::RiskLevel::Low
-            }
+            crate::common::risk::RiskLevel::from_p_value_with_significance(
+                p_value,
+                significance_level,
+            )
         }
     }
 }
@@ -315,6 +329,14 @@ mod tests {
             "0.05".parse::<RiskThreshold>().unwrap(),
             RiskThreshold::Custom(0.05)
         );
+        assert_eq!(
+            "mad".parse::<RiskThreshold>().unwrap(),
+            RiskThreshold::MadConformity
+        );
+        assert!("nigrini"
+            .parse::<RiskThreshold>()
+            .unwrap()
+            .uses_mad_conformity());
 
         assert!("invalid".parse::<RiskThreshold>().is_err());
         assert!("2.0".parse::<RiskThreshold>().is_err()); // Out of range
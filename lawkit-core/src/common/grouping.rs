@@ -0,0 +1,456 @@
+use crate::common::batch::BatchAnalysis;
+use crate::common::input::NumberRecord;
+use crate::common::memory::IncrementalBenford;
+use crate::common::output::formatter::FormattableResult;
+use crate::common::risk::RiskLevel;
+use crate::error::{BenfError, Result};
+use crate::laws::benford::{BenfordResult, DigitTest};
+use rayon::prelude::*;
+use serde::Serialize;
+use std::collections::HashMap;
+
+/// ベンフォードの桁数を並列に集計するときの1タスクあたりのレコード数
+const BENFORD_CHUNK_SIZE: usize = 4096;
+
+/// グループ別分析に渡す1グループ分のデータ
+pub trait GroupSample: Send + Sync {
+    /// 分析の対象になる数値の個数（`min_count` と比べる）
+    fn count(&self) -> usize;
+}
+
+impl GroupSample for Vec<f64> {
+    fn count(&self) -> usize {
+        self.len()
+    }
+}
+
+impl GroupSample for IncrementalBenford {
+    fn count(&self) -> usize {
+        self.total_count()
+    }
+}
+
+/// 1グループ分のベンフォード集計：第1桁の個数と、指定された桁テストのビンごとの個数
+#[derive(Debug, Clone)]
+pub struct BenfordGroupCounts {
+    pub first_digit: IncrementalBenford,
+    /// `analyze_benford_groups` に渡した桁テストの順
+    pub digit_tests: Vec<(DigitTest, Vec<usize>)>,
+}
+
+impl BenfordGroupCounts {
+    fn new(digit_tests: &[DigitTest]) -> Self {
+        Self {
+            first_digit: IncrementalBenford::new(),
+            digit_tests: digit_tests
+                .iter()
+                .map(|&test| (test, vec![0; test.bin_count()]))
+                .collect(),
+        }
+    }
+
+    fn add(&mut self, value: f64) {
+        self.first_digit.add(value);
+        for (test, counts) in &mut self.digit_tests {
+            if let Some(index) = test.bin_index(value) {
+                counts[index] += 1;
+            }
+        }
+    }
+
+    fn merge(&mut self, other: &BenfordGroupCounts) {
+        self.first_digit.merge(&other.first_digit);
+        for ((_, counts), (_, other_counts)) in self.digit_tests.iter_mut().zip(&other.digit_tests)
+        {
+            for (count, other_count) in counts.iter_mut().zip(other_counts) {
+                *count += other_count;
+            }
+        }
+    }
+}
+
+impl GroupSample for BenfordGroupCounts {
+    fn count(&self) -> usize {
+        self.first_digit.count()
+    }
+}
+
+/// 1グループの分析結果
+#[derive(Debug, Clone, Serialize)]
+pub struct GroupEntry<R> {
+    pub rank: usize,
+    pub group: String,
+    pub deviation: f64,
+    pub result: R,
+}
+
+/// 数値が足りない、または分析できなかったグループ
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct SkippedGroup {
+    pub group: String,
+    pub count: usize,
+    pub reason: String,
+}
+
+/// カテゴリ列ごとに法則を適用した結果
+#[derive(Debug, Clone, Serialize)]
+pub struct GroupResult<R> {
+    pub group_by: String,
+    /// 法則からの偏差の大きい順
+    pub groups: Vec<GroupEntry<R>>,
+    pub skipped: Vec<SkippedGroup>,
+    /// グループの値が空だった数値の個数
+    pub ungrouped: usize,
+}
+
+impl<R: BatchAnalysis> GroupResult<R> {
+    /// 最もリスクの高いグループのリスクレベル
+    pub fn worst_risk_level(&self) -> Option<RiskLevel> {
+        self.groups
+            .iter()
+            .map(|entry| entry.result.risk_level())
+            .max()
+    }
+
+    /// 最もリスクの高いグループの終了コード（分析できたグループがなければ 1）
+    pub fn exit_code(&self) -> i32 {
+        self.worst_risk_level()
+            .map_or(1, |risk_level| risk_level.exit_code())
+    }
+}
+
+impl<R: BatchAnalysis> FormattableResult for GroupResult<R> {
+    const TITLE: &'static str = "Group Analysis";
+    const ROOT: &'static str = "group_result";
+
+    /// テキストでは偏差順のグループ表とスキップしたグループの一覧を出力する
+    fn format_text(&self) -> Result<String> {
+        Ok(format_group_summary(self))
+    }
+}
+
+/// レコードをグループの値ごとに分け（初出順）、グループごとに `analyze` で分析する
+///
+/// `min_count` 未満のグループは分析せずにスキップする。グループの値を持つレコードが
+/// 1つもなければエラー。
+pub fn analyze_groups<R, F>(
+    group_by: &str,
+    records: &[NumberRecord],
+    min_count: usize,
+    analyze: F,
+) -> Result<GroupResult<R>>
+where
+    R: BatchAnalysis,
+    F: Fn(&str, &[f64]) -> Result<R> + Sync,
+{
+    let mut index = HashMap::new();
+    let mut groups: Vec<(String, Vec<f64>)> = Vec::new();
+    for record in records {
+        if let Some(group) = &record.group {
+            let position = *index.entry(group.clone()).or_insert_with(|| {
+                groups.push((group.clone(), Vec::new()));
+                groups.len() - 1
+            });
+            groups[position].1.push(record.value);
+        }
+    }
+
+    rank_groups(
+        group_by,
+        groups,
+        ungrouped(records),
+        min_count,
+        |group, values| analyze(group, values),
+    )
+}
+
+/// ベンフォード分析をグループ別に行う
+///
+/// 数値そのものは保持せず、レコードを並列に `BenfordGroupCounts`（第1桁と
+/// `digit_tests` の各ビンの個数）へ集計して結合し、`to_result` で各グループの結果に
+/// 変換する。`min_count` は第1桁を数えられた数値の個数と比べる。
+pub fn analyze_benford_groups<F>(
+    group_by: &str,
+    records: &[NumberRecord],
+    min_count: usize,
+    digit_tests: &[DigitTest],
+    to_result: F,
+) -> Result<GroupResult<BenfordResult>>
+where
+    F: Fn(&str, &BenfordGroupCounts) -> Result<BenfordResult> + Sync,
+{
+    let groups = records
+        .par_chunks(BENFORD_CHUNK_SIZE)
+        .map(|chunk| {
+            let mut groups = BenfordGroups::new(digit_tests);
+            for record in chunk {
+                if let Some(group) = &record.group {
+                    groups.entry(group).add(record.value);
+                }
+            }
+            groups
+        })
+        .reduce(|| BenfordGroups::new(digit_tests), BenfordGroups::merge);
+
+    rank_groups(
+        group_by,
+        groups.groups,
+        ungrouped(records),
+        min_count,
+        to_result,
+    )
+}
+
+/// 初出順を保つグループごとのベンフォード集計
+struct BenfordGroups<'a> {
+    digit_tests: &'a [DigitTest],
+    index: HashMap<String, usize>,
+    groups: Vec<(String, BenfordGroupCounts)>,
+}
+
+impl<'a> BenfordGroups<'a> {
+    fn new(digit_tests: &'a [DigitTest]) -> Self {
+        Self {
+            digit_tests,
+            index: HashMap::new(),
+            groups: Vec::new(),
+        }
+    }
+
+    fn entry(&mut self, group: &str) -> &mut BenfordGroupCounts {
+        let position = match self.index.get(group) {
+            Some(&position) => position,
+            None => {
+                self.index.insert(group.to_string(), self.groups.len());
+                self.groups
+                    .push((group.to_string(), BenfordGroupCounts::new(self.digit_tests)));
+                self.groups.len() - 1
+            }
+        };
+        &mut self.groups[position].1
+    }
+
+    fn merge(mut self, other: BenfordGroups<'a>) -> BenfordGroups<'a> {
+        for (group, counts) in &other.groups {
+            self.entry(group).merge(counts);
+        }
+        self
+    }
+}
+
+fn ungrouped(records: &[NumberRecord]) -> usize {
+    records
+        .iter()
+        .filter(|record| record.group.is_none())
+        .count()
+}
+
+/// 各グループを並列に分析し、偏差の大きい順に並べる（同じ偏差では初出順）
+fn rank_groups<S, R, F>(
+    group_by: &str,
+    groups: Vec<(String, S)>,
+    ungrouped: usize,
+    min_count: usize,
+    analyze: F,
+) -> Result<GroupResult<R>>
+where
+    S: GroupSample,
+    R: BatchAnalysis,
+    F: Fn(&str, &S) -> Result<R> + Sync,
+{
+    if groups.is_empty() {
+        return Err(BenfError::InvalidInput(format!(
+            "no values found in group column '{group_by}' \
+             (grouping applies to CSV/TSV, spreadsheet and JSON inputs)"
+        )));
+    }
+
+    let outcomes: Vec<(String, usize, Option<Result<R>>)> = groups
+        .into_par_iter()
+        .map(|(group, sample)| {
+            let count = sample.count();
+            let result = (count >= min_count).then(|| analyze(&group, &sample));
+            (group, count, result)
+        })
+        .collect();
+
+    let mut entries = Vec::new();
+    let mut skipped = Vec::new();
+    for (group, count, result) in outcomes {
+        match result {
+            Some(Ok(result)) => entries.push((group, result)),
+            Some(Err(e)) => skipped.push(SkippedGroup {
+                group,
+                count,
+                reason: e.to_string(),
+            }),
+            None => skipped.push(SkippedGroup {
+                group,
+                count,
+                reason: format!("fewer than {min_count} numbers"),
+            }),
+        }
+    }
+
+    entries.sort_by(|(_, a), (_, b)| b.deviation().total_cmp(&a.deviation()));
+    let groups = entries
+        .into_iter()
+        .enumerate()
+        .map(|(index, (group, result))| GroupEntry {
+            rank: index + 1,
+            group,
+            deviation: result.deviation(),
+            result,
+        })
+        .collect();
+
+    Ok(GroupResult {
+        group_by: group_by.to_string(),
+        groups,
+        skipped,
+        ungrouped,
+    })
+}
+
+/// 偏差順のグループ表とスキップしたグループの一覧
+pub fn format_group_summary<R: BatchAnalysis>(result: &GroupResult<R>) -> String {
+    let mut output = format!(
+        "{} by {} ({} groups, ranked by deviation)\n\n",
+        GroupResult::<R>::TITLE,
+        result.group_by,
+        result.groups.len() + result.skipped.len()
+    );
+    output.push_str(&format!(
+        "{:>4}  {:<8}  {:>9}  {:>8}  Group\n",
+        "Rank", "Risk", "Deviation", "Numbers"
+    ));
+    for entry in &result.groups {
+        output.push_str(&format!(
+            "{:>4}  {:<8}  {:>9.4}  {:>8}  {}\n",
+            entry.rank,
+            entry.result.risk_level().to_string(),
+            entry.deviation,
+            entry.result.numbers_analyzed(),
+            entry.group
+        ));
+    }
+
+    if !result.skipped.is_empty() {
+        output.push_str("\nSkipped:\n");
+        for skipped in &result.skipped {
+            output.push_str(&format!(
+                "  {} ({} numbers): {}\n",
+                skipped.group, skipped.count, skipped.reason
+            ));
+        }
+    }
+    if result.ungrouped > 0 {
+        output.push_str(&format!(
+            "\nNumbers without a group: {}\n",
+            result.ungrouped
+        ));
+    }
+    output
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::input::Provenance;
+    use crate::laws::benford::analyze_benford_law;
+    use crate::laws::pareto::{analyze_pareto_distribution, ParetoResult};
+
+    fn record(group: Option<&str>, value: f64) -> NumberRecord {
+        NumberRecord::new(value, Provenance::default(), value.to_string())
+            .with_group(group.map(str::to_string))
+    }
+
+    fn analyze_pareto(name: &str, numbers: &[f64]) -> Result<ParetoResult> {
+        analyze_pareto_distribution(numbers, name)
+    }
+
+    #[test]
+    fn test_analyze_groups() {
+        let mut records = Vec::new();
+        // V001 hides a few huge invoices; V002 bills about 80/20; V003 has too few invoices
+        for i in 0..50 {
+            records.push(record(Some("V002"), if i < 10 { 16.0 } else { 2.25 }));
+            records.push(record(Some("V001"), if i < 5 { 10000.0 } else { 1.0 }));
+        }
+        records.push(record(Some("V003"), 42.0));
+        records.push(record(None, 7.0));
+
+        let result = analyze_groups("vendor_id", &records, 10, analyze_pareto).unwrap();
+        let ranked: Vec<&str> = result.groups.iter().map(|g| g.group.as_str()).collect();
+        assert_eq!(ranked, ["V001", "V002"]);
+        assert_eq!(result.groups[0].rank, 1);
+        assert!(result.groups[0].deviation > result.groups[1].deviation);
+        assert_eq!(result.groups[1].result.numbers_analyzed, 50);
+        assert_eq!(result.groups[1].result.dataset_name, "V002");
+
+        assert_eq!(
+            result.skipped,
+            [SkippedGroup {
+                group: "V003".to_string(),
+                count: 1,
+                reason: "fewer than 10 numbers".to_string(),
+            }]
+        );
+        assert_eq!(result.ungrouped, 1);
+        assert_eq!(
+            result.exit_code(),
+            result.worst_risk_level().unwrap().exit_code()
+        );
+
+        let summary = format_group_summary(&result);
+        assert!(summary.contains("Group Analysis by vendor_id (3 groups, ranked by deviation)"));
+        assert!(summary.contains("V003 (1 numbers): fewer than 10 numbers"));
+        assert!(summary.contains("Numbers without a group: 1"));
+    }
+
+    #[test]
+    fn test_analyze_groups_without_group_values() {
+        let records = [record(None, 1.0), record(None, 2.0)];
+        assert!(analyze_groups("vendor_id", &records, 1, analyze_pareto).is_err());
+    }
+
+    #[test]
+    fn test_benford_groups_merge_chunks() {
+        // Enough records to span several parallel chunks, interleaving the groups
+        let records: Vec<NumberRecord> = (1..=3 * BENFORD_CHUNK_SIZE)
+            .map(|i| {
+                let group = if i % 3 == 0 { "CC-100" } else { "CC-200" };
+                record(Some(group), i as f64)
+            })
+            .collect();
+
+        let tests = [DigitTest::FirstTwo, DigitTest::LastTwo];
+        let result =
+            analyze_benford_groups("cost_center", &records, 10, &tests, |group, counts| {
+                let numbers: Vec<f64> = records
+                    .iter()
+                    .filter(|r| r.group.as_deref() == Some(group))
+                    .map(|r| r.value)
+                    .collect();
+                assert_eq!(counts.first_digit.total_count(), numbers.len());
+                let mut expected = IncrementalBenford::new();
+                expected.add_batch(&numbers);
+                assert_eq!(counts.first_digit.get_counts(), expected.get_counts());
+                for (&test, (counted_test, bins)) in tests.iter().zip(&counts.digit_tests) {
+                    assert_eq!(*counted_test, test);
+                    assert_eq!(*bins, test.count_bins(&numbers));
+                }
+                analyze_benford_law(&numbers, group)
+            })
+            .unwrap();
+
+        let counted: HashMap<&str, usize> = result
+            .groups
+            .iter()
+            .map(|entry| (entry.group.as_str(), entry.result.numbers_analyzed))
+            .collect();
+        assert_eq!(counted["CC-100"], BENFORD_CHUNK_SIZE);
+        assert_eq!(counted["CC-200"], 2 * BENFORD_CHUNK_SIZE);
+        assert!(result.skipped.is_empty());
+    }
+}
@@ -0,0 +1,540 @@
+use super::file_detector::{detect_file_format, parse_file_records_by_format_with};
+use super::options::InputOptions;
+use super::provenance::NumberRecord;
+use crate::error::{BenfError, Result};
+use std::fs::File;
+use std::io::{self, BufReader, Read};
+use std::path::{Path, PathBuf};
+
+/// Archives and compressed files nested deeper than this are rejected instead of unpacked
+const MAX_NESTING: usize = 8;
+
+/// Bytes one input may unpack to across all its members and nesting levels; more is
+/// rejected as a decompression bomb
+const MAX_UNPACKED_BYTES: u64 = 1 << 30;
+
+/// Compression formats that are decompressed transparently, detected by magic bytes
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Compression {
+    Gzip,
+    Zstd,
+    Bzip2,
+    Xz,
+}
+
+impl Compression {
+    /// Identify a compressed stream from its first bytes
+    pub fn detect(header: &[u8]) -> Option<Self> {
+        const BZIP2_BLOCK: &[u8] = &[0x31, 0x41, 0x59, 0x26, 0x53, 0x59];
+        const BZIP2_END: &[u8] = &[0x17, 0x72, 0x45, 0x38, 0x50, 0x90];
+
+        if header.starts_with(&[0x1F, 0x8B, 0x08]) {
+            Some(Self::Gzip)
+        } else if header.starts_with(&[0x28, 0xB5, 0x2F, 0xFD]) {
+            Some(Self::Zstd)
+        } else if header.starts_with(&[0xFD, b'7', b'z', b'X', b'Z', 0x00]) {
+            Some(Self::Xz)
+        } else if header.len() >= 10
+            && header.starts_with(b"BZh")
+            && (b'1'..=b'9').contains(&header[3])
+            && (&header[4..10] == BZIP2_BLOCK || &header[4..10] == BZIP2_END)
+        {
+            Some(Self::Bzip2)
+        } else {
+            None
+        }
+    }
+
+    /// Wrap `reader` in a decoder for this compression (concatenated streams included)
+    pub fn decoder<'a>(self, reader: impl Read + 'a) -> Result<Box<dyn Read + 'a>> {
+        let reader = BufReader::new(reader);
+        Ok(match self {
+            Self::Gzip => Box::new(flate2::read::MultiGzDecoder::new(reader)),
+            Self::Zstd => Box::new(zstd::stream::read::Decoder::with_buffer(reader)?),
+            Self::Bzip2 => Box::new(bzip2::read::MultiBzDecoder::new(reader)),
+            Self::Xz => Box::new(xz2::read::XzDecoder::new_multi_decoder(reader)),
+        })
+    }
+}
+
+/// Archive formats whose members are read as separate inputs
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ArchiveFormat {
+    Zip,
+    Tar,
+}
+
+/// The numbers read from one member of an archive, or from a plain or compressed file
+#[derive(Debug, Clone, PartialEq)]
+pub struct ArchiveMember {
+    /// `archive.zip:dir/member.csv` for archive members, the file path otherwise
+    pub name: String,
+    pub records: Vec<NumberRecord>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Packing {
+    Compressed(Compression),
+    Archive(ArchiveFormat),
+}
+
+/// Whether `path` is a compressed file or a ZIP/tar archive rather than a plain input
+pub fn is_packed(path: &Path) -> bool {
+    matches!(detect_packing(path), Ok(Some(_)))
+}
+
+/// Read every member of the archive (or compressed file) at `path` as a separate input
+///
+/// Compressed files are decompressed first and archives nested in archives are traversed,
+/// so `reports.tar.gz` yields one member per file in the tarball. Members without numbers
+/// are skipped; a plain file yields itself as the only member. Each record's `file` is
+/// set to its member name.
+pub fn parse_archive_members(path: &Path) -> Result<Vec<ArchiveMember>> {
+    parse_archive_members_with(path, &InputOptions::default())
+}
+
+/// Like `parse_archive_members`, applying the column, sheet, key and locale options to
+/// every member
+pub fn parse_archive_members_with(
+    path: &Path,
+    options: &InputOptions,
+) -> Result<Vec<ArchiveMember>> {
+    let mut members = Vec::new();
+    let mut budget = MAX_UNPACKED_BYTES;
+    let name = path.display().to_string();
+    read_members(path, &name, options, 0, &mut budget, &mut members)?;
+    Ok(members)
+}
+
+/// `budget` is the number of bytes that may still be unpacked
+fn read_members(
+    path: &Path,
+    name: &str,
+    options: &InputOptions,
+    depth: usize,
+    budget: &mut u64,
+    members: &mut Vec<ArchiveMember>,
+) -> Result<()> {
+    let packing = detect_packing(path)?;
+    if packing.is_some() && depth >= MAX_NESTING {
+        return Err(BenfError::InvalidInput(format!(
+            "{name}: archives nested more than {MAX_NESTING} levels deep"
+        )));
+    }
+
+    match packing {
+        None => {
+            let format = detect_file_format(path);
+            let mut records = match parse_file_records_by_format_with(path, &format, options) {
+                // A member without numbers (a README, say) does not fail the whole archive
+                Err(BenfError::NoNumbersFound) if depth > 0 => return Ok(()),
+                result => result?,
+            };
+            if !records.is_empty() {
+                for record in &mut records {
+                    record.source.file = Some(name.to_string());
+                }
+                members.push(ArchiveMember {
+                    name: name.to_string(),
+                    records,
+                });
+            }
+            Ok(())
+        }
+        Some(Packing::Compressed(compression)) => {
+            let dir = temp_dir()?;
+            let inner = dir.path().join(decompressed_name(path));
+            let mut decoder = compression.decoder(File::open(path)?)?;
+            unpack(&mut decoder, &inner, budget).map_err(|e| in_member(e, name))?;
+            read_members(&inner, name, options, depth + 1, budget, members)
+        }
+        Some(Packing::Archive(ArchiveFormat::Zip)) => {
+            let mut archive = zip::ZipArchive::new(File::open(path)?)
+                .map_err(|e| BenfError::FileError(format!("Failed to open ZIP archive: {e}")))?;
+            let dir = temp_dir()?;
+            for index in 0..archive.len() {
+                let mut entry = archive.by_index(index).map_err(|e| {
+                    BenfError::FileError(format!("Failed to read ZIP archive: {e}"))
+                })?;
+                let member = entry.name().to_string();
+                if entry.is_dir() || is_metadata(&member) {
+                    continue;
+                }
+                let member_name = format!("{name}:{member}");
+                let extracted = extract(&mut entry, dir.path(), index, &member, budget)
+                    .map_err(|e| in_member(e, &member_name))?;
+                read_members(
+                    &extracted,
+                    &member_name,
+                    options,
+                    depth + 1,
+                    budget,
+                    members,
+                )
+                .map_err(|e| in_member(e, &member_name))?;
+            }
+            Ok(())
+        }
+        Some(Packing::Archive(ArchiveFormat::Tar)) => {
+            let mut archive = tar::Archive::new(File::open(path)?);
+            let dir = temp_dir()?;
+            let entries = archive
+                .entries()
+                .map_err(|e| BenfError::FileError(format!("Failed to open tar archive: {e}")))?;
+            for (index, entry) in entries.enumerate() {
+                let mut entry = entry.map_err(|e| {
+                    BenfError::FileError(format!("Failed to read tar archive: {e}"))
+                })?;
+                let member = entry.path()?.to_string_lossy().into_owned();
+                if !entry.header().entry_type().is_file() || is_metadata(&member) {
+                    continue;
+                }
+                let member_name = format!("{name}:{member}");
+                let extracted = extract(&mut entry, dir.path(), index, &member, budget)
+                    .map_err(|e| in_member(e, &member_name))?;
+                read_members(
+                    &extracted,
+                    &member_name,
+                    options,
+                    depth + 1,
+                    budget,
+                    members,
+                )
+                .map_err(|e| in_member(e, &member_name))?;
+            }
+            Ok(())
+        }
+    }
+}
+
+fn detect_packing(path: &Path) -> Result<Option<Packing>> {
+    let mut header = Vec::with_capacity(512);
+    File::open(path)
+        .and_then(|file| file.take(512).read_to_end(&mut header))
+        .map_err(|e| BenfError::FileError(format!("Failed to read {}: {e}", path.display())))?;
+
+    if let Some(compression) = Compression::detect(&header) {
+        return Ok(Some(Packing::Compressed(compression)));
+    }
+    // POSIX and GNU tar headers carry "ustar" at offset 257
+    if header.get(257..262) == Some(b"ustar".as_slice()) || extension(path) == "tar" {
+        return Ok(Some(Packing::Archive(ArchiveFormat::Tar)));
+    }
+    if header.starts_with(b"PK\x03\x04") && is_plain_zip(path) {
+        return Ok(Some(Packing::Archive(ArchiveFormat::Zip)));
+    }
+    Ok(None)
+}
+
+/// Tell plain ZIP archives from the ZIP-based Office and OpenDocument formats
+fn is_plain_zip(path: &Path) -> bool {
+    match extension(path).as_str() {
+        "zip" => true,
+        "xlsx" | "xls" | "docx" | "doc" | "pptx" | "ppt" | "ods" | "odt" => false,
+        _ => File::open(path)
+            .ok()
+            .and_then(|file| zip::ZipArchive::new(file).ok())
+            .is_some_and(|archive| {
+                archive
+                    .file_names()
+                    .all(|name| name != "[Content_Types].xml" && name != "mimetype")
+            }),
+    }
+}
+
+fn extension(path: &Path) -> String {
+    path.extension()
+        .and_then(|ext| ext.to_str())
+        .unwrap_or("")
+        .to_lowercase()
+}
+
+/// File name of the decompressed content, so that its format can still be detected
+fn decompressed_name(path: &Path) -> PathBuf {
+    let stem = path.file_stem().unwrap_or_default();
+    match extension(path).as_str() {
+        "gz" | "gzip" | "zst" | "zstd" | "bz2" | "bzip2" | "xz" => PathBuf::from(stem),
+        "tgz" | "tzst" | "tbz" | "tbz2" | "txz" => Path::new(stem).with_extension("tar"),
+        // Detected by magic bytes alone: the content decides the format
+        _ => PathBuf::from("content"),
+    }
+}
+
+/// Skip the resource forks and dotfiles that archivers add next to the real members
+fn is_metadata(member: &str) -> bool {
+    member.starts_with("__MACOSX/")
+        || Path::new(member)
+            .file_name()
+            .and_then(|name| name.to_str())
+            .is_some_and(|name| name.starts_with('.'))
+}
+
+/// Write the `index`th member to its own directory in `dir` under its base name
+///
+/// Members with the same base name in different archive directories do not collide, and
+/// as the directories inside the archive are never recreated, member paths cannot escape
+/// `dir`.
+fn extract(
+    entry: &mut impl Read,
+    dir: &Path,
+    index: usize,
+    member: &str,
+    budget: &mut u64,
+) -> Result<PathBuf> {
+    let file_name = Path::new(member)
+        .file_name()
+        .map(PathBuf::from)
+        .unwrap_or_else(|| PathBuf::from("member"));
+    let member_dir = dir.join(index.to_string());
+    std::fs::create_dir(&member_dir)?;
+    let path = member_dir.join(file_name);
+    unpack(entry, &path, budget)?;
+    Ok(path)
+}
+
+/// Copy `reader` into a new file at `path`, failing once more than `budget` bytes come out
+fn unpack(reader: &mut impl Read, path: &Path, budget: &mut u64) -> Result<()> {
+    let written = io::copy(
+        &mut reader.take(budget.saturating_add(1)),
+        &mut File::create(path)?,
+    )
+    .map_err(|e| BenfError::FileError(format!("Failed to unpack: {e}")))?;
+    if written > *budget {
+        return Err(BenfError::InvalidInput(format!(
+            "unpacks to more than {} MiB",
+            MAX_UNPACKED_BYTES >> 20
+        )));
+    }
+    *budget -= written;
+    Ok(())
+}
+
+fn temp_dir() -> Result<tempfile::TempDir> {
+    tempfile::tempdir()
+        .map_err(|e| BenfError::FileError(format!("Failed to create temporary directory: {e}")))
+}
+
+/// Name the archive member an error came from, unless an inner member already did
+fn in_member(error: BenfError, member: &str) -> BenfError {
+    let prefix = |message: String| {
+        if message.starts_with(member) {
+            message
+        } else {
+            format!("{member}: {message}")
+        }
+    };
+    match error {
+        BenfError::InvalidInput(message) => BenfError::InvalidInput(prefix(message)),
+        BenfError::FileError(message) => BenfError::FileError(prefix(message)),
+        BenfError::ParseError(message) => BenfError::ParseError(prefix(message)),
+        BenfError::IoError(message) => BenfError::IoError(prefix(message)),
+        other => other,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Write;
+
+    const MONTHS: &str = "month,amount\njan,1200\nfeb,3400\nmar,560\n";
+
+    fn compressed(compression: Compression, data: &[u8]) -> Vec<u8> {
+        match compression {
+            Compression::Gzip => {
+                let mut encoder =
+                    flate2::write::GzEncoder::new(Vec::new(), flate2::Compression::default());
+                encoder.write_all(data).unwrap();
+                encoder.finish().unwrap()
+            }
+            Compression::Zstd => zstd::encode_all(data, 0).unwrap(),
+            Compression::Bzip2 => {
+                let mut encoder =
+                    bzip2::write::BzEncoder::new(Vec::new(), bzip2::Compression::default());
+                encoder.write_all(data).unwrap();
+                encoder.finish().unwrap()
+            }
+            Compression::Xz => {
+                let mut encoder = xz2::write::XzEncoder::new(Vec::new(), 6);
+                encoder.write_all(data).unwrap();
+                encoder.finish().unwrap()
+            }
+        }
+    }
+
+    fn values(members: &[ArchiveMember]) -> Vec<f64> {
+        members
+            .iter()
+            .flat_map(|member| member.records.iter().map(|record| record.value))
+            .collect()
+    }
+
+    #[test]
+    fn test_decompression() {
+        let dir = tempfile::tempdir().unwrap();
+        for (compression, extension) in [
+            (Compression::Gzip, "csv.gz"),
+            (Compression::Zstd, "csv.zst"),
+            (Compression::Bzip2, "csv.bz2"),
+            (Compression::Xz, "csv.xz"),
+        ] {
+            let data = compressed(compression, MONTHS.as_bytes());
+            assert_eq!(Compression::detect(&data), Some(compression));
+
+            let path = dir.path().join(format!("months.{extension}"));
+            std::fs::write(&path, &data).unwrap();
+            let members = parse_archive_members(&path).unwrap();
+            assert_eq!(members.len(), 1);
+            assert_eq!(values(&members), [1200.0, 3400.0, 560.0]);
+            let source = &members[0].records[0].source;
+            assert_eq!(
+                source.file.as_deref(),
+                Some(path.display().to_string().as_str())
+            );
+            assert_eq!(source.header.as_deref(), Some("amount"), "read as CSV");
+        }
+
+        // Magic bytes decide, whatever the extension says
+        let path = dir.path().join("months.csv");
+        std::fs::write(&path, compressed(Compression::Gzip, MONTHS.as_bytes())).unwrap();
+        assert!(is_packed(&path));
+        assert_eq!(values(&parse_archive_members(&path).unwrap()).len(), 3);
+
+        assert_eq!(Compression::detect(b"BZh is not bzip2"), None);
+    }
+
+    #[test]
+    fn test_zip_and_tar_members() {
+        let dir = tempfile::tempdir().unwrap();
+
+        let zip_path = dir.path().join("reports.zip");
+        let mut writer = zip::ZipWriter::new(File::create(&zip_path).unwrap());
+        let options = zip::write::FileOptions::default();
+        writer.add_directory("2024/", options).unwrap();
+        writer.start_file("2024/q1.csv", options).unwrap();
+        writer.write_all(MONTHS.as_bytes()).unwrap();
+        writer.start_file("2024/q2.json", options).unwrap();
+        writer.write_all(br#"{"apr": 780, "may": 91}"#).unwrap();
+        writer.start_file("README.md", options).unwrap();
+        writer.write_all(b"Quarterly reports").unwrap();
+        writer
+            .start_file("__MACOSX/2024/._q1.csv", options)
+            .unwrap();
+        writer.write_all(b"\x00\x05\x16\x07").unwrap();
+        writer.finish().unwrap();
+
+        let members = parse_archive_members(&zip_path).unwrap();
+        let names: Vec<String> = members.iter().map(|member| member.name.clone()).collect();
+        let zip_name = zip_path.display().to_string();
+        assert_eq!(
+            names,
+            [
+                format!("{zip_name}:2024/q1.csv"),
+                format!("{zip_name}:2024/q2.json")
+            ]
+        );
+        assert_eq!(values(&members), [1200.0, 3400.0, 560.0, 780.0, 91.0]);
+        assert_eq!(members[1].records[0].source.file, Some(names[1].clone()));
+
+        // A gzipped tarball holding the ZIP and a compressed text file
+        let mut builder = tar::Builder::new(Vec::new());
+        let add = |builder: &mut tar::Builder<Vec<u8>>, name: &str, data: &[u8]| {
+            let mut header = tar::Header::new_gnu();
+            header.set_size(data.len() as u64);
+            header.set_mode(0o644);
+            header.set_cksum();
+            builder.append_data(&mut header, name, data).unwrap();
+        };
+        add(
+            &mut builder,
+            "bundle/reports.zip",
+            &std::fs::read(&zip_path).unwrap(),
+        );
+        add(
+            &mut builder,
+            "bundle/notes.txt.xz",
+            &compressed(Compression::Xz, b"total 4321\n"),
+        );
+        let tarball = compressed(Compression::Gzip, &builder.into_inner().unwrap());
+        let tar_path = dir.path().join("bundle.tgz");
+        std::fs::write(&tar_path, tarball).unwrap();
+
+        let members = parse_archive_members(&tar_path).unwrap();
+        let tar_name = tar_path.display().to_string();
+        assert_eq!(members.len(), 3);
+        assert_eq!(
+            members[0].name,
+            format!("{tar_name}:bundle/reports.zip:2024/q1.csv")
+        );
+        assert_eq!(members[2].name, format!("{tar_name}:bundle/notes.txt.xz"));
+        assert_eq!(values(&members[2..]), [4321.0]);
+    }
+
+    #[test]
+    fn test_same_member_names_in_different_directories() {
+        let dir = tempfile::tempdir().unwrap();
+        let zip_path = dir.path().join("branches.zip");
+        let mut writer = zip::ZipWriter::new(File::create(&zip_path).unwrap());
+        let options = zip::write::FileOptions::default();
+        writer.start_file("north/data.csv", options).unwrap();
+        writer.write_all(b"amount\n120\n").unwrap();
+        writer.start_file("south/data.csv", options).unwrap();
+        writer.write_all(b"amount\n340\n").unwrap();
+        writer.finish().unwrap();
+
+        let members = parse_archive_members(&zip_path).unwrap();
+        assert_eq!(values(&members), [120.0, 340.0]);
+        assert!(members[1].name.ends_with(":south/data.csv"));
+    }
+
+    #[test]
+    fn test_unpacked_size_limit() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("zeros.csv.gz");
+        std::fs::write(&path, compressed(Compression::Gzip, &[b'0'; 64 * 1024])).unwrap();
+
+        let read = |budget: u64| {
+            let mut budget = budget;
+            let mut members = Vec::new();
+            read_members(
+                &path,
+                "zeros.csv.gz",
+                &InputOptions::default(),
+                0,
+                &mut budget,
+                &mut members,
+            )
+            .map(|_| budget)
+        };
+        let error = read(1024).unwrap_err();
+        assert!(error
+            .to_string()
+            .contains("zeros.csv.gz: unpacks to more than"));
+        // Within the limit the unpacked bytes are charged to the budget
+        assert_eq!(read(1 << 20).unwrap(), (1 << 20) - 64 * 1024);
+    }
+
+    #[test]
+    fn test_archive_errors() {
+        let dir = tempfile::tempdir().unwrap();
+        let zip_path = dir.path().join("reports.zip");
+        let mut writer = zip::ZipWriter::new(File::create(&zip_path).unwrap());
+        writer
+            .start_file("q1.csv", zip::write::FileOptions::default())
+            .unwrap();
+        writer.write_all(MONTHS.as_bytes()).unwrap();
+        writer.finish().unwrap();
+
+        // Options apply to every member, and failures name the member
+        let options = InputOptions {
+            column: Some("total".parse().unwrap()),
+            ..Default::default()
+        };
+        let error = parse_archive_members_with(&zip_path, &options).unwrap_err();
+        assert!(error.to_string().contains("reports.zip:q1.csv: "));
+
+        let broken = dir.path().join("broken.gz");
+        std::fs::write(&broken, [0x1F, 0x8B, 0x08, 0x00, 0x01]).unwrap();
+        assert!(parse_archive_members(&broken).is_err());
+    }
+}
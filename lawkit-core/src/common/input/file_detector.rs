@@ -1,4 +1,7 @@
-use crate::common::international::extract_numbers_international;
+use super::options::InputOptions;
+use super::parser::parse_text_records_with;
+use super::provenance::{record_values, NumberRecord};
+use crate::common::memory::BatchAccumulator;
 use std::path::Path;
 
 #[derive(Debug, Clone, PartialEq)]
@@ -15,6 +18,9 @@ pub enum FileFormat {
     Yaml,         // .yaml, .yml
     Toml,         // .toml
     Html,         // .html, .htm
+    Parquet,      // .parquet
+    Arrow,        // .arrow, .feather, .ipc (Arrow IPC file or stream)
+    Sqlite,       // .sqlite, .sqlite3, .db
     Text,         // .txt, or fallback
 }
 
@@ -39,6 +45,9 @@ pub fn detect_file_format(file_path: &Path) -> FileFormat {
         "yaml" | "yml" => FileFormat::Yaml,
         "toml" => FileFormat::Toml,
         "html" | "htm" => FileFormat::Html,
+        "parquet" | "pq" => FileFormat::Parquet,
+        "arrow" | "feather" | "ipc" | "arrows" => FileFormat::Arrow,
+        "sqlite" | "sqlite3" | "db" | "db3" => FileFormat::Sqlite,
         "txt" => FileFormat::Text,
         _ => {
             // Try content-based detection for files without clear extensions
@@ -49,6 +58,16 @@ pub fn detect_file_format(file_path: &Path) -> FileFormat {
 
 /// Attempt to detect format by examining file content
 fn detect_format_by_content(file_path: &Path) -> Option<FileFormat> {
+    // Columnar and database formats are checked on the first bytes alone, as they are
+    // often large
+    let mut header = [0u8; 16];
+    let header_len = std::fs::File::open(file_path)
+        .and_then(|mut file| std::io::Read::read(&mut file, &mut header))
+        .ok()?;
+    if let Some(format) = detect_binary_format(&header[..header_len]) {
+        return Some(format);
+    }
+
     // Read first few bytes to detect file signature
     let bytes = std::fs::read(file_path).ok()?;
 
@@ -88,6 +107,24 @@ fn detect_format_by_content(file_path: &Path) -> Option<FileFormat> {
     }
 }
 
+/// Detect Parquet, Arrow IPC and SQLite files by their magic bytes
+fn detect_binary_format(header: &[u8]) -> Option<FileFormat> {
+    use crate::common::input::formats::columnar::{
+        ARROW_FILE_MAGIC, ARROW_STREAM_MAGIC, PARQUET_MAGIC,
+    };
+    use crate::common::input::formats::sqlite::SQLITE_MAGIC;
+
+    if header.starts_with(SQLITE_MAGIC) {
+        Some(FileFormat::Sqlite)
+    } else if header.starts_with(PARQUET_MAGIC) {
+        Some(FileFormat::Parquet)
+    } else if header.starts_with(ARROW_FILE_MAGIC) || header.starts_with(ARROW_STREAM_MAGIC) {
+        Some(FileFormat::Arrow)
+    } else {
+        None
+    }
+}
+
 /// Detect format for text-based files
 fn detect_text_format(content: &str) -> Option<FileFormat> {
     let trimmed = content.trim();
@@ -161,36 +198,123 @@ pub fn parse_file_by_format(
     file_path: &Path,
     format: &FileFormat,
 ) -> crate::error::Result<Vec<f64>> {
+    record_values(parse_file_records_by_format(file_path, format)?)
+}
+
+/// Parse file based on detected format, keeping where each number came from
+///
+/// `file` is left unset; `parse_file_records` fills it in.
+pub fn parse_file_records_by_format(
+    file_path: &Path,
+    format: &FileFormat,
+) -> crate::error::Result<Vec<NumberRecord>> {
+    parse_file_records_by_format_with(file_path, format, &InputOptions::default())
+}
+
+/// Parse file based on detected format, reading only the selected column, sheet and range
+///
+/// Column, sheet and range selections apply to CSV/TSV and spreadsheets (Parquet, Arrow and
+/// SQLite take a column only), SQL queries to SQLite, XPath selections
+/// to XML and path and key selections to JSON/YAML/TOML; other formats reject them.
+/// The locale applies to every format.
+pub fn parse_file_records_by_format_with(
+    file_path: &Path,
+    format: &FileFormat,
+    options: &InputOptions,
+) -> crate::error::Result<Vec<NumberRecord>> {
     use crate::common::input::formats::*;
 
+    check_selection(format, options)?;
+
     match format {
-        FileFormat::Excel => excel::parse_excel_file(file_path),
-        FileFormat::Pdf => pdf::parse_pdf_file(file_path),
-        FileFormat::Word => word::parse_word_file(file_path),
-        FileFormat::PowerPoint => powerpoint::parse_powerpoint_file(file_path),
-        FileFormat::Csv => csv::parse_csv_file(file_path),
-        FileFormat::Tsv => csv::parse_csv_file(file_path), // TSV uses same parser as CSV
-        FileFormat::Json => json_xml::parse_json_file(file_path),
-        FileFormat::Xml => json_xml::parse_xml_file(file_path),
-        FileFormat::Yaml => json_xml::parse_yaml_file(file_path),
-        FileFormat::Toml => json_xml::parse_toml_file(file_path),
-        FileFormat::Html => html::parse_html_file(file_path),
+        FileFormat::Excel => excel::parse_excel_file_records_with(file_path, options),
+        FileFormat::Csv | FileFormat::Tsv => csv::parse_csv_file_records_with(file_path, options),
+        FileFormat::OpenDocument => {
+            opendocument::parse_opendocument_file_records_with(file_path, options)
+        }
+        FileFormat::Xml => json_xml::parse_xml_file_records_with(file_path, options),
+        FileFormat::Parquet => columnar::parse_parquet_file_records_with(file_path, options),
+        FileFormat::Arrow => columnar::parse_arrow_file_records_with(file_path, options),
+        FileFormat::Sqlite => sqlite::parse_sqlite_file_records_with(file_path, options),
+        _ if options.has_tabular_selection() => Err(crate::error::BenfError::InvalidInput(
+            format!("column, sheet and range selection do not apply to {format:?} input"),
+        )),
+        FileFormat::Pdf => pdf::parse_pdf_file_records_with(file_path, options.locale),
+        FileFormat::Word => word::parse_word_file_records_with(file_path, options.locale),
+        FileFormat::PowerPoint => {
+            powerpoint::parse_powerpoint_file_records_with(file_path, options.locale)
+        }
+        FileFormat::Json => json_xml::parse_json_file_records_with(file_path, options),
+        FileFormat::Yaml => json_xml::parse_yaml_file_records_with(file_path, options),
+        FileFormat::Toml => json_xml::parse_toml_file_records_with(file_path, options),
+        FileFormat::Html => html::parse_html_file_records_with(file_path, options.locale),
         FileFormat::Text => {
             // Fallback: read as plain text
             let content = std::fs::read_to_string(file_path).map_err(|e| {
                 crate::error::BenfError::FileError(format!("Failed to read text file: {e}"))
             })?;
-            let numbers = extract_numbers_international(&content);
-            if numbers.is_empty() {
-                Err(crate::error::BenfError::NoNumbersFound)
-            } else {
-                Ok(numbers)
-            }
+            parse_text_records_with(&content, options)
         }
-        FileFormat::OpenDocument => opendocument::parse_opendocument_file(file_path),
     }
 }
 
+/// Feed the numbers of a file into `accumulator`, returning how many were added
+///
+/// Parquet and Arrow files are read one record batch at a time, so only the accumulator's
+/// running totals stay in memory; other formats are parsed whole and added as one batch.
+pub fn accumulate_file_by_format_with<A: BatchAccumulator>(
+    file_path: &Path,
+    format: &FileFormat,
+    options: &InputOptions,
+    accumulator: &mut A,
+) -> crate::error::Result<usize> {
+    use crate::common::input::formats::columnar::ColumnarBatches;
+
+    check_selection(format, options)?;
+
+    match format {
+        FileFormat::Parquet => {
+            ColumnarBatches::open_parquet(file_path, options)?.accumulate(accumulator)
+        }
+        FileFormat::Arrow => {
+            ColumnarBatches::open_arrow(file_path, options)?.accumulate(accumulator)
+        }
+        _ => {
+            let values: Vec<f64> = parse_file_records_by_format_with(file_path, format, options)?
+                .iter()
+                .map(|record| record.value)
+                .collect();
+            accumulator.add_batch(&values);
+            Ok(values.len())
+        }
+    }
+}
+
+/// Reject selections that do not apply to `format`
+fn check_selection(format: &FileFormat, options: &InputOptions) -> crate::error::Result<()> {
+    if options.xpath.is_some() && *format != FileFormat::Xml {
+        return Err(crate::error::BenfError::InvalidInput(format!(
+            "XPath selection only applies to XML input, not {format:?}"
+        )));
+    }
+    if options.query.is_some() && *format != FileFormat::Sqlite {
+        return Err(crate::error::BenfError::InvalidInput(format!(
+            "SQL queries only apply to SQLite input, not {format:?}"
+        )));
+    }
+    if options.keys.is_active()
+        && !matches!(
+            format,
+            FileFormat::Json | FileFormat::Yaml | FileFormat::Toml
+        )
+    {
+        return Err(crate::error::BenfError::InvalidInput(format!(
+            "path and key selection only apply to JSON, YAML and TOML input, not {format:?}"
+        )));
+    }
+    Ok(())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -228,6 +352,38 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_binary_format_detection() {
+        assert_eq!(
+            detect_file_format(&PathBuf::from("events.parquet")),
+            FileFormat::Parquet
+        );
+        assert_eq!(
+            detect_file_format(&PathBuf::from("events.feather")),
+            FileFormat::Arrow
+        );
+
+        assert_eq!(
+            detect_binary_format(b"PAR1\x15\x04"),
+            Some(FileFormat::Parquet)
+        );
+        assert_eq!(detect_binary_format(b"ARROW1"), Some(FileFormat::Arrow));
+        assert_eq!(
+            detect_binary_format(&[0xFF, 0xFF, 0xFF, 0xFF, 0x10, 0x01]),
+            Some(FileFormat::Arrow)
+        );
+        assert_eq!(
+            detect_binary_format(b"SQLite format 3\0"),
+            Some(FileFormat::Sqlite)
+        );
+        assert_eq!(
+            detect_file_format(&PathBuf::from("ledger.sqlite")),
+            FileFormat::Sqlite
+        );
+        assert_eq!(detect_binary_format(b"PAR"), None);
+        assert_eq!(detect_binary_format(b"a,b,c\n"), None);
+    }
+
     #[test]
     fn test_text_format_detection() {
         assert_eq!(
@@ -0,0 +1,557 @@
+use crate::common::input::options::{ColumnSelector, InputOptions};
+use crate::common::input::provenance::{
+    record_values, records_from_text, NumberRecord, Provenance,
+};
+use crate::common::international::NumberLocale;
+use crate::common::memory::{BatchAccumulator, MemoryConfig};
+use crate::error::{BenfError, Result};
+use arrow_array::cast::AsArray;
+use arrow_array::types::{
+    ArrowPrimitiveType, Decimal128Type, Float16Type, Float32Type, Float64Type, Int16Type,
+    Int32Type, Int64Type, Int8Type, UInt16Type, UInt32Type, UInt64Type, UInt8Type,
+};
+use arrow_array::{Array, RecordBatch};
+use arrow_ipc::reader::{FileReader, StreamReader};
+use arrow_schema::{ArrowError, DataType, Schema};
+use parquet::arrow::arrow_reader::ParquetRecordBatchReaderBuilder;
+use parquet::arrow::ProjectionMask;
+use std::fs::File;
+use std::io::{BufReader, Read};
+use std::path::Path;
+
+/// First bytes of a Parquet file ("PAR1", also its last four bytes)
+pub const PARQUET_MAGIC: &[u8] = b"PAR1";
+/// First bytes of an Arrow IPC file (Feather v2)
+pub const ARROW_FILE_MAGIC: &[u8] = b"ARROW1";
+/// First bytes of an Arrow IPC stream (continuation marker of the schema message)
+pub const ARROW_STREAM_MAGIC: &[u8] = &[0xFF, 0xFF, 0xFF, 0xFF];
+
+/// A column read from a Parquet or Arrow file
+#[derive(Debug, Clone)]
+struct SelectedColumn {
+    index: usize, // 0-based position in the file schema
+    name: String,
+    data_type: DataType,
+}
+
+type BatchIterator = Box<dyn Iterator<Item = std::result::Result<RecordBatch, ArrowError>>>;
+
+/// Numbers of a Parquet or Arrow IPC file, read one record batch at a time
+///
+/// Only the selected columns are decoded, and at most one batch (`MemoryConfig::chunk_size`
+/// rows for Parquet, one written record batch for Arrow) is held in memory. Each item holds the numbers
+/// of one batch; `accumulate` feeds them into a `common::memory` accumulator instead.
+pub struct ColumnarBatches {
+    batches: BatchIterator,
+    columns: Vec<SelectedColumn>,
+    locale: NumberLocale,
+    rows_read: usize,
+}
+
+impl ColumnarBatches {
+    /// Open a Parquet file, reading the column chosen by `options.column` or else every
+    /// numeric column
+    pub fn open_parquet(file_path: &Path, options: &InputOptions) -> Result<Self> {
+        let file = open(file_path, "Parquet")?;
+        let builder = ParquetRecordBatchReaderBuilder::try_new(file)
+            .map_err(|e| BenfError::ParseError(format!("Invalid Parquet file: {e}")))?;
+
+        let columns = select_columns(builder.schema(), options)?;
+        let mask = ProjectionMask::roots(
+            builder.parquet_schema(),
+            columns.iter().map(|column| column.index),
+        );
+        let reader = builder
+            .with_projection(mask)
+            .with_batch_size(MemoryConfig::default().chunk_size)
+            .build()
+            .map_err(|e| BenfError::ParseError(format!("Invalid Parquet file: {e}")))?;
+
+        Ok(Self::new(Box::new(reader), columns, options.locale))
+    }
+
+    /// Open an Arrow IPC file (`.arrow`, Feather v2) or stream, reading the column chosen by
+    /// `options.column` or else every numeric column
+    pub fn open_arrow(file_path: &Path, options: &InputOptions) -> Result<Self> {
+        let invalid = |e: ArrowError| BenfError::ParseError(format!("Invalid Arrow file: {e}"));
+
+        let mut magic = [0u8; 6];
+        let is_file = open(file_path, "Arrow")?
+            .read_exact(&mut magic)
+            .is_ok_and(|_| magic == ARROW_FILE_MAGIC);
+
+        // The schema comes first, so it is read once more with the projection applied
+        if is_file {
+            let schema = FileReader::try_new(open(file_path, "Arrow")?, None)
+                .map_err(invalid)?
+                .schema();
+            let columns = select_columns(&schema, options)?;
+            let file = open(file_path, "Arrow")?;
+            let reader = FileReader::try_new(file, Some(projection(&columns))).map_err(invalid)?;
+            Ok(Self::new(Box::new(reader), columns, options.locale))
+        } else {
+            let stream = || open(file_path, "Arrow").map(BufReader::new);
+            let schema = StreamReader::try_new(stream()?, None)
+                .map_err(invalid)?
+                .schema();
+            let columns = select_columns(&schema, options)?;
+            let reader =
+                StreamReader::try_new(stream()?, Some(projection(&columns))).map_err(invalid)?;
+            Ok(Self::new(Box::new(reader), columns, options.locale))
+        }
+    }
+
+    fn new(batches: BatchIterator, columns: Vec<SelectedColumn>, locale: NumberLocale) -> Self {
+        ColumnarBatches {
+            batches,
+            columns,
+            locale,
+            rows_read: 0,
+        }
+    }
+
+    /// Names of the columns numbers are read from
+    pub fn column_names(&self) -> Vec<&str> {
+        self.columns
+            .iter()
+            .map(|column| column.name.as_str())
+            .collect()
+    }
+
+    /// Feed every batch into `accumulator`, returning how many numbers were added
+    pub fn accumulate<A: BatchAccumulator>(self, accumulator: &mut A) -> Result<usize> {
+        let mut count = 0;
+        for batch in self {
+            let values: Vec<f64> = batch?.into_iter().map(|record| record.value).collect();
+            count += values.len();
+            accumulator.add_batch(&values);
+        }
+        Ok(count)
+    }
+
+    fn batch_records(&self, batch: &RecordBatch) -> Result<Vec<NumberRecord>> {
+        let mut records = Vec::new();
+        // Projected batches keep the file's column order, like `self.columns`
+        for (column, array) in self.columns.iter().zip(batch.columns()) {
+            let source = |offset: usize| Provenance {
+                row: Some(self.rows_read + offset + 1),
+                column: Some(column.index + 1),
+                header: Some(column.name.clone()),
+                ..Default::default()
+            };
+            let mut push = |offset: usize, value: f64, text: String| {
+                if value != 0.0 && value.is_finite() {
+                    records.push(NumberRecord::new(value, source(offset), text));
+                }
+            };
+
+            match array.data_type() {
+                DataType::Int8 => primitive_values::<Int8Type>(array, |v| v as f64, &mut push),
+                DataType::Int16 => primitive_values::<Int16Type>(array, |v| v as f64, &mut push),
+                DataType::Int32 => primitive_values::<Int32Type>(array, |v| v as f64, &mut push),
+                DataType::Int64 => primitive_values::<Int64Type>(array, |v| v as f64, &mut push),
+                DataType::UInt8 => primitive_values::<UInt8Type>(array, |v| v as f64, &mut push),
+                DataType::UInt16 => primitive_values::<UInt16Type>(array, |v| v as f64, &mut push),
+                DataType::UInt32 => primitive_values::<UInt32Type>(array, |v| v as f64, &mut push),
+                DataType::UInt64 => primitive_values::<UInt64Type>(array, |v| v as f64, &mut push),
+                DataType::Float16 => {
+                    primitive_values::<Float16Type>(array, |v| v.to_f64(), &mut push)
+                }
+                DataType::Float32 => {
+                    primitive_values::<Float32Type>(array, |v| v as f64, &mut push)
+                }
+                DataType::Float64 => primitive_values::<Float64Type>(array, |v| v, &mut push),
+                DataType::Decimal128(_, scale) => {
+                    let decimals = array.as_primitive::<Decimal128Type>();
+                    let divisor = 10f64.powi(*scale as i32);
+                    for offset in 0..decimals.len() {
+                        if decimals.is_valid(offset) {
+                            push(
+                                offset,
+                                decimals.value(offset) as f64 / divisor,
+                                decimals.value_as_string(offset),
+                            );
+                        }
+                    }
+                }
+                DataType::Utf8 => {
+                    for (offset, text) in array.as_string::<i32>().iter().enumerate() {
+                        if let Some(text) = text {
+                            records.extend(records_from_text(text, &source(offset), self.locale)?);
+                        }
+                    }
+                }
+                DataType::LargeUtf8 => {
+                    for (offset, text) in array.as_string::<i64>().iter().enumerate() {
+                        if let Some(text) = text {
+                            records.extend(records_from_text(text, &source(offset), self.locale)?);
+                        }
+                    }
+                }
+                _ => {} // Rejected by `select_columns`
+            }
+        }
+        Ok(records)
+    }
+}
+
+impl Iterator for ColumnarBatches {
+    type Item = Result<Vec<NumberRecord>>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let batch = match self.batches.next()? {
+            Ok(batch) => batch,
+            Err(e) => {
+                return Some(Err(BenfError::ParseError(format!(
+                    "Failed to read record batch: {e}"
+                ))))
+            }
+        };
+        let records = self.batch_records(&batch);
+        self.rows_read += batch.num_rows();
+        Some(records)
+    }
+}
+
+fn primitive_values<T: ArrowPrimitiveType>(
+    array: &dyn Array,
+    to_f64: impl Fn(T::Native) -> f64,
+    push: &mut impl FnMut(usize, f64, String),
+) where
+    T::Native: std::fmt::Display,
+{
+    for (offset, value) in array.as_primitive::<T>().iter().enumerate() {
+        if let Some(value) = value {
+            push(offset, to_f64(value), value.to_string());
+        }
+    }
+}
+
+fn open(file_path: &Path, kind: &str) -> Result<File> {
+    File::open(file_path)
+        .map_err(|e| BenfError::FileError(format!("Failed to read {kind} file: {e}")))
+}
+
+fn projection(columns: &[SelectedColumn]) -> Vec<usize> {
+    columns.iter().map(|column| column.index).collect()
+}
+
+fn is_numeric(data_type: &DataType) -> bool {
+    data_type.is_integer()
+        || data_type.is_floating()
+        || matches!(data_type, DataType::Decimal128(..))
+}
+
+fn is_text(data_type: &DataType) -> bool {
+    matches!(data_type, DataType::Utf8 | DataType::LargeUtf8)
+}
+
+/// The column chosen by `options.column`, or every numeric column when none is chosen
+fn select_columns(schema: &Schema, options: &InputOptions) -> Result<Vec<SelectedColumn>> {
+    options.ensure_no_spreadsheet_options()?;
+
+    let fields = schema.fields();
+    let index = match &options.column {
+        None => None,
+        Some(ColumnSelector::Index(index)) => {
+            if index.get() > fields.len() {
+                return Err(BenfError::InvalidInput(format!(
+                    "column {index} not found ({} columns)",
+                    fields.len()
+                )));
+            }
+            Some(index.get() - 1)
+        }
+        Some(column @ ColumnSelector::Header(name)) => Some(
+            fields
+                .iter()
+                .position(|field| column.matches_header(field.name()))
+                .ok_or_else(|| {
+                    let available: Vec<&str> =
+                        fields.iter().map(|field| field.name().as_str()).collect();
+                    BenfError::InvalidInput(format!(
+                        "column '{name}' not found (available: {})",
+                        available.join(", ")
+                    ))
+                })?,
+        ),
+    };
+
+    let column = |index: usize| SelectedColumn {
+        index,
+        name: fields[index].name().clone(),
+        data_type: fields[index].data_type().clone(),
+    };
+    match index {
+        Some(index) => {
+            let selected = column(index);
+            if !is_numeric(&selected.data_type) && !is_text(&selected.data_type) {
+                return Err(BenfError::InvalidInput(format!(
+                    "column '{}' has unsupported type {}",
+                    selected.name, selected.data_type
+                )));
+            }
+            Ok(vec![selected])
+        }
+        None => {
+            let numeric: Vec<SelectedColumn> = (0..fields.len())
+                .map(column)
+                .filter(|column| is_numeric(&column.data_type))
+                .collect();
+            if numeric.is_empty() {
+                return Err(BenfError::NoNumbersFound);
+            }
+            Ok(numeric)
+        }
+    }
+}
+
+/// Parse Parquet files and extract numbers
+pub fn parse_parquet_file(file_path: &Path) -> Result<Vec<f64>> {
+    record_values(parse_parquet_file_records(file_path)?)
+}
+
+/// Parse Parquet files, keeping the row and column of each number
+pub fn parse_parquet_file_records(file_path: &Path) -> Result<Vec<NumberRecord>> {
+    parse_parquet_file_records_with(file_path, &InputOptions::default())
+}
+
+/// Parse Parquet files, reading only the column selected by `options.column`
+pub fn parse_parquet_file_records_with(
+    file_path: &Path,
+    options: &InputOptions,
+) -> Result<Vec<NumberRecord>> {
+    collect_records(ColumnarBatches::open_parquet(file_path, options)?)
+}
+
+/// Parse Arrow IPC files and streams and extract numbers
+pub fn parse_arrow_file(file_path: &Path) -> Result<Vec<f64>> {
+    record_values(parse_arrow_file_records(file_path)?)
+}
+
+/// Parse Arrow IPC files and streams, keeping the row and column of each number
+pub fn parse_arrow_file_records(file_path: &Path) -> Result<Vec<NumberRecord>> {
+    parse_arrow_file_records_with(file_path, &InputOptions::default())
+}
+
+/// Parse Arrow IPC files and streams, reading only the column selected by `options.column`
+pub fn parse_arrow_file_records_with(
+    file_path: &Path,
+    options: &InputOptions,
+) -> Result<Vec<NumberRecord>> {
+    collect_records(ColumnarBatches::open_arrow(file_path, options)?)
+}
+
+fn collect_records(batches: ColumnarBatches) -> Result<Vec<NumberRecord>> {
+    let mut records = Vec::new();
+    for batch in batches {
+        records.extend(batch?);
+    }
+    Ok(records)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::memory::IncrementalBenford;
+    use arrow_array::{ArrayRef, BooleanArray, Float64Array, Int64Array, StringArray};
+    use arrow_ipc::writer::{FileWriter, StreamWriter};
+    use parquet::arrow::ArrowWriter;
+    use parquet::file::properties::WriterProperties;
+    use std::sync::Arc;
+
+    fn ledger_batch() -> RecordBatch {
+        let ids: ArrayRef = Arc::new(Int64Array::from_iter_values(1001..1021));
+        let amounts: ArrayRef = Arc::new(Float64Array::from_iter(
+            (1..=20).map(|i| (i != 5).then_some(i as f64 * 123.25)),
+        ));
+        let notes: ArrayRef = Arc::new(StringArray::from_iter_values(
+            (1..=20).map(|i| format!("invoice {}", i * 7)),
+        ));
+        let paid: ArrayRef = Arc::new(BooleanArray::from(vec![true; 20]));
+        RecordBatch::try_from_iter([
+            ("id", ids),
+            ("amount", amounts),
+            ("note", notes),
+            ("paid", paid),
+        ])
+        .unwrap()
+    }
+
+    fn write_parquet(path: &Path) {
+        // Small row groups so reading spans several of them
+        let properties = WriterProperties::builder()
+            .set_max_row_group_size(6)
+            .build();
+        let batch = ledger_batch();
+        let mut writer = ArrowWriter::try_new(
+            File::create(path).unwrap(),
+            batch.schema(),
+            Some(properties),
+        )
+        .unwrap();
+        writer.write(&batch).unwrap();
+        writer.close().unwrap();
+    }
+
+    #[test]
+    fn test_parquet_columns() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("ledger.parquet");
+        write_parquet(&path);
+
+        // Every numeric column by default, nulls skipped
+        let all = parse_parquet_file_records(&path).unwrap();
+        assert_eq!(all.len(), 20 + 19);
+
+        let options = InputOptions {
+            column: Some("Amount".parse().unwrap()),
+            ..Default::default()
+        };
+        let amounts = parse_parquet_file_records_with(&path, &options).unwrap();
+        assert_eq!(amounts.len(), 19);
+        assert_eq!(amounts[0].value, 123.25);
+        let last = amounts.last().unwrap();
+        assert_eq!(last.value, 20.0 * 123.25);
+        assert_eq!(last.source.row, Some(20));
+        assert_eq!(last.source.to_string(), "row 20, column 2 (amount)");
+
+        // Text columns are read when selected explicitly
+        let options = InputOptions {
+            column: Some("3".parse().unwrap()),
+            ..Default::default()
+        };
+        let notes = record_values(parse_parquet_file_records_with(&path, &options).unwrap());
+        assert_eq!(notes.unwrap()[..3], [7.0, 14.0, 21.0]);
+    }
+
+    #[test]
+    fn test_parquet_streams_into_accumulator() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("ledger.parquet");
+        write_parquet(&path);
+
+        let options = InputOptions {
+            column: Some("amount".parse().unwrap()),
+            ..Default::default()
+        };
+        let batches = ColumnarBatches::open_parquet(&path, &options).unwrap();
+        assert_eq!(batches.column_names(), ["amount"]);
+
+        let mut benford = IncrementalBenford::new();
+        assert_eq!(batches.accumulate(&mut benford).unwrap(), 19);
+        assert_eq!(benford.total_count(), 19);
+    }
+
+    /// Records the size of every batch it is fed
+    #[derive(Default)]
+    struct BatchSizes(Vec<usize>);
+
+    impl BatchAccumulator for BatchSizes {
+        fn add_batch(&mut self, values: &[f64]) {
+            self.0.push(values.len());
+        }
+    }
+
+    #[test]
+    fn test_input_streams_bounded_batches() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("large.parquet");
+        let chunk_size = MemoryConfig::default().chunk_size;
+        let amounts: ArrayRef = Arc::new(Float64Array::from_iter_values(
+            (1..=chunk_size * 5 / 2).map(|i| i as f64 * 1.25),
+        ));
+        let batch = RecordBatch::try_from_iter([("amount", amounts)]).unwrap();
+        let mut writer =
+            ArrowWriter::try_new(File::create(&path).unwrap(), batch.schema(), None).unwrap();
+        writer.write(&batch).unwrap();
+        writer.close().unwrap();
+
+        let options = InputOptions::default();
+        let input = path.to_str().unwrap();
+        let mut sizes = BatchSizes::default();
+        let count =
+            crate::common::input::accumulate_input_auto_with(input, &options, &mut sizes).unwrap();
+
+        // Fed chunk by chunk, never the whole file at once
+        assert_eq!(count, chunk_size * 5 / 2);
+        assert_eq!(sizes.0, [chunk_size, chunk_size, chunk_size / 2]);
+
+        let mut counts = crate::laws::benford::FirstDigitCounts::new();
+        crate::common::input::accumulate_input_auto_with(input, &options, &mut counts).unwrap();
+        let streamed = counts
+            .into_result(
+                "ledger".to_string(),
+                &crate::common::filtering::RiskThreshold::Auto,
+                5,
+                0.05,
+            )
+            .unwrap();
+        let values = record_values(parse_parquet_file_records_with(&path, &options).unwrap());
+        let loaded =
+            crate::laws::benford::BenfordResult::new("ledger".to_string(), &values.unwrap())
+                .unwrap();
+        assert_eq!(streamed, loaded);
+    }
+
+    #[test]
+    fn test_arrow_file_and_stream() {
+        let dir = tempfile::tempdir().unwrap();
+        let batch = ledger_batch();
+
+        let file_path = dir.path().join("ledger.arrow");
+        let mut writer =
+            FileWriter::try_new(File::create(&file_path).unwrap(), &batch.schema()).unwrap();
+        writer.write(&batch).unwrap();
+        writer.write(&batch).unwrap();
+        writer.finish().unwrap();
+
+        let stream_path = dir.path().join("ledger.arrows");
+        let mut writer =
+            StreamWriter::try_new(File::create(&stream_path).unwrap(), &batch.schema()).unwrap();
+        writer.write(&batch).unwrap();
+        writer.finish().unwrap();
+
+        let options = InputOptions {
+            column: Some("id".parse().unwrap()),
+            ..Default::default()
+        };
+        let ids = parse_arrow_file_records_with(&file_path, &options).unwrap();
+        assert_eq!(ids.len(), 40);
+        assert_eq!(ids[20].source.row, Some(21));
+        assert_eq!(ids[20].value, 1001.0);
+
+        let streamed = parse_arrow_file_records_with(&stream_path, &options).unwrap();
+        assert_eq!(streamed, ids[..20]);
+        assert_eq!(parse_arrow_file(&stream_path).unwrap().len(), 39);
+    }
+
+    #[test]
+    fn test_columnar_selection_errors() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("ledger.parquet");
+        write_parquet(&path);
+
+        let column = |column: &str| InputOptions {
+            column: Some(column.parse().unwrap()),
+            ..Default::default()
+        };
+        let error = parse_parquet_file_records_with(&path, &column("total")).unwrap_err();
+        assert!(error
+            .to_string()
+            .contains("available: id, amount, note, paid"));
+        assert!(parse_parquet_file_records_with(&path, &column("9")).is_err());
+        assert!(parse_parquet_file_records_with(&path, &column("paid")).is_err());
+
+        let sheet = InputOptions {
+            sheet: Some("Sheet1".to_string()),
+            ..Default::default()
+        };
+        assert!(parse_parquet_file_records_with(&path, &sheet).is_err());
+
+        let text = dir.path().join("ledger.txt");
+        std::fs::write(&text, "1 2 3").unwrap();
+        assert!(parse_parquet_file(&text).is_err());
+        assert!(parse_arrow_file(&text).is_err());
+    }
+}
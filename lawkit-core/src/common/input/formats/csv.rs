@@ -1,52 +1,218 @@
-use crate::common::international::extract_numbers_international;
+use crate::common::input::options::{ColumnSelector, InputOptions};
+use crate::common::input::provenance::{record_values, NumberRecord, Provenance};
+use crate::common::international::extract_numbers_international_with;
 use std::path::Path;
 
 /// Parse CSV/TSV files and extract numbers
 pub fn parse_csv_file(file_path: &Path) -> crate::error::Result<Vec<f64>> {
+    record_values(parse_csv_file_records(file_path)?)
+}
+
+/// Parse CSV/TSV files, keeping the line, field and header of each number
+pub fn parse_csv_file_records(file_path: &Path) -> crate::error::Result<Vec<NumberRecord>> {
+    parse_csv_file_records_with(file_path, &InputOptions::default())
+}
+
+/// Parse CSV/TSV files, reading only the selected column
+pub fn parse_csv_file_records_with(
+    file_path: &Path,
+    options: &InputOptions,
+) -> crate::error::Result<Vec<NumberRecord>> {
     let content = std::fs::read_to_string(file_path)
         .map_err(|e| crate::error::BenfError::FileError(format!("Failed to read CSV file: {e}")))?;
 
-    parse_csv_content(&content)
+    parse_csv_records_with(&content, options)
 }
 
 /// Parse CSV content from string
 pub fn parse_csv_content(content: &str) -> crate::error::Result<Vec<f64>> {
-    let mut all_numbers = Vec::new();
+    record_values(parse_csv_records(content)?)
+}
+
+/// Parse CSV content, keeping the line, field and header each number came from
+///
+/// The first row is treated as a header when none of its fields contain numbers.
+pub fn parse_csv_records(content: &str) -> crate::error::Result<Vec<NumberRecord>> {
+    parse_csv_records_with(content, &InputOptions::default())
+}
+
+/// Parse CSV content, reading only the selected column
+///
+/// Selecting a column by header always treats the first row as the header row. With
+/// `options.group_by` each record is tagged with its row's value in that column, which is
+/// not read for numbers.
+pub fn parse_csv_records_with(
+    content: &str,
+    options: &InputOptions,
+) -> crate::error::Result<Vec<NumberRecord>> {
+    options.ensure_no_spreadsheet_options()?;
 
-    // Simple CSV parsing - split by lines and then by commas/tabs
-    for line in content.lines() {
-        let line = line.trim();
-        if line.is_empty() || line.starts_with('#') {
-            continue; // Skip empty lines and comments
+    let mut reader = csv::ReaderBuilder::new()
+        .delimiter(detect_delimiter(content))
+        .has_headers(false)
+        .flexible(true)
+        .comment(Some(b'#'))
+        .trim(csv::Trim::All)
+        .from_reader(content.as_bytes());
+
+    let mut records = Vec::new();
+    let mut headers: Option<Vec<String>> = None;
+    let mut first_row = true;
+    let mut selected_column = match &options.column {
+        Some(ColumnSelector::Index(index)) => Some(index.get() - 1),
+        _ => None,
+    };
+    let mut group_column = match &options.group_by {
+        Some(ColumnSelector::Index(index)) => Some(index.get() - 1),
+        _ => None,
+    };
+
+    for row in reader.records() {
+        let row =
+            row.map_err(|e| crate::error::BenfError::ParseError(format!("Invalid CSV: {e}")))?;
+        // csv does not count skipped blank lines, so derive the line from the byte offset
+        let line = row
+            .position()
+            .map(|position| line_at(content, position.byte() as usize));
+
+        if std::mem::take(&mut first_row)
+            && (matches!(options.column, Some(ColumnSelector::Header(_)))
+                || matches!(options.group_by, Some(ColumnSelector::Header(_)))
+                || row
+                    .iter()
+                    .map(|field| extract_numbers_international_with(field, options.locale))
+                    .collect::<crate::error::Result<Vec<_>>>()?
+                    .iter()
+                    .all(Vec::is_empty))
+        {
+            let row_headers: Vec<String> = row.iter().map(str::to_string).collect();
+            if let Some(column @ ColumnSelector::Header(_)) = &options.column {
+                selected_column = Some(header_index(&row_headers, column)?);
+            }
+            if let Some(column @ ColumnSelector::Header(_)) = &options.group_by {
+                group_column = Some(header_index(&row_headers, column)?);
+            }
+            headers = Some(row_headers);
+            continue;
         }
 
-        // Try comma-separated first
-        let fields: Vec<&str> = if line.contains(',') {
-            line.split(',').collect()
-        } else {
-            // Fall back to tab-separated
-            line.split('\t').collect()
-        };
+        let group = group_column
+            .and_then(|column| row.get(column))
+            .filter(|field| !field.is_empty())
+            .map(str::to_string);
 
-        for field in fields {
-            let field = field.trim().trim_matches('"'); // Remove quotes and whitespace
+        for (column, field) in row.iter().enumerate() {
+            if selected_column.is_some_and(|selected| selected != column)
+                || group_column == Some(column)
+            {
+                continue;
+            }
+
+            let source = Provenance {
+                line,
+                column: Some(column + 1),
+                header: headers
+                    .as_ref()
+                    .and_then(|headers| headers.get(column))
+                    .filter(|header| !header.is_empty())
+                    .cloned(),
+                ..Default::default()
+            };
 
             // Extract numbers from each field (including international numerals)
-            let numbers = extract_numbers_international(field);
-            all_numbers.extend(numbers);
+            for value in extract_numbers_international_with(field, options.locale)? {
+                records.push(
+                    NumberRecord::new(value, source.clone(), field).with_group(group.clone()),
+                );
+            }
         }
     }
 
-    if all_numbers.is_empty() {
-        return Err(crate::error::BenfError::NoNumbersFound);
-    }
+    Ok(records)
+}
 
-    Ok(all_numbers)
+/// 0-based index of the header `column` names
+fn header_index(headers: &[String], column: &ColumnSelector) -> crate::error::Result<usize> {
+    headers
+        .iter()
+        .position(|header| column.matches_header(header))
+        .ok_or_else(|| {
+            crate::error::BenfError::InvalidInput(format!(
+                "column '{column}' not found (available: {})",
+                headers.join(", ")
+            ))
+        })
+}
+
+/// Guess the field delimiter from the first few rows
+///
+/// A delimiter that appears the same number of times on every sampled row wins, preferring
+/// tab, then semicolon, comma and pipe; otherwise the most frequent one on the first row.
+pub fn detect_delimiter(content: &str) -> u8 {
+    const CANDIDATES: [u8; 4] = [b'\t', b';', b',', b'|'];
+
+    let sample: Vec<&str> = content
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .take(5)
+        .collect();
+    let Some(first) = sample.first() else {
+        return b',';
+    };
+
+    let counts = |delimiter: u8| -> Vec<usize> {
+        sample
+            .iter()
+            .map(|line| count_unquoted(line, delimiter))
+            .collect()
+    };
+
+    CANDIDATES
+        .into_iter()
+        .find(|&delimiter| {
+            let counts = counts(delimiter);
+            counts[0] > 0 && counts.iter().all(|&count| count == counts[0])
+        })
+        .or_else(|| {
+            CANDIDATES
+                .into_iter()
+                .map(|delimiter| (delimiter, count_unquoted(first, delimiter)))
+                .filter(|&(_, count)| count > 0)
+                .max_by_key(|&(_, count)| count)
+                .map(|(delimiter, _)| delimiter)
+        })
+        .unwrap_or(b',')
+}
+
+/// 1-based line of the first non-blank character at or after `offset`
+fn line_at(content: &str, offset: usize) -> usize {
+    let (before, after) = content.as_bytes().split_at(offset);
+    let skipped = after
+        .iter()
+        .take_while(|b| b.is_ascii_whitespace())
+        .filter(|&&b| b == b'\n')
+        .count();
+    before.iter().filter(|&&b| b == b'\n').count() + skipped + 1
+}
+
+/// Occurrences of `delimiter` outside double-quoted fields
+fn count_unquoted(line: &str, delimiter: u8) -> usize {
+    let mut in_quotes = false;
+    line.bytes()
+        .filter(|&b| {
+            if b == b'"' {
+                in_quotes = !in_quotes;
+            }
+            !in_quotes && b == delimiter
+        })
+        .count()
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::common::international::NumberLocale;
 
     #[test]
     fn test_csv_content_parsing() {
@@ -64,6 +230,21 @@ Revenue,9876.54,2023-01-03"#;
         assert!(numbers.contains(&2023.0)); // Years from dates
     }
 
+    #[test]
+    fn test_csv_records_keep_line_and_field() {
+        let csv_content = "Name,Amount\nSales,1234.56\n\nExpenses,567.89";
+
+        let records = parse_csv_records(csv_content).unwrap();
+        let expenses = records.iter().find(|r| r.value == 567.89).unwrap();
+        assert_eq!(expenses.source.line, Some(4));
+        assert_eq!(expenses.source.column, Some(2));
+        assert_eq!(expenses.source.header.as_deref(), Some("Amount"));
+        assert_eq!(expenses.text, "567.89");
+
+        let values: Vec<f64> = records.iter().map(|r| r.value).collect();
+        assert_eq!(values, parse_csv_content(csv_content).unwrap());
+    }
+
     #[test]
     fn test_tsv_content_parsing() {
         let tsv_content =
@@ -75,6 +256,114 @@ Revenue,9876.54,2023-01-03"#;
         assert!(numbers.contains(&567.89));
     }
 
+    #[test]
+    fn test_csv_quoted_fields_and_column_selection() {
+        let csv_content = "id,description,amount,date\n\
+                           1,\"Widgets, large\",\"1234.50\",2023-01-01\n\
+                           2,\"Bolts \"\"M8\"\"\",567.25,2023-01-02\n";
+
+        let by_header = InputOptions {
+            column: Some(ColumnSelector::Header("Amount".to_string())),
+            ..Default::default()
+        };
+        let records = parse_csv_records_with(csv_content, &by_header).unwrap();
+        let values: Vec<f64> = records.iter().map(|r| r.value).collect();
+        assert_eq!(values, vec![1234.5, 567.25]);
+        assert!(records.iter().all(|r| r.source.column == Some(3)));
+        assert_eq!(records[1].source.line, Some(3));
+
+        let by_index = InputOptions {
+            column: Some("3".parse().unwrap()),
+            ..Default::default()
+        };
+        assert_eq!(
+            parse_csv_records_with(csv_content, &by_index).unwrap(),
+            records
+        );
+
+        let missing = InputOptions {
+            column: Some(ColumnSelector::Header("total".to_string())),
+            ..Default::default()
+        };
+        assert!(parse_csv_records_with(csv_content, &missing).is_err());
+    }
+
+    #[test]
+    fn test_csv_group_by_column() {
+        let csv_content = "vendor_id,amount,note\n\
+                           V001,1200,first\n\
+                           V002,350,\n\
+                           ,99,no vendor\n\
+                           V001,47.5,\n";
+
+        let options = InputOptions {
+            column: Some(ColumnSelector::Header("amount".to_string())),
+            group_by: Some(ColumnSelector::Header("Vendor_ID".to_string())),
+            ..Default::default()
+        };
+        let records = parse_csv_records_with(csv_content, &options).unwrap();
+        let groups: Vec<(f64, Option<&str>)> = records
+            .iter()
+            .map(|r| (r.value, r.group.as_deref()))
+            .collect();
+        assert_eq!(
+            groups,
+            vec![
+                (1200.0, Some("V001")),
+                (350.0, Some("V002")),
+                (99.0, None),
+                (47.5, Some("V001"))
+            ]
+        );
+
+        // Numeric group keys are not read as numbers
+        let by_index = InputOptions {
+            group_by: Some("1".parse().unwrap()),
+            ..Default::default()
+        };
+        let records = parse_csv_records_with("1,10\n2,20\n1,30", &by_index).unwrap();
+        assert_eq!(records.len(), 3);
+        assert_eq!(records[2].value, 30.0);
+        assert_eq!(records[2].group.as_deref(), Some("1"));
+
+        let missing = InputOptions {
+            group_by: Some(ColumnSelector::Header("cost_center".to_string())),
+            ..Default::default()
+        };
+        assert!(parse_csv_records_with(csv_content, &missing).is_err());
+    }
+
+    #[test]
+    fn test_delimiter_detection() {
+        assert_eq!(detect_delimiter("a,b,c\n1,2,3"), b',');
+        assert_eq!(detect_delimiter("a\tb\n1\t2"), b'\t');
+        assert_eq!(detect_delimiter("name;amount\nx;1,5\ny;2,75"), b';');
+        assert_eq!(detect_delimiter("\"a,b\"|c\n\"1,2\"|3"), b'|');
+        assert_eq!(detect_delimiter("12345"), b',');
+
+        let semicolon = "name;amount\nwidget;1200\nbolt;350";
+        let options = InputOptions {
+            column: Some(ColumnSelector::Header("amount".to_string())),
+            ..Default::default()
+        };
+        let values = record_values(parse_csv_records_with(semicolon, &options).unwrap());
+        assert_eq!(values.unwrap(), vec![1200.0, 350.0]);
+    }
+
+    #[test]
+    fn test_european_ledger_with_locale() {
+        let ledger =
+            "Datum;Betrag\n2023-01-15;1.234,50\n2023-01-16;(987,25)\n2023-01-17;45.678,00\n";
+        let options = InputOptions {
+            column: Some(ColumnSelector::Header("Betrag".to_string())),
+            locale: NumberLocale::Eu,
+            ..Default::default()
+        };
+
+        let values = record_values(parse_csv_records_with(ledger, &options).unwrap()).unwrap();
+        assert_eq!(values, vec![1234.5, -987.25, 45678.0]);
+    }
+
     #[test]
     fn test_csv_with_japanese_numerals() {
         let csv_content = "商品,金額\n商品A,一千二百三十四\n商品B,五六七八";
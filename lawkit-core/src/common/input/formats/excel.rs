@@ -1,9 +1,29 @@
-use crate::common::international::extract_numbers_international;
+use crate::common::input::options::{ColumnSelector, InputOptions};
+use crate::common::input::provenance::{
+    cell_reference, record_values, records_from_text, NumberRecord, Provenance,
+};
 use calamine::{open_workbook_auto, DataType, Reader, Xls, Xlsx};
 use std::path::Path;
 
 /// Parse Excel files (.xlsx, .xls) and extract numbers
 pub fn parse_excel_file(file_path: &Path) -> crate::error::Result<Vec<f64>> {
+    record_values(parse_excel_file_records(file_path)?)
+}
+
+/// Parse Excel files, keeping the sheet and cell of each number
+pub fn parse_excel_file_records(file_path: &Path) -> crate::error::Result<Vec<NumberRecord>> {
+    parse_excel_file_records_with(file_path, &InputOptions::default())
+}
+
+/// Parse Excel files, reading only the selected sheet, range and column
+///
+/// A column selected by header is looked up in the first row of the range
+/// (or of the sheet's used area when no range is given), and so is the group column of
+/// `options.group_by`, whose cell tags every number read from its row.
+pub fn parse_excel_file_records_with(
+    file_path: &Path,
+    options: &InputOptions,
+) -> crate::error::Result<Vec<NumberRecord>> {
     let extension = file_path
         .extension()
         .and_then(|ext| ext.to_str())
@@ -11,118 +31,206 @@ pub fn parse_excel_file(file_path: &Path) -> crate::error::Result<Vec<f64>> {
         .to_lowercase();
 
     match extension.as_str() {
-        "xlsx" => parse_xlsx_file(file_path),
-        "xls" => parse_xls_file(file_path),
+        "xlsx" => parse_xlsx_file(file_path, options),
+        "xls" => parse_xls_file(file_path, options),
         _ => {
             // Try auto-detection
-            parse_excel_auto(file_path)
+            parse_excel_auto(file_path, options)
         }
     }
 }
 
 /// Parse XLSX files specifically
-fn parse_xlsx_file(file_path: &Path) -> crate::error::Result<Vec<f64>> {
+fn parse_xlsx_file(
+    file_path: &Path,
+    options: &InputOptions,
+) -> crate::error::Result<Vec<NumberRecord>> {
     let mut workbook: Xlsx<_> = calamine::open_workbook(file_path).map_err(|e| {
         crate::error::BenfError::FileError(format!("Failed to open XLSX file: {e}"))
     })?;
 
-    extract_numbers_from_xlsx(&mut workbook)
+    extract_records_from_workbook(&mut workbook, options)
 }
 
 /// Parse XLS files specifically  
-fn parse_xls_file(file_path: &Path) -> crate::error::Result<Vec<f64>> {
+fn parse_xls_file(
+    file_path: &Path,
+    options: &InputOptions,
+) -> crate::error::Result<Vec<NumberRecord>> {
     let mut workbook: Xls<_> = calamine::open_workbook(file_path)
         .map_err(|e| crate::error::BenfError::FileError(format!("Failed to open XLS file: {e}")))?;
 
-    extract_numbers_from_xls(&mut workbook)
+    extract_records_from_workbook(&mut workbook, options)
 }
 
 /// Auto-detect Excel format and parse
-fn parse_excel_auto(file_path: &Path) -> crate::error::Result<Vec<f64>> {
+fn parse_excel_auto(
+    file_path: &Path,
+    options: &InputOptions,
+) -> crate::error::Result<Vec<NumberRecord>> {
     let workbook = open_workbook_auto(file_path).map_err(|e| {
         crate::error::BenfError::FileError(format!("Failed to open Excel file: {e}"))
     })?;
 
     match workbook {
-        calamine::Sheets::Xlsx(mut xlsx) => extract_numbers_from_xlsx(&mut xlsx),
-        calamine::Sheets::Xls(mut xls) => extract_numbers_from_xls(&mut xls),
-        calamine::Sheets::Xlsb(mut xlsb) => extract_numbers_from_xlsb(&mut xlsb),
-        calamine::Sheets::Ods(mut ods) => extract_numbers_from_ods(&mut ods),
+        calamine::Sheets::Xlsx(mut xlsx) => extract_records_from_workbook(&mut xlsx, options),
+        calamine::Sheets::Xls(mut xls) => extract_records_from_workbook(&mut xls, options),
+        calamine::Sheets::Xlsb(mut xlsb) => extract_records_from_workbook(&mut xlsb, options),
+        calamine::Sheets::Ods(mut ods) => extract_records_from_workbook(&mut ods, options),
     }
 }
 
-/// Extract numbers from XLSX workbook
-fn extract_numbers_from_xlsx(
-    workbook: &mut Xlsx<std::io::BufReader<std::fs::File>>,
-) -> crate::error::Result<Vec<f64>> {
-    extract_numbers_from_workbook(workbook)
-}
-
-/// Extract numbers from XLS workbook
-fn extract_numbers_from_xls(
-    workbook: &mut Xls<std::io::BufReader<std::fs::File>>,
-) -> crate::error::Result<Vec<f64>> {
-    extract_numbers_from_workbook(workbook)
-}
-
-/// Extract numbers from XLSB workbook
-fn extract_numbers_from_xlsb(
-    workbook: &mut calamine::Xlsb<std::io::BufReader<std::fs::File>>,
-) -> crate::error::Result<Vec<f64>> {
-    extract_numbers_from_workbook(workbook)
-}
-
-/// Extract numbers from ODS workbook
-fn extract_numbers_from_ods(
-    workbook: &mut calamine::Ods<std::io::BufReader<std::fs::File>>,
-) -> crate::error::Result<Vec<f64>> {
-    extract_numbers_from_workbook(workbook)
-}
-
 /// Extract numbers from Excel workbook (generic version using Reader trait bound)
-fn extract_numbers_from_workbook<R: Reader<std::io::BufReader<std::fs::File>>>(
+fn extract_records_from_workbook<R: Reader<std::io::BufReader<std::fs::File>>>(
     workbook: &mut R,
-) -> crate::error::Result<Vec<f64>> {
-    let mut all_numbers = Vec::new();
+    options: &InputOptions,
+) -> crate::error::Result<Vec<NumberRecord>> {
+    let mut records = Vec::new();
 
-    // Get all worksheet names
-    let sheet_names = workbook.sheet_names().to_vec();
+    // Get all worksheet names, or just the selected one
+    let mut sheet_names = workbook.sheet_names().to_vec();
+    if let Some(selected) = &options.sheet {
+        let sheet = sheet_names
+            .iter()
+            .find(|name| name.eq_ignore_ascii_case(selected))
+            .cloned()
+            .ok_or_else(|| {
+                crate::error::BenfError::InvalidInput(format!(
+                    "sheet '{selected}' not found (available: {})",
+                    sheet_names.join(", ")
+                ))
+            })?;
+        sheet_names = vec![sheet];
+    }
 
+    let mut header_found = false;
+    let mut group_header_found = false;
     for sheet_name in sheet_names {
         if let Some(Ok(range)) = workbook.worksheet_range(&sheet_name) {
+            // Cell references are relative to the range's top-left corner
+            let (start_row, start_column) = range.start().unwrap_or((0, 0));
+            let (start_row, start_column) = (start_row as usize, start_column as usize);
+            let first_row = options
+                .range
+                .map_or(start_row, |cells| cells.start.0.max(start_row));
+            let in_selection = |row: usize, column: usize| {
+                options
+                    .range
+                    .map_or(true, |cells| cells.contains(row, column))
+            };
+
+            let header_cell = |selector: &ColumnSelector| {
+                range
+                    .rows()
+                    .nth(first_row - start_row)
+                    .into_iter()
+                    .flat_map(|row| row.iter().enumerate())
+                    .map(|(offset, cell)| (start_column + offset, cell_text(cell)))
+                    .find(|(column, text)| {
+                        in_selection(first_row, *column) && selector.matches_header(text)
+                    })
+            };
+
+            // Resolve the selected column to an absolute column index
+            let (column_filter, header) = match &options.column {
+                None => (None, None),
+                Some(ColumnSelector::Index(index)) => (Some(index.get() - 1), None),
+                Some(selector @ ColumnSelector::Header(_)) => match header_cell(selector) {
+                    Some((column, text)) => (Some(column), Some(text)),
+                    None => continue, // This sheet has no such column
+                },
+            };
+            header_found |= header.is_some();
+
+            // Resolve the group column the same way
+            let (group_column, group_header) = match &options.group_by {
+                None => (None, false),
+                Some(ColumnSelector::Index(index)) => (Some(index.get() - 1), false),
+                Some(selector @ ColumnSelector::Header(_)) => match header_cell(selector) {
+                    Some((column, _)) => (Some(column), true),
+                    None => continue,
+                },
+            };
+            group_header_found |= group_header;
+
             // Process each cell in the range
-            for row in range.rows() {
-                for cell in row {
-                    match cell {
+            for (row_index, row) in range.rows().enumerate() {
+                let row_number = start_row + row_index;
+                if (header.is_some() || group_header) && row_number <= first_row {
+                    continue; // Skip the header row and anything above it
+                }
+                let group = group_column
+                    .and_then(|column| column.checked_sub(start_column))
+                    .and_then(|offset| row.get(offset))
+                    .map(cell_text)
+                    .filter(|text| !text.is_empty());
+
+                for (column_index, cell) in row.iter().enumerate() {
+                    let column_number = start_column + column_index;
+                    if !in_selection(row_number, column_number)
+                        || column_filter.is_some_and(|column| column != column_number)
+                        || group_column == Some(column_number)
+                    {
+                        continue;
+                    }
+
+                    let source = Provenance {
+                        sheet: Some(sheet_name.clone()),
+                        cell: Some(cell_reference(row_number, column_number)),
+                        header: header.clone(),
+                        ..Default::default()
+                    };
+                    let cell_records = match cell {
                         // Direct numeric values
-                        DataType::Float(f) => {
-                            if *f != 0.0 && f.is_finite() {
-                                all_numbers.push(*f);
-                            }
+                        DataType::Float(f) if *f != 0.0 && f.is_finite() => {
+                            vec![NumberRecord::new(*f, source, f.to_string())]
                         }
-                        DataType::Int(i) => {
-                            if *i != 0 {
-                                all_numbers.push(*i as f64);
-                            }
+                        DataType::Int(i) if *i != 0 => {
+                            vec![NumberRecord::new(*i as f64, source, i.to_string())]
                         }
                         // Text that might contain numbers (including international numerals)
-                        DataType::String(s) => {
-                            let extracted = extract_numbers_international(s);
-                            all_numbers.extend(extracted);
-                        }
+                        DataType::String(s) => records_from_text(s, &source, options.locale)?,
                         // Skip other types (empty, bool, error, etc.)
-                        _ => {}
-                    }
+                        _ => Vec::new(),
+                    };
+                    records.extend(
+                        cell_records
+                            .into_iter()
+                            .map(|record| record.with_group(group.clone())),
+                    );
                 }
             }
         }
     }
 
-    if all_numbers.is_empty() {
+    if let Some(ColumnSelector::Header(name)) = &options.column {
+        if !header_found {
+            return Err(crate::error::BenfError::InvalidInput(format!(
+                "column '{name}' not found in the header row"
+            )));
+        }
+    }
+    if let Some(ColumnSelector::Header(name)) = &options.group_by {
+        if !group_header_found {
+            return Err(crate::error::BenfError::InvalidInput(format!(
+                "group column '{name}' not found in the header row"
+            )));
+        }
+    }
+
+    if records.is_empty() {
         return Err(crate::error::BenfError::NoNumbersFound);
     }
 
-    Ok(all_numbers)
+    Ok(records)
+}
+
+/// Cell content as text, for header lookup
+fn cell_text(cell: &DataType) -> String {
+    match cell {
+        DataType::String(s) => s.trim().to_string(),
+        other => other.to_string(),
+    }
 }
 
 #[cfg(test)]
@@ -1,19 +1,50 @@
-use crate::common::international::extract_numbers_international;
+use crate::common::input::provenance::{
+    record_values, records_from_text, NumberRecord, Provenance,
+};
+use crate::common::international::NumberLocale;
 use regex::Regex;
 use scraper::Html;
 use std::path::Path;
 
 /// Parse HTML files and extract numbers from text content
 pub fn parse_html_file(file_path: &Path) -> crate::error::Result<Vec<f64>> {
+    record_values(parse_html_file_records(file_path)?)
+}
+
+/// Parse HTML files, keeping the text node each number came from
+pub fn parse_html_file_records(file_path: &Path) -> crate::error::Result<Vec<NumberRecord>> {
+    parse_html_file_records_with(file_path, NumberLocale::default())
+}
+
+/// Parse HTML files whose numbers are written in `locale`
+pub fn parse_html_file_records_with(
+    file_path: &Path,
+    locale: NumberLocale,
+) -> crate::error::Result<Vec<NumberRecord>> {
     let content = std::fs::read_to_string(file_path).map_err(|e| {
         crate::error::BenfError::FileError(format!("Failed to read HTML file: {e}"))
     })?;
 
-    parse_html_content(&content)
+    parse_html_records_with(&content, locale)
 }
 
 /// Parse HTML content from string
 pub fn parse_html_content(content: &str) -> crate::error::Result<Vec<f64>> {
+    record_values(parse_html_records(content)?)
+}
+
+/// Parse HTML content, keeping the text node each number came from
+///
+/// The DOM carries no source positions, so records only report the text.
+pub fn parse_html_records(content: &str) -> crate::error::Result<Vec<NumberRecord>> {
+    parse_html_records_with(content, NumberLocale::default())
+}
+
+/// Parse HTML content whose numbers are written in `locale`
+pub fn parse_html_records_with(
+    content: &str,
+    locale: NumberLocale,
+) -> crate::error::Result<Vec<NumberRecord>> {
     let _document = Html::parse_document(content);
 
     // First remove script and style elements from the document
@@ -30,28 +61,20 @@ pub fn parse_html_content(content: &str) -> crate::error::Result<Vec<f64>> {
     // Parse the cleaned HTML
     let clean_document = Html::parse_document(&clean_html);
 
-    let mut all_text = String::new();
-
-    // Extract all text from the cleaned document
-    for text in clean_document.tree.nodes().filter_map(|node| {
-        if let scraper::node::Node::Text(text_node) = node.value() {
-            Some(text_node.text.as_ref())
-        } else {
-            None
-        }
-    }) {
-        all_text.push_str(text);
-        all_text.push(' ');
-    }
-
-    // Extract numbers from the collected text (including international numerals)
-    let numbers = extract_numbers_international(&all_text);
-
-    if numbers.is_empty() {
-        return Err(crate::error::BenfError::NoNumbersFound);
-    }
-
-    Ok(numbers)
+    // Extract numbers from each text node (including international numerals)
+    let records = clean_document
+        .tree
+        .nodes()
+        .filter_map(|node| {
+            if let scraper::node::Node::Text(text_node) = node.value() {
+                Some(text_node.text.as_ref())
+            } else {
+                None
+            }
+        })
+        .map(|text| records_from_text(text, &Provenance::default(), locale))
+        .collect::<crate::error::Result<Vec<_>>>()?;
+    Ok(records.into_iter().flatten().collect())
 }
 
 /// Parse HTML content from a URL response (for web scraping)
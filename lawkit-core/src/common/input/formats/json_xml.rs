@@ -1,217 +1,370 @@
-use crate::common::international::extract_numbers_international;
+use crate::common::input::formats::xml::XmlDocument;
+use crate::common::input::options::{ColumnSelector, InputOptions};
+use crate::common::input::provenance::{
+    record_values, records_from_text, NumberRecord, Provenance,
+};
+use crate::common::input::selector::{json_pointer, PathSegment};
 use std::path::Path;
 
 /// Parse JSON files and extract numbers
 pub fn parse_json_file(file_path: &Path) -> crate::error::Result<Vec<f64>> {
+    record_values(parse_json_file_records(file_path)?)
+}
+
+/// Parse JSON files, keeping the JSON pointer of each number
+pub fn parse_json_file_records(file_path: &Path) -> crate::error::Result<Vec<NumberRecord>> {
+    parse_json_file_records_with(file_path, &InputOptions::default())
+}
+
+/// Parse JSON files, reading the values selected by `options.keys` in `options.locale`
+pub fn parse_json_file_records_with(
+    file_path: &Path,
+    options: &InputOptions,
+) -> crate::error::Result<Vec<NumberRecord>> {
     let content = std::fs::read_to_string(file_path).map_err(|e| {
         crate::error::BenfError::FileError(format!("Failed to read JSON file: {e}"))
     })?;
 
-    parse_json_content(&content)
+    parse_json_records_with(&content, options)
 }
 
 /// Parse JSON content from string
 pub fn parse_json_content(content: &str) -> crate::error::Result<Vec<f64>> {
+    record_values(parse_json_records(content)?)
+}
+
+/// Parse JSON content, keeping the JSON pointer of each number
+pub fn parse_json_records(content: &str) -> crate::error::Result<Vec<NumberRecord>> {
+    parse_json_records_with(content, &InputOptions::default())
+}
+
+/// Parse JSON content, reading only the values selected by the JSONPath and key regexes in
+/// `options.keys`
+///
+/// With `options.group_by` each number is tagged with that key's value in the innermost
+/// enclosing object that has it; the key's own values are not read.
+pub fn parse_json_records_with(
+    content: &str,
+    options: &InputOptions,
+) -> crate::error::Result<Vec<NumberRecord>> {
+    options.ensure_no_tabular_options()?;
+
     let json_value: serde_json::Value = serde_json::from_str(content)
         .map_err(|e| crate::error::BenfError::ParseError(format!("Invalid JSON: {e}")))?;
 
-    let numbers = extract_numbers_from_json_value(&json_value);
-
-    if numbers.is_empty() {
-        return Err(crate::error::BenfError::NoNumbersFound);
+    let mut records = Vec::new();
+    let mut error = None;
+    options.keys.visit_json(&json_value, &mut |path, value| {
+        let group = match &options.group_by {
+            // The group key itself is a category, not a number to analyze
+            Some(key) if matches!(path.last(), Some(PathSegment::Key(name)) if key.matches_key(name)) => {
+                return;
+            }
+            Some(key) => json_group(&json_value, path, key),
+            None => None,
+        };
+        let source = Provenance::json_pointer(json_pointer(path));
+        match value {
+            serde_json::Value::Number(n) => {
+                if let Some(f) = n.as_f64().filter(|f| *f != 0.0 && f.is_finite()) {
+                    records.push(NumberRecord::new(f, source, n.to_string()).with_group(group));
+                }
+            }
+            // Extract numbers from string content (including international numerals)
+            serde_json::Value::String(s) => match records_from_text(s, &source, options.locale) {
+                Ok(found) => records.extend(
+                    found
+                        .into_iter()
+                        .map(|record| record.with_group(group.clone())),
+                ),
+                Err(e) => {
+                    error.get_or_insert(e);
+                }
+            },
+            _ => {}
+        }
+    });
+    match error {
+        Some(e) => Err(e),
+        None => Ok(records),
     }
+}
 
-    Ok(numbers)
+/// Value of `key` in the innermost object along `path` that has it, e.g. the `vendor_id`
+/// of the invoice a total belongs to
+fn json_group(
+    root: &serde_json::Value,
+    path: &[PathSegment],
+    key: &ColumnSelector,
+) -> Option<String> {
+    let mut node = root;
+    let mut group = None;
+    for segment in path {
+        if let serde_json::Value::Object(obj) = node {
+            let text = obj
+                .iter()
+                .find(|(name, _)| key.matches_key(name))
+                .and_then(|(_, value)| match value {
+                    serde_json::Value::String(s) => Some(s.trim().to_string()),
+                    serde_json::Value::Number(n) => Some(n.to_string()),
+                    serde_json::Value::Bool(b) => Some(b.to_string()),
+                    _ => None,
+                })
+                .filter(|text| !text.is_empty());
+            group = text.or(group);
+        }
+        node = match (segment, node) {
+            (PathSegment::Key(name), serde_json::Value::Object(obj)) => &obj[name],
+            (PathSegment::Index(index), serde_json::Value::Array(arr)) => &arr[*index],
+            _ => break,
+        };
+    }
+    group
 }
 
 /// Parse XML files and extract numbers
 pub fn parse_xml_file(file_path: &Path) -> crate::error::Result<Vec<f64>> {
+    record_values(parse_xml_file_records(file_path)?)
+}
+
+/// Parse XML files, keeping the line of each number
+pub fn parse_xml_file_records(file_path: &Path) -> crate::error::Result<Vec<NumberRecord>> {
+    parse_xml_file_records_with(file_path, &InputOptions::default())
+}
+
+/// Parse XML files, reading the nodes selected by `options.xpath` in `options.locale`
+pub fn parse_xml_file_records_with(
+    file_path: &Path,
+    options: &InputOptions,
+) -> crate::error::Result<Vec<NumberRecord>> {
     let content = std::fs::read_to_string(file_path)
         .map_err(|e| crate::error::BenfError::FileError(format!("Failed to read XML file: {e}")))?;
 
-    parse_xml_content(&content)
+    parse_xml_records_with(&content, options)
 }
 
 /// Parse XML content from string
 pub fn parse_xml_content(content: &str) -> crate::error::Result<Vec<f64>> {
-    // Simple XML parsing - extract text content and parse numbers
-    // For more complex XML, consider using a proper XML parser
-    let text_content = extract_text_from_xml(content);
-    let numbers = extract_numbers_international(&text_content);
+    record_values(parse_xml_records(content)?)
+}
+
+/// Parse XML content, keeping the line of each number in element text and attribute values
+pub fn parse_xml_records(content: &str) -> crate::error::Result<Vec<NumberRecord>> {
+    parse_xml_records_with(content, &InputOptions::default())
+}
 
-    if numbers.is_empty() {
-        return Err(crate::error::BenfError::NoNumbersFound);
+/// Parse XML content, reading only the nodes selected by the XPath-like `options.xpath`
+pub fn parse_xml_records_with(
+    content: &str,
+    options: &InputOptions,
+) -> crate::error::Result<Vec<NumberRecord>> {
+    options.ensure_no_tabular_options()?;
+    if options.keys.is_active() {
+        return Err(crate::error::BenfError::InvalidInput(
+            "path and key selection do not apply to XML; use an XPath instead".to_string(),
+        ));
     }
 
-    Ok(numbers)
+    let document = XmlDocument::parse(content)?;
+    match &options.xpath {
+        Some(expression) => document.selected_records(expression, options.locale),
+        None => document.records(options.locale),
+    }
 }
 
 /// Parse YAML files and extract numbers
 pub fn parse_yaml_file(file_path: &Path) -> crate::error::Result<Vec<f64>> {
+    record_values(parse_yaml_file_records(file_path)?)
+}
+
+/// Parse YAML files, keeping the JSON pointer of each number
+pub fn parse_yaml_file_records(file_path: &Path) -> crate::error::Result<Vec<NumberRecord>> {
+    parse_yaml_file_records_with(file_path, &InputOptions::default())
+}
+
+/// Parse YAML files, reading the values selected by `options.keys` in `options.locale`
+pub fn parse_yaml_file_records_with(
+    file_path: &Path,
+    options: &InputOptions,
+) -> crate::error::Result<Vec<NumberRecord>> {
     let content = std::fs::read_to_string(file_path).map_err(|e| {
         crate::error::BenfError::FileError(format!("Failed to read YAML file: {e}"))
     })?;
 
-    parse_yaml_content(&content)
+    parse_yaml_records_with(&content, options)
 }
 
 /// Parse YAML content from string
 pub fn parse_yaml_content(content: &str) -> crate::error::Result<Vec<f64>> {
-    let yaml_value: serde_yaml::Value = serde_yaml::from_str(content)
-        .map_err(|e| crate::error::BenfError::ParseError(format!("Invalid YAML: {e}")))?;
+    record_values(parse_yaml_records(content)?)
+}
 
-    let numbers = extract_numbers_from_yaml_value(&yaml_value);
+/// Parse YAML content, keeping the JSON pointer of each number
+pub fn parse_yaml_records(content: &str) -> crate::error::Result<Vec<NumberRecord>> {
+    parse_yaml_records_with(content, &InputOptions::default())
+}
 
-    if numbers.is_empty() {
-        return Err(crate::error::BenfError::NoNumbersFound);
-    }
+/// Parse YAML content, reading only the values selected by the JSONPath and key regexes in
+/// `options.keys`
+pub fn parse_yaml_records_with(
+    content: &str,
+    options: &InputOptions,
+) -> crate::error::Result<Vec<NumberRecord>> {
+    options.ensure_no_tabular_options()?;
 
-    Ok(numbers)
+    let yaml_value: serde_yaml::Value = serde_yaml::from_str(content)
+        .map_err(|e| crate::error::BenfError::ParseError(format!("Invalid YAML: {e}")))?;
+
+    let mut records = Vec::new();
+    extract_records_from_yaml_value(&yaml_value, &mut Vec::new(), &mut records, options)?;
+    Ok(records)
 }
 
 /// Parse TOML files and extract numbers
 pub fn parse_toml_file(file_path: &Path) -> crate::error::Result<Vec<f64>> {
+    record_values(parse_toml_file_records(file_path)?)
+}
+
+/// Parse TOML files, keeping the JSON pointer of each number
+pub fn parse_toml_file_records(file_path: &Path) -> crate::error::Result<Vec<NumberRecord>> {
+    parse_toml_file_records_with(file_path, &InputOptions::default())
+}
+
+/// Parse TOML files, reading the values selected by `options.keys` in `options.locale`
+pub fn parse_toml_file_records_with(
+    file_path: &Path,
+    options: &InputOptions,
+) -> crate::error::Result<Vec<NumberRecord>> {
     let content = std::fs::read_to_string(file_path).map_err(|e| {
         crate::error::BenfError::FileError(format!("Failed to read TOML file: {e}"))
     })?;
 
-    parse_toml_content(&content)
+    parse_toml_records_with(&content, options)
 }
 
 /// Parse TOML content from string
 pub fn parse_toml_content(content: &str) -> crate::error::Result<Vec<f64>> {
-    let toml_value: toml::Value = toml::from_str(content)
-        .map_err(|e| crate::error::BenfError::ParseError(format!("Invalid TOML: {e}")))?;
-
-    let numbers = extract_numbers_from_toml_value(&toml_value);
-
-    if numbers.is_empty() {
-        return Err(crate::error::BenfError::NoNumbersFound);
-    }
+    record_values(parse_toml_records(content)?)
+}
 
-    Ok(numbers)
+/// Parse TOML content, keeping the JSON pointer of each number
+pub fn parse_toml_records(content: &str) -> crate::error::Result<Vec<NumberRecord>> {
+    parse_toml_records_with(content, &InputOptions::default())
 }
 
-/// Recursively extract numbers from JSON value
-fn extract_numbers_from_json_value(value: &serde_json::Value) -> Vec<f64> {
-    let mut numbers = Vec::new();
+/// Parse TOML content, reading only the values selected by the JSONPath and key regexes in
+/// `options.keys`
+pub fn parse_toml_records_with(
+    content: &str,
+    options: &InputOptions,
+) -> crate::error::Result<Vec<NumberRecord>> {
+    options.ensure_no_tabular_options()?;
 
-    match value {
-        serde_json::Value::Number(n) => {
-            if let Some(f) = n.as_f64() {
-                if f != 0.0 && f.is_finite() {
-                    numbers.push(f);
-                }
-            }
-        }
-        serde_json::Value::String(s) => {
-            // Extract numbers from string content (including international numerals)
-            numbers.extend(extract_numbers_international(s));
-        }
-        serde_json::Value::Array(arr) => {
-            for item in arr {
-                numbers.extend(extract_numbers_from_json_value(item));
-            }
-        }
-        serde_json::Value::Object(obj) => {
-            for (_, val) in obj {
-                numbers.extend(extract_numbers_from_json_value(val));
-            }
-        }
-        _ => {} // Skip null, bool
-    }
+    let toml_value: toml::Value = toml::from_str(content)
+        .map_err(|e| crate::error::BenfError::ParseError(format!("Invalid TOML: {e}")))?;
 
-    numbers
+    let mut records = Vec::new();
+    extract_records_from_toml_value(&toml_value, &mut Vec::new(), &mut records, options)?;
+    Ok(records)
 }
 
 /// Recursively extract numbers from YAML value
-fn extract_numbers_from_yaml_value(value: &serde_yaml::Value) -> Vec<f64> {
-    let mut numbers = Vec::new();
-
+fn extract_records_from_yaml_value(
+    value: &serde_yaml::Value,
+    path: &mut Vec<PathSegment>,
+    records: &mut Vec<NumberRecord>,
+    options: &InputOptions,
+) -> crate::error::Result<()> {
     match value {
         serde_yaml::Value::Number(n) => {
             if let Some(f) = n.as_f64() {
-                if f != 0.0 && f.is_finite() {
-                    numbers.push(f);
+                if f != 0.0 && f.is_finite() && options.keys.selects(path) {
+                    let source = Provenance::json_pointer(json_pointer(path));
+                    records.push(NumberRecord::new(f, source, n.to_string()));
                 }
             }
         }
-        serde_yaml::Value::String(s) => {
-            numbers.extend(extract_numbers_international(s));
+        serde_yaml::Value::String(s) if options.keys.selects(path) => {
+            records.extend(records_from_text(
+                s,
+                &Provenance::json_pointer(json_pointer(path)),
+                options.locale,
+            )?);
         }
         serde_yaml::Value::Sequence(seq) => {
-            for item in seq {
-                numbers.extend(extract_numbers_from_yaml_value(item));
+            for (index, item) in seq.iter().enumerate() {
+                path.push(PathSegment::Index(index));
+                extract_records_from_yaml_value(item, path, records, options)?;
+                path.pop();
             }
         }
         serde_yaml::Value::Mapping(map) => {
-            for (_, val) in map {
-                numbers.extend(extract_numbers_from_yaml_value(val));
+            for (key, val) in map {
+                let key = match key {
+                    serde_yaml::Value::String(s) => s.clone(),
+                    other => serde_yaml::to_string(other)
+                        .unwrap_or_default()
+                        .trim()
+                        .to_string(),
+                };
+                if options.keys.skips_key(&key) {
+                    continue;
+                }
+                path.push(PathSegment::Key(key));
+                extract_records_from_yaml_value(val, path, records, options)?;
+                path.pop();
             }
         }
-        _ => {} // Skip null, bool
+        _ => {} // Skip null, bool and unselected strings
     }
-
-    numbers
+    Ok(())
 }
 
 /// Recursively extract numbers from TOML value
-fn extract_numbers_from_toml_value(value: &toml::Value) -> Vec<f64> {
-    let mut numbers = Vec::new();
-
+fn extract_records_from_toml_value(
+    value: &toml::Value,
+    path: &mut Vec<PathSegment>,
+    records: &mut Vec<NumberRecord>,
+    options: &InputOptions,
+) -> crate::error::Result<()> {
+    let selected = |path: &[PathSegment]| options.keys.selects(path);
+    let source = |path: &[PathSegment]| Provenance::json_pointer(json_pointer(path));
     match value {
-        toml::Value::Integer(i) => {
-            if *i != 0 {
-                numbers.push(*i as f64);
-            }
+        toml::Value::Integer(i) if *i != 0 && selected(path) => {
+            records.push(NumberRecord::new(*i as f64, source(path), i.to_string()));
         }
-        toml::Value::Float(f) => {
-            if *f != 0.0 && f.is_finite() {
-                numbers.push(*f);
-            }
+        toml::Value::Float(f) if *f != 0.0 && f.is_finite() && selected(path) => {
+            records.push(NumberRecord::new(*f, source(path), f.to_string()));
         }
-        toml::Value::String(s) => {
-            numbers.extend(extract_numbers_international(s));
+        toml::Value::String(s) if selected(path) => {
+            records.extend(records_from_text(s, &source(path), options.locale)?);
         }
         toml::Value::Array(arr) => {
-            for item in arr {
-                numbers.extend(extract_numbers_from_toml_value(item));
+            for (index, item) in arr.iter().enumerate() {
+                path.push(PathSegment::Index(index));
+                extract_records_from_toml_value(item, path, records, options)?;
+                path.pop();
             }
         }
         toml::Value::Table(table) => {
-            for (_, val) in table {
-                numbers.extend(extract_numbers_from_toml_value(val));
-            }
-        }
-        _ => {} // Skip datetime, bool
-    }
-
-    numbers
-}
-
-/// Simple XML text extraction
-fn extract_text_from_xml(xml_content: &str) -> String {
-    let mut text = String::new();
-    let mut in_tag = false;
-
-    for c in xml_content.chars() {
-        match c {
-            '<' => in_tag = true,
-            '>' => {
-                in_tag = false;
-                text.push(' '); // Add space between elements
+            for (key, val) in table {
+                if options.keys.skips_key(key) {
+                    continue;
+                }
+                path.push(PathSegment::Key(key.clone()));
+                extract_records_from_toml_value(val, path, records, options)?;
+                path.pop();
             }
-            _ if !in_tag => text.push(c),
-            _ => {} // Skip tag content
         }
+        _ => {} // Skip datetime, bool and unselected values
     }
-
-    text
+    Ok(())
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::common::international::NumberLocale;
 
     #[test]
     fn test_json_parsing() {
@@ -266,11 +419,150 @@ amount = 234.56
         assert!(numbers.contains(&567890.0));
     }
 
+    #[test]
+    fn test_json_records_carry_pointers() {
+        let json_content = r#"{"items": [{"amount": 123.45}, {"amount": "USD 678"}]}"#;
+
+        let records = parse_json_records(json_content).unwrap();
+        let pointers: Vec<_> = records
+            .iter()
+            .map(|r| r.source.json_pointer.as_deref().unwrap())
+            .collect();
+        assert_eq!(pointers, vec!["/items/0/amount", "/items/1/amount"]);
+        assert_eq!(records[1].value, 678.0);
+    }
+
+    #[test]
+    fn test_json_group_by_key() {
+        use crate::common::input::options::ColumnSelector;
+
+        let json_content = r#"{
+            "vendor_id": "HQ",
+            "invoices": [
+                {"vendor_id": "V001", "total": 1250.5, "lines": [{"amount": 310}]},
+                {"vendor_id": 42, "total": 88},
+                {"total": 19.99}
+            ]
+        }"#;
+        let options = InputOptions {
+            group_by: Some(ColumnSelector::Header("vendor_id".to_string())),
+            ..Default::default()
+        };
+
+        let records = parse_json_records_with(json_content, &options).unwrap();
+        let groups: Vec<(f64, Option<&str>)> = records
+            .iter()
+            .map(|r| (r.value, r.group.as_deref()))
+            .collect();
+        assert_eq!(
+            groups,
+            vec![
+                (310.0, Some("V001")),
+                (1250.5, Some("V001")),
+                (88.0, Some("42")),
+                (19.99, Some("HQ"))
+            ]
+        );
+    }
+
+    #[test]
+    fn test_yaml_and_toml_records_carry_pointers() {
+        let yaml = "report:\n  revenue: 1234567\n";
+        let records = parse_yaml_records(yaml).unwrap();
+        assert_eq!(
+            records[0].source.json_pointer.as_deref(),
+            Some("/report/revenue")
+        );
+
+        let toml = "[[transactions]]\namount = 123.45\n";
+        let records = parse_toml_records(toml).unwrap();
+        assert_eq!(
+            records[0].source.json_pointer.as_deref(),
+            Some("/transactions/0/amount")
+        );
+    }
+
+    #[test]
+    fn test_xml_records_carry_lines() {
+        let xml_content = "<root>\n  <item>123</item>\n  <value>\n    456.78\n  </value>\n</root>";
+        let records = parse_xml_records(xml_content).unwrap();
+        let lines: Vec<_> = records.iter().map(|r| (r.value, r.source.line)).collect();
+        assert_eq!(lines, vec![(123.0, Some(2)), (456.78, Some(4))]);
+    }
+
     #[test]
     fn test_xml_text_extraction() {
         let xml_content = "<root><item>123</item><value>456.78</value></root>";
-        let text = extract_text_from_xml(xml_content);
-        assert!(text.contains("123"));
-        assert!(text.contains("456.78"));
+        let numbers = parse_xml_content(xml_content).unwrap();
+        assert!(numbers.contains(&123.0));
+        assert!(numbers.contains(&456.78));
+    }
+
+    #[test]
+    fn test_xml_attributes_and_xpath_option() {
+        let xml_content = r#"<invoices>
+  <invoice number="2024-0017"><Amount value="1.234,50" currency="EUR"/></invoice>
+  <invoice number="2024-0018"><Amount value="987,00" currency="EUR"/></invoice>
+</invoices>"#;
+
+        let options = InputOptions {
+            xpath: Some("//Amount/@value".to_string()),
+            locale: NumberLocale::Eu,
+            ..Default::default()
+        };
+        let values = record_values(parse_xml_records_with(xml_content, &options).unwrap());
+        assert_eq!(values.unwrap(), vec![1234.5, 987.0]);
+
+        // Without a selection, attribute values are read too
+        let all = parse_xml_content(xml_content).unwrap();
+        assert!(all.contains(&2024.0) && all.contains(&987.0));
+
+        assert!(parse_xml_content("<invoices><invoice></invoices>").is_err());
+    }
+
+    #[test]
+    fn test_path_and_key_selection() {
+        use crate::common::input::selector::{JsonPath, KeySelection};
+        use regex::Regex;
+
+        let options = InputOptions {
+            keys: KeySelection {
+                path: Some(JsonPath::parse("$.invoices[*]").unwrap()),
+                include_keys: Some(Regex::new("^(total|amount)$").unwrap()),
+                exclude_keys: Some(Regex::new("^tax$").unwrap()),
+            },
+            ..Default::default()
+        };
+
+        let json = r#"{
+            "invoices": [
+                {"id": 101, "total": 1250.5, "tax": {"total": 99.0}},
+                {"id": 102, "lines": [{"amount": 310}, {"qty": 4}]}
+            ],
+            "customer_count": 42
+        }"#;
+        let records = parse_json_records_with(json, &options).unwrap();
+        let sources: Vec<_> = records.iter().map(|r| r.source.to_string()).collect();
+        assert_eq!(
+            record_values(records).unwrap(),
+            vec![1250.5, 310.0],
+            "{sources:?}"
+        );
+        assert!(sources[0].contains("/invoices/0/total"));
+
+        let yaml = "invoices:\n  - id: 101\n    total: 1250.5\n    tax:\n      total: 99\n  - id: 102\n    lines:\n      - amount: 310\n      - qty: 4\ncustomer_count: 42\n";
+        assert_eq!(
+            record_values(parse_yaml_records_with(yaml, &options).unwrap()).unwrap(),
+            vec![1250.5, 310.0]
+        );
+
+        let toml = "customer_count = 42\n\n[[invoices]]\nid = 101\ntotal = 1250.5\ntax = { total = 99 }\n\n[[invoices]]\nid = 102\nlines = [{ amount = 310 }, { qty = 4 }]\n";
+        assert_eq!(
+            record_values(parse_toml_records_with(toml, &options).unwrap()).unwrap(),
+            vec![1250.5, 310.0]
+        );
+
+        // Key selection does not apply to XML
+        assert!(parse_xml_records_with("<a>1</a>", &options).is_err());
     }
 }
@@ -1,3 +1,4 @@
+pub mod columnar;
 pub mod csv;
 pub mod excel;
 pub mod html;
@@ -5,9 +6,12 @@ pub mod json_xml;
 pub mod opendocument;
 pub mod pdf;
 pub mod powerpoint;
+pub mod sqlite;
 pub mod word;
+pub mod xml;
 
 // Re-export key functionality for easier imports
+pub use columnar::*;
 pub use csv::*;
 pub use excel::*;
 pub use html::*;
@@ -15,4 +19,6 @@ pub use json_xml::*;
 pub use opendocument::*;
 pub use pdf::*;
 pub use powerpoint::*;
+pub use sqlite::*;
 pub use word::*;
+pub use xml::{XmlDocument, XmlElement, XmlNode};
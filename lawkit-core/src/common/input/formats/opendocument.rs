@@ -1,9 +1,29 @@
-use crate::common::international::extract_numbers_international;
+use crate::common::input::options::InputOptions;
+use crate::common::input::provenance::{
+    record_values, records_from_text, NumberRecord, Provenance,
+};
+use crate::common::international::NumberLocale;
 use std::io::Read;
 use std::path::Path;
 
 /// Parse OpenDocument files (.odt, .ods) and extract numbers from content
 pub fn parse_opendocument_file(file_path: &Path) -> crate::error::Result<Vec<f64>> {
+    record_values(parse_opendocument_file_records(file_path)?)
+}
+
+/// Parse OpenDocument files, keeping the paragraph (ODT) or sheet and cell (ODS) of each number
+pub fn parse_opendocument_file_records(
+    file_path: &Path,
+) -> crate::error::Result<Vec<NumberRecord>> {
+    parse_opendocument_file_records_with(file_path, &InputOptions::default())
+}
+
+/// Parse OpenDocument files, reading only the selected sheet, range and column of ODS files
+/// and reading numbers in the chosen locale
+pub fn parse_opendocument_file_records_with(
+    file_path: &Path,
+    options: &InputOptions,
+) -> crate::error::Result<Vec<NumberRecord>> {
     let extension = file_path
         .extension()
         .and_then(|ext| ext.to_str())
@@ -11,11 +31,14 @@ pub fn parse_opendocument_file(file_path: &Path) -> crate::error::Result<Vec<f64
         .to_lowercase();
 
     match extension.as_str() {
-        "odt" => parse_odt_file(file_path),
+        "odt" if options.has_selection() => Err(crate::error::BenfError::InvalidInput(
+            "column, sheet and range selection do not apply to text documents".to_string(),
+        )),
+        "odt" => parse_odt_file(file_path, options.locale),
         "ods" => {
             // .ods files are already handled by the Excel parser (calamine)
             // Redirect to the existing Excel parser
-            crate::common::input::formats::excel::parse_excel_file(file_path)
+            crate::common::input::formats::excel::parse_excel_file_records_with(file_path, options)
         }
         _ => Err(crate::error::BenfError::ParseError(format!(
             "Unsupported OpenDocument file extension: {extension}"
@@ -24,7 +47,10 @@ pub fn parse_opendocument_file(file_path: &Path) -> crate::error::Result<Vec<f64
 }
 
 /// Parse ODT (OpenDocument Text) files using ZIP extraction and XML parsing
-fn parse_odt_file(file_path: &Path) -> crate::error::Result<Vec<f64>> {
+fn parse_odt_file(
+    file_path: &Path,
+    locale: NumberLocale,
+) -> crate::error::Result<Vec<NumberRecord>> {
     // OpenDocument Text (.odt) files are ZIP archives containing XML files
     // The main content is stored in content.xml
     // Text content is in various elements like <text:p>, <text:span>, etc.
@@ -77,25 +103,40 @@ fn parse_odt_file(file_path: &Path) -> crate::error::Result<Vec<f64>> {
         crate::error::BenfError::ParseError(format!("Failed to read content.xml data: {e}"))
     })?;
 
-    // Extract text content from XML
-    let text_content = extract_text_from_odt_xml(&contents)?;
-
-    if text_content.trim().is_empty() {
-        return Err(crate::error::BenfError::NoNumbersFound);
-    }
+    // Extract text content from XML, one entry per text element
+    let elements = extract_text_elements_from_odt_xml(&contents)?;
 
     // Extract numbers using international number processing
-    let numbers = extract_numbers_international(&text_content);
-
-    if numbers.is_empty() {
+    let records: Vec<NumberRecord> = elements
+        .iter()
+        .enumerate()
+        .map(|(index, text)| {
+            let source = Provenance {
+                paragraph: Some(index + 1),
+                ..Default::default()
+            };
+            records_from_text(text, &source, locale)
+        })
+        .collect::<crate::error::Result<Vec<_>>>()?
+        .into_iter()
+        .flatten()
+        .collect();
+
+    if records.is_empty() {
         Err(crate::error::BenfError::NoNumbersFound)
     } else {
-        Ok(numbers)
+        Ok(records)
     }
 }
 
 /// Extract text content from OpenDocument XML
+#[cfg(test)]
 fn extract_text_from_odt_xml(xml_content: &str) -> crate::error::Result<String> {
+    Ok(extract_text_elements_from_odt_xml(xml_content)?.join(" "))
+}
+
+/// Extract the text of each OpenDocument text or table element
+fn extract_text_elements_from_odt_xml(xml_content: &str) -> crate::error::Result<Vec<String>> {
     use regex::Regex;
 
     // OpenDocument text content is in various elements
@@ -131,7 +172,7 @@ fn extract_text_from_odt_xml(xml_content: &str) -> crate::error::Result<String>
         }
     }
 
-    Ok(extracted_text.join(" "))
+    Ok(extracted_text)
 }
 
 /// Extract inner text from XML, removing nested tags
@@ -257,6 +298,119 @@ mod tests {
         assert_eq!(decoded, "Profit & Loss: <500,000> \"estimated\"  margin");
     }
 
+    /// Write a minimal two-sheet ODS file: "Summary" and a "Ledger" with id/amount columns
+    fn write_sample_ods(dir: &Path) -> PathBuf {
+        use std::io::Write;
+
+        let cell = |value: &str| {
+            if value.parse::<f64>().is_ok() {
+                format!(
+                    r#"<table:table-cell office:value-type="float" office:value="{value}"><text:p>{value}</text:p></table:table-cell>"#
+                )
+            } else {
+                format!(
+                    r#"<table:table-cell office:value-type="string"><text:p>{value}</text:p></table:table-cell>"#
+                )
+            }
+        };
+        let row = |values: &[&str]| {
+            let cells: String = values.iter().map(|value| cell(value)).collect();
+            format!("<table:table-row>{cells}</table:table-row>")
+        };
+        let content = format!(
+            r#"<?xml version="1.0" encoding="UTF-8"?>
+<office:document-content xmlns:office="urn:oasis:names:tc:opendocument:xmlns:office:1.0" xmlns:table="urn:oasis:names:tc:opendocument:xmlns:table:1.0" xmlns:text="urn:oasis:names:tc:opendocument:xmlns:text:1.0" office:version="1.2"><office:body><office:spreadsheet><table:table table:name="Summary">{}</table:table><table:table table:name="Ledger">{}{}{}{}</table:table></office:spreadsheet></office:body></office:document-content>"#,
+            row(&["99"]),
+            row(&["id", "amount"]),
+            row(&["101", "1234.5"]),
+            row(&["102", "567.25"]),
+            row(&["103", "890"]),
+        );
+
+        let path = dir.join("ledger.ods");
+        let mut zip = zip::ZipWriter::new(std::fs::File::create(&path).unwrap());
+        let stored =
+            zip::write::FileOptions::default().compression_method(zip::CompressionMethod::Stored);
+        zip.start_file("mimetype", stored).unwrap();
+        zip.write_all(b"application/vnd.oasis.opendocument.spreadsheet")
+            .unwrap();
+        zip.start_file("content.xml", stored).unwrap();
+        zip.write_all(content.as_bytes()).unwrap();
+        zip.finish().unwrap();
+        path
+    }
+
+    #[test]
+    fn test_ods_sheet_column_and_range_selection() {
+        use crate::common::input::options::ColumnSelector;
+
+        let dir = tempfile::tempdir().unwrap();
+        let path = write_sample_ods(dir.path());
+
+        let all = parse_opendocument_file(&path).unwrap();
+        assert!(all.contains(&99.0) && all.contains(&101.0));
+
+        let by_header = InputOptions {
+            column: Some(ColumnSelector::Header("Amount".to_string())),
+            sheet: Some("ledger".to_string()),
+            ..Default::default()
+        };
+        let records = parse_opendocument_file_records_with(&path, &by_header).unwrap();
+        let values: Vec<f64> = records.iter().map(|r| r.value).collect();
+        assert_eq!(values, vec![1234.5, 567.25, 890.0]);
+        assert_eq!(records[0].source.sheet.as_deref(), Some("Ledger"));
+        assert_eq!(records[0].source.cell.as_deref(), Some("B2"));
+        assert_eq!(records[0].source.header.as_deref(), Some("amount"));
+
+        let by_range = InputOptions {
+            sheet: Some("Ledger".to_string()),
+            range: Some("A2:B3".parse().unwrap()),
+            column: Some("1".parse().unwrap()),
+            ..Default::default()
+        };
+        let values = record_values(parse_opendocument_file_records_with(&path, &by_range).unwrap());
+        assert_eq!(values.unwrap(), vec![101.0, 102.0]);
+
+        let missing_sheet = InputOptions {
+            sheet: Some("Budget".to_string()),
+            ..Default::default()
+        };
+        assert!(parse_opendocument_file_records_with(&path, &missing_sheet).is_err());
+    }
+
+    #[test]
+    fn test_ods_group_by_column() {
+        use crate::common::input::options::ColumnSelector;
+
+        let dir = tempfile::tempdir().unwrap();
+        let path = write_sample_ods(dir.path());
+
+        let options = InputOptions {
+            sheet: Some("Ledger".to_string()),
+            group_by: Some(ColumnSelector::Header("id".to_string())),
+            ..Default::default()
+        };
+        let records = parse_opendocument_file_records_with(&path, &options).unwrap();
+        let groups: Vec<(f64, Option<&str>)> = records
+            .iter()
+            .map(|r| (r.value, r.group.as_deref()))
+            .collect();
+        assert_eq!(
+            groups,
+            vec![
+                (1234.5, Some("101")),
+                (567.25, Some("102")),
+                (890.0, Some("103"))
+            ]
+        );
+
+        let missing = InputOptions {
+            group_by: Some(ColumnSelector::Header("vendor".to_string())),
+            ..options
+        };
+        assert!(parse_opendocument_file_records_with(&path, &missing).is_err());
+    }
+
     #[test]
     fn test_ods_redirect() {
         // Test that .ods files are redirected to Excel parser
@@ -1,22 +1,48 @@
-use crate::common::international::extract_numbers_international;
-use pdf_extract::extract_text;
+use crate::common::input::provenance::{
+    record_values, records_from_text, NumberRecord, Provenance,
+};
+use crate::common::international::NumberLocale;
+use pdf_extract::extract_text_by_pages;
 use std::path::Path;
 
 /// Parse PDF files and extract numbers from text content
 pub fn parse_pdf_file(file_path: &Path) -> crate::error::Result<Vec<f64>> {
-    // Extract text from PDF file path
-    let text = extract_text(file_path).map_err(|e| {
+    record_values(parse_pdf_file_records(file_path)?)
+}
+
+/// Parse PDF files, keeping the page and line of each number
+pub fn parse_pdf_file_records(file_path: &Path) -> crate::error::Result<Vec<NumberRecord>> {
+    parse_pdf_file_records_with(file_path, NumberLocale::default())
+}
+
+/// Parse PDF files whose numbers are written in `locale`
+pub fn parse_pdf_file_records_with(
+    file_path: &Path,
+    locale: NumberLocale,
+) -> crate::error::Result<Vec<NumberRecord>> {
+    // Extract text from PDF file path, one string per page
+    let pages = extract_text_by_pages(file_path).map_err(|e| {
         crate::error::BenfError::ParseError(format!("Failed to extract text from PDF: {e}"))
     })?;
 
     // Extract numbers from the text (including international numerals)
-    let numbers = extract_numbers_international(&text);
+    let mut records = Vec::new();
+    for (page_index, page) in pages.iter().enumerate() {
+        for (line_index, line) in page.lines().enumerate() {
+            let source = Provenance {
+                page: Some(page_index + 1),
+                line: Some(line_index + 1),
+                ..Default::default()
+            };
+            records.extend(records_from_text(line, &source, locale)?);
+        }
+    }
 
-    if numbers.is_empty() {
+    if records.is_empty() {
         return Err(crate::error::BenfError::NoNumbersFound);
     }
 
-    Ok(numbers)
+    Ok(records)
 }
 
 #[cfg(test)]
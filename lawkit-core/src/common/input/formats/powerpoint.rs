@@ -1,9 +1,25 @@
-use crate::common::international::extract_numbers_international;
+use crate::common::input::provenance::{
+    record_values, records_from_text, NumberRecord, Provenance,
+};
+use crate::common::international::NumberLocale;
 use std::io::Read;
 use std::path::Path;
 
 /// Parse PowerPoint files (.pptx, .ppt) and extract numbers from slide content
 pub fn parse_powerpoint_file(file_path: &Path) -> crate::error::Result<Vec<f64>> {
+    record_values(parse_powerpoint_file_records(file_path)?)
+}
+
+/// Parse PowerPoint files, keeping the slide (as `page`) of each number
+pub fn parse_powerpoint_file_records(file_path: &Path) -> crate::error::Result<Vec<NumberRecord>> {
+    parse_powerpoint_file_records_with(file_path, NumberLocale::default())
+}
+
+/// Parse PowerPoint files whose numbers are written in `locale`
+pub fn parse_powerpoint_file_records_with(
+    file_path: &Path,
+    locale: NumberLocale,
+) -> crate::error::Result<Vec<NumberRecord>> {
     let extension = file_path
         .extension()
         .and_then(|ext| ext.to_str())
@@ -11,7 +27,7 @@ pub fn parse_powerpoint_file(file_path: &Path) -> crate::error::Result<Vec<f64>>
         .to_lowercase();
 
     match extension.as_str() {
-        "pptx" => parse_pptx_file(file_path),
+        "pptx" => parse_pptx_file(file_path, locale),
         "ppt" => {
             // .ppt files require different handling (legacy format)
             // For now, return an error suggesting conversion to .pptx
@@ -26,7 +42,10 @@ pub fn parse_powerpoint_file(file_path: &Path) -> crate::error::Result<Vec<f64>>
 }
 
 /// Parse PPTX files using ZIP extraction and XML parsing
-fn parse_pptx_file(file_path: &Path) -> crate::error::Result<Vec<f64>> {
+fn parse_pptx_file(
+    file_path: &Path,
+    locale: NumberLocale,
+) -> crate::error::Result<Vec<NumberRecord>> {
     // PowerPoint (.pptx) files are ZIP archives containing XML files
     // The slide content is stored in ppt/slides/slide*.xml files
     // Text content is in <a:t> elements within the XML structure
@@ -50,7 +69,7 @@ fn parse_pptx_file(file_path: &Path) -> crate::error::Result<Vec<f64>> {
         ))
     })?;
 
-    let mut all_text = String::new();
+    let mut records = Vec::new();
 
     // Iterate through all files in the ZIP archive
     for i in 0..archive.len() {
@@ -61,7 +80,7 @@ fn parse_pptx_file(file_path: &Path) -> crate::error::Result<Vec<f64>> {
         let file_name = file.name().to_string();
 
         // Look for slide XML files
-        if file_name.starts_with("ppt/slides/slide") && file_name.ends_with(".xml") {
+        if let Some(slide_number) = slide_number(&file_name) {
             let mut contents = String::new();
             file.read_to_string(&mut contents).map_err(|e| {
                 crate::error::BenfError::ParseError(format!("Failed to read slide XML: {e}"))
@@ -69,25 +88,31 @@ fn parse_pptx_file(file_path: &Path) -> crate::error::Result<Vec<f64>> {
 
             // Extract text content from XML
             let slide_text = extract_text_from_slide_xml(&contents)?;
-            all_text.push_str(&slide_text);
-            all_text.push(' '); // Add separator between slides
+            let source = Provenance {
+                page: Some(slide_number),
+                ..Default::default()
+            };
+            records.extend(records_from_text(&slide_text, &source, locale)?);
         }
     }
 
-    if all_text.trim().is_empty() {
-        return Err(crate::error::BenfError::NoNumbersFound);
-    }
-
-    // Extract numbers using international number processing
-    let numbers = extract_numbers_international(&all_text);
-
-    if numbers.is_empty() {
+    if records.is_empty() {
         Err(crate::error::BenfError::NoNumbersFound)
     } else {
-        Ok(numbers)
+        records.sort_by_key(|record| record.source.page);
+        Ok(records)
     }
 }
 
+/// Slide number of a `ppt/slides/slideN.xml` entry
+fn slide_number(file_name: &str) -> Option<usize> {
+    file_name
+        .strip_prefix("ppt/slides/slide")?
+        .strip_suffix(".xml")?
+        .parse()
+        .ok()
+}
+
 /// Extract text content from a PowerPoint slide XML
 fn extract_text_from_slide_xml(xml_content: &str) -> crate::error::Result<String> {
     use regex::Regex;
@@ -126,6 +151,13 @@ mod tests {
     use super::*;
     use std::path::PathBuf;
 
+    #[test]
+    fn test_slide_number() {
+        assert_eq!(slide_number("ppt/slides/slide3.xml"), Some(3));
+        assert_eq!(slide_number("ppt/slides/_rels/slide3.xml.rels"), None);
+        assert_eq!(slide_number("ppt/slideLayouts/slideLayout1.xml"), None);
+    }
+
     #[test]
     fn test_powerpoint_parsing_concept() {
         // Test with non-existent file
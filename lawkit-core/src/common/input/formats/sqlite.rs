@@ -0,0 +1,342 @@
+use crate::common::input::options::{ColumnSelector, InputOptions};
+use crate::common::input::provenance::{
+    record_values, records_from_text, NumberRecord, Provenance,
+};
+use crate::error::{BenfError, Result};
+use rusqlite::types::ValueRef;
+use rusqlite::{Connection, OpenFlags};
+use std::path::Path;
+
+/// First bytes of a SQLite 3 database file
+pub const SQLITE_MAGIC: &[u8] = b"SQLite format 3\0";
+
+/// Parse SQLite databases and extract numbers
+pub fn parse_sqlite_file(file_path: &Path) -> Result<Vec<f64>> {
+    record_values(parse_sqlite_file_records(file_path)?)
+}
+
+/// Parse SQLite databases, keeping the table, row and column of each number
+pub fn parse_sqlite_file_records(file_path: &Path) -> Result<Vec<NumberRecord>> {
+    parse_sqlite_file_records_with(file_path, &InputOptions::default())
+}
+
+/// Parse SQLite databases, reading the result set of `options.query` or else every table
+///
+/// INTEGER and REAL values are read from every column; TEXT values only from a column
+/// selected with `options.column`, in `options.locale`. When whole tables are read without
+/// a column selection, key columns (see `key_columns`) are skipped. The database is opened
+/// read-only.
+pub fn parse_sqlite_file_records_with(
+    file_path: &Path,
+    options: &InputOptions,
+) -> Result<Vec<NumberRecord>> {
+    options.ensure_no_spreadsheet_options()?;
+
+    let connection = Connection::open_with_flags(
+        file_path,
+        OpenFlags::SQLITE_OPEN_READ_ONLY | OpenFlags::SQLITE_OPEN_NO_MUTEX,
+    )
+    .map_err(|e| BenfError::FileError(format!("Failed to open SQLite database: {e}")))?;
+
+    match &options.query {
+        Some(query) => query_records(&connection, query, None, &[], options),
+        None => {
+            let mut records = Vec::new();
+            for table in table_names(&connection)? {
+                let skipped = match options.column {
+                    Some(_) => Vec::new(),
+                    None => key_columns(&connection, &table)?,
+                };
+                let query = format!("SELECT * FROM {}", quote_identifier(&table));
+                records.extend(query_records(
+                    &connection,
+                    &query,
+                    Some(&table),
+                    &skipped,
+                    options,
+                )?);
+            }
+            Ok(records)
+        }
+    }
+}
+
+/// Run `query` against an open database and read the numbers of its result set
+pub fn query_sqlite_records(
+    connection: &Connection,
+    query: &str,
+    options: &InputOptions,
+) -> Result<Vec<NumberRecord>> {
+    query_records(connection, query, None, &[], options)
+}
+
+fn sql_error(e: rusqlite::Error) -> BenfError {
+    BenfError::ParseError(format!("SQLite query failed: {e}"))
+}
+
+/// User tables in name order, skipping SQLite's internal ones
+fn table_names(connection: &Connection) -> Result<Vec<String>> {
+    let mut statement = connection
+        .prepare(
+            "SELECT name FROM sqlite_master \
+             WHERE type = 'table' AND name NOT LIKE 'sqlite_%' ORDER BY name",
+        )
+        .map_err(sql_error)?;
+    let names = statement
+        .query_map([], |row| row.get(0))
+        .map_err(sql_error)?
+        .collect::<rusqlite::Result<Vec<String>>>()
+        .map_err(sql_error)?;
+    Ok(names)
+}
+
+fn quote_identifier(name: &str) -> String {
+    format!("\"{}\"", name.replace('"', "\"\""))
+}
+
+/// Columns of `table` that identify rows rather than measure anything: the primary key,
+/// foreign keys and columns named `id` or `*_id`
+fn key_columns(connection: &Connection, table: &str) -> Result<Vec<String>> {
+    let table = quote_identifier(table);
+
+    let mut statement = connection
+        .prepare(&format!("PRAGMA table_info({table})"))
+        .map_err(sql_error)?;
+    let columns = statement
+        .query_map([], |row| {
+            Ok((row.get::<_, String>("name")?, row.get::<_, i64>("pk")?))
+        })
+        .map_err(sql_error)?
+        .collect::<rusqlite::Result<Vec<_>>>()
+        .map_err(sql_error)?;
+    let mut keys: Vec<String> = columns
+        .into_iter()
+        .filter(|(name, pk)| {
+            let name = name.to_lowercase();
+            *pk > 0 || name == "id" || name.ends_with("_id")
+        })
+        .map(|(name, _)| name)
+        .collect();
+
+    let mut statement = connection
+        .prepare(&format!("PRAGMA foreign_key_list({table})"))
+        .map_err(sql_error)?;
+    let foreign_keys = statement
+        .query_map([], |row| row.get::<_, String>("from"))
+        .map_err(sql_error)?
+        .collect::<rusqlite::Result<Vec<_>>>()
+        .map_err(sql_error)?;
+    keys.extend(foreign_keys);
+    Ok(keys)
+}
+
+fn query_records(
+    connection: &Connection,
+    query: &str,
+    table: Option<&str>,
+    skipped: &[String],
+    options: &InputOptions,
+) -> Result<Vec<NumberRecord>> {
+    let mut statement = connection.prepare(query).map_err(sql_error)?;
+    if !statement.readonly() {
+        return Err(BenfError::InvalidInput(
+            "SQLite query must not modify the database".to_string(),
+        ));
+    }
+
+    let names: Vec<String> = statement
+        .column_names()
+        .into_iter()
+        .map(str::to_string)
+        .collect();
+    let selected = match &options.column {
+        None => None,
+        Some(ColumnSelector::Index(index)) if index.get() <= names.len() => Some(index.get() - 1),
+        Some(ColumnSelector::Index(index)) => {
+            return Err(BenfError::InvalidInput(format!(
+                "column {index} not found ({} columns)",
+                names.len()
+            )))
+        }
+        Some(column @ ColumnSelector::Header(name)) => Some(
+            names
+                .iter()
+                .position(|header| column.matches_header(header))
+                .ok_or_else(|| {
+                    BenfError::InvalidInput(format!(
+                        "column '{name}' not found (available: {})",
+                        names.join(", ")
+                    ))
+                })?,
+        ),
+    };
+
+    let mut records = Vec::new();
+    let mut rows = statement.query([]).map_err(sql_error)?;
+    let mut row_number = 0;
+    while let Some(row) = rows.next().map_err(sql_error)? {
+        row_number += 1;
+        for (index, name) in names.iter().enumerate() {
+            if selected.is_some_and(|selected| selected != index) || skipped.contains(name) {
+                continue;
+            }
+
+            let source = Provenance {
+                table: table.map(str::to_string),
+                row: Some(row_number),
+                column: Some(index + 1),
+                header: Some(name.clone()),
+                ..Default::default()
+            };
+            match row.get_ref(index).map_err(sql_error)? {
+                ValueRef::Integer(i) if i != 0 => {
+                    records.push(NumberRecord::new(i as f64, source, i.to_string()));
+                }
+                ValueRef::Real(f) if f != 0.0 && f.is_finite() => {
+                    records.push(NumberRecord::new(f, source, f.to_string()));
+                }
+                // Text holds identifiers and codes as often as amounts, so only read it on request
+                ValueRef::Text(text) if selected.is_some() => {
+                    let text = String::from_utf8_lossy(text);
+                    records.extend(records_from_text(&text, &source, options.locale)?);
+                }
+                _ => {} // Skip NULL, BLOB, zero and unselected text
+            }
+        }
+    }
+    Ok(records)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ledger_database(path: &Path) {
+        let connection = Connection::open(path).unwrap();
+        connection
+            .execute_batch(
+                "CREATE TABLE journal (id INTEGER PRIMARY KEY, year INTEGER, amount REAL, memo TEXT);
+                 CREATE TABLE accounts (code TEXT, balance REAL);
+                 INSERT INTO accounts VALUES ('A-1', 1500.5), ('A-2', NULL);",
+            )
+            .unwrap();
+        for i in 1..=12 {
+            connection
+                .execute(
+                    "INSERT INTO journal VALUES (?1, ?2, ?3, ?4)",
+                    rusqlite::params![
+                        i,
+                        if i % 2 == 0 { 2025 } else { 2024 },
+                        i as f64 * 111.5,
+                        format!("batch {}", i * 3)
+                    ],
+                )
+                .unwrap();
+        }
+    }
+
+    #[test]
+    fn test_sqlite_query() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("ledger.sqlite");
+        ledger_database(&path);
+
+        let options = InputOptions {
+            query: Some("SELECT amount FROM journal WHERE year = 2025".to_string()),
+            ..Default::default()
+        };
+        let records = parse_sqlite_file_records_with(&path, &options).unwrap();
+        let values: Vec<f64> = records.iter().map(|record| record.value).collect();
+        assert_eq!(values, [223.0, 446.0, 669.0, 892.0, 1115.0, 1338.0]);
+        assert_eq!(records[1].source.to_string(), "row 2, column 1 (amount)");
+
+        // Text columns are read only when selected
+        let options = InputOptions {
+            query: Some("SELECT id, memo FROM journal ORDER BY id".to_string()),
+            column: Some("memo".parse().unwrap()),
+            ..Default::default()
+        };
+        let memos = record_values(parse_sqlite_file_records_with(&path, &options).unwrap());
+        assert_eq!(memos.unwrap()[..3], [3.0, 6.0, 9.0]);
+    }
+
+    #[test]
+    fn test_sqlite_all_tables() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("ledger.db");
+        ledger_database(&path);
+
+        let records = parse_sqlite_file_records(&path).unwrap();
+        // accounts.balance, then journal year and amount for each row (the key is skipped)
+        assert_eq!(records.len(), 1 + 12 * 2);
+        assert_eq!(
+            records[0].source.to_string(),
+            "table accounts, row 1, column 2 (balance)"
+        );
+        assert_eq!(records[1].source.table.as_deref(), Some("journal"));
+    }
+
+    #[test]
+    fn test_sqlite_skips_key_columns() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("invoices.db");
+        let connection = Connection::open(&path).unwrap();
+        connection
+            .execute_batch(
+                "CREATE TABLE vendors (vendor_no INTEGER PRIMARY KEY, rating REAL);
+                 CREATE TABLE invoices (
+                     invoice_no INTEGER, vendor_no INTEGER REFERENCES vendors(vendor_no),
+                     customer_id INTEGER, total REAL, PRIMARY KEY (invoice_no));
+                 INSERT INTO vendors VALUES (7, 4.5);
+                 INSERT INTO invoices VALUES (1001, 7, 501, 120.0), (1002, 7, 502, 87.5);",
+            )
+            .unwrap();
+
+        let headers = |options: &InputOptions| -> Vec<String> {
+            parse_sqlite_file_records_with(&path, options)
+                .unwrap()
+                .into_iter()
+                .map(|record| record.source.header.unwrap())
+                .collect()
+        };
+        assert_eq!(
+            headers(&InputOptions::default()),
+            ["total", "total", "rating"]
+        );
+
+        // An explicit query still reads keys
+        let options = InputOptions {
+            query: Some("SELECT vendor_no, customer_id FROM invoices LIMIT 1".to_string()),
+            ..Default::default()
+        };
+        assert_eq!(headers(&options), ["vendor_no", "customer_id"]);
+    }
+
+    #[test]
+    fn test_sqlite_errors() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("ledger.sqlite");
+        ledger_database(&path);
+
+        let query = |sql: &str| InputOptions {
+            query: Some(sql.to_string()),
+            ..Default::default()
+        };
+        assert!(parse_sqlite_file_records_with(&path, &query("SELECT nope FROM journal")).is_err());
+        assert!(parse_sqlite_file_records_with(&path, &query("DELETE FROM journal")).is_err());
+        assert_eq!(
+            parse_sqlite_file(&path).unwrap().len(),
+            25,
+            "the database is left untouched"
+        );
+
+        let options = InputOptions {
+            column: Some("total".parse().unwrap()),
+            ..query("SELECT amount FROM journal")
+        };
+        let error = parse_sqlite_file_records_with(&path, &options).unwrap_err();
+        assert!(error.to_string().contains("available: amount"));
+
+        assert!(parse_sqlite_file(&dir.path().join("missing.sqlite")).is_err());
+    }
+}
@@ -1,9 +1,25 @@
-use crate::common::international::extract_numbers_international;
+use crate::common::input::provenance::{
+    record_values, records_from_text, NumberRecord, Provenance,
+};
+use crate::common::international::NumberLocale;
 use docx_rs::*;
 use std::path::Path;
 
 /// Parse Word files (.docx, .doc) and extract numbers from text content
 pub fn parse_word_file(file_path: &Path) -> crate::error::Result<Vec<f64>> {
+    record_values(parse_word_file_records(file_path)?)
+}
+
+/// Parse Word files, keeping the paragraph of each number
+pub fn parse_word_file_records(file_path: &Path) -> crate::error::Result<Vec<NumberRecord>> {
+    parse_word_file_records_with(file_path, NumberLocale::default())
+}
+
+/// Parse Word files whose numbers are written in `locale`
+pub fn parse_word_file_records_with(
+    file_path: &Path,
+    locale: NumberLocale,
+) -> crate::error::Result<Vec<NumberRecord>> {
     let extension = file_path
         .extension()
         .and_then(|ext| ext.to_str())
@@ -11,7 +27,7 @@ pub fn parse_word_file(file_path: &Path) -> crate::error::Result<Vec<f64>> {
         .to_lowercase();
 
     match extension.as_str() {
-        "docx" => parse_docx_file(file_path),
+        "docx" => parse_docx_file(file_path, locale),
         "doc" => {
             // .doc files require different handling (legacy format)
             // For now, return an error suggesting conversion to .docx
@@ -26,7 +42,10 @@ pub fn parse_word_file(file_path: &Path) -> crate::error::Result<Vec<f64>> {
 }
 
 /// Parse DOCX files specifically using docx-rs
-fn parse_docx_file(file_path: &Path) -> crate::error::Result<Vec<f64>> {
+fn parse_docx_file(
+    file_path: &Path,
+    locale: NumberLocale,
+) -> crate::error::Result<Vec<NumberRecord>> {
     // Read the DOCX file
     let file_bytes = std::fs::read(file_path).map_err(|e| {
         crate::error::BenfError::FileError(format!("Failed to read Word file: {e}"))
@@ -37,34 +56,39 @@ fn parse_docx_file(file_path: &Path) -> crate::error::Result<Vec<f64>> {
         crate::error::BenfError::ParseError(format!("Failed to parse DOCX file: {e:?}"))
     })?;
 
-    // Extract all text content from the document
-    let mut all_text = String::new();
+    let mut records = Vec::new();
+    let mut paragraph_number = 0;
 
     // Extract text from paragraphs
     for child in &doc.document.children {
         if let DocumentChild::Paragraph(paragraph) = child {
+            paragraph_number += 1;
+            let mut paragraph_text = String::new();
             for run_child in &paragraph.children {
                 if let ParagraphChild::Run(run) = run_child {
                     for text_child in &run.children {
                         if let RunChild::Text(text) = text_child {
-                            all_text.push_str(&text.text);
-                            all_text.push(' ');
+                            paragraph_text.push_str(&text.text);
+                            paragraph_text.push(' ');
                         }
                     }
                 }
             }
-            all_text.push('\n'); // Add line break after each paragraph
+
+            // Extract numbers from the paragraph (including international numerals)
+            let source = Provenance {
+                paragraph: Some(paragraph_number),
+                ..Default::default()
+            };
+            records.extend(records_from_text(&paragraph_text, &source, locale)?);
         }
     }
 
-    // Extract numbers from the collected text (including international numerals)
-    let numbers = extract_numbers_international(&all_text);
-
-    if numbers.is_empty() {
+    if records.is_empty() {
         return Err(crate::error::BenfError::NoNumbersFound);
     }
 
-    Ok(numbers)
+    Ok(records)
 }
 
 #[cfg(test)]
@@ -0,0 +1,633 @@
+use crate::common::input::provenance::{records_from_text, NumberRecord, Provenance};
+use crate::common::international::NumberLocale;
+use crate::error::{BenfError, Result};
+use quick_xml::events::{BytesStart, Event};
+use serde_json::{Map, Value};
+
+/// An XML element with its attributes, children and direct text
+#[derive(Debug, Clone, PartialEq)]
+pub struct XmlElement {
+    pub name: String, // Qualified name, e.g. "us-gaap:Revenues"
+    pub attributes: Vec<(String, String)>,
+    pub children: Vec<XmlElement>,
+    pub text: Vec<(usize, String)>, // Direct text and CDATA chunks with their start line
+    pub line: usize,                // Line of the start tag
+}
+
+/// A parsed XML document
+#[derive(Debug, Clone, PartialEq)]
+pub struct XmlDocument {
+    pub root: XmlElement,
+}
+
+/// What an XPath-like expression selected
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum XmlNode<'a> {
+    Element(&'a XmlElement),
+    Attribute {
+        element: &'a XmlElement,
+        value: &'a str,
+    },
+    Text(&'a XmlElement),
+}
+
+impl XmlElement {
+    /// Name without the namespace prefix
+    pub fn local_name(&self) -> &str {
+        self.name
+            .rsplit_once(':')
+            .map_or(self.name.as_str(), |(_, local)| local)
+    }
+
+    pub fn attribute(&self, name: &str) -> Option<&str> {
+        self.attributes
+            .iter()
+            .find(|(key, _)| key == name)
+            .map(|(_, value)| value.as_str())
+    }
+
+    /// Direct text of the element, without the text of its children
+    pub fn text(&self) -> String {
+        self.text.iter().map(|(_, chunk)| chunk.as_str()).collect()
+    }
+
+    /// Convert to JSON: attributes become `@name` keys, text becomes `#text` next to
+    /// attributes or children, repeated children become arrays and a text-only element
+    /// becomes its trimmed text (null when empty)
+    pub fn to_value(&self) -> Value {
+        let text = self.text();
+        let text = text.trim();
+        if self.attributes.is_empty() && self.children.is_empty() {
+            return if text.is_empty() {
+                Value::Null
+            } else {
+                Value::String(text.to_string())
+            };
+        }
+
+        let mut object = Map::new();
+        for (name, value) in &self.attributes {
+            object.insert(format!("@{name}"), Value::String(value.clone()));
+        }
+        for child in &self.children {
+            let value = child.to_value();
+            match object.get_mut(&child.name) {
+                Some(Value::Array(items)) => items.push(value),
+                Some(existing) => *existing = Value::Array(vec![existing.take(), value]),
+                None => {
+                    object.insert(child.name.clone(), value);
+                }
+            }
+        }
+        if !text.is_empty() {
+            object.insert("#text".to_string(), Value::String(text.to_string()));
+        }
+        Value::Object(object)
+    }
+
+    /// Numbers in the text of this element and its descendants
+    fn text_records(&self, locale: NumberLocale, records: &mut Vec<NumberRecord>) -> Result<()> {
+        push_text_records(&self.text, locale, records)?;
+        for child in &self.children {
+            child.text_records(locale, records)?;
+        }
+        Ok(())
+    }
+
+    /// Numbers in the text and attribute values of this element and its descendants
+    fn all_records(&self, locale: NumberLocale, records: &mut Vec<NumberRecord>) -> Result<()> {
+        for (_, value) in &self.attributes {
+            records.extend(records_from_text(
+                value,
+                &Provenance::line(self.line),
+                locale,
+            )?);
+        }
+        push_text_records(&self.text, locale, records)?;
+        for child in &self.children {
+            child.all_records(locale, records)?;
+        }
+        Ok(())
+    }
+
+    fn descendants<'a>(&'a self, found: &mut Vec<&'a XmlElement>) {
+        for child in &self.children {
+            found.push(child);
+            child.descendants(found);
+        }
+    }
+}
+
+fn push_text_records(
+    text: &[(usize, String)],
+    locale: NumberLocale,
+    records: &mut Vec<NumberRecord>,
+) -> Result<()> {
+    for (start_line, chunk) in text {
+        for (offset, line) in chunk.lines().enumerate() {
+            records.extend(records_from_text(
+                line,
+                &Provenance::line(start_line + offset),
+                locale,
+            )?);
+        }
+    }
+    Ok(())
+}
+
+impl XmlDocument {
+    /// Parse with a streaming reader, failing on malformed XML
+    pub fn parse(content: &str) -> Result<Self> {
+        let line_starts: Vec<usize> = std::iter::once(0)
+            .chain(content.match_indices('\n').map(|(index, _)| index + 1))
+            .collect();
+        let line_at = |offset: u64| line_starts.partition_point(|&start| start <= offset as usize);
+        let invalid =
+            |e: &dyn std::fmt::Display| BenfError::ParseError(format!("Invalid XML: {e}"));
+
+        let mut reader = quick_xml::Reader::from_str(content);
+        let mut stack: Vec<XmlElement> = Vec::new();
+        let mut root = None;
+        // Entity references split text into several events; they continue the current chunk
+        let mut in_text = false;
+
+        loop {
+            let offset = reader.buffer_position();
+            let event = reader.read_event().map_err(|e| invalid(&e))?;
+            let continues_text = std::mem::take(&mut in_text);
+            let text = match event {
+                Event::Start(start) => {
+                    stack.push(element_from_start(&start, line_at(offset))?);
+                    continue;
+                }
+                Event::Empty(start) => {
+                    let element = element_from_start(&start, line_at(offset))?;
+                    attach(&mut stack, &mut root, element)?;
+                    continue;
+                }
+                Event::End(_) => {
+                    let element = stack.pop().ok_or_else(|| invalid(&"unexpected end tag"))?;
+                    attach(&mut stack, &mut root, element)?;
+                    continue;
+                }
+                Event::Text(text) => text.decode().map_err(|e| invalid(&e))?.into_owned(),
+                Event::CData(data) => data.decode().map_err(|e| invalid(&e))?.into_owned(),
+                Event::GeneralRef(reference) => {
+                    if let Some(c) = reference.resolve_char_ref().map_err(|e| invalid(&e))? {
+                        c.to_string()
+                    } else {
+                        let name = reference.decode().map_err(|e| invalid(&e))?;
+                        quick_xml::escape::resolve_predefined_entity(&name)
+                            .map_or_else(|| format!("&{name};"), str::to_string)
+                    }
+                }
+                Event::Eof => break,
+                _ => continue, // Declaration, comments, processing instructions, DOCTYPE
+            };
+            in_text = true;
+
+            match stack.last_mut() {
+                Some(element) => match element.text.last_mut() {
+                    Some((_, chunk)) if continues_text => chunk.push_str(&text),
+                    _ => element.text.push((line_at(offset), text)),
+                },
+                None if text.trim().is_empty() => {}
+                None => return Err(invalid(&"text outside the root element")),
+            }
+        }
+
+        if let Some(element) = stack.last() {
+            return Err(invalid(&format!("unclosed element <{}>", element.name)));
+        }
+        let root = root.ok_or_else(|| invalid(&"no root element"))?;
+        Ok(XmlDocument { root })
+    }
+
+    /// The document as JSON, keyed by the root element name
+    pub fn to_value(&self) -> Value {
+        let mut object = Map::new();
+        object.insert(self.root.name.clone(), self.root.to_value());
+        Value::Object(object)
+    }
+
+    /// Evaluate an XPath-like expression
+    ///
+    /// Supported: `/` and `//` steps, element names (a name without a prefix also matches
+    /// prefixed elements by local name), `*`, a final `@name`, `@*` or `text()` step, and
+    /// predicates `[n]` (1-based), `[@name]`, `[@name='value']` and `[child='value']`.
+    pub fn select(&self, expression: &str) -> Result<Vec<XmlNode<'_>>> {
+        let steps = parse_path(expression)?;
+        let root = std::slice::from_ref(&self.root);
+
+        // `None` is the document node, whose only child is the root element
+        let mut context: Vec<Option<&XmlElement>> = vec![None];
+        let mut selected = Vec::new();
+        for (index, step) in steps.iter().enumerate() {
+            let last = index + 1 == steps.len();
+
+            // `//step` is `/descendant-or-self::node()/step`
+            let parents: Vec<Option<&XmlElement>> = if step.descendant {
+                let mut all = Vec::new();
+                for node in &context {
+                    all.push(*node);
+                    let mut found = Vec::new();
+                    match node {
+                        Some(element) => element.descendants(&mut found),
+                        None => {
+                            found.push(&self.root);
+                            self.root.descendants(&mut found);
+                        }
+                    }
+                    all.extend(found.into_iter().map(Some));
+                }
+                all
+            } else {
+                context.clone()
+            };
+
+            match &step.test {
+                NodeTest::Attribute(_) | NodeTest::Text if !last => {
+                    return Err(BenfError::InvalidInput(format!(
+                        "XPath '{expression}': attribute and text() steps must come last"
+                    )));
+                }
+                NodeTest::Attribute(name) => {
+                    for element in parents.into_iter().flatten() {
+                        for (key, value) in &element.attributes {
+                            if name == "*" || key == name {
+                                selected.push(XmlNode::Attribute { element, value });
+                            }
+                        }
+                    }
+                }
+                NodeTest::Text => {
+                    selected.extend(parents.into_iter().flatten().map(XmlNode::Text));
+                }
+                NodeTest::Name(name) => {
+                    let mut next = Vec::new();
+                    for parent in parents {
+                        let children = parent.map_or(root, |element| element.children.as_slice());
+                        let matching: Vec<&XmlElement> = children
+                            .iter()
+                            .filter(|child| name_matches(name, child))
+                            .collect();
+                        next.extend(
+                            apply_predicates(matching, &step.predicates)
+                                .into_iter()
+                                .map(Some),
+                        );
+                    }
+                    if last {
+                        selected.extend(next.into_iter().flatten().map(XmlNode::Element));
+                    } else {
+                        context = next;
+                    }
+                }
+            }
+        }
+
+        Ok(selected)
+    }
+
+    /// Numbers in every text node and attribute value
+    pub fn records(&self, locale: NumberLocale) -> Result<Vec<NumberRecord>> {
+        let mut records = Vec::new();
+        self.root.all_records(locale, &mut records)?;
+        Ok(records)
+    }
+
+    /// Numbers in the nodes an XPath-like expression selects
+    ///
+    /// Selected elements contribute the text of their subtree; attributes are only read
+    /// when selected with `@name`.
+    pub fn selected_records(
+        &self,
+        expression: &str,
+        locale: NumberLocale,
+    ) -> Result<Vec<NumberRecord>> {
+        let mut records = Vec::new();
+        for node in self.select(expression)? {
+            match node {
+                XmlNode::Element(element) => element.text_records(locale, &mut records)?,
+                XmlNode::Attribute { element, value } => records.extend(records_from_text(
+                    value,
+                    &Provenance::line(element.line),
+                    locale,
+                )?),
+                XmlNode::Text(element) => push_text_records(&element.text, locale, &mut records)?,
+            }
+        }
+        Ok(records)
+    }
+}
+
+fn element_from_start(start: &BytesStart, line: usize) -> Result<XmlElement> {
+    let invalid = |e: &dyn std::fmt::Display| BenfError::ParseError(format!("Invalid XML: {e}"));
+
+    let name = String::from_utf8_lossy(start.name().as_ref()).into_owned();
+    let mut attributes = Vec::new();
+    for attribute in start.attributes() {
+        let attribute = attribute.map_err(|e| invalid(&e))?;
+        let key = String::from_utf8_lossy(attribute.key.as_ref()).into_owned();
+        let value = attribute
+            .unescape_value()
+            .map_err(|e| invalid(&e))?
+            .into_owned();
+        attributes.push((key, value));
+    }
+
+    Ok(XmlElement {
+        name,
+        attributes,
+        children: Vec::new(),
+        text: Vec::new(),
+        line,
+    })
+}
+
+fn attach(
+    stack: &mut [XmlElement],
+    root: &mut Option<XmlElement>,
+    element: XmlElement,
+) -> Result<()> {
+    match stack.last_mut() {
+        Some(parent) => parent.children.push(element),
+        None if root.is_none() => *root = Some(element),
+        None => {
+            return Err(BenfError::ParseError(
+                "Invalid XML: more than one root element".to_string(),
+            ))
+        }
+    }
+    Ok(())
+}
+
+struct Step {
+    descendant: bool,
+    test: NodeTest,
+    predicates: Vec<Predicate>,
+}
+
+enum NodeTest {
+    Name(String), // "*" matches any element
+    Attribute(String),
+    Text,
+}
+
+enum Predicate {
+    Position(usize),
+    HasAttribute(String),
+    AttributeEquals(String, String),
+    ChildEquals(String, String),
+}
+
+fn name_matches(test: &str, element: &XmlElement) -> bool {
+    test == "*" || element.name == test || (!test.contains(':') && element.local_name() == test)
+}
+
+fn apply_predicates<'a>(
+    mut nodes: Vec<&'a XmlElement>,
+    predicates: &[Predicate],
+) -> Vec<&'a XmlElement> {
+    for predicate in predicates {
+        nodes = match predicate {
+            Predicate::Position(position) => nodes.get(position - 1).copied().into_iter().collect(),
+            Predicate::HasAttribute(name) => nodes
+                .into_iter()
+                .filter(|element| element.attribute(name).is_some())
+                .collect(),
+            Predicate::AttributeEquals(name, value) => nodes
+                .into_iter()
+                .filter(|element| element.attribute(name) == Some(value.as_str()))
+                .collect(),
+            Predicate::ChildEquals(name, value) => nodes
+                .into_iter()
+                .filter(|element| {
+                    element
+                        .children
+                        .iter()
+                        .any(|child| name_matches(name, child) && child.text().trim() == value)
+                })
+                .collect(),
+        };
+    }
+    nodes
+}
+
+/// Split an expression into steps; a relative path starts at the document node
+fn parse_path(expression: &str) -> Result<Vec<Step>> {
+    let invalid =
+        |reason: &str| BenfError::InvalidInput(format!("invalid XPath '{expression}': {reason}"));
+
+    let mut rest = expression.trim();
+    if rest.is_empty() {
+        return Err(invalid("empty expression"));
+    }
+
+    let mut steps = Vec::new();
+    let mut first = true;
+    while !rest.is_empty() {
+        let descendant = if let Some(after) = rest.strip_prefix("//") {
+            rest = after;
+            true
+        } else if let Some(after) = rest.strip_prefix('/') {
+            rest = after;
+            false
+        } else if first {
+            false
+        } else {
+            return Err(invalid("expected '/'"));
+        };
+        first = false;
+
+        // A step ends at the next '/' outside brackets and quotes
+        let mut depth = 0;
+        let mut quote = None;
+        let end = rest
+            .char_indices()
+            .find(|&(_, c)| {
+                match (quote, c) {
+                    (Some(q), c) if c == q => quote = None,
+                    (Some(_), _) => {}
+                    (None, '\'' | '"') => quote = Some(c),
+                    (None, '[') => depth += 1,
+                    (None, ']') => depth -= 1,
+                    (None, '/') if depth == 0 => return true,
+                    _ => {}
+                }
+                false
+            })
+            .map_or(rest.len(), |(index, _)| index);
+        let (step, after) = rest.split_at(end);
+        rest = after;
+
+        let (test, predicates) = match step.find('[') {
+            Some(index) => step.split_at(index),
+            None => (step, ""),
+        };
+        let test = test.trim();
+        let test = if let Some(name) = test.strip_prefix('@') {
+            NodeTest::Attribute(name.to_string())
+        } else if test == "text()" {
+            NodeTest::Text
+        } else if !test.is_empty() {
+            NodeTest::Name(test.to_string())
+        } else {
+            return Err(invalid("empty step"));
+        };
+
+        let mut parsed = Vec::new();
+        let mut predicates = predicates.trim();
+        while let Some(inner) = predicates.strip_prefix('[') {
+            let close = inner.find(']').ok_or_else(|| invalid("unclosed '['"))?;
+            parsed.push(
+                parse_predicate(inner[..close].trim())
+                    .ok_or_else(|| invalid("unsupported predicate"))?,
+            );
+            predicates = inner[close + 1..].trim();
+        }
+        if !predicates.is_empty() {
+            return Err(invalid("unexpected text after predicate"));
+        }
+
+        steps.push(Step {
+            descendant,
+            test,
+            predicates: parsed,
+        });
+    }
+
+    Ok(steps)
+}
+
+fn parse_predicate(predicate: &str) -> Option<Predicate> {
+    if let Ok(position) = predicate.parse::<usize>() {
+        return (position > 0).then_some(Predicate::Position(position));
+    }
+
+    let unquote = |value: &str| {
+        let value = value.trim();
+        let quoted = value.len() >= 2
+            && ((value.starts_with('\'') && value.ends_with('\''))
+                || (value.starts_with('"') && value.ends_with('"')));
+        quoted.then(|| value[1..value.len() - 1].to_string())
+    };
+
+    match predicate.split_once('=') {
+        Some((left, right)) => {
+            let value = unquote(right)?;
+            match left.trim().strip_prefix('@') {
+                Some(name) => Some(Predicate::AttributeEquals(name.to_string(), value)),
+                None => Some(Predicate::ChildEquals(left.trim().to_string(), value)),
+            }
+        }
+        None => predicate
+            .strip_prefix('@')
+            .map(|name| Predicate::HasAttribute(name.to_string())),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const LEDGER: &str = r#"<?xml version="1.0" encoding="UTF-8"?>
+<ledger company="ACME &amp; Co">
+  <!-- exported 2024 -->
+  <entry id="1" type="debit">
+    <Amount value="123.45"/>
+    <memo>Office &lt;supplies&gt;</memo>
+  </entry>
+  <entry id="2" type="credit">
+    <Amount value="678.90"/>
+    <memo><![CDATA[Refund <a&b> 42]]></memo>
+  </entry>
+</ledger>"#;
+
+    #[test]
+    fn test_attributes_and_structure() {
+        let document = XmlDocument::parse(LEDGER).unwrap();
+        let value = document.to_value();
+
+        assert_eq!(value["ledger"]["@company"], "ACME & Co");
+        assert_eq!(value["ledger"]["entry"][0]["Amount"]["@value"], "123.45");
+        assert_eq!(value["ledger"]["entry"][0]["memo"], "Office <supplies>");
+        assert_eq!(value["ledger"]["entry"][1]["memo"], "Refund <a&b> 42");
+        assert_eq!(value["ledger"]["entry"][1]["@id"], "2");
+    }
+
+    #[test]
+    fn test_records_include_attribute_values() {
+        let document = XmlDocument::parse(LEDGER).unwrap();
+        let values: Vec<(f64, Option<usize>)> = document
+            .records(NumberLocale::default())
+            .unwrap()
+            .iter()
+            .map(|record| (record.value, record.source.line))
+            .collect();
+
+        assert!(values.contains(&(123.45, Some(5))));
+        assert!(values.contains(&(678.9, Some(9))));
+        assert!(values.contains(&(42.0, Some(10))));
+    }
+
+    #[test]
+    fn test_xpath_selection() {
+        let document = XmlDocument::parse(LEDGER).unwrap();
+        let values = |expression: &str| -> Vec<f64> {
+            document
+                .selected_records(expression, NumberLocale::default())
+                .unwrap()
+                .iter()
+                .map(|record| record.value)
+                .collect()
+        };
+
+        assert_eq!(values("//Amount/@value"), vec![123.45, 678.9]);
+        assert_eq!(
+            values("/ledger/entry[@type='credit']/Amount/@value"),
+            vec![678.9]
+        );
+        assert_eq!(values("/ledger/entry[2]/memo"), vec![42.0]);
+        assert_eq!(values("ledger/entry/@id"), vec![1.0, 2.0]);
+        assert_eq!(
+            values("//entry[memo='Office <supplies>']/*/@*"),
+            vec![123.45]
+        );
+        assert!(values("//Missing").is_empty());
+
+        assert!(document.select("//entry/@id/memo").is_err());
+        assert!(document.select("//entry[last()]").is_err());
+    }
+
+    #[test]
+    fn test_namespaced_xbrl_facts() {
+        let xbrl = r#"<xbrli:xbrl xmlns:xbrli="http://www.xbrl.org/2003/instance" xmlns:us-gaap="http://fasb.org/us-gaap/2023">
+  <us-gaap:Revenues contextRef="FY2023" unitRef="USD" decimals="-6">383285000000</us-gaap:Revenues>
+  <us-gaap:NetIncomeLoss contextRef="FY2023" unitRef="USD" decimals="-6">96995000000</us-gaap:NetIncomeLoss>
+</xbrli:xbrl>"#;
+        let document = XmlDocument::parse(xbrl).unwrap();
+
+        let values = |expression: &str| -> Vec<f64> {
+            document
+                .selected_records(expression, NumberLocale::default())
+                .unwrap()
+                .iter()
+                .map(|record| record.value)
+                .collect()
+        };
+        assert_eq!(values("//us-gaap:Revenues"), vec![383285000000.0]);
+        assert_eq!(
+            values("/xbrl/*[@unitRef='USD']"),
+            vec![383285000000.0, 96995000000.0]
+        );
+    }
+
+    #[test]
+    fn test_malformed_xml_is_rejected() {
+        assert!(XmlDocument::parse("<a><b></a>").is_err());
+        assert!(XmlDocument::parse("<a>").is_err());
+        assert!(XmlDocument::parse("<a/><b/>").is_err());
+        assert!(XmlDocument::parse("just text").is_err());
+    }
+}
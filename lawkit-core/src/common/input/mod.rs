@@ -1,5 +1,16 @@
+pub mod archive;
 pub mod file_detector;
 pub mod formats;
+pub mod options;
 pub mod parser;
+pub mod provenance;
+pub mod selector;
 
+pub use archive::{
+    is_packed, parse_archive_members, parse_archive_members_with, ArchiveFormat, ArchiveMember,
+    Compression,
+};
+pub use options::{CellRange, ColumnSelector, InputOptions};
 pub use parser::*;
+pub use provenance::{NumberRecord, Provenance};
+pub use selector::{JsonPath, KeySelection, PathSegment};
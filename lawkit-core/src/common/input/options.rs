@@ -0,0 +1,204 @@
+use crate::common::input::selector::KeySelection;
+use crate::common::international::NumberLocale;
+use crate::error::{BenfError, Result};
+use std::num::NonZeroUsize;
+use std::str::FromStr;
+
+/// Which column of a table to read numbers from
+#[derive(Debug, Clone, PartialEq)]
+pub enum ColumnSelector {
+    /// 1-based column index (CSV field number, spreadsheet column A = 1)
+    Index(NonZeroUsize),
+    /// Header text in the first row, matched case-insensitively
+    Header(String),
+}
+
+impl ColumnSelector {
+    /// True when `header` names this column
+    pub fn matches_header(&self, header: &str) -> bool {
+        match self {
+            ColumnSelector::Header(name) => header.trim().eq_ignore_ascii_case(name.trim()),
+            ColumnSelector::Index(_) => false,
+        }
+    }
+
+    /// True when `key` names this column; an index is compared as text, for document keys
+    pub fn matches_key(&self, key: &str) -> bool {
+        match self {
+            ColumnSelector::Header(name) => key == name.trim(),
+            ColumnSelector::Index(index) => key == index.to_string(),
+        }
+    }
+}
+
+impl std::fmt::Display for ColumnSelector {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ColumnSelector::Index(index) => write!(f, "{index}"),
+            ColumnSelector::Header(name) => write!(f, "{name}"),
+        }
+    }
+}
+
+impl FromStr for ColumnSelector {
+    type Err = BenfError;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let s = s.trim();
+        if s.is_empty() {
+            return Err(BenfError::InvalidInput(
+                "column must not be empty".to_string(),
+            ));
+        }
+        match s.parse::<usize>() {
+            Ok(index) => NonZeroUsize::new(index)
+                .map(ColumnSelector::Index)
+                .ok_or_else(|| BenfError::InvalidInput("column index is 1-based".to_string())),
+            Err(_) => Ok(ColumnSelector::Header(s.to_string())),
+        }
+    }
+}
+
+/// Rectangular spreadsheet range such as "B2:D100", stored as 0-based (row, column)
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct CellRange {
+    pub start: (usize, usize),
+    pub end: (usize, usize),
+}
+
+impl CellRange {
+    pub fn contains(&self, row: usize, column: usize) -> bool {
+        (self.start.0..=self.end.0).contains(&row) && (self.start.1..=self.end.1).contains(&column)
+    }
+}
+
+impl FromStr for CellRange {
+    type Err = BenfError;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let (first, last) = s.split_once(':').unwrap_or((s, s));
+        let first = parse_cell(first)?;
+        let last = parse_cell(last)?;
+
+        Ok(CellRange {
+            start: (first.0.min(last.0), first.1.min(last.1)),
+            end: (first.0.max(last.0), first.1.max(last.1)),
+        })
+    }
+}
+
+/// Parse an A1-style cell reference into 0-based (row, column)
+fn parse_cell(cell: &str) -> Result<(usize, usize)> {
+    let cell = cell.trim().to_ascii_uppercase();
+    let split = cell
+        .find(|c: char| c.is_ascii_digit())
+        .unwrap_or(cell.len());
+    let (letters, digits) = cell.split_at(split);
+
+    let invalid = || BenfError::InvalidInput(format!("invalid cell reference: {cell}"));
+    if letters.is_empty() || !letters.chars().all(|c| c.is_ascii_uppercase()) {
+        return Err(invalid());
+    }
+    let row: usize = digits.parse().map_err(|_| invalid())?;
+    if row == 0 {
+        return Err(invalid());
+    }
+    let column = letters
+        .bytes()
+        .try_fold(0usize, |acc, b| {
+            acc.checked_mul(26)?.checked_add((b - b'A') as usize + 1)
+        })
+        .ok_or_else(invalid)?;
+
+    Ok((row - 1, column - 1))
+}
+
+/// How input is read: column, sheet and range restrictions for tabular inputs
+/// (CSV/TSV, Excel, ODS), node selection for XML, path and key selection for
+/// JSON/YAML/TOML, the SQL query for SQLite, the category to group numbers by and the
+/// locale numbers are written in
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct InputOptions {
+    pub column: Option<ColumnSelector>,
+    pub sheet: Option<String>,
+    pub range: Option<CellRange>,
+    pub xpath: Option<String>, // XPath-like node selection for XML inputs
+    pub keys: KeySelection,    // JSONPath and key regexes for JSON/YAML/TOML inputs
+    pub query: Option<String>, // SQL query for SQLite inputs
+    pub group_by: Option<ColumnSelector>, // Category column (CSV/TSV, Excel) or key (JSON)
+    pub locale: NumberLocale,
+}
+
+impl InputOptions {
+    /// True when a column, sheet, range, XML node, document path, SQL query or group column
+    /// is selected; otherwise every number is read as plain text
+    pub fn has_selection(&self) -> bool {
+        self.has_tabular_selection()
+            || self.xpath.is_some()
+            || self.keys.is_active()
+            || self.query.is_some()
+            || self.group_by.is_some()
+    }
+
+    /// True when a column, sheet or range is restricted
+    pub fn has_tabular_selection(&self) -> bool {
+        self.column.is_some() || self.sheet.is_some() || self.range.is_some()
+    }
+
+    /// Fail when a column, sheet or range is given for a non-tabular input
+    pub fn ensure_no_tabular_options(&self) -> Result<()> {
+        if self.has_tabular_selection() {
+            return Err(BenfError::InvalidInput(
+                "column, sheet and range selection only apply to CSV/TSV and spreadsheets"
+                    .to_string(),
+            ));
+        }
+        Ok(())
+    }
+
+    /// Fail when a spreadsheet-only restriction is given for a CSV/TSV input
+    pub fn ensure_no_spreadsheet_options(&self) -> Result<()> {
+        if self.sheet.is_some() || self.range.is_some() {
+            return Err(BenfError::InvalidInput(
+                "sheet and range selection only apply to spreadsheets".to_string(),
+            ));
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_column_selector_parsing() {
+        assert_eq!(
+            "3".parse::<ColumnSelector>().unwrap(),
+            ColumnSelector::Index(NonZeroUsize::new(3).unwrap())
+        );
+        assert_eq!(
+            "amount".parse::<ColumnSelector>().unwrap(),
+            ColumnSelector::Header("amount".to_string())
+        );
+        assert!("0".parse::<ColumnSelector>().is_err());
+        assert!(ColumnSelector::Header("Amount".to_string()).matches_header(" amount "));
+    }
+
+    #[test]
+    fn test_cell_range_parsing() {
+        let range: CellRange = "B2:D100".parse().unwrap();
+        assert_eq!(range.start, (1, 1));
+        assert_eq!(range.end, (99, 3));
+        assert!(range.contains(1, 1));
+        assert!(!range.contains(0, 1));
+
+        let single: CellRange = "aa10".parse().unwrap();
+        assert_eq!(single.start, (9, 26));
+        assert_eq!(single.start, single.end);
+
+        assert!("B0:C3".parse::<CellRange>().is_err());
+        assert!(format!("{}1", "Z".repeat(20)).parse::<CellRange>().is_err());
+        assert!("12".parse::<CellRange>().is_err());
+    }
+}
@@ -1,18 +1,72 @@
-use super::file_detector::{detect_file_format, parse_file_by_format};
-use crate::common::international::extract_numbers_international;
+use super::archive::{is_packed, parse_archive_members_with};
+use super::file_detector::{
+    accumulate_file_by_format_with, detect_file_format, parse_file_by_format,
+};
+use super::formats::csv::parse_csv_records_with;
+use super::formats::json_xml::{
+    parse_json_records_with, parse_xml_records_with, parse_yaml_records_with,
+};
+use super::options::InputOptions;
+use super::provenance::{record_values, records_from_text, NumberRecord, Provenance};
+use crate::common::international::NumberLocale;
+use crate::common::memory::BatchAccumulator;
 use std::path::Path;
 
 /// Extract numbers from text input
 use crate::error::Result;
 
 pub fn parse_text_input(text: &str) -> Result<Vec<f64>> {
-    let numbers = extract_numbers_international(text);
+    record_values(parse_text_records(text)?)
+}
 
-    if numbers.is_empty() {
-        return Err(crate::error::BenfError::NoNumbersFound);
+/// Like `parse_text_input`, reading numbers in the chosen locale and column
+pub fn parse_text_input_with(text: &str, options: &InputOptions) -> Result<Vec<f64>> {
+    record_values(parse_text_records_with(text, options)?)
+}
+
+/// Extract numbers from text input, keeping the line each number came from
+pub fn parse_text_records(text: &str) -> Result<Vec<NumberRecord>> {
+    parse_text_records_with(text, &InputOptions::default())
+}
+
+/// Extract numbers from text input in the chosen locale, reading it as XML when an XPath
+/// is given, as JSON (or YAML) when a path or key regex is given, as CSV when a column
+/// is selected and as JSON or else CSV when only a group column is given
+pub fn parse_text_records_with(text: &str, options: &InputOptions) -> Result<Vec<NumberRecord>> {
+    if options.query.is_some() {
+        Err(crate::error::BenfError::InvalidInput(
+            "SQL queries need a SQLite database file, not text input".to_string(),
+        ))
+    } else if options.xpath.is_some() {
+        parse_xml_records_with(text, options)
+    } else if options.keys.is_active() {
+        parse_json_records_with(text, options).or_else(|_| parse_yaml_records_with(text, options))
+    } else if options.has_tabular_selection() {
+        parse_csv_records_with(text, options)
+    } else if options.group_by.is_some() {
+        parse_json_records_with(text, options).or_else(|_| parse_csv_records_with(text, options))
+    } else {
+        let lines = text
+            .lines()
+            .enumerate()
+            .map(|(index, line)| parse_line_records_with(line, index + 1, options.locale))
+            .collect::<Result<Vec<_>>>()?;
+        Ok(lines.into_iter().flatten().collect())
     }
+}
 
-    Ok(numbers)
+/// Extract numbers from a single line of text
+pub fn parse_line_records(line: &str, line_number: usize) -> Result<Vec<NumberRecord>> {
+    parse_line_records_with(line, line_number, NumberLocale::default())
+}
+
+/// Extract numbers written in `locale` from a single line of text
+pub fn parse_line_records_with(
+    line: &str,
+    line_number: usize,
+    locale: NumberLocale,
+) -> Result<Vec<NumberRecord>> {
+    records_from_text(line, &Provenance::line(line_number), locale)
 }
 
 /// Parse any supported file format and extract numbers
@@ -25,6 +79,11 @@ pub fn parse_file_input(file_path: &Path) -> Result<Vec<f64>> {
         )));
     }
 
+    // Compressed files and archives go through their members
+    if is_packed(file_path) {
+        return record_values(parse_file_records(file_path)?);
+    }
+
     // Detect file format
     let format = detect_file_format(file_path);
 
@@ -32,6 +91,38 @@ pub fn parse_file_input(file_path: &Path) -> Result<Vec<f64>> {
     parse_file_by_format(file_path, &format)
 }
 
+/// Parse any supported file format, keeping where each number came from
+///
+/// Every record carries the file path plus the format-specific location
+/// (line and column, sheet and cell, page, paragraph or JSON pointer).
+/// Compressed files are decompressed and the members of ZIP and tar archives are
+/// pooled, each record naming its member as `archive.zip:member.csv`.
+pub fn parse_file_records(file_path: &Path) -> Result<Vec<NumberRecord>> {
+    parse_file_records_with(file_path, &InputOptions::default())
+}
+
+/// Like `parse_file_records`, reading only the selected column, sheet and range
+pub fn parse_file_records_with(
+    file_path: &Path,
+    options: &InputOptions,
+) -> Result<Vec<NumberRecord>> {
+    if !file_path.exists() {
+        return Err(crate::error::BenfError::FileError(format!(
+            "File not found: {}",
+            file_path.display()
+        )));
+    }
+
+    let records: Vec<NumberRecord> = parse_archive_members_with(file_path, options)?
+        .into_iter()
+        .flat_map(|member| member.records)
+        .collect();
+    if records.is_empty() {
+        return Err(crate::error::BenfError::NoNumbersFound);
+    }
+    Ok(records)
+}
+
 /// Parse input that could be either a file path or text content
 pub fn parse_input_auto(input: &str) -> Result<Vec<f64>> {
     let path = Path::new(input);
@@ -44,3 +135,59 @@ pub fn parse_input_auto(input: &str) -> Result<Vec<f64>> {
         parse_text_input(input)
     }
 }
+
+/// Like `parse_input_auto`, reading only the selected column, sheet and range
+pub fn parse_input_auto_with(input: &str, options: &InputOptions) -> Result<Vec<f64>> {
+    record_values(parse_input_records_auto_with(input, options)?)
+}
+
+/// Like `parse_input_auto_with`, feeding the numbers into `accumulator` instead of returning
+/// them
+///
+/// Parquet and Arrow files are read one record batch at a time (see
+/// `accumulate_file_by_format_with`); archives, other files and text are parsed whole.
+/// Returns how many numbers were added.
+pub fn accumulate_input_auto_with<A: BatchAccumulator>(
+    input: &str,
+    options: &InputOptions,
+    accumulator: &mut A,
+) -> Result<usize> {
+    let path = Path::new(input);
+
+    let count = if path.is_file() && !is_packed(path) {
+        accumulate_file_by_format_with(path, &detect_file_format(path), options, accumulator)?
+    } else {
+        let values = parse_input_auto_with(input, options)?;
+        accumulator.add_batch(&values);
+        values.len()
+    };
+    if count == 0 {
+        return Err(crate::error::BenfError::NoNumbersFound);
+    }
+    Ok(count)
+}
+
+/// Like `parse_input_auto`, but keeps where each number came from
+pub fn parse_input_records_auto(input: &str) -> Result<Vec<NumberRecord>> {
+    parse_input_records_auto_with(input, &InputOptions::default())
+}
+
+/// Like `parse_input_records_auto`, reading only the selected column, sheet and range
+///
+/// Text that is not a file path is read as CSV when a column is selected.
+pub fn parse_input_records_auto_with(
+    input: &str,
+    options: &InputOptions,
+) -> Result<Vec<NumberRecord>> {
+    let path = Path::new(input);
+
+    if path.exists() {
+        parse_file_records_with(path, options)
+    } else {
+        let records = parse_text_records_with(input, options)?;
+        if records.is_empty() {
+            return Err(crate::error::BenfError::NoNumbersFound);
+        }
+        Ok(records)
+    }
+}
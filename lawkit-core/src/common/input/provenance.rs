@@ -0,0 +1,206 @@
+use crate::common::international::NumberLocale;
+use serde::Serialize;
+
+/// Where a number was read from
+///
+/// Each parser fills in the fields that make sense for its format; the rest stay `None`.
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct Provenance {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub file: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub line: Option<usize>, // 1-based line (within the page for PDF)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub table: Option<String>, // SQLite table
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub row: Option<usize>, // 1-based row for Parquet/Arrow and SQLite result sets
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub column: Option<usize>, // 1-based field index for CSV/TSV, Parquet/Arrow and SQLite
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub header: Option<String>, // CSV/TSV header or Parquet/Arrow/SQLite column name
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sheet: Option<String>, // Spreadsheet sheet name
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cell: Option<String>, // Spreadsheet cell reference, e.g. "B12"
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub page: Option<usize>, // 1-based PDF page or PowerPoint slide
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub paragraph: Option<usize>, // 1-based paragraph for Word/ODT documents
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub json_pointer: Option<String>, // RFC 6901 pointer for JSON/YAML/TOML
+}
+
+impl Provenance {
+    pub fn line(line: usize) -> Self {
+        Provenance {
+            line: Some(line),
+            ..Default::default()
+        }
+    }
+
+    pub fn json_pointer(pointer: String) -> Self {
+        Provenance {
+            json_pointer: Some(pointer),
+            ..Default::default()
+        }
+    }
+
+    /// True when no location is known
+    pub fn is_empty(&self) -> bool {
+        *self == Provenance::default()
+    }
+}
+
+impl std::fmt::Display for Provenance {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let mut parts = Vec::new();
+        if let Some(file) = &self.file {
+            parts.push(file.clone());
+        }
+        if let Some(sheet) = &self.sheet {
+            parts.push(format!("sheet {sheet}"));
+        }
+        if let Some(table) = &self.table {
+            parts.push(format!("table {table}"));
+        }
+        if let Some(cell) = &self.cell {
+            parts.push(format!("cell {cell}"));
+        }
+        if let Some(page) = self.page {
+            parts.push(format!("page {page}"));
+        }
+        if let Some(paragraph) = self.paragraph {
+            parts.push(format!("paragraph {paragraph}"));
+        }
+        if let Some(line) = self.line {
+            parts.push(format!("line {line}"));
+        }
+        if let Some(row) = self.row {
+            parts.push(format!("row {row}"));
+        }
+        match (self.column, &self.header) {
+            (Some(column), Some(header)) => parts.push(format!("column {column} ({header})")),
+            (Some(column), None) => parts.push(format!("column {column}")),
+            (None, Some(header)) => parts.push(format!("column {header}")),
+            (None, None) => {}
+        }
+        if let Some(pointer) = &self.json_pointer {
+            parts.push(pointer.clone());
+        }
+
+        if parts.is_empty() {
+            write!(f, "-")
+        } else {
+            write!(f, "{}", parts.join(", "))
+        }
+    }
+}
+
+/// A number extracted from the input together with where it came from
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct NumberRecord {
+    pub value: f64,
+    #[serde(flatten)]
+    pub source: Provenance,
+    pub text: String, // Raw line, field or cell the value came from
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub group: Option<String>, // Category of the row or object (see `InputOptions::group_by`)
+}
+
+impl NumberRecord {
+    pub fn new(value: f64, source: Provenance, text: impl Into<String>) -> Self {
+        NumberRecord {
+            value,
+            source,
+            text: text.into(),
+            group: None,
+        }
+    }
+
+    /// Tag the record with the category it belongs to
+    pub fn with_group(mut self, group: Option<String>) -> Self {
+        self.group = group;
+        self
+    }
+}
+
+/// Numbers from `text` written in `locale`, each tagged with a copy of `source`
+pub fn records_from_text(
+    text: &str,
+    source: &Provenance,
+    locale: NumberLocale,
+) -> crate::error::Result<Vec<NumberRecord>> {
+    Ok(
+        crate::common::international::extract_numbers_international_with(text, locale)?
+            .into_iter()
+            .map(|value| NumberRecord::new(value, source.clone(), text.trim()))
+            .collect(),
+    )
+}
+
+/// Drop the provenance, failing with `NoNumbersFound` when nothing was extracted
+pub fn record_values(records: Vec<NumberRecord>) -> crate::error::Result<Vec<f64>> {
+    if records.is_empty() {
+        return Err(crate::error::BenfError::NoNumbersFound);
+    }
+    Ok(records.into_iter().map(|record| record.value).collect())
+}
+
+/// Append a key or index to an RFC 6901 JSON pointer
+pub fn json_pointer_child(pointer: &str, token: &str) -> String {
+    format!("{pointer}/{}", token.replace('~', "~0").replace('/', "~1"))
+}
+
+/// Spreadsheet cell reference for 0-based row and column, e.g. (11, 1) → "B12"
+pub fn cell_reference(row: usize, column: usize) -> String {
+    let mut letters = Vec::new();
+    let mut remaining = column + 1;
+    while remaining > 0 {
+        let offset = (remaining - 1) % 26;
+        letters.push((b'A' + offset as u8) as char);
+        remaining = (remaining - 1) / 26;
+    }
+    let letters: String = letters.into_iter().rev().collect();
+    format!("{letters}{}", row + 1)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cell_reference() {
+        assert_eq!(cell_reference(0, 0), "A1");
+        assert_eq!(cell_reference(11, 1), "B12");
+        assert_eq!(cell_reference(0, 25), "Z1");
+        assert_eq!(cell_reference(0, 26), "AA1");
+        assert_eq!(cell_reference(4, 701), "ZZ5");
+    }
+
+    #[test]
+    fn test_json_pointer_escaping() {
+        assert_eq!(json_pointer_child("", "items"), "/items");
+        assert_eq!(json_pointer_child("/items", "0"), "/items/0");
+        assert_eq!(json_pointer_child("", "a/b~c"), "/a~1b~0c");
+    }
+
+    #[test]
+    fn test_provenance_display() {
+        let source = Provenance {
+            file: Some("ledger.csv".to_string()),
+            line: Some(4),
+            column: Some(2),
+            header: Some("amount".to_string()),
+            ..Default::default()
+        };
+        assert_eq!(source.to_string(), "ledger.csv, line 4, column 2 (amount)");
+
+        let cell = Provenance {
+            sheet: Some("Sales".to_string()),
+            cell: Some("B12".to_string()),
+            ..Default::default()
+        };
+        assert_eq!(cell.to_string(), "sheet Sales, cell B12");
+        assert_eq!(Provenance::default().to_string(), "-");
+    }
+}
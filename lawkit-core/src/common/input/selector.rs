@@ -0,0 +1,380 @@
+use crate::common::input::provenance::json_pointer_child;
+use crate::error::{BenfError, Result};
+use regex::Regex;
+use serde_json::Value;
+use std::fmt;
+use std::str::FromStr;
+
+/// One step from a document root to a value: an object key or an array index
+#[derive(Debug, Clone, PartialEq)]
+pub enum PathSegment {
+    Key(String),
+    Index(usize),
+}
+
+/// JSON pointer (RFC 6901) for a path, e.g. [invoices, 0, total] → "/invoices/0/total"
+pub fn json_pointer(path: &[PathSegment]) -> String {
+    path.iter()
+        .fold(String::new(), |pointer, segment| match segment {
+            PathSegment::Key(key) => json_pointer_child(&pointer, key),
+            PathSegment::Index(index) => json_pointer_child(&pointer, &index.to_string()),
+        })
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum PathStep {
+    Name(String), // `.name` or `['name']`; a numeric name also indexes arrays
+    Index(usize), // `[n]`
+    Wildcard,     // `.*` or `[*]`
+    Descendant,   // `..`: any number of segments
+}
+
+/// JSONPath-style selector such as `$.invoices[*].total`
+///
+/// Supports `$`, `.name`, `['name']`, `[n]`, `[*]`, `.*` and `..` (recursive descent).
+/// The leading `$` is optional, so dotted paths like `invoices.total` work too. A name
+/// step applied to an array applies to every element, so `$.invoices.total` also reads
+/// the `total` of each invoice.
+#[derive(Debug, Clone, PartialEq)]
+pub struct JsonPath {
+    expression: String,
+    steps: Vec<PathStep>,
+}
+
+impl JsonPath {
+    pub fn parse(expression: &str) -> Result<Self> {
+        let trimmed = expression.trim();
+        let invalid =
+            |reason: &str| BenfError::InvalidInput(format!("invalid path '{trimmed}': {reason}"));
+
+        let normalized = match trimmed.strip_prefix('$') {
+            Some(rest) => rest.to_string(),
+            None if trimmed.is_empty() => return Err(invalid("path is empty")),
+            None if trimmed.starts_with(['.', '[']) => trimmed.to_string(),
+            None => format!(".{trimmed}"), // Dotted path such as `invoices.total`
+        };
+
+        let mut rest = normalized.as_str();
+        let mut steps = Vec::new();
+        while !rest.is_empty() {
+            if let Some(after) = rest.strip_prefix("..") {
+                if after.is_empty() {
+                    return Err(invalid("'..' must be followed by a name"));
+                }
+                steps.push(PathStep::Descendant);
+                // Keep one '.' so the name that follows parses as a `.name` step
+                rest = if after.starts_with('[') {
+                    after
+                } else {
+                    &rest[1..]
+                };
+            } else if let Some(after) = rest.strip_prefix('.') {
+                let end = after.find(['.', '[']).unwrap_or(after.len());
+                let name = after[..end].trim();
+                if name.is_empty() {
+                    return Err(invalid("empty name"));
+                }
+                steps.push(match name {
+                    "*" => PathStep::Wildcard,
+                    name => PathStep::Name(name.to_string()),
+                });
+                rest = &after[end..];
+            } else if let Some(after) = rest.strip_prefix('[') {
+                let (step, remaining) =
+                    parse_bracket(after).ok_or_else(|| invalid("expected [n], [*] or ['name']"))?;
+                steps.push(step);
+                rest = remaining;
+            } else {
+                return Err(invalid("expected '.' or '['"));
+            }
+        }
+
+        Ok(JsonPath {
+            expression: trimmed.to_string(),
+            steps,
+        })
+    }
+
+    /// True when the value at `path`, or one of its ancestors, is selected
+    pub fn selects(&self, path: &[PathSegment]) -> bool {
+        matches_prefix(&self.steps, path)
+    }
+}
+
+/// Parse the inside of `[...]`, returning the step and the text after `]`
+fn parse_bracket(text: &str) -> Option<(PathStep, &str)> {
+    if let Some(quote) = text.chars().next().filter(|c| *c == '\'' || *c == '"') {
+        let inner = &text[1..];
+        let end = inner.find(quote)?;
+        let after = inner[end + 1..].strip_prefix(']')?;
+        return Some((PathStep::Name(inner[..end].to_string()), after));
+    }
+
+    let end = text.find(']')?;
+    let step = match text[..end].trim() {
+        "*" => PathStep::Wildcard,
+        index => PathStep::Index(index.parse().ok()?),
+    };
+    Some((step, &text[end + 1..]))
+}
+
+fn matches_prefix(steps: &[PathStep], path: &[PathSegment]) -> bool {
+    let Some((step, rest)) = steps.split_first() else {
+        return true;
+    };
+    if *step == PathStep::Descendant {
+        return (0..=path.len()).any(|skip| matches_prefix(rest, &path[skip..]));
+    }
+    let Some((segment, tail)) = path.split_first() else {
+        return false;
+    };
+
+    match (step, segment) {
+        (PathStep::Wildcard, _) => matches_prefix(rest, tail),
+        (PathStep::Index(i), PathSegment::Index(j)) => i == j && matches_prefix(rest, tail),
+        (PathStep::Name(name), PathSegment::Key(key)) => name == key && matches_prefix(rest, tail),
+        (PathStep::Name(name), PathSegment::Index(j)) => match name.parse::<usize>() {
+            Ok(i) => i == *j && matches_prefix(rest, tail),
+            // Arrays are traversed transparently by name steps
+            Err(_) => matches_prefix(steps, tail),
+        },
+        _ => false,
+    }
+}
+
+impl FromStr for JsonPath {
+    type Err = BenfError;
+
+    fn from_str(s: &str) -> Result<Self> {
+        JsonPath::parse(s)
+    }
+}
+
+impl fmt::Display for JsonPath {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.expression)
+    }
+}
+
+/// Which values of a JSON, YAML or TOML document to read
+///
+/// A value is read when `path` selects it (or one of its ancestors), some key on its path
+/// matches `include_keys`, and no key on its path matches `exclude_keys`. Unset parts
+/// select everything.
+#[derive(Debug, Clone, Default)]
+pub struct KeySelection {
+    pub path: Option<JsonPath>,
+    pub include_keys: Option<Regex>,
+    pub exclude_keys: Option<Regex>,
+}
+
+impl KeySelection {
+    /// True when any of path, include or exclude is set
+    pub fn is_active(&self) -> bool {
+        self.path.is_some() || self.include_keys.is_some() || self.exclude_keys.is_some()
+    }
+
+    /// True when the value at `path` should be read
+    pub fn selects(&self, path: &[PathSegment]) -> bool {
+        let mut keys = path.iter().filter_map(|segment| match segment {
+            PathSegment::Key(key) => Some(key.as_str()),
+            PathSegment::Index(_) => None,
+        });
+
+        if let Some(exclude) = &self.exclude_keys {
+            if keys.clone().any(|key| exclude.is_match(key)) {
+                return false;
+            }
+        }
+        if let Some(include) = &self.include_keys {
+            if !keys.any(|key| include.is_match(key)) {
+                return false;
+            }
+        }
+        self.path
+            .as_ref()
+            .map_or(true, |json_path| json_path.selects(path))
+    }
+
+    /// True when everything below the key is excluded, so the subtree can be skipped
+    pub fn skips_key(&self, key: &str) -> bool {
+        self.exclude_keys
+            .as_ref()
+            .is_some_and(|exclude| exclude.is_match(key))
+    }
+
+    /// Call `visit` with each selected number and string in `value`, in document order
+    pub fn visit_json<'a>(
+        &self,
+        value: &'a Value,
+        visit: &mut impl FnMut(&[PathSegment], &'a Value),
+    ) {
+        self.visit_json_at(value, &mut Vec::new(), visit);
+    }
+
+    fn visit_json_at<'a>(
+        &self,
+        value: &'a Value,
+        path: &mut Vec<PathSegment>,
+        visit: &mut impl FnMut(&[PathSegment], &'a Value),
+    ) {
+        match value {
+            Value::Number(_) | Value::String(_) if self.selects(path) => visit(path, value),
+            Value::Array(arr) => {
+                for (index, item) in arr.iter().enumerate() {
+                    path.push(PathSegment::Index(index));
+                    self.visit_json_at(item, path, visit);
+                    path.pop();
+                }
+            }
+            Value::Object(obj) => {
+                for (key, child) in obj {
+                    if self.skips_key(key) {
+                        continue;
+                    }
+                    path.push(PathSegment::Key(key.clone()));
+                    self.visit_json_at(child, path, visit);
+                    path.pop();
+                }
+            }
+            _ => {} // Skip null, bool and unselected values
+        }
+    }
+}
+
+impl PartialEq for KeySelection {
+    fn eq(&self, other: &Self) -> bool {
+        let pattern = |regex: &Option<Regex>| regex.as_ref().map(|r| r.as_str().to_string());
+        self.path == other.path
+            && pattern(&self.include_keys) == pattern(&other.include_keys)
+            && pattern(&self.exclude_keys) == pattern(&other.exclude_keys)
+    }
+}
+
+/// Compile a key regex, reporting which option it came from
+pub fn parse_key_regex(pattern: &str, option: &str) -> Result<Regex> {
+    Regex::new(pattern)
+        .map_err(|e| BenfError::InvalidInput(format!("invalid {option} regex '{pattern}': {e}")))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn path(segments: &[&str]) -> Vec<PathSegment> {
+        segments
+            .iter()
+            .map(|s| match s.parse() {
+                Ok(index) => PathSegment::Index(index),
+                Err(_) => PathSegment::Key(s.to_string()),
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_json_path_parsing() {
+        let parsed = JsonPath::parse("$.invoices[*].total").unwrap();
+        assert_eq!(
+            parsed.steps,
+            vec![
+                PathStep::Name("invoices".to_string()),
+                PathStep::Wildcard,
+                PathStep::Name("total".to_string()),
+            ]
+        );
+        assert_eq!(parsed.to_string(), "$.invoices[*].total");
+
+        assert_eq!(
+            JsonPath::parse("invoices.total").unwrap().steps,
+            JsonPath::parse("$.invoices.total").unwrap().steps
+        );
+        assert_eq!(
+            JsonPath::parse("$..['unit price'][2]").unwrap().steps,
+            vec![
+                PathStep::Descendant,
+                PathStep::Name("unit price".to_string()),
+                PathStep::Index(2),
+            ]
+        );
+        assert_eq!(JsonPath::parse("$..total").unwrap().steps.len(), 2);
+        assert!(JsonPath::parse("$").unwrap().steps.is_empty());
+
+        for invalid in ["", "$.", "$..", "$[x]", "$['open", "$.a..", "$a"] {
+            assert!(JsonPath::parse(invalid).is_err(), "{invalid} should fail");
+        }
+    }
+
+    #[test]
+    fn test_json_path_matching() {
+        let totals = JsonPath::parse("$.invoices[*].total").unwrap();
+        assert!(totals.selects(&path(&["invoices", "0", "total"])));
+        assert!(totals.selects(&path(&["invoices", "3", "total", "net"])));
+        assert!(!totals.selects(&path(&["invoices", "0", "id"])));
+        assert!(!totals.selects(&path(&["refunds", "0", "total"])));
+
+        // Name steps look through arrays; numeric names index them
+        let dotted = JsonPath::parse("invoices.total").unwrap();
+        assert!(dotted.selects(&path(&["invoices", "1", "total"])));
+        let second = JsonPath::parse("invoices.1").unwrap();
+        assert!(second.selects(&path(&["invoices", "1", "total"])));
+        assert!(!second.selects(&path(&["invoices", "0", "total"])));
+
+        let anywhere = JsonPath::parse("$..amount").unwrap();
+        assert!(anywhere.selects(&path(&["amount"])));
+        assert!(anywhere.selects(&path(&["orders", "2", "lines", "0", "amount"])));
+        assert!(!anywhere.selects(&path(&["orders", "2", "quantity"])));
+    }
+
+    #[test]
+    fn test_key_selection() {
+        let selection = KeySelection {
+            path: Some(JsonPath::parse("$.orders").unwrap()),
+            include_keys: Some(Regex::new("^(total|amount)$").unwrap()),
+            exclude_keys: Some(Regex::new("^tax").unwrap()),
+        };
+        assert!(selection.is_active());
+        assert!(selection.selects(&path(&["orders", "0", "total"])));
+        assert!(selection.selects(&path(&["orders", "0", "amount", "0"])));
+        assert!(!selection.selects(&path(&["orders", "0", "quantity"])));
+        assert!(!selection.selects(&path(&["orders", "0", "tax", "amount"])));
+        assert!(!selection.selects(&path(&["summary", "total"])));
+        assert!(selection.skips_key("tax_rate"));
+
+        assert!(!KeySelection::default().is_active());
+        assert!(KeySelection::default().selects(&path(&["anything", "0"])));
+    }
+
+    #[test]
+    fn test_visit_json() {
+        let document = serde_json::json!({
+            "invoices": [
+                {"id": "A-1", "total": 120.5, "tax": {"total": 9.5}},
+                {"id": "A-2", "total": "340"}
+            ],
+            "count": 2
+        });
+        let selection = KeySelection {
+            path: Some(JsonPath::parse("$.invoices[*]").unwrap()),
+            exclude_keys: Some(Regex::new("^(id|tax)$").unwrap()),
+            ..Default::default()
+        };
+
+        let mut visited = Vec::new();
+        selection.visit_json(&document, &mut |path, value| {
+            visited.push((json_pointer(path), value.clone()));
+        });
+        assert_eq!(
+            visited,
+            vec![
+                ("/invoices/0/total".to_string(), serde_json::json!(120.5)),
+                ("/invoices/1/total".to_string(), serde_json::json!("340")),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_json_pointer_from_path() {
+        assert_eq!(json_pointer(&path(&["a/b", "0", "c~d"])), "/a~1b/0/c~0d");
+        assert_eq!(json_pointer(&[]), "");
+    }
+}
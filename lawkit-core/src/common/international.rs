@@ -1,6 +1,9 @@
 /// International numeral conversion module
 /// Supports Chinese, Hindi, and Arabic numerals
 // Note: regex is used for Chinese financial numerals in convert_chinese_numerals
+use regex::Regex;
+use std::sync::OnceLock;
+
 /// Convert international numerals to standard Arabic digits
 pub fn convert_international_numerals(text: &str) -> String {
     let mut result = text.to_string();
@@ -22,8 +25,6 @@ pub fn convert_international_numerals(text: &str) -> String {
 
 /// Convert Chinese numerals to Arabic numerals
 fn convert_chinese_numerals(text: &str) -> String {
-    use regex::Regex;
-
     // Traditional Chinese financial numerals for fraud prevention
     let financial_pattern = Regex::new(r"[壹貳參肆伍陸柒捌玖拾佰仟萬億]+").unwrap();
 
@@ -145,11 +146,233 @@ fn parse_chinese_financial_number(chinese: &str) -> Result<u64, String> {
 
 /// Extract numbers from text with international numeral support
 pub fn extract_numbers_international(text: &str) -> Vec<f64> {
-    // First convert all international numerals to standard Arabic digits
-    let converted = convert_international_numerals(text);
+    // First convert all international numerals to standard Arabic digits,
+    // then use the existing number extraction logic
+    crate::laws::benford::japanese::extract_numbers(&convert_international_numerals(text))
+}
+
+/// Extract numbers from text, reading separators and negatives the way `locale` writes them
+pub fn extract_numbers_international_with(
+    text: &str,
+    locale: NumberLocale,
+) -> crate::error::Result<Vec<f64>> {
+    match locale.separators() {
+        None => Ok(extract_numbers_international(text)),
+        Some(separators) => {
+            let converted = convert_international_numerals(text);
+            extract_localized_numbers(&converted, locale, &separators)
+        }
+    }
+}
+
+/// How numbers are written: decimal mark, digit grouping and negative notation
+///
+/// Every locale except `Plain` also reads accounting negatives `(1,234.00)`,
+/// trailing minus signs `1,234.00-` and currency symbols next to the number.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum NumberLocale {
+    /// `1234.56` with a leading minus only; separators split numbers (historical behavior)
+    #[default]
+    Plain,
+    /// `1,234,567.89` (en, ja, zh, ...)
+    En,
+    /// `1.234.567,89` (de, es, it, nl, pt, ...)
+    Eu,
+    /// `1 234 567,89` with space or no-break space grouping (fr, ru, pl, sv, ...)
+    Fr,
+    /// `1'234'567.89` (de-CH, fr-CH, it-CH)
+    Ch,
+    /// `12,34,567.89` lakh/crore grouping (en-IN, hi)
+    In,
+}
+
+/// Separator characters for a `NumberLocale`
+struct Separators {
+    decimal: char,
+    grouping: &'static [char],
+    lakh: bool, // Indian 2-digit groups before the last 3 digits
+}
+
+impl NumberLocale {
+    fn separators(self) -> Option<Separators> {
+        let (decimal, grouping, lakh): (char, &'static [char], bool) = match self {
+            NumberLocale::Plain => return None,
+            NumberLocale::En => ('.', &[','], false),
+            NumberLocale::Eu => (',', &['.'], false),
+            NumberLocale::Fr => (',', &[' ', '\u{00A0}', '\u{202F}'], false),
+            NumberLocale::Ch => ('.', &['\'', '\u{2019}'], false),
+            NumberLocale::In => ('.', &[','], true),
+        };
+        Some(Separators {
+            decimal,
+            grouping,
+            lakh,
+        })
+    }
+
+    pub fn name(self) -> &'static str {
+        match self {
+            NumberLocale::Plain => "plain",
+            NumberLocale::En => "en",
+            NumberLocale::Eu => "eu",
+            NumberLocale::Fr => "fr",
+            NumberLocale::Ch => "ch",
+            NumberLocale::In => "in",
+        }
+    }
+}
 
-    // Then use the existing number extraction logic
-    crate::laws::benford::japanese::extract_numbers(&converted)
+impl std::str::FromStr for NumberLocale {
+    type Err = crate::error::BenfError;
+
+    /// Accepts the variant names and common language or language-region tags (`de`, `de-CH`, `en_IN`)
+    fn from_str(s: &str) -> crate::error::Result<Self> {
+        let tag = s.trim().to_lowercase().replace('_', "-");
+        let by_tag = |tag: &str| match tag {
+            "plain" | "default" | "c" => Some(NumberLocale::Plain),
+            "en" | "us" | "gb" | "ja" | "zh" | "ko" | "th" | "he" | "en-us" | "en-gb" => {
+                Some(NumberLocale::En)
+            }
+            "eu" | "de" | "es" | "it" | "nl" | "pt" | "da" | "id" | "tr" | "el" | "ro" | "hr"
+            | "sl" | "de-de" | "de-at" | "pt-br" | "es-es" | "it-it" | "nl-nl" => {
+                Some(NumberLocale::Eu)
+            }
+            "fr" | "ru" | "pl" | "cs" | "sk" | "sv" | "fi" | "nb" | "no" | "hu" | "bg" | "uk"
+            | "fr-fr" | "fr-ca" | "ru-ru" | "sv-se" => Some(NumberLocale::Fr),
+            "ch" | "swiss" | "de-ch" | "fr-ch" | "it-ch" | "rm-ch" => Some(NumberLocale::Ch),
+            "in" | "indian" | "hi" | "en-in" | "hi-in" => Some(NumberLocale::In),
+            _ => None,
+        };
+
+        by_tag(&tag)
+            .or_else(|| tag.split('-').next().and_then(by_tag))
+            .ok_or_else(|| {
+                crate::error::BenfError::InvalidInput(format!(
+                    "unknown locale '{s}' (use plain, en, eu, fr, ch, in or a language tag such as de-DE)"
+                ))
+            })
+    }
+}
+
+impl std::fmt::Display for NumberLocale {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.name())
+    }
+}
+
+/// Currency symbols and ISO 4217-style codes that may sit next to a number
+const CURRENCY_PATTERN: &str = r"(?:[$€£¥￥₹₩₽₺₫₪฿¢]|[A-Z]{3})";
+
+/// The number pattern for `locale`, compiled the first time the locale is used
+fn localized_number_pattern(
+    locale: NumberLocale,
+    separators: &Separators,
+) -> crate::error::Result<&'static Regex> {
+    // One slot per `NumberLocale` variant, indexed by discriminant
+    static PATTERNS: [OnceLock<Result<Regex, regex::Error>>; 6] = [
+        OnceLock::new(),
+        OnceLock::new(),
+        OnceLock::new(),
+        OnceLock::new(),
+        OnceLock::new(),
+        OnceLock::new(),
+    ];
+
+    PATTERNS[locale as usize]
+        .get_or_init(|| {
+            let grouping: String = separators
+                .grouping
+                .iter()
+                .map(|c| regex::escape(&c.to_string()))
+                .collect();
+            let decimal = regex::escape(&separators.decimal.to_string());
+            Regex::new(&format!(
+                r"(\()?(?:{CURRENCY_PATTERN}\s?)?([-−])?(?:{CURRENCY_PATTERN}\s?)?(\d+(?:[{grouping}]\d+)*(?:{decimal}\d+)?)(-)?(?:\s?{CURRENCY_PATTERN})?(\))?"
+            ))
+        })
+        .as_ref()
+        .map_err(|e| {
+            crate::error::BenfError::ParseError(format!(
+                "invalid number pattern for locale '{locale}': {e}"
+            ))
+        })
+}
+
+/// Extract numbers written with locale separators, accounting negatives and currency symbols
+fn extract_localized_numbers(
+    text: &str,
+    locale: NumberLocale,
+    separators: &Separators,
+) -> crate::error::Result<Vec<f64>> {
+    let number_pattern = localized_number_pattern(locale, separators)?;
+
+    let mut numbers = Vec::new();
+    for caps in number_pattern.captures_iter(text) {
+        let whole = caps.get(0).unwrap();
+        let before = text[..whole.start()].chars().next_back();
+        let after = text[whole.end()..].chars().next();
+
+        // A minus glued to a word or digit is a hyphen ("2023-01-01", "A-12"), not a sign
+        let leading_minus = caps.get(2).is_some() && !before.is_some_and(char::is_alphanumeric);
+        let trailing_minus = caps.get(4).is_some() && !after.is_some_and(char::is_alphanumeric);
+        let parenthesized = caps.get(1).is_some() && caps.get(5).is_some();
+        let negative = leading_minus || trailing_minus || parenthesized;
+
+        for value in parse_localized_number(&caps[3], separators) {
+            if value != 0.0 {
+                numbers.push(if negative { -value } else { value });
+            }
+        }
+    }
+
+    Ok(numbers)
+}
+
+/// Parse a run of digits and separators, splitting it when the grouping is not valid
+fn parse_localized_number(body: &str, separators: &Separators) -> Vec<f64> {
+    let (integer, fraction) = match body.rsplit_once(separators.decimal) {
+        Some((integer, fraction)) => (integer, Some(fraction)),
+        None => (body, None),
+    };
+    let groups: Vec<&str> = integer.split(separators.grouping).collect();
+
+    if valid_grouping(&groups, separators.lakh) {
+        let digits = match fraction {
+            Some(fraction) => format!("{}.{fraction}", groups.concat()),
+            None => groups.concat(),
+        };
+        return digits.parse().into_iter().collect();
+    }
+
+    // Not a grouped number: the separators delimit a list ("1,2,3")
+    let last = groups.len() - 1;
+    groups
+        .iter()
+        .enumerate()
+        .filter_map(|(index, group)| match fraction {
+            Some(fraction) if index == last => format!("{group}.{fraction}").parse().ok(),
+            _ => group.parse().ok(),
+        })
+        .collect()
+}
+
+/// Thousands grouping (1-3 digits, then groups of 3), or lakh grouping (1-2 digits, groups of 2, last group of 3)
+fn valid_grouping(groups: &[&str], lakh: bool) -> bool {
+    let Some((first, rest)) = groups.split_first() else {
+        return false;
+    };
+    if rest.is_empty() {
+        return true;
+    }
+
+    let thousands = (1..=3).contains(&first.len()) && rest.iter().all(|group| group.len() == 3);
+    let indian = lakh
+        && (1..=2).contains(&first.len())
+        && rest
+            .split_last()
+            .is_some_and(|(last, middle)| last.len() == 3 && middle.iter().all(|g| g.len() == 2));
+
+    thousands || indian
 }
 
 #[cfg(test)]
@@ -205,6 +428,113 @@ mod tests {
         assert!(numbers.contains(&123.0));
     }
 
+    #[test]
+    fn test_locale_separators() {
+        assert_eq!(
+            extract_numbers_international_with("Total: 1.234.567,89 EUR", NumberLocale::Eu)
+                .unwrap(),
+            vec![1234567.89]
+        );
+        assert_eq!(
+            extract_numbers_international_with("Betrag CHF 1'234.50", NumberLocale::Ch).unwrap(),
+            vec![1234.5]
+        );
+        assert_eq!(
+            extract_numbers_international_with("₹12,34,567.00", NumberLocale::In).unwrap(),
+            vec![1234567.0]
+        );
+        assert_eq!(
+            extract_numbers_international_with("1\u{202F}234\u{202F}567,5 €", NumberLocale::Fr)
+                .unwrap(),
+            vec![1234567.5]
+        );
+        assert_eq!(
+            extract_numbers_international_with("$1,234,567.89", NumberLocale::En).unwrap(),
+            vec![1234567.89]
+        );
+
+        // The historical default still splits on separators
+        assert_eq!(extract_numbers_international("1,234.5"), vec![1.0, 234.5]);
+    }
+
+    #[test]
+    fn test_locale_negative_notations() {
+        let en = NumberLocale::En;
+        assert_eq!(
+            extract_numbers_international_with("(1,234.00)", en).unwrap(),
+            vec![-1234.0]
+        );
+        assert_eq!(
+            extract_numbers_international_with("($1,234.00)", en).unwrap(),
+            vec![-1234.0]
+        );
+        assert_eq!(
+            extract_numbers_international_with("1,234.00- CR", en).unwrap(),
+            vec![-1234.0]
+        );
+        assert_eq!(
+            extract_numbers_international_with("-$500 and $-250", en).unwrap(),
+            vec![-500.0, -250.0]
+        );
+        assert_eq!(
+            extract_numbers_international_with("(1.234,56 €)", NumberLocale::Eu).unwrap(),
+            vec![-1234.56]
+        );
+
+        // Hyphens inside dates and codes are not signs
+        assert_eq!(
+            extract_numbers_international_with("2023-01-15 A-12", en).unwrap(),
+            vec![2023.0, 1.0, 15.0, 12.0]
+        );
+        // Parentheses around a note are not a negative when they do not enclose just the number
+        assert_eq!(
+            extract_numbers_international_with("(see note 3)", en).unwrap(),
+            vec![3.0]
+        );
+    }
+
+    #[test]
+    fn test_locale_invalid_grouping_splits() {
+        assert_eq!(
+            extract_numbers_international_with("1,2,3", NumberLocale::En).unwrap(),
+            vec![1.0, 2.0, 3.0]
+        );
+        assert_eq!(
+            extract_numbers_international_with("12,34,567", NumberLocale::En).unwrap(),
+            vec![12.0, 34.0, 567.0]
+        );
+        assert_eq!(
+            extract_numbers_international_with("12,34,567", NumberLocale::In).unwrap(),
+            vec![1234567.0]
+        );
+    }
+
+    #[test]
+    fn test_locale_patterns_compiled_once() {
+        for locale in [
+            NumberLocale::En,
+            NumberLocale::Eu,
+            NumberLocale::Fr,
+            NumberLocale::Ch,
+            NumberLocale::In,
+        ] {
+            let separators = locale.separators().unwrap();
+            let first = localized_number_pattern(locale, &separators).unwrap();
+            let second = localized_number_pattern(locale, &separators).unwrap();
+            assert!(std::ptr::eq(first, second), "{locale} pattern rebuilt");
+        }
+    }
+
+    #[test]
+    fn test_locale_from_str() {
+        assert_eq!("de-DE".parse::<NumberLocale>().unwrap(), NumberLocale::Eu);
+        assert_eq!("de_CH".parse::<NumberLocale>().unwrap(), NumberLocale::Ch);
+        assert_eq!("en-IN".parse::<NumberLocale>().unwrap(), NumberLocale::In);
+        assert_eq!("fr-BE".parse::<NumberLocale>().unwrap(), NumberLocale::Fr);
+        assert_eq!("EN".parse::<NumberLocale>().unwrap(), NumberLocale::En);
+        assert!("xx".parse::<NumberLocale>().is_err());
+    }
+
     #[test]
     fn test_pure_hindi_numerals() {
         // Test with pure Devanagari digits only
@@ -137,6 +137,37 @@ impl<T: Clone> Iterator for ChunkIterator<T> {
     }
 }
 
+/// バッチ単位で数値を受け取るインクリメンタル集計器
+///
+/// Parquet の行グループなど、ファイル全体を読み込まずに逐次集計する入力で使う。
+pub trait BatchAccumulator {
+    fn add_batch(&mut self, values: &[f64]);
+}
+
+impl BatchAccumulator for IncrementalStatistics {
+    fn add_batch(&mut self, values: &[f64]) {
+        IncrementalStatistics::add_batch(self, values);
+    }
+}
+
+impl BatchAccumulator for IncrementalBenford {
+    fn add_batch(&mut self, values: &[f64]) {
+        IncrementalBenford::add_batch(self, values);
+    }
+}
+
+impl BatchAccumulator for IncrementalPareto {
+    fn add_batch(&mut self, values: &[f64]) {
+        IncrementalPareto::add_batch(self, values);
+    }
+}
+
+impl BatchAccumulator for crate::laws::benford::FirstDigitCounts {
+    fn add_batch(&mut self, values: &[f64]) {
+        crate::laws::benford::FirstDigitCounts::add_batch(self, values);
+    }
+}
+
 /// メモリ効率的な統計計算
 #[derive(Debug, Clone)]
 pub struct IncrementalStatistics {
@@ -496,11 +527,16 @@ impl IncrementalPareto {
     }
 }
 
+/// 語彙成長曲線を記録するトークン数の間隔（前回の記録点に対する倍率）
+const VOCABULARY_CHECKPOINT_RATIO: f64 = 1.05;
+
 /// インクリメンタルジップ分析
 #[derive(Debug, Clone)]
 pub struct IncrementalZipf {
     frequency_map: std::collections::HashMap<String, usize>,
     total_count: usize,
+    vocabulary_growth: Vec<(usize, usize)>, // (トークン数, 語彙数)
+    next_checkpoint: usize,
 }
 
 impl Default for IncrementalZipf {
@@ -514,12 +550,17 @@ impl IncrementalZipf {
         Self {
             frequency_map: std::collections::HashMap::new(),
             total_count: 0,
+            vocabulary_growth: Vec::new(),
+            next_checkpoint: 1,
         }
     }
 
     pub fn add_word(&mut self, word: String) {
         *self.frequency_map.entry(word).or_insert(0) += 1;
         self.total_count += 1;
+        if self.total_count >= self.next_checkpoint {
+            self.record_vocabulary();
+        }
     }
 
     pub fn add_words(&mut self, words: &[String]) {
@@ -533,6 +574,29 @@ impl IncrementalZipf {
             *self.frequency_map.entry(word.clone()).or_insert(0) += count;
         }
         self.total_count += other.total_count;
+        // 結合後の語彙数は結合時点でしか分からないので、その1点だけを記録する
+        self.record_vocabulary();
+    }
+
+    /// 現在の (トークン数, 語彙数) を記録し、次の記録点をおよそ 5% 先に進める
+    fn record_vocabulary(&mut self) {
+        if self.vocabulary_growth.last().map(|&(tokens, _)| tokens) != Some(self.total_count) {
+            self.vocabulary_growth
+                .push((self.total_count, self.frequency_map.len()));
+        }
+        self.next_checkpoint = (self.total_count + 1)
+            .max((self.total_count as f64 * VOCABULARY_CHECKPOINT_RATIO).ceil() as usize);
+    }
+
+    /// 語彙成長曲線（トークン数の対数でほぼ等間隔の (トークン数, 語彙数)、最後は現在値）
+    pub fn vocabulary_growth(&self) -> Vec<(usize, usize)> {
+        let mut growth = self.vocabulary_growth.clone();
+        if self.total_count > 0
+            && growth.last().map(|&(tokens, _)| tokens) != Some(self.total_count)
+        {
+            growth.push((self.total_count, self.frequency_map.len()));
+        }
+        growth
     }
 
     pub fn get_sorted_frequencies(&self) -> Vec<(String, usize)> {
@@ -541,7 +605,7 @@ impl IncrementalZipf {
             .iter()
             .map(|(word, &count)| (word.clone(), count))
             .collect();
-        frequencies.sort_by(|a, b| b.1.cmp(&a.1));
+        frequencies.sort_by_key(|b| std::cmp::Reverse(b.1));
         frequencies
     }
 
@@ -799,9 +863,8 @@ where
 
     for word in data_iter {
         if let Some(chunk) = processor.push(word) {
-            let mut chunk_zipf = IncrementalZipf::new();
-            chunk_zipf.add_words(&chunk);
-            zipf.merge(&chunk_zipf);
+            // 出現順に追加して語彙成長曲線を正確に保つ
+            zipf.add_words(&chunk);
             chunks_processed += 1;
         }
     }
@@ -810,9 +873,7 @@ where
 
     if let Some(remaining) = processor.finish() {
         total_processed += remaining.len();
-        let mut chunk_zipf = IncrementalZipf::new();
-        chunk_zipf.add_words(&remaining);
-        zipf.merge(&chunk_zipf);
+        zipf.add_words(&remaining);
         chunks_processed += 1;
     }
 
@@ -1043,6 +1104,24 @@ mod tests {
                                             // Don't test exact mean since streaming might not process all items
     }
 
+    #[test]
+    fn test_incremental_zipf_vocabulary_growth() {
+        let mut zipf = IncrementalZipf::new();
+        for i in 0..1000 {
+            zipf.add_word(format!("w{}", i % 100));
+        }
+        let growth = zipf.vocabulary_growth();
+        assert_eq!(growth.first(), Some(&(1, 1)));
+        assert_eq!(growth.last(), Some(&(1000, 100)));
+        assert!(growth
+            .windows(2)
+            .all(|w| w[0].0 < w[1].0 && w[0].1 <= w[1].1));
+        // 対数間隔なので全トークン数よりずっと少ない点数
+        assert!(growth.len() < 100);
+        // 先頭 100 トークンまでは語彙数 = トークン数
+        assert!(growth.iter().filter(|p| p.0 <= 100).all(|p| p.0 == p.1));
+    }
+
     #[test]
     fn test_resource_monitor() {
         let mut monitor = ResourceMonitor::new();
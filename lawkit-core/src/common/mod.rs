@@ -1,4 +1,7 @@
+pub mod batch;
+pub mod diff;
 pub mod filtering;
+pub mod grouping;
 pub mod input;
 pub mod international;
 pub mod memory;
@@ -1,3 +1,4 @@
+use crate::common::input::NumberRecord;
 use crate::error::Result;
 use std::collections::HashMap;
 
@@ -25,6 +26,7 @@ pub struct AdvancedOutlierInfo {
     pub confidence: f64,
     pub method_scores: HashMap<String, f64>,
     pub is_outlier: bool,
+    pub record: Option<NumberRecord>, // 入力中の出所（with_records で付与）
 }
 
 /// 高度な異常値検出結果
@@ -37,6 +39,16 @@ pub struct AdvancedOutlierResult {
     pub method_params: HashMap<String, f64>,
 }
 
+impl AdvancedOutlierResult {
+    /// 検出に使った数値と同じ順序のレコードから、各異常値の出所を付与
+    pub fn with_records(mut self, records: &[NumberRecord]) -> Self {
+        for outlier in &mut self.outliers {
+            outlier.record = records.get(outlier.index).cloned();
+        }
+        self
+    }
+}
+
 /// LOF (Local Outlier Factor) による異常値検出
 pub fn detect_outliers_lof(numbers: &[f64], k: usize) -> Result<AdvancedOutlierResult> {
     if numbers.len() < k + 1 {
@@ -94,6 +106,7 @@ pub fn detect_outliers_lof(numbers: &[f64], k: usize) -> Result<AdvancedOutlierR
                         scores
                     },
                     is_outlier: true,
+                    record: None,
                 });
             }
         }
@@ -144,6 +157,7 @@ pub fn detect_outliers_isolation(
                     scores
                 },
                 is_outlier: true,
+                record: None,
             });
         }
     }
@@ -231,6 +245,7 @@ pub fn detect_outliers_dbscan(
                     scores
                 },
                 is_outlier: true,
+                record: None,
             });
         }
     }
@@ -322,6 +337,7 @@ pub fn detect_outliers_ensemble(numbers: &[f64]) -> Result<AdvancedOutlierResult
                     scores
                 },
                 is_outlier: true,
+                record: None,
             });
         }
     }
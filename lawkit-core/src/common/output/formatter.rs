@@ -1,5 +1,10 @@
-use crate::laws::benford::BenfordResult;
+use crate::laws::{
+    benford::BenfordResult, integration::IntegrationResult, normal::NormalResult,
+    pareto::ParetoResult, poisson::PoissonResult, zipf::ZipfResult,
+};
 use clap::ArgMatches; // clap::ArgMatches をインポート
+use serde::Serialize;
+use serde_json::Value;
 use std::io::{self, Write}; // io::Write をインポート
 
 #[derive(Debug, Clone)]
@@ -66,18 +71,239 @@ pub fn create_output_writer(matches: &ArgMatches) -> crate::error::Result<Box<dy
     }
 }
 
-pub fn format_output(result: &BenfordResult, format: &OutputFormat) -> String {
+/// A law result that `format_output` can render in every `OutputFormat`
+///
+/// Structured formats all encode the same document, the serde serialization of the
+/// result, so their schema follows the result's fields: JSON, YAML and TOML keep the
+/// nesting, CSV has one `field,value` row per leaf (dotted paths, array indices as
+/// segments) and XML nests one element per field under `ROOT`.
+pub trait FormattableResult: Serialize {
+    /// Heading of the text report
+    const TITLE: &'static str;
+    /// Root element of the XML document
+    const ROOT: &'static str;
+
+    /// Human-readable report; one `field: value` line per leaf unless overridden
+    fn format_text(&self) -> crate::error::Result<String> {
+        let mut output = format!("{}\n\n", Self::TITLE);
+        for (field, value) in flatten_document(&to_document(self)?) {
+            output.push_str(&format!("{field}: {value}\n"));
+        }
+        Ok(output)
+    }
+
+    /// CSV report; one `field,value` row per leaf unless overridden
+    fn format_csv(&self) -> crate::error::Result<String> {
+        format_flattened_csv(&to_document(self)?)
+    }
+}
+
+impl FormattableResult for BenfordResult {
+    const TITLE: &'static str = "Benford's Law Analysis Results";
+    const ROOT: &'static str = "benford_result";
+
+    fn format_text(&self) -> crate::error::Result<String> {
+        Ok(format_benford_text(self))
+    }
+}
+
+impl FormattableResult for ParetoResult {
+    const TITLE: &'static str = "Pareto Principle Analysis Results";
+    const ROOT: &'static str = "pareto_result";
+}
+
+impl FormattableResult for ZipfResult {
+    const TITLE: &'static str = "Zipf's Law Analysis Results";
+    const ROOT: &'static str = "zipf_result";
+}
+
+impl FormattableResult for NormalResult {
+    const TITLE: &'static str = "Normal Distribution Analysis Results";
+    const ROOT: &'static str = "normal_result";
+}
+
+impl FormattableResult for PoissonResult {
+    const TITLE: &'static str = "Poisson Distribution Analysis Results";
+    const ROOT: &'static str = "poisson_result";
+}
+
+impl FormattableResult for IntegrationResult {
+    const TITLE: &'static str = "Integrated Analysis Results";
+    const ROOT: &'static str = "integration_result";
+}
+
+/// Render a law result as text, JSON, CSV, YAML, TOML or XML
+pub fn format_output<R: FormattableResult>(
+    result: &R,
+    format: &OutputFormat,
+) -> crate::error::Result<String> {
     match format {
-        OutputFormat::Text => format_text(result),
-        OutputFormat::Json => format_json(result),
-        OutputFormat::Csv => format_csv(result),
-        OutputFormat::Yaml => format_yaml(result),
-        OutputFormat::Toml => format_toml(result),
-        OutputFormat::Xml => format_xml(result),
+        OutputFormat::Text => result.format_text(),
+        OutputFormat::Json => Ok(serde_json::to_string_pretty(&to_document(result)?)?),
+        OutputFormat::Csv => result.format_csv(),
+        OutputFormat::Yaml => serde_yaml::to_string(&to_document(result)?)
+            .map_err(|e| crate::error::BenfError::SerializationError(e.to_string())),
+        OutputFormat::Toml => toml::to_string_pretty(&without_nulls(to_document(result)?))
+            .map_err(|e| crate::error::BenfError::SerializationError(e.to_string())),
+        OutputFormat::Xml => Ok(format_xml(R::ROOT, &to_document(result)?)),
+    }
+}
+
+/// The document every structured format encodes (non-finite numbers become null)
+fn to_document<R: Serialize + ?Sized>(result: &R) -> crate::error::Result<Value> {
+    Ok(serde_json::to_value(result)?)
+}
+
+/// Leaf values keyed by dotted path, in document order (null and empty containers are empty)
+pub fn flatten_document(document: &Value) -> Vec<(String, String)> {
+    fn walk(path: &str, value: &Value, rows: &mut Vec<(String, String)>) {
+        let child = |segment: &str| {
+            if path.is_empty() {
+                segment.to_string()
+            } else {
+                format!("{path}.{segment}")
+            }
+        };
+        match value {
+            // Keep empty containers as empty rows so every field of the schema appears
+            Value::Object(map) if map.is_empty() => rows.push((path.to_string(), String::new())),
+            Value::Array(items) if items.is_empty() => rows.push((path.to_string(), String::new())),
+            Value::Object(map) => {
+                for (key, value) in map {
+                    walk(&child(key), value, rows);
+                }
+            }
+            Value::Array(items) => {
+                for (index, item) in items.iter().enumerate() {
+                    walk(&child(&index.to_string()), item, rows);
+                }
+            }
+            leaf => rows.push((path.to_string(), leaf_text(leaf))),
+        }
+    }
+
+    let mut rows = Vec::new();
+    walk("", document, &mut rows);
+    rows
+}
+
+fn leaf_text(value: &Value) -> String {
+    match value {
+        Value::Null => String::new(),
+        Value::String(text) => text.clone(),
+        other => other.to_string(),
+    }
+}
+
+fn format_flattened_csv(document: &Value) -> crate::error::Result<String> {
+    let mut writer = csv::Writer::from_writer(Vec::new());
+    let serialization_error =
+        |e: csv::Error| crate::error::BenfError::SerializationError(e.to_string());
+
+    writer
+        .write_record(["field", "value"])
+        .map_err(serialization_error)?;
+    for (field, value) in flatten_document(document) {
+        writer
+            .write_record([field, value])
+            .map_err(serialization_error)?;
+    }
+
+    let bytes = writer
+        .into_inner()
+        .map_err(|e| crate::error::BenfError::SerializationError(e.to_string()))?;
+    String::from_utf8(bytes).map_err(|e| crate::error::BenfError::SerializationError(e.to_string()))
+}
+
+/// TOML has no null, so drop absent values (`Option::None`, non-finite numbers)
+fn without_nulls(value: Value) -> Value {
+    match value {
+        Value::Object(map) => Value::Object(
+            map.into_iter()
+                .filter(|(_, value)| !value.is_null())
+                .map(|(key, value)| (key, without_nulls(value)))
+                .collect(),
+        ),
+        Value::Array(items) => Value::Array(
+            items
+                .into_iter()
+                .filter(|item| !item.is_null())
+                .map(without_nulls)
+                .collect(),
+        ),
+        other => other,
+    }
+}
+
+/// Nest one element per field; array items are `<item>`, keys that are not XML names
+/// (such as the numeric keys of frequency tables) become `<entry key="...">`, and null
+/// or empty values are empty elements
+fn format_xml(root: &str, document: &Value) -> String {
+    let mut output = String::from("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+    write_xml_element(&mut output, root, None, document, 0);
+    output
+}
+
+fn write_xml_element(
+    output: &mut String,
+    name: &str,
+    key: Option<&str>,
+    value: &Value,
+    depth: usize,
+) {
+    let indent = "  ".repeat(depth);
+    let open = match key {
+        Some(key) => format!("{name} key=\"{}\"", escape_xml(key)),
+        None => name.to_string(),
+    };
+
+    match value {
+        Value::Null => output.push_str(&format!("{indent}<{open}/>\n")),
+        Value::Array(items) if items.is_empty() => output.push_str(&format!("{indent}<{open}/>\n")),
+        Value::Object(map) if map.is_empty() => output.push_str(&format!("{indent}<{open}/>\n")),
+        Value::Array(items) => {
+            output.push_str(&format!("{indent}<{open}>\n"));
+            for item in items {
+                write_xml_element(output, "item", None, item, depth + 1);
+            }
+            output.push_str(&format!("{indent}</{name}>\n"));
+        }
+        Value::Object(map) => {
+            output.push_str(&format!("{indent}<{open}>\n"));
+            for (key, value) in map {
+                if is_xml_name(key) {
+                    write_xml_element(output, key, None, value, depth + 1);
+                } else {
+                    write_xml_element(output, "entry", Some(key), value, depth + 1);
+                }
+            }
+            output.push_str(&format!("{indent}</{name}>\n"));
+        }
+        leaf => output.push_str(&format!(
+            "{indent}<{open}>{}</{name}>\n",
+            escape_xml(&leaf_text(leaf))
+        )),
     }
 }
 
-fn format_text(result: &BenfordResult) -> String {
+fn is_xml_name(name: &str) -> bool {
+    let mut chars = name.chars();
+    chars
+        .next()
+        .is_some_and(|first| first.is_ascii_alphabetic() || first == '_')
+        && chars.all(|c| c.is_ascii_alphanumeric() || matches!(c, '_' | '-' | '.'))
+        && !name.to_ascii_lowercase().starts_with("xml")
+}
+
+fn escape_xml(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+        .replace('\'', "&apos;")
+}
+
+fn format_benford_text(result: &BenfordResult) -> String {
     format!(
         "Benford's Law Analysis Results\n\
         \n\
@@ -145,79 +371,216 @@ fn format_distribution_bars(result: &BenfordResult) -> String {
     output
 }
 
-fn format_json(result: &BenfordResult) -> String {
-    format!(
-        r#"{{
-  "dataset": "{}"
-  "numbers_analyzed": {},
-  "risk_level": "{}"
-  "digits": {{
-    "1": {{"observed": {:.1}, "expected": {:.1}, "deviation": {:.1}}},
-    "2": {{"observed": {:.1}, "expected": {:.1}, "deviation": {:.1}}},
-    "3": {{"observed": {:.1}, "expected": {:.1}, "deviation": {:.1}}},
-    "4": {{"observed": {:.1}, "expected": {:.1}, "deviation": {:.1}}},
-    "5": {{"observed": {:.1}, "expected": {:.1}, "deviation": {:.1}}},
-    "6": {{"observed": {:.1}, "expected": {:.1}, "deviation": {:.1}}},
-    "7": {{"observed": {:.1}, "expected": {:.1}, "deviation": {:.1}}},
-    "8": {{"observed": {:.1}, "expected": {:.1}, "deviation": {:.1}}},
-    "9": {{"observed": {:.1}, "expected": {:.1}, "deviation": {:.1}}}
-  }},
-  "statistics": {{
-    "chi_square": {:.2},
-    "p_value": {:.3},
-    "mad": {:.1}
-  }},
-  "verdict": "{}"
-}}"#,
-        result.dataset_name,
-        result.numbers_analyzed,
-        result.risk_level,
-        result.digit_distribution[0],
-        result.expected_distribution[0],
-        result.digit_distribution[0] - result.expected_distribution[0],
-        result.digit_distribution[1],
-        result.expected_distribution[1],
-        result.digit_distribution[1] - result.expected_distribution[1],
-        result.digit_distribution[2],
-        result.expected_distribution[2],
-        result.digit_distribution[2] - result.expected_distribution[2],
-        result.digit_distribution[3],
-        result.expected_distribution[3],
-        result.digit_distribution[3] - result.expected_distribution[3],
-        result.digit_distribution[4],
-        result.expected_distribution[4],
-        result.digit_distribution[4] - result.expected_distribution[4],
-        result.digit_distribution[5],
-        result.expected_distribution[5],
-        result.digit_distribution[5] - result.expected_distribution[5],
-        result.digit_distribution[6],
-        result.expected_distribution[6],
-        result.digit_distribution[6] - result.expected_distribution[6],
-        result.digit_distribution[7],
-        result.expected_distribution[7],
-        result.digit_distribution[7] - result.expected_distribution[7],
-        result.digit_distribution[8],
-        result.expected_distribution[8],
-        result.digit_distribution[8] - result.expected_distribution[8],
-        result.chi_square,
-        result.p_value,
-        result.mean_absolute_deviation,
-        result.verdict
-    )
-}
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::laws::compare::{compare_datasets, ComparedLaw};
+    use quick_xml::events::Event;
 
-fn format_csv(_result: &BenfordResult) -> String {
-    "CSV format not yet implemented".to_string()
-}
+    const FORMATS: [OutputFormat; 6] = [
+        OutputFormat::Text,
+        OutputFormat::Json,
+        OutputFormat::Csv,
+        OutputFormat::Yaml,
+        OutputFormat::Toml,
+        OutputFormat::Xml,
+    ];
 
-fn format_yaml(_result: &BenfordResult) -> String {
-    "YAML format not yet implemented".to_string()
-}
+    fn sample_numbers() -> Vec<f64> {
+        (1..=200)
+            .map(|i| ((i * 37) % 97 + 1) as f64 * (1.0 + (i % 7) as f64))
+            .collect()
+    }
 
-fn format_toml(_result: &BenfordResult) -> String {
-    "TOML format not yet implemented".to_string()
-}
+    /// Read the CSV output back into (field, value) rows
+    fn csv_rows(output: &str) -> Vec<(String, String)> {
+        let mut reader = csv::Reader::from_reader(output.as_bytes());
+        assert_eq!(reader.headers().unwrap(), vec!["field", "value"]);
+        reader
+            .records()
+            .map(|row| {
+                let row = row.unwrap();
+                (row[0].to_string(), row[1].to_string())
+            })
+            .collect()
+    }
+
+    /// Read the XML output back into (field, value) rows, using the same dotted paths as CSV
+    fn xml_rows(output: &str, root: &str) -> Vec<(String, String)> {
+        let mut reader = quick_xml::Reader::from_str(output);
+        let mut path: Vec<String> = Vec::new();
+        let mut counters: Vec<usize> = Vec::new();
+        let mut text: Option<String> = None;
+        let mut rows = Vec::new();
+
+        let segment = |element: &quick_xml::events::BytesStart, counters: &mut Vec<usize>| {
+            let name = String::from_utf8(element.name().as_ref().to_vec()).unwrap();
+            let index = counters.last_mut().map(|count| {
+                *count += 1;
+                *count - 1
+            });
+            match name.as_str() {
+                "item" => index.unwrap().to_string(),
+                "entry" => element
+                    .try_get_attribute("key")
+                    .unwrap()
+                    .unwrap()
+                    .unescape_value()
+                    .unwrap()
+                    .to_string(),
+                _ => name,
+            }
+        };
+
+        loop {
+            match reader.read_event().unwrap() {
+                Event::Start(element) => {
+                    path.push(segment(&element, &mut counters));
+                    counters.push(0);
+                    text = None;
+                }
+                Event::Empty(element) => {
+                    path.push(segment(&element, &mut counters));
+                    rows.push((path[1..].join("."), String::new()));
+                    path.pop();
+                }
+                Event::Text(content) => text
+                    .get_or_insert_with(String::new)
+                    .push_str(&content.decode().unwrap()),
+                Event::GeneralRef(entity) => {
+                    let name = entity.decode().unwrap();
+                    let resolved = quick_xml::escape::resolve_predefined_entity(&name).unwrap();
+                    text.get_or_insert_with(String::new).push_str(resolved);
+                }
+                Event::End(_) => {
+                    // Only leaf elements carry values; the rest is indentation
+                    let value = text.take();
+                    if counters.pop() == Some(0) {
+                        rows.push((path[1..].join("."), value.unwrap_or_default()));
+                    }
+                    path.pop();
+                }
+                Event::Eof => break,
+                _ => {}
+            }
+        }
+
+        assert!(output.contains(&format!("<{root}>")));
+        rows
+    }
 
-fn format_xml(_result: &BenfordResult) -> String {
-    "XML format not yet implemented".to_string()
+    /// serde_json parses floats to within an ulp, so compare numbers with a relative tolerance
+    fn assert_same_document(actual: &Value, expected: &Value) {
+        match (actual, expected) {
+            (Value::Number(a), Value::Number(b)) => {
+                let (a, b) = (a.as_f64().unwrap(), b.as_f64().unwrap());
+                assert!((a - b).abs() <= 1e-12 * b.abs().max(1.0), "{a} != {b}");
+            }
+            (Value::Array(a), Value::Array(b)) => {
+                assert_eq!(a.len(), b.len());
+                a.iter()
+                    .zip(b)
+                    .for_each(|(a, b)| assert_same_document(a, b));
+            }
+            (Value::Object(a), Value::Object(b)) => {
+                assert_eq!(a.keys().collect::<Vec<_>>(), b.keys().collect::<Vec<_>>());
+                a.values()
+                    .zip(b.values())
+                    .for_each(|(a, b)| assert_same_document(a, b));
+            }
+            _ => assert_eq!(actual, expected),
+        }
+    }
+
+    fn assert_round_trips<R: FormattableResult>(result: &R) {
+        let document = to_document(result).unwrap();
+        let rows = flatten_document(&document);
+
+        let json: Value =
+            serde_json::from_str(&format_output(result, &OutputFormat::Json).unwrap()).unwrap();
+        assert_same_document(&json, &document);
+
+        let yaml: Value =
+            serde_yaml::from_str(&format_output(result, &OutputFormat::Yaml).unwrap()).unwrap();
+        assert_same_document(&yaml, &document);
+
+        let toml: Value =
+            toml::from_str(&format_output(result, &OutputFormat::Toml).unwrap()).unwrap();
+        assert_same_document(&toml, &without_nulls(document.clone()));
+
+        assert_eq!(
+            csv_rows(&format_output(result, &OutputFormat::Csv).unwrap()),
+            rows
+        );
+        assert_eq!(
+            xml_rows(&format_output(result, &OutputFormat::Xml).unwrap(), R::ROOT),
+            rows
+        );
+
+        for format in &FORMATS {
+            let output = format_output(result, format).unwrap();
+            assert!(!output.contains("not yet implemented"));
+        }
+    }
+
+    #[test]
+    fn test_benford_round_trips() {
+        let result = BenfordResult::new("ledger <2023>".to_string(), &sample_numbers()).unwrap();
+        assert_round_trips(&result);
+
+        let text = format_output(&result, &OutputFormat::Text).unwrap();
+        assert!(text.starts_with("Benford's Law Analysis Results"));
+
+        let xml = format_output(&result, &OutputFormat::Xml).unwrap();
+        assert!(xml.contains("<dataset_name>ledger &lt;2023&gt;</dataset_name>"));
+    }
+
+    #[test]
+    fn test_csv_schema_is_field_value_rows() {
+        let result = BenfordResult::new("test".to_string(), &sample_numbers()).unwrap();
+        let csv = format_output(&result, &OutputFormat::Csv).unwrap();
+        let rows = csv_rows(&csv);
+
+        assert_eq!(
+            rows[0],
+            ("chi_square".to_string(), result.chi_square.to_string())
+        );
+        assert!(rows.contains(&("dataset_name".to_string(), "test".to_string())));
+        assert!(rows
+            .iter()
+            .any(|(field, _)| field == "digit_distribution.8"));
+        assert!(rows.iter().any(|(field, _)| field == "z_statistics.0.bin"));
+    }
+
+    #[test]
+    fn test_other_law_results_round_trip() {
+        let numbers = sample_numbers();
+        let counts: Vec<f64> = numbers.iter().map(|n| (n % 9.0).floor()).collect();
+
+        assert_round_trips(&ParetoResult::new("pareto".to_string(), &numbers).unwrap());
+        assert_round_trips(&ZipfResult::new("zipf".to_string(), &numbers).unwrap());
+        assert_round_trips(&NormalResult::new("normal".to_string(), &numbers).unwrap());
+        assert_round_trips(&PoissonResult::new("poisson".to_string(), &counts).unwrap());
+        assert_round_trips(&IntegrationResult::new("all".to_string(), &numbers));
+        let shifted: Vec<f64> = numbers.iter().map(|n| n * 1.5).collect();
+        assert_round_trips(
+            &compare_datasets(ComparedLaw::Benford, "a", &numbers, "b", &shifted, 0.05).unwrap(),
+        );
+
+        let text = format_output(
+            &ParetoResult::new("pareto".to_string(), &numbers).unwrap(),
+            &OutputFormat::Text,
+        )
+        .unwrap();
+        assert!(text.starts_with("Pareto Principle Analysis Results"));
+        assert!(text.contains("dataset_name: pareto"));
+    }
+
+    #[test]
+    fn test_xml_names() {
+        assert!(is_xml_name("chi_square"));
+        assert!(!is_xml_name("0"));
+        assert!(!is_xml_name("xmlns"));
+        assert!(!is_xml_name("a b"));
+    }
 }
@@ -1,4 +1,6 @@
-#[derive(Debug, Clone, PartialEq)]
+use serde::Serialize;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize)]
 pub enum RiskLevel {
     Low,      // p > 0.1 - Normal distribution
     Medium,   // 0.05 < p ≤ 0.1 - Moderate attention
@@ -19,11 +21,17 @@ impl std::fmt::Display for RiskLevel {
 
 impl RiskLevel {
     pub fn from_p_value(p_value: f64) -> Self {
-        if p_value <= 0.01 {
+        Self::from_p_value_with_significance(p_value, 0.05)
+    }
+
+    /// Same bands as `from_p_value`, scaled to the significance level α:
+    /// Critical ≤ α/5, High ≤ α, Medium ≤ 2α
+    pub fn from_p_value_with_significance(p_value: f64, significance_level: f64) -> Self {
+        if p_value <= significance_level / 5.0 {
             RiskLevel::Critical
-        } else if p_value <= 0.05 {
+        } else if p_value <= significance_level {
             RiskLevel::High
-        } else if p_value <= 0.1 {
+        } else if p_value <= significance_level * 2.0 {
             RiskLevel::Medium
         } else {
             RiskLevel::Low
@@ -14,48 +14,432 @@ pub fn calculate_chi_square(observed: &[f64], expected: &[f64]) -> f64 {
         .sum()
 }
 
-/// Calculate p-value from chi-square statistic and degrees of freedom
-/// This is a simplified approximation - in production, use a proper statistics library
+/// Calculate chi-square statistic from percentage distributions
+///
+/// Equivalent to running `calculate_chi_square` on the underlying counts, which is
+/// what the chi-square distribution (and therefore `calculate_p_value`) expects.
+pub fn calculate_chi_square_from_percentages(
+    observed_percentages: &[f64],
+    expected_percentages: &[f64],
+    sample_size: usize,
+) -> f64 {
+    calculate_chi_square(observed_percentages, expected_percentages) * sample_size as f64 / 100.0
+}
+
+/// Calculate the upper-tail p-value P(X ≥ chi_square) of the chi-square distribution
 pub fn calculate_p_value(chi_square: f64, degrees_of_freedom: i32) -> f64 {
-    // Simplified p-value calculation using normal approximation
-    // For more accuracy, use a proper chi-square distribution function
+    if degrees_of_freedom <= 0 || chi_square.is_nan() {
+        return 1.0;
+    }
 
     if chi_square <= 0.0 {
         return 1.0;
     }
 
-    // Simple approximation based on common critical values
-    match degrees_of_freedom {
-        8 => {
-            if chi_square >= 20.09 {
-                0.01
-            }
-            // p <= 0.01
-            else if chi_square >= 15.51 {
-                0.05
-            }
-            // p <= 0.05
-            else if chi_square >= 13.36 {
-                0.1
+    regularized_gamma_q(degrees_of_freedom as f64 / 2.0, chi_square / 2.0)
+}
+
+/// Two-sided p-value P(|Z| ≥ |z|) of the standard normal distribution
+///
+/// Uses Z² ~ χ²(1), so the tail is Q(1/2, z²/2).
+pub fn two_sided_normal_p_value(z: f64) -> f64 {
+    if z.is_nan() {
+        return 1.0;
+    }
+    if z == 0.0 {
+        return 1.0;
+    }
+    regularized_gamma_q(0.5, z * z / 2.0)
+}
+
+/// Nigrini's Z-statistic for one digit bin, with continuity correction
+///
+/// Proportions are fractions (0-1); the 1/(2N) correction is applied only when it is
+/// smaller than the absolute difference.
+pub fn digit_z_statistic(observed: f64, expected: f64, sample_size: usize) -> f64 {
+    if sample_size == 0 || expected <= 0.0 || expected >= 1.0 {
+        return 0.0;
+    }
+
+    let n = sample_size as f64;
+    let difference = (observed - expected).abs();
+    let correction = 1.0 / (2.0 * n);
+    let numerator = if correction < difference {
+        difference - correction
+    } else {
+        difference
+    };
+
+    numerator / (expected * (1.0 - expected) / n).sqrt()
+}
+
+/// Natural logarithm of the gamma function (Lanczos approximation, g = 7)
+pub fn ln_gamma(x: f64) -> f64 {
+    const COEFFICIENTS: [f64; 9] = [
+        0.999_999_999_999_809_9,
+        676.520_368_121_885_1,
+        -1_259.139_216_722_402_8,
+        771.323_428_777_653_1,
+        -176.615_029_162_140_6,
+        12.507_343_278_686_905,
+        -0.138_571_095_265_720_12,
+        9.984_369_578_019_572e-6,
+        1.505_632_735_149_311_6e-7,
+    ];
+
+    if x < 0.5 {
+        // Reflection formula: Γ(x)Γ(1-x) = π / sin(πx)
+        let pi = std::f64::consts::PI;
+        return (pi / (pi * x).sin()).abs().ln() - ln_gamma(1.0 - x);
+    }
+
+    let x = x - 1.0;
+    let mut sum = COEFFICIENTS[0];
+    for (i, &c) in COEFFICIENTS.iter().enumerate().skip(1) {
+        sum += c / (x + i as f64);
+    }
+
+    let t = x + 7.5;
+    0.5 * (2.0 * std::f64::consts::PI).ln() + (x + 0.5) * t.ln() - t + sum.ln()
+}
+
+/// Regularized lower incomplete gamma function P(a, x)
+pub fn regularized_gamma_p(a: f64, x: f64) -> f64 {
+    if a <= 0.0 || x <= 0.0 {
+        return 0.0;
+    }
+
+    if x < a + 1.0 {
+        gamma_series(a, x)
+    } else {
+        1.0 - gamma_continued_fraction(a, x)
+    }
+}
+
+/// Regularized upper incomplete gamma function Q(a, x) = 1 - P(a, x)
+pub fn regularized_gamma_q(a: f64, x: f64) -> f64 {
+    if a <= 0.0 {
+        return 0.0;
+    }
+    if x <= 0.0 {
+        return 1.0;
+    }
+
+    if x < a + 1.0 {
+        1.0 - gamma_series(a, x)
+    } else {
+        gamma_continued_fraction(a, x)
+    }
+}
+
+const GAMMA_MAX_ITERATIONS: usize = 500;
+const GAMMA_EPSILON: f64 = 1e-15;
+
+/// Series expansion of P(a, x), converges quickly for x < a + 1
+fn gamma_series(a: f64, x: f64) -> f64 {
+    let mut term = 1.0 / a;
+    let mut sum = term;
+    let mut denominator = a;
+
+    for _ in 0..GAMMA_MAX_ITERATIONS {
+        denominator += 1.0;
+        term *= x / denominator;
+        sum += term;
+        if term.abs() < sum.abs() * GAMMA_EPSILON {
+            break;
+        }
+    }
+
+    (sum.ln() - x + a * x.ln() - ln_gamma(a))
+        .exp()
+        .clamp(0.0, 1.0)
+}
+
+/// Continued fraction of Q(a, x) (modified Lentz), converges quickly for x ≥ a + 1
+fn gamma_continued_fraction(a: f64, x: f64) -> f64 {
+    const TINY: f64 = 1e-300;
+
+    let mut b = x + 1.0 - a;
+    let mut c = 1.0 / TINY;
+    let mut d = 1.0 / b;
+    let mut h = d;
+
+    for i in 1..=GAMMA_MAX_ITERATIONS {
+        let an = -(i as f64) * (i as f64 - a);
+        b += 2.0;
+
+        d = an * d + b;
+        if d.abs() < TINY {
+            d = TINY;
+        }
+        c = b + an / c;
+        if c.abs() < TINY {
+            c = TINY;
+        }
+
+        d = 1.0 / d;
+        let delta = d * c;
+        h *= delta;
+        if (delta - 1.0).abs() < GAMMA_EPSILON {
+            break;
+        }
+    }
+
+    (a * x.ln() - x - ln_gamma(a) + h.ln())
+        .exp()
+        .clamp(0.0, 1.0)
+}
+
+/// Upper tail P(Z ≥ z) of the standard normal distribution
+pub fn normal_upper_tail(z: f64) -> f64 {
+    if z.is_nan() {
+        return f64::NAN;
+    }
+    let tail = 0.5 * regularized_gamma_q(0.5, z * z / 2.0);
+    if z >= 0.0 {
+        tail
+    } else {
+        1.0 - tail
+    }
+}
+
+/// ln P(Z ≥ z), accurate far into the upper tail where the probability underflows
+pub fn ln_normal_upper_tail(z: f64) -> f64 {
+    if z < 30.0 {
+        return normal_upper_tail(z).ln();
+    }
+    // Mills ratio asymptotic series: P(Z ≥ z) = φ(z)/z · (1 - 1/z² + 3/z⁴ - 15/z⁶ + …)
+    let z2 = z * z;
+    -0.5 * z2 - 0.5 * (2.0 * std::f64::consts::PI).ln() - z.ln()
+        + (1.0 - 1.0 / z2 + 3.0 / (z2 * z2) - 15.0 / (z2 * z2 * z2)).ln()
+}
+
+/// Quantile z with P(Z ≤ z) = p of the standard normal distribution
+///
+/// Acklam's rational approximation followed by one Halley step, accurate to about 1e-15.
+pub fn normal_quantile(p: f64) -> f64 {
+    const A: [f64; 6] = [
+        -3.969_683_028_665_376e1,
+        2.209_460_984_245_205e2,
+        -2.759_285_104_469_687e2,
+        1.383_577_518_672_69e2,
+        -3.066_479_806_614_716e1,
+        2.506_628_277_459_239,
+    ];
+    const B: [f64; 5] = [
+        -5.447_609_879_822_406e1,
+        1.615_858_368_580_409e2,
+        -1.556_989_798_598_866e2,
+        6.680_131_188_771_972e1,
+        -1.328_068_155_288_572e1,
+    ];
+    const C: [f64; 6] = [
+        -7.784_894_002_430_293e-3,
+        -3.223_964_580_411_365e-1,
+        -2.400_758_277_161_838,
+        -2.549_732_539_343_734,
+        4.374_664_141_464_968,
+        2.938_163_982_698_783,
+    ];
+    const D: [f64; 4] = [
+        7.784_695_709_041_462e-3,
+        3.224_671_290_700_398e-1,
+        2.445_134_137_142_996,
+        3.754_408_661_907_416,
+    ];
+    const P_LOW: f64 = 0.02425;
+
+    if p.is_nan() || !(0.0..=1.0).contains(&p) {
+        return f64::NAN;
+    }
+    if p == 0.0 {
+        return f64::NEG_INFINITY;
+    }
+    if p == 1.0 {
+        return f64::INFINITY;
+    }
+
+    let tail = |q: f64| {
+        (((((C[0] * q + C[1]) * q + C[2]) * q + C[3]) * q + C[4]) * q + C[5])
+            / ((((D[0] * q + D[1]) * q + D[2]) * q + D[3]) * q + 1.0)
+    };
+    let x = if p < P_LOW {
+        tail((-2.0 * p.ln()).sqrt())
+    } else if p > 1.0 - P_LOW {
+        -tail((-2.0 * (1.0 - p).ln()).sqrt())
+    } else {
+        let q = p - 0.5;
+        let r = q * q;
+        (((((A[0] * r + A[1]) * r + A[2]) * r + A[3]) * r + A[4]) * r + A[5]) * q
+            / (((((B[0] * r + B[1]) * r + B[2]) * r + B[3]) * r + B[4]) * r + 1.0)
+    };
+
+    // 1 - P(Z ≥ x) loses precision in the lower tail, so use symmetry there
+    let cdf = if x < 0.0 {
+        normal_upper_tail(-x)
+    } else {
+        1.0 - normal_upper_tail(x)
+    };
+    let error = cdf - p;
+    let u = error * (2.0 * std::f64::consts::PI).sqrt() * (x * x / 2.0).exp();
+    x - u / (1.0 + x * u / 2.0)
+}
+
+/// Upper tail P(K ≥ λ) of the Kolmogorov distribution (asymptotic KS p-value)
+///
+/// Uses the alternating series 2 Σ (-1)^(k-1) e^(-2k²λ²) for λ ≥ 1 and the
+/// Jacobi theta form, which converges faster, below that.
+pub fn kolmogorov_p_value(lambda: f64) -> f64 {
+    if lambda.is_nan() {
+        return 1.0;
+    }
+    if lambda <= 0.0 {
+        return 1.0;
+    }
+    if lambda < 1.0 {
+        let pi2 = std::f64::consts::PI * std::f64::consts::PI;
+        let cdf: f64 = (1..=20)
+            .map(|k| {
+                let odd = (2 * k - 1) as f64;
+                (-odd * odd * pi2 / (8.0 * lambda * lambda)).exp()
+            })
+            .sum::<f64>()
+            * (2.0 * std::f64::consts::PI).sqrt()
+            / lambda;
+        return (1.0 - cdf).clamp(0.0, 1.0);
+    }
+    let tail: f64 = (1..=100)
+        .map(|k| {
+            let sign = if k % 2 == 1 { 1.0 } else { -1.0 };
+            let k = k as f64;
+            sign * (-2.0 * k * k * lambda * lambda).exp()
+        })
+        .sum();
+    (2.0 * tail).clamp(0.0, 1.0)
+}
+
+/// Hurwitz zeta function ζ(s, q) = Σ_{k≥0} (k + q)^(-s) for s > 1, q > 0
+pub fn hurwitz_zeta(s: f64, q: f64) -> f64 {
+    ln_hurwitz_zeta(s, q).exp()
+}
+
+/// ln ζ(s, q), which stays finite when ζ(s, q) itself underflows (large q or s)
+///
+/// Sums the first terms directly and the remainder with the Euler–Maclaurin formula,
+/// everything scaled by q^s.
+pub fn ln_hurwitz_zeta(s: f64, q: f64) -> f64 {
+    const DIRECT_TERMS: usize = 10;
+    // B_2j / (2j)!
+    const BERNOULLI_COEFFICIENTS: [f64; 6] = [
+        1.0 / 12.0,
+        -1.0 / 720.0,
+        1.0 / 30240.0,
+        -1.0 / 1209600.0,
+        1.0 / 47900160.0,
+        -691.0 / 1307674368000.0,
+    ];
+
+    if s <= 1.0 || q <= 0.0 {
+        return f64::INFINITY;
+    }
+
+    let mut scaled = 0.0;
+    for k in 0..DIRECT_TERMS {
+        scaled += (1.0 + k as f64 / q).powf(-s);
+    }
+    let a = q + DIRECT_TERMS as f64;
+    let ratio = (a / q).powf(-s); // (a/q)^(-s)
+    scaled += ratio * (a / (s - 1.0) + 0.5);
+
+    let mut factor = ratio * s / a;
+    for (j, coefficient) in BERNOULLI_COEFFICIENTS.iter().enumerate() {
+        scaled += coefficient * factor;
+        let k = 2.0 * (j + 1) as f64;
+        factor *= (s + k - 1.0) * (s + k) / (a * a);
+    }
+
+    scaled.ln() - s * q.ln()
+}
+
+/// Minimize `f` with the Nelder–Mead simplex method, starting around `start`
+///
+/// The initial simplex offsets each coordinate of `start` by `step`. NaN counts as +∞,
+/// so constraints can be expressed by returning NaN or infinity outside the domain.
+pub fn minimize_nelder_mead<F>(f: F, start: &[f64], step: f64, max_iterations: usize) -> Vec<f64>
+where
+    F: Fn(&[f64]) -> f64,
+{
+    let evaluate = |x: &[f64]| {
+        let value = f(x);
+        if value.is_nan() {
+            f64::INFINITY
+        } else {
+            value
+        }
+    };
+    let towards = |from: &[f64], to: &[f64], t: f64| -> Vec<f64> {
+        from.iter().zip(to).map(|(a, b)| a + t * (b - a)).collect()
+    };
+
+    let n = start.len();
+    let mut simplex: Vec<(Vec<f64>, f64)> = vec![(start.to_vec(), evaluate(start))];
+    for i in 0..n {
+        let mut point = start.to_vec();
+        point[i] += step;
+        let value = evaluate(&point);
+        simplex.push((point, value));
+    }
+
+    for _ in 0..max_iterations {
+        simplex.sort_by(|a, b| a.1.total_cmp(&b.1));
+        let (best, worst) = (simplex[0].1, simplex[n].1);
+        if (worst - best).abs() <= 1e-12 * (1.0 + best.abs()) {
+            break;
+        }
+
+        let mut centroid = vec![0.0; n];
+        for (point, _) in &simplex[..n] {
+            for (c, x) in centroid.iter_mut().zip(point) {
+                *c += x / n as f64;
             }
-            // p <= 0.1
-            else {
-                0.5
-            } // p > 0.1 (approximate)
         }
-        _ => {
-            // Fallback approximation
-            if chi_square >= 20.0 {
-                0.01
-            } else if chi_square >= 15.0 {
-                0.05
-            } else if chi_square >= 12.0 {
-                0.1
+
+        let reflected = towards(&centroid, &simplex[n].0, -1.0);
+        let reflected_value = evaluate(&reflected);
+        if reflected_value < best {
+            let expanded = towards(&centroid, &simplex[n].0, -2.0);
+            let expanded_value = evaluate(&expanded);
+            simplex[n] = if expanded_value < reflected_value {
+                (expanded, expanded_value)
+            } else {
+                (reflected, reflected_value)
+            };
+        } else if reflected_value < simplex[n - 1].1 {
+            simplex[n] = (reflected, reflected_value);
+        } else {
+            let (contracted, limit) = if reflected_value < worst {
+                (towards(&centroid, &reflected, 0.5), reflected_value)
             } else {
-                0.5
+                (towards(&centroid, &simplex[n].0, 0.5), worst)
+            };
+            let contracted_value = evaluate(&contracted);
+            if contracted_value < limit {
+                simplex[n] = (contracted, contracted_value);
+            } else {
+                let best_point = simplex[0].0.clone();
+                for vertex in simplex.iter_mut().skip(1) {
+                    vertex.0 = towards(&best_point, &vertex.0, 0.5);
+                    vertex.1 = evaluate(&vertex.0);
+                }
             }
         }
     }
+
+    simplex
+        .into_iter()
+        .min_by(|a, b| a.1.total_cmp(&b.1))
+        .map(|(point, _)| point)
+        .unwrap_or_default()
 }
 
 /// Calculate Mean Absolute Deviation (MAD)
@@ -68,3 +452,172 @@ pub fn calculate_mad(observed: &[f64], expected: &[f64]) -> f64 {
 
     sum / observed.len() as f64
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assert_close(actual: f64, expected: f64, tolerance: f64) {
+        assert!(
+            (actual - expected).abs() < tolerance,
+            "expected {expected}, got {actual}"
+        );
+    }
+
+    #[test]
+    fn test_ln_gamma_known_values() {
+        assert_close(ln_gamma(1.0), 0.0, 1e-12);
+        assert_close(ln_gamma(5.0), 24.0_f64.ln(), 1e-12);
+        assert_close(ln_gamma(0.5), std::f64::consts::PI.sqrt().ln(), 1e-12);
+        assert_close(ln_gamma(100.0), 359.134_205_369_575, 1e-9);
+    }
+
+    #[test]
+    fn test_p_value_matches_critical_values() {
+        // Published chi-square critical values
+        assert_close(calculate_p_value(3.841_459, 1), 0.05, 1e-6);
+        assert_close(calculate_p_value(6.634_897, 1), 0.01, 1e-6);
+        assert_close(calculate_p_value(13.361_566, 8), 0.10, 1e-6);
+        assert_close(calculate_p_value(15.507_313, 8), 0.05, 1e-6);
+        assert_close(calculate_p_value(20.090_235, 8), 0.01, 1e-6);
+        assert_close(calculate_p_value(124.342_113, 100), 0.05, 1e-6);
+    }
+
+    #[test]
+    fn test_two_sided_normal_p_value() {
+        assert_close(two_sided_normal_p_value(1.959_964), 0.05, 1e-6);
+        assert_close(two_sided_normal_p_value(-2.575_829), 0.01, 1e-6);
+        assert_close(two_sided_normal_p_value(0.0), 1.0, 1e-12);
+    }
+
+    #[test]
+    fn test_digit_z_statistic() {
+        // Nigrini's example: 30.1% expected, 33% observed in 1,000 records
+        let expected = std::f64::consts::LOG10_2;
+        let n = 1000;
+        let by_hand = ((0.33_f64 - expected).abs() - 1.0 / 2000.0)
+            / (expected * (1.0 - expected) / n as f64).sqrt();
+        assert_close(digit_z_statistic(0.33, expected, n), by_hand, 1e-12);
+        // The correction is skipped when it exceeds the difference
+        let tiny = expected + 0.0001;
+        assert!(digit_z_statistic(tiny, expected, n) > 0.0);
+        assert_eq!(digit_z_statistic(0.5, expected, 0), 0.0);
+    }
+
+    #[test]
+    fn test_p_value_is_continuous() {
+        // df = 2 has the closed form p = exp(-x/2)
+        for &x in &[0.1, 1.0, 2.5, 7.3, 30.0] {
+            assert_close(calculate_p_value(x, 2), (-x / 2.0_f64).exp(), 1e-12);
+        }
+
+        let p = calculate_p_value(23.5, 8);
+        assert!(p > 0.001 && p < 0.01);
+        assert!(calculate_p_value(23.0, 8) > p);
+    }
+
+    #[test]
+    fn test_p_value_edge_cases() {
+        assert_eq!(calculate_p_value(0.0, 8), 1.0);
+        assert_eq!(calculate_p_value(-1.0, 8), 1.0);
+        assert_eq!(calculate_p_value(10.0, 0), 1.0);
+        assert_eq!(calculate_p_value(f64::NAN, 8), 1.0);
+        assert!(calculate_p_value(1e6, 8) < 1e-100);
+    }
+
+    #[test]
+    fn test_regularized_gamma_complement() {
+        for &(a, x) in &[(0.5, 0.2), (4.0, 3.0), (4.0, 9.0), (50.0, 45.0)] {
+            assert_close(
+                regularized_gamma_p(a, x) + regularized_gamma_q(a, x),
+                1.0,
+                1e-12,
+            );
+        }
+    }
+
+    #[test]
+    fn test_chi_square_from_percentages_matches_counts() {
+        let observed_counts = [80.0, 60.0, 60.0];
+        let expected_counts = [100.0, 50.0, 50.0];
+        let observed_pct = [40.0, 30.0, 30.0];
+        let expected_pct = [50.0, 25.0, 25.0];
+
+        assert_close(
+            calculate_chi_square_from_percentages(&observed_pct, &expected_pct, 200),
+            calculate_chi_square(&observed_counts, &expected_counts),
+            1e-12,
+        );
+    }
+    #[test]
+    fn test_normal_upper_tail() {
+        assert_close(normal_upper_tail(0.0), 0.5, 1e-12);
+        assert_close(normal_upper_tail(1.959964), 0.025, 1e-6);
+        assert_close(normal_upper_tail(-1.0), 0.841344746, 1e-8);
+        // ln P(Z ≥ 40) ≈ -804.608
+        assert_close(ln_normal_upper_tail(40.0), -804.6084420137538, 1e-6);
+        assert_close(
+            ln_normal_upper_tail(29.9),
+            normal_upper_tail(29.9).ln(),
+            1e-9,
+        );
+    }
+
+    #[test]
+    fn test_normal_quantile() {
+        assert_close(normal_quantile(0.975), 1.959_963_984_540_054, 1e-12);
+        assert_close(normal_quantile(0.5), 0.0, 1e-15);
+        assert_close(normal_quantile(0.005), -2.575_829_303_548_901, 1e-12);
+        assert_close(normal_quantile(1e-10), -6.361_340_902_404_056, 1e-9);
+        assert!(normal_quantile(1.5).is_nan());
+    }
+
+    #[test]
+    fn test_kolmogorov_p_value() {
+        // Critical values of the limiting distribution
+        assert_close(kolmogorov_p_value(1.358_1), 0.05, 1e-4);
+        assert_close(kolmogorov_p_value(1.627_6), 0.01, 1e-4);
+        assert_close(kolmogorov_p_value(0.827_5), 0.5, 1e-3);
+        assert_eq!(kolmogorov_p_value(0.0), 1.0);
+        // Both series agree where they meet
+        assert_close(
+            kolmogorov_p_value(1.0 - 1e-12),
+            kolmogorov_p_value(1.0),
+            1e-9,
+        );
+    }
+
+    #[test]
+    fn test_hurwitz_zeta_known_values() {
+        let pi = std::f64::consts::PI;
+        assert_close(hurwitz_zeta(2.0, 1.0), pi * pi / 6.0, 1e-12);
+        assert_close(hurwitz_zeta(4.0, 1.0), pi.powi(4) / 90.0, 1e-12);
+        // ζ(2, 3) = π²/6 - 1 - 1/4
+        assert_close(hurwitz_zeta(2.0, 3.0), pi * pi / 6.0 - 1.25, 1e-12);
+        assert_close(hurwitz_zeta(1.5, 1.0), 2.612375348685488, 1e-10);
+        // Large q: ζ(s, q) ≈ q^(1-s)/(s-1)
+        assert_close(ln_hurwitz_zeta(3.0, 1e12), (0.5e-24f64).ln(), 1e-9);
+    }
+
+    #[test]
+    fn test_nelder_mead_finds_minimum() {
+        let rosenbrock = |x: &[f64]| (1.0 - x[0]).powi(2) + 100.0 * (x[1] - x[0] * x[0]).powi(2);
+        let minimum = minimize_nelder_mead(rosenbrock, &[-1.2, 1.0], 0.5, 5000);
+        assert_close(minimum[0], 1.0, 1e-4);
+        assert_close(minimum[1], 1.0, 1e-4);
+
+        // NaN outside the domain keeps the search inside x > 0
+        let constrained = |x: &[f64]| {
+            if x[0] > 0.0 {
+                x[0] - x[0].ln()
+            } else {
+                f64::NAN
+            }
+        };
+        assert_close(
+            minimize_nelder_mead(constrained, &[3.0], 1.0, 500)[0],
+            1.0,
+            1e-4,
+        );
+    }
+}
@@ -1,6 +1,6 @@
 use crate::error::Result;
 use std::fs::File;
-use std::io::{BufRead, BufReader};
+use std::io::{BufRead, BufReader, Read};
 use std::path::Path;
 
 /// diffxの技術を活用した最適化IO処理
@@ -78,6 +78,13 @@ impl OptimizedFileReader {
         Ok(results)
     }
 
+    /// 残りの入力をすべて読み込む（行をまたぐ CSV の引用フィールドなど、行単位で扱えない場合）
+    pub fn read_to_string(&mut self) -> Result<String> {
+        let mut buffer = String::new();
+        self.reader.read_to_string(&mut buffer)?;
+        Ok(buffer)
+    }
+
     /// バッチ処理（diffxのバッチサイズ最適化）
     pub fn read_lines_batched<F, T>(
         &mut self,
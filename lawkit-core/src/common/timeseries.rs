@@ -1,4 +1,4 @@
-use crate::diffx_core_mock::{diff, DiffResult};
+use crate::common::diff::{diff, DiffResult};
 use crate::error::Result;
 
 /// 時系列データポイント
@@ -245,7 +245,7 @@ fn detect_changepoints(timestamps: &[f64], values: &[f64]) -> Result<Vec<ChangeP
             .sum::<f64>()
             / after_window.len() as f64;
 
-        // diffx-coreを使用した詳細な統計構造比較
+        // 前後の統計量を構造的に比較
         let before_stats = serde_json::json!({
             "mean": before_mean,
             "variance": before_var,
@@ -260,8 +260,8 @@ fn detect_changepoints(timestamps: &[f64], values: &[f64]) -> Result<Vec<ChangeP
             "cv": if after_mean.abs() > 0.0 { after_var.sqrt() / after_mean.abs() } else { 0.0 }
         });
 
-        // diffx-coreで構造的差分を検出
-        let diff_results = diff(&before_stats, &after_stats, None);
+        // 構造的差分を検出
+        let diff_results = diff(&before_stats, &after_stats);
 
         // 平均の変化を検出
         let mean_change = (after_mean - before_mean).abs();
@@ -270,21 +270,19 @@ fn detect_changepoints(timestamps: &[f64], values: &[f64]) -> Result<Vec<ChangeP
         if pooled_std > 0.0 {
             let significance = mean_change / pooled_std;
 
-            // diffx-coreの差分情報を活用した変化タイプの精密判定
+            // 差分情報を活用した変化タイプの精密判定
             let mut change_type = ChangeType::LevelShift;
             let mut max_change_ratio = 0.0;
 
-            if let Ok(results) = &diff_results {
-                for diff_result in results {
-                    if let DiffResult::Modified(path, old_val, new_val) = diff_result {
-                        if path.contains("variance") || path.contains("std_dev") {
-                            if let (Some(old), Some(new)) = (old_val.as_f64(), new_val.as_f64()) {
-                                let ratio = (new / old.max(0.001)).max(old / new.max(0.001));
-                                if ratio > max_change_ratio {
-                                    max_change_ratio = ratio;
-                                    if ratio > 2.0 {
-                                        change_type = ChangeType::VarianceChange;
-                                    }
+            for diff_result in &diff_results {
+                if let DiffResult::Modified(path, old_val, new_val) = diff_result {
+                    if path.contains("variance") || path.contains("std_dev") {
+                        if let (Some(old), Some(new)) = (old_val.as_f64(), new_val.as_f64()) {
+                            let ratio = (new / old.max(0.001)).max(old / new.max(0.001));
+                            if ratio > max_change_ratio {
+                                max_change_ratio = ratio;
+                                if ratio > 2.0 {
+                                    change_type = ChangeType::VarianceChange;
                                 }
                             }
                         }
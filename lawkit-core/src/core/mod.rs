@@ -45,9 +45,14 @@ impl BenfordResult {
 
         let digit_distribution = crate::laws::benford::calculate_digit_distribution(numbers);
         let expected_distribution = crate::laws::benford::BENFORD_EXPECTED_PERCENTAGES;
-        let chi_square = crate::common::statistics::calculate_chi_square(
+        let digit_count = numbers
+            .iter()
+            .filter(|&&n| crate::laws::benford::get_first_digit(n).is_some())
+            .count();
+        let chi_square = crate::common::statistics::calculate_chi_square_from_percentages(
             &digit_distribution,
             &expected_distribution,
+            digit_count,
         );
         let p_value = crate::common::statistics::calculate_p_value(chi_square, 8); // 8 degrees of freedom
         let mean_absolute_deviation =
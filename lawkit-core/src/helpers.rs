@@ -1,8 +1,18 @@
 use anyhow::{anyhow, Result};
+use rayon::prelude::*;
 use serde::Serialize;
 use serde_json::Value;
-
-use crate::OutputFormat;
+use std::str::FromStr;
+
+use crate::common::filtering::RiskThreshold;
+use crate::common::input::selector::{JsonPath, KeySelection};
+use crate::common::international::convert_international_numerals;
+use crate::common::risk::RiskLevel;
+use crate::laws::benford::japanese::convert_japanese_numerals;
+use crate::laws::benford::{get_first_digit, DigitTest};
+use crate::laws::compare::ComparedLaw;
+use crate::laws::pareto::{InequalityOptions, PowerLawOptions};
+use crate::{LawkitOptions, OutputFormat};
 
 // ============================================================================
 // UTILITY FUNCTIONS - FOR INTERNAL USE ONLY
@@ -50,215 +60,325 @@ pub fn format_output<T: Serialize>(results: &[T], format: OutputFormat) -> Resul
     }
 }
 
-// Helper functions
-pub(crate) fn extract_numbers_from_value(value: &Value) -> Result<Vec<f64>> {
-    let mut numbers = Vec::new();
-    extract_numbers_recursive(value, &mut numbers);
-    Ok(numbers)
+// ============================================================================
+// ANALYSIS SETTINGS - LawkitOptions resolved with their defaults
+// ============================================================================
+
+const DEFAULT_SIGNIFICANCE_LEVEL: f64 = 0.05;
+const DEFAULT_PARETO_RATIO: f64 = 0.8;
+const DEFAULT_BATCH_SIZE: usize = 10_000;
+const DEFAULT_VALIDATION_SAMPLE_SIZE: usize = 10;
+
+/// Numeral systems recognised in string values
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) enum NumeralSupport {
+    /// Plain `str::parse::<f64>()`
+    Ascii,
+    /// Full-width digits and kanji numerals
+    Japanese,
+    /// Japanese, Chinese, Hindi and Arabic-Indic numerals
+    International,
 }
 
-fn extract_numbers_recursive(value: &Value, numbers: &mut Vec<f64>) {
-    match value {
-        Value::Number(n) => {
-            if let Some(f) = n.as_f64() {
-                numbers.push(f);
-            }
-        }
-        Value::String(s) => {
-            // Try to parse as number
-            if let Ok(f) = s.parse::<f64>() {
-                numbers.push(f);
-            }
-        }
-        Value::Array(arr) => {
-            for item in arr {
-                extract_numbers_recursive(item, numbers);
-            }
+/// `LawkitOptions` validated once and resolved against their defaults
+#[derive(Debug, Clone)]
+pub(crate) struct AnalysisSettings {
+    // Input selection
+    pub keys: KeySelection,
+    pub numerals: NumeralSupport,
+    pub min_value: Option<f64>,
+    pub memory_limit_mb: Option<usize>,
+
+    // Statistical decisions
+    pub significance_level: f64,
+    pub significance_level_set: bool,
+    pub risk_threshold: RiskThreshold,
+    pub min_sample_size: Option<usize>,
+    pub outlier_detection: Option<bool>,
+
+    // Law-specific
+    pub benford_digit_tests: Option<Vec<DigitTest>>,
+    pub pareto_ratio: f64,
+    pub pareto_category_limit: Option<usize>,
+    pub zipf_rank_limit: Option<usize>,
+    pub zipf_frequency_cutoff: Option<f64>,
+    pub zipf_mandelbrot: bool,
+    pub power_law: Option<PowerLawOptions>,
+    pub inequality: Option<InequalityOptions>,
+    pub compare_law: Option<ComparedLaw>,
+
+    // Generation defaults
+    pub generate_count: Option<usize>,
+    pub generate_range_min: Option<f64>,
+    pub generate_range_max: Option<f64>,
+    pub generate_seed: Option<u64>,
+
+    // Execution and reporting
+    pub parallel: bool,
+    pub batch_size: Option<usize>,
+    pub show_details: bool,
+    pub show_recommendations: bool,
+}
+
+impl AnalysisSettings {
+    pub fn from_options(options: &LawkitOptions) -> Result<Self> {
+        let specific = options.lawkit_options.clone().unwrap_or_default();
+
+        let significance_level = match (specific.significance_level, specific.confidence_level) {
+            (Some(alpha), _) => alpha,
+            (None, Some(confidence)) => 1.0 - confidence,
+            (None, None) => DEFAULT_SIGNIFICANCE_LEVEL,
+        };
+        if !(significance_level > 0.0 && significance_level < 1.0) {
+            return Err(anyhow!(
+                "Significance level must be between 0 and 1 (exclusive): {significance_level}"
+            ));
         }
-        Value::Object(obj) => {
-            for value in obj.values() {
-                extract_numbers_recursive(value, numbers);
+
+        let risk_threshold = match specific.risk_threshold.as_deref() {
+            Some(threshold) => RiskThreshold::from_str(threshold).map_err(|e| anyhow!(e))?,
+            None => RiskThreshold::Auto,
+        };
+
+        let benford_digit_tests = specific
+            .benford_digits
+            .as_deref()
+            .map(DigitTest::parse_list)
+            .transpose()
+            .map_err(|e| anyhow!(e))?;
+        if let Some(base) = specific.benford_base {
+            if base != 10 {
+                return Err(anyhow!("Unsupported Benford base: {base} (supported: 10)"));
             }
         }
-        _ => {} // Ignore null, bool
-    }
-}
 
-pub(crate) fn get_first_digit(number: f64) -> Option<u8> {
-    if number <= 0.0 {
-        return None;
-    }
+        let compare_law = specific
+            .compare_law
+            .as_deref()
+            .map(ComparedLaw::parse)
+            .transpose()
+            .map_err(|e| anyhow!(e))?;
+
+        let pareto_ratio = specific.pareto_ratio.unwrap_or(DEFAULT_PARETO_RATIO);
+        if !(pareto_ratio > 0.0 && pareto_ratio < 1.0) {
+            return Err(anyhow!(
+                "Pareto ratio must be between 0 and 1 (exclusive): {pareto_ratio}"
+            ));
+        }
 
-    let mut num = number;
-    while num >= 10.0 {
-        num /= 10.0;
+        let numerals = if specific.enable_international_numerals.unwrap_or(false) {
+            NumeralSupport::International
+        } else if specific.enable_japanese_numerals.unwrap_or(false) {
+            NumeralSupport::Japanese
+        } else {
+            NumeralSupport::Ascii
+        };
+
+        let keys = KeySelection {
+            path: options
+                .path_filter
+                .as_deref()
+                .map(JsonPath::parse)
+                .transpose()
+                .map_err(|e| anyhow!(e))?,
+            include_keys: options.include_keys_regex.clone(),
+            exclude_keys: options.ignore_keys_regex.clone(),
+        };
+
+        let batch_size = if options.use_memory_optimization.unwrap_or(false) {
+            Some(options.batch_size.unwrap_or(DEFAULT_BATCH_SIZE).max(1))
+        } else {
+            options.batch_size
+        };
+
+        Ok(Self {
+            keys,
+            numerals,
+            min_value: specific.analysis_threshold,
+            memory_limit_mb: specific.memory_limit_mb,
+            significance_level,
+            significance_level_set: specific.significance_level.is_some()
+                || specific.confidence_level.is_some(),
+            risk_threshold,
+            min_sample_size: specific.min_sample_size,
+            benford_digit_tests,
+            outlier_detection: specific.enable_outlier_detection,
+            pareto_ratio,
+            pareto_category_limit: specific.pareto_category_limit,
+            zipf_rank_limit: specific.zipf_rank_limit,
+            zipf_frequency_cutoff: specific.zipf_frequency_cutoff,
+            zipf_mandelbrot: specific.zipf_mandelbrot.unwrap_or(false),
+            power_law: (specific.power_law_fit.unwrap_or(false)
+                || specific.power_law_bootstrap.is_some())
+            .then(|| PowerLawOptions {
+                bootstrap: specific
+                    .power_law_bootstrap
+                    .unwrap_or(PowerLawOptions::default().bootstrap),
+                ..PowerLawOptions::default()
+            }),
+            inequality: (specific.inequality_indices.unwrap_or(false)
+                || specific.inequality_bootstrap.is_some()
+                || specific.atkinson_epsilons.is_some()
+                || specific.top_share_percentiles.is_some())
+            .then(|| {
+                let defaults = InequalityOptions::default();
+                InequalityOptions {
+                    atkinson_epsilons: specific
+                        .atkinson_epsilons
+                        .clone()
+                        .unwrap_or(defaults.atkinson_epsilons),
+                    top_share_percentiles: specific
+                        .top_share_percentiles
+                        .clone()
+                        .unwrap_or(defaults.top_share_percentiles),
+                    bootstrap: specific.inequality_bootstrap.unwrap_or(defaults.bootstrap),
+                    confidence_level: 1.0 - significance_level,
+                    ..defaults
+                }
+            }),
+            compare_law,
+            generate_count: specific.generate_count,
+            generate_range_min: specific.generate_range_min,
+            generate_range_max: specific.generate_range_max,
+            generate_seed: specific.generate_seed,
+            parallel: specific.enable_parallel_processing.unwrap_or(false),
+            batch_size,
+            show_details: options.show_details.unwrap_or(false),
+            show_recommendations: options.show_recommendations.unwrap_or(true),
+        })
     }
-    while num < 1.0 {
-        num *= 10.0;
-    }
-
-    Some(num.floor() as u8)
-}
 
-pub(crate) fn calculate_chi_square(observed: &[f64], expected: &[f64]) -> f64 {
-    observed
-        .iter()
-        .zip(expected.iter())
-        .map(|(obs, exp)| (obs - exp).powi(2) / exp)
-        .sum()
-}
-
-pub(crate) fn calculate_p_value(chi_square: f64, degrees_of_freedom: i32) -> f64 {
-    // P-value approximation based on chi-square critical values
-    // For more accuracy, use a proper chi-square distribution function
-    if chi_square <= 0.0 {
-        return 1.0;
+    /// Minimum sample size used by `validate` when none is configured
+    pub fn validation_sample_size(&self) -> usize {
+        self.min_sample_size
+            .unwrap_or(DEFAULT_VALIDATION_SAMPLE_SIZE)
     }
 
-    match degrees_of_freedom {
-        8 => {
-            // Critical values for df=8: χ²(0.01)=20.09, χ²(0.05)=15.51, χ²(0.10)=13.36
-            if chi_square >= 20.09 {
-                0.01 // p <= 0.01
-            } else if chi_square >= 15.51 {
-                0.05 // p <= 0.05
-            } else if chi_square >= 13.36 {
-                0.10 // p <= 0.10
-            } else {
-                0.50 // p > 0.10
-            }
-        }
-        _ => {
-            // Fallback approximation
-            if chi_square >= 20.0 {
-                0.01
-            } else if chi_square >= 15.0 {
-                0.05
-            } else if chi_square >= 12.0 {
-                0.10
-            } else {
-                0.50
-            }
+    /// Reject samples smaller than the configured `min_sample_size`
+    pub fn check_sample_size(&self, count: usize) -> Result<()> {
+        match self.min_sample_size {
+            Some(min) if count < min => Err(anyhow!(
+                "Insufficient data: {count} numbers (minimum sample size: {min})"
+            )),
+            _ => Ok(()),
         }
     }
-}
 
-pub(crate) fn calculate_mad(observed: &[f64], expected: &[f64]) -> f64 {
-    observed
-        .iter()
-        .zip(expected.iter())
-        .map(|(obs, exp)| (obs - exp).abs())
-        .sum::<f64>()
-        / observed.len() as f64
-}
-
-pub(crate) fn calculate_zipf_coefficient(_ranks: &[f64], _frequencies: &[f64]) -> f64 {
-    // Simplified Zipf coefficient calculation
-    // In a real implementation, use linear regression on log-log plot
-    -1.0 // Default Zipf coefficient
-}
-
-pub(crate) fn calculate_correlation(x: &[f64], y: &[f64]) -> f64 {
-    if x.len() != y.len() || x.is_empty() {
-        return 0.0;
+    /// The significance level, if the caller set one through `significance_level`
+    /// or `confidence_level`
+    pub fn requested_significance_level(&self) -> Option<f64> {
+        self.significance_level_set
+            .then_some(self.significance_level)
     }
 
-    let mean_x = x.iter().sum::<f64>() / x.len() as f64;
-    let mean_y = y.iter().sum::<f64>() / y.len() as f64;
-
-    let numerator: f64 = x
-        .iter()
-        .zip(y.iter())
-        .map(|(xi, yi)| (xi - mean_x) * (yi - mean_y))
-        .sum();
-
-    let denom_x: f64 = x.iter().map(|xi| (xi - mean_x).powi(2)).sum();
-    let denom_y: f64 = y.iter().map(|yi| (yi - mean_y).powi(2)).sum();
-
-    if denom_x == 0.0 || denom_y == 0.0 {
-        0.0
-    } else {
-        numerator / (denom_x * denom_y).sqrt()
+    /// Whether `pareto_ratio` differs from the 80/20 default
+    pub fn has_custom_pareto_ratio(&self) -> bool {
+        (self.pareto_ratio - DEFAULT_PARETO_RATIO).abs() > f64::EPSILON
     }
 }
 
-pub(crate) fn calculate_skewness(numbers: &[f64], mean: f64, std_dev: f64) -> f64 {
-    if std_dev == 0.0 {
-        return 0.0;
+// Helper functions
+pub(crate) fn extract_numbers_from_value(
+    value: &Value,
+    settings: &AnalysisSettings,
+) -> Result<Vec<f64>> {
+    let mut numbers = Vec::new();
+    settings.keys.visit_json(value, &mut |_, leaf| match leaf {
+        Value::Number(n) => numbers.extend(n.as_f64()),
+        Value::String(s) => numbers.extend(parse_number_string(s, settings.numerals)),
+        _ => {}
+    });
+    // Strings such as "NaN" and "inf" parse as floats but are not data points
+    numbers.retain(|x| x.is_finite());
+
+    if let Some(min_value) = settings.min_value {
+        numbers.retain(|x| x.abs() >= min_value);
     }
 
-    let n = numbers.len() as f64;
-    let sum_cubed = numbers
-        .iter()
-        .map(|x| ((x - mean) / std_dev).powi(3))
-        .sum::<f64>();
-
-    sum_cubed / n
-}
-
-pub(crate) fn calculate_kurtosis(numbers: &[f64], mean: f64, std_dev: f64) -> f64 {
-    if std_dev == 0.0 {
-        return 0.0;
+    if let Some(limit_mb) = settings.memory_limit_mb {
+        let required_bytes = numbers.len() * std::mem::size_of::<f64>();
+        if required_bytes > limit_mb * 1024 * 1024 {
+            return Err(anyhow!(
+                "Input needs {:.1} MB, exceeding memory limit of {limit_mb} MB",
+                required_bytes as f64 / (1024.0 * 1024.0)
+            ));
+        }
     }
 
-    let n = numbers.len() as f64;
-    let sum_fourth = numbers
-        .iter()
-        .map(|x| ((x - mean) / std_dev).powi(4))
-        .sum::<f64>();
+    Ok(numbers)
+}
 
-    (sum_fourth / n) - 3.0 // Excess kurtosis
+fn parse_number_string(s: &str, numerals: NumeralSupport) -> Option<f64> {
+    let converted = match numerals {
+        NumeralSupport::Ascii => return s.parse::<f64>().ok(),
+        NumeralSupport::Japanese => convert_japanese_numerals(s),
+        NumeralSupport::International => convert_international_numerals(s),
+    };
+    converted.trim().parse::<f64>().ok()
 }
 
-pub(crate) fn calculate_normality_p_value(numbers: &[f64]) -> f64 {
-    // Simplified normality test
-    // In a real implementation, use Shapiro-Wilk or similar
-    if numbers.len() < 3 {
-        return 0.0;
+/// Count first digits 1-9, in `batch_size` chunks (in parallel if enabled)
+pub(crate) fn count_first_digits(numbers: &[f64], settings: &AnalysisSettings) -> [usize; 9] {
+    fn count_chunk(chunk: &[f64]) -> [usize; 9] {
+        let mut counts = [0; 9];
+        for &num in chunk {
+            if let Some(digit) = get_first_digit(num) {
+                counts[digit as usize - 1] += 1;
+            }
+        }
+        counts
     }
 
-    let mean = numbers.iter().sum::<f64>() / numbers.len() as f64;
-    let variance = numbers.iter().map(|x| (x - mean).powi(2)).sum::<f64>() / numbers.len() as f64;
-    let std_dev = variance.sqrt();
-
-    let skewness = calculate_skewness(numbers, mean, std_dev);
-    let kurtosis = calculate_kurtosis(numbers, mean, std_dev);
+    fn add_counts(mut a: [usize; 9], b: [usize; 9]) -> [usize; 9] {
+        for (x, y) in a.iter_mut().zip(b) {
+            *x += y;
+        }
+        a
+    }
 
-    // Simple heuristic
-    if skewness.abs() < 0.5 && kurtosis.abs() < 1.0 {
-        0.8 // Likely normal
-    } else if skewness.abs() > 2.0 || kurtosis.abs() > 3.0 {
-        0.01 // Clearly non-normal
+    let batch_size = settings.batch_size.unwrap_or(DEFAULT_BATCH_SIZE).max(1);
+    if settings.parallel {
+        numbers
+            .par_chunks(batch_size)
+            .map(count_chunk)
+            .reduce(|| [0; 9], add_counts)
+    } else if settings.batch_size.is_some() {
+        numbers
+            .chunks(batch_size)
+            .map(count_chunk)
+            .fold([0; 9], add_counts)
     } else {
-        0.3 // Moderate deviation
+        count_chunk(numbers)
     }
 }
 
-pub(crate) fn calculate_poisson_p_value(variance_ratio: f64, _sample_size: usize) -> f64 {
-    // Simplified Poisson test
-    let deviation = (variance_ratio - 1.0).abs();
+/// Values outside the 1.5 × IQR fences
+pub(crate) fn detect_iqr_outliers(numbers: &[f64]) -> Vec<f64> {
+    let q1 = calculate_percentile(numbers, 0.25);
+    let q3 = calculate_percentile(numbers, 0.75);
+    let iqr = q3 - q1;
+    let lower_fence = q1 - 1.5 * iqr;
+    let upper_fence = q3 + 1.5 * iqr;
 
-    if deviation < 0.1 {
-        0.8 // Likely Poisson
-    } else if deviation > 0.5 {
-        0.01 // Clearly non-Poisson
-    } else {
-        0.3 // Moderate deviation
-    }
+    numbers
+        .iter()
+        .cloned()
+        .filter(|&x| x < lower_fence || x > upper_fence)
+        .collect()
 }
 
 pub(crate) fn calculate_percentile(numbers: &[f64], percentile: f64) -> f64 {
     let mut sorted = numbers.to_vec();
-    sorted.sort_by(|a, b| a.partial_cmp(b).unwrap());
+    sorted.sort_by(f64::total_cmp);
 
     let index = (percentile * (sorted.len() - 1) as f64).round() as usize;
     sorted[index.min(sorted.len() - 1)]
 }
 
-pub(crate) fn generate_recommendations(laws: &[String], risks: &[String]) -> Vec<String> {
+pub(crate) fn generate_recommendations(laws: &[String], risks: &[RiskLevel]) -> Vec<String> {
     let mut recommendations = Vec::new();
 
-    let high_risk_count = risks.iter().filter(|&r| r == "HIGH").count();
+    let high_risk_count = risks.iter().filter(|&&r| r >= RiskLevel::High).count();
 
     if high_risk_count == 0 {
         recommendations.push("Data appears to follow expected statistical patterns".to_string());
@@ -1,3 +1,6 @@
+use crate::common::risk::RiskLevel;
+use serde::Serialize;
+
 /// Benford's Law expected percentages for first digits 1-9
 pub const BENFORD_EXPECTED_PERCENTAGES: [f64; 9] = [
     30.103, // 1
@@ -53,6 +56,271 @@ pub fn calculate_digit_distribution(numbers: &[f64]) -> [f64; 9] {
     distribution
 }
 
+/// Nigrini's digit tests
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum DigitTest {
+    First,      // 1-9
+    Second,     // 0-9
+    FirstTwo,   // 10-99
+    FirstThree, // 100-999
+    LastTwo,    // 00-99
+}
+
+impl DigitTest {
+    pub const ALL: [DigitTest; 5] = [
+        DigitTest::First,
+        DigitTest::Second,
+        DigitTest::FirstTwo,
+        DigitTest::FirstThree,
+        DigitTest::LastTwo,
+    ];
+
+    /// Name accepted by `--digits`
+    pub fn name(&self) -> &'static str {
+        match self {
+            DigitTest::First => "first",
+            DigitTest::Second => "second",
+            DigitTest::FirstTwo => "first-two",
+            DigitTest::FirstThree => "first-three",
+            DigitTest::LastTwo => "last-two",
+        }
+    }
+
+    /// Human-readable title for reports
+    pub fn title(&self) -> &'static str {
+        match self {
+            DigitTest::First => "First Digit",
+            DigitTest::Second => "Second Digit",
+            DigitTest::FirstTwo => "First-Two Digits",
+            DigitTest::FirstThree => "First-Three Digits",
+            DigitTest::LastTwo => "Last-Two Digits",
+        }
+    }
+
+    /// Digit value represented by the first bin
+    pub fn first_value(&self) -> u32 {
+        match self {
+            DigitTest::First => 1,
+            DigitTest::Second | DigitTest::LastTwo => 0,
+            DigitTest::FirstTwo => 10,
+            DigitTest::FirstThree => 100,
+        }
+    }
+
+    pub fn bin_count(&self) -> usize {
+        match self {
+            DigitTest::First => 9,
+            DigitTest::Second => 10,
+            DigitTest::FirstTwo => 90,
+            DigitTest::FirstThree => 900,
+            DigitTest::LastTwo => 100,
+        }
+    }
+
+    pub fn degrees_of_freedom(&self) -> i32 {
+        self.bin_count() as i32 - 1
+    }
+
+    /// Label for a bin (last-two digits keep their leading zero)
+    pub fn bin_label(&self, index: usize) -> String {
+        let value = self.first_value() + index as u32;
+        match self {
+            DigitTest::LastTwo => format!("{value:02}"),
+            _ => value.to_string(),
+        }
+    }
+
+    /// Expected percentages for each bin
+    pub fn expected_distribution(&self) -> Vec<f64> {
+        match self {
+            DigitTest::First => BENFORD_EXPECTED_PERCENTAGES.to_vec(),
+            // P(d2) = Σ_{d1=1..9} log10(1 + 1/(10·d1 + d2))
+            DigitTest::Second => (0..10)
+                .map(|d2| {
+                    (1..10)
+                        .map(|d1| (1.0 + 1.0 / (10 * d1 + d2) as f64).log10())
+                        .sum::<f64>()
+                        * 100.0
+                })
+                .collect(),
+            DigitTest::FirstTwo | DigitTest::FirstThree => (0..self.bin_count())
+                .map(|i| {
+                    let d = (self.first_value() + i as u32) as f64;
+                    (1.0 + 1.0 / d).log10() * 100.0
+                })
+                .collect(),
+            // Last digits of naturally occurring numbers are uniform
+            DigitTest::LastTwo => vec![1.0; 100],
+        }
+    }
+
+    /// Bin index for a number, or None if the number is too small for this test
+    ///
+    /// Second, first-two and last-two digit tests need |x| ≥ 10, first-three needs |x| ≥ 100.
+    pub fn bin_index(&self, number: f64) -> Option<usize> {
+        let value = match self {
+            DigitTest::First => get_first_digit(number).map(u32::from),
+            DigitTest::Second => leading_digits(number, 2).map(|d| d % 10),
+            DigitTest::FirstTwo => leading_digits(number, 2),
+            DigitTest::FirstThree => leading_digits(number, 3),
+            DigitTest::LastTwo => {
+                let abs_num = number.abs();
+                (abs_num.is_finite() && abs_num >= 10.0).then(|| (abs_num.trunc() % 100.0) as u32)
+            }
+        }?;
+        Some((value - self.first_value()) as usize)
+    }
+
+    /// Nigrini's MAD critical values in percentage points:
+    /// upper bounds of close, acceptable and marginally acceptable conformity
+    ///
+    /// Nigrini publishes no ranges for the last-two digits test; it reuses the
+    /// first-two digits ranges, whose expected bin sizes are comparable.
+    pub fn mad_critical_values(&self) -> [f64; 3] {
+        match self {
+            DigitTest::First => [0.6, 1.2, 1.5],
+            DigitTest::Second => [0.8, 1.0, 1.2],
+            DigitTest::FirstTwo | DigitTest::LastTwo => [0.12, 0.18, 0.22],
+            DigitTest::FirstThree => [0.036, 0.044, 0.050],
+        }
+    }
+
+    /// Classify a MAD (in percentage points) against Nigrini's ranges
+    pub fn conformity(&self, mean_absolute_deviation: f64) -> Conformity {
+        let [close, acceptable, marginal] = self.mad_critical_values();
+        if mean_absolute_deviation <= close {
+            Conformity::Close
+        } else if mean_absolute_deviation <= acceptable {
+            Conformity::Acceptable
+        } else if mean_absolute_deviation <= marginal {
+            Conformity::Marginal
+        } else {
+            Conformity::Nonconformity
+        }
+    }
+
+    /// Count how many numbers fall into each bin
+    pub fn count_bins(&self, numbers: &[f64]) -> Vec<usize> {
+        let mut counts = vec![0; self.bin_count()];
+        for &number in numbers {
+            if let Some(index) = self.bin_index(number) {
+                counts[index] += 1;
+            }
+        }
+        counts
+    }
+
+    /// Parse a comma-separated list such as "first,first-two"
+    ///
+    /// "both" means first and second digits, "all" runs every test.
+    pub fn parse_list(s: &str) -> std::result::Result<Vec<Self>, String> {
+        let mut tests = Vec::new();
+        for part in s.split(',').map(str::trim).filter(|p| !p.is_empty()) {
+            let parsed = match part.to_lowercase().as_str() {
+                "both" => vec![DigitTest::First, DigitTest::Second],
+                "all" => DigitTest::ALL.to_vec(),
+                other => vec![other.parse()?],
+            };
+            for test in parsed {
+                if !tests.contains(&test) {
+                    tests.push(test);
+                }
+            }
+        }
+        if tests.is_empty() {
+            return Err("no digit test specified".to_string());
+        }
+        Ok(tests)
+    }
+}
+
+impl std::str::FromStr for DigitTest {
+    type Err = String;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "first" | "1" => Ok(DigitTest::First),
+            "second" | "2" => Ok(DigitTest::Second),
+            "first-two" | "first2" | "12" => Ok(DigitTest::FirstTwo),
+            "first-three" | "first3" | "123" => Ok(DigitTest::FirstThree),
+            "last-two" | "last2" => Ok(DigitTest::LastTwo),
+            _ => Err(format!(
+                "Invalid digit test: {s}. Use first, second, first-two, first-three, last-two, both or all"
+            )),
+        }
+    }
+}
+
+impl std::fmt::Display for DigitTest {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.name())
+    }
+}
+
+/// Nigrini's MAD-based conformity classes
+///
+/// Unlike p-values, MAD does not shrink with sample size, which makes it usable on large ledgers.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Conformity {
+    Close,
+    Acceptable,
+    Marginal,
+    Nonconformity,
+}
+
+impl Conformity {
+    /// Machine-readable name (matches the serialized form)
+    pub fn name(&self) -> &'static str {
+        match self {
+            Conformity::Close => "close",
+            Conformity::Acceptable => "acceptable",
+            Conformity::Marginal => "marginal",
+            Conformity::Nonconformity => "nonconformity",
+        }
+    }
+
+    pub fn risk_level(&self) -> RiskLevel {
+        match self {
+            Conformity::Close => RiskLevel::Low,
+            Conformity::Acceptable => RiskLevel::Medium,
+            Conformity::Marginal => RiskLevel::High,
+            Conformity::Nonconformity => RiskLevel::Critical,
+        }
+    }
+}
+
+impl std::fmt::Display for Conformity {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Conformity::Close => write!(f, "Close conformity"),
+            Conformity::Acceptable => write!(f, "Acceptable conformity"),
+            Conformity::Marginal => write!(f, "Marginally acceptable conformity"),
+            Conformity::Nonconformity => write!(f, "Nonconformity"),
+        }
+    }
+}
+
+/// First `count` significant digits as an integer, for numbers with at least `count` integer digits
+fn leading_digits(number: f64, count: usize) -> Option<u32> {
+    let abs_num = number.abs();
+    if !abs_num.is_finite() || abs_num < 10f64.powi(count as i32 - 1) {
+        return None;
+    }
+
+    // Shortest round-trip scientific notation avoids division rounding (e.g. "1.2e3")
+    let formatted = format!("{abs_num:e}");
+    let mantissa = formatted.split('e').next()?;
+    let digits: String = mantissa
+        .chars()
+        .filter(char::is_ascii_digit)
+        .chain(std::iter::repeat('0'))
+        .take(count)
+        .collect();
+    digits.parse().ok()
+}
+
 /// Perform Benford's Law analysis on a dataset
 pub fn analyze_benford_law(
     numbers: &[f64],
@@ -60,3 +328,70 @@ pub fn analyze_benford_law(
 ) -> crate::error::Result<crate::laws::benford::BenfordResult> {
     crate::laws::benford::BenfordResult::new(dataset_name.to_string(), numbers)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_expected_distributions_sum_to_100() {
+        for test in DigitTest::ALL {
+            let expected = test.expected_distribution();
+            assert_eq!(expected.len(), test.bin_count());
+            let total: f64 = expected.iter().sum();
+            assert!((total - 100.0).abs() < 0.01, "{test}: {total}");
+        }
+    }
+
+    #[test]
+    fn test_second_digit_expected_values() {
+        let expected = DigitTest::Second.expected_distribution();
+        // Nigrini's table: 0 → 11.968%, 9 → 8.500%
+        assert!((expected[0] - 11.968).abs() < 0.001);
+        assert!((expected[9] - 8.500).abs() < 0.001);
+    }
+
+    #[test]
+    fn test_bin_index() {
+        assert_eq!(DigitTest::First.bin_index(4567.0), Some(3));
+        assert_eq!(DigitTest::Second.bin_index(4567.0), Some(5));
+        assert_eq!(DigitTest::Second.bin_index(4.0), None);
+        assert_eq!(DigitTest::FirstTwo.bin_index(4567.0), Some(35));
+        assert_eq!(DigitTest::FirstTwo.bin_index(10.0), Some(0));
+        assert_eq!(DigitTest::FirstTwo.bin_index(-1.2e3), Some(2));
+        assert_eq!(DigitTest::FirstThree.bin_index(4567.0), Some(356));
+        assert_eq!(DigitTest::FirstThree.bin_index(45.0), None);
+        assert_eq!(DigitTest::LastTwo.bin_index(4567.89), Some(67));
+        assert_eq!(DigitTest::LastTwo.bin_index(1200.0), Some(0));
+        assert_eq!(DigitTest::LastTwo.bin_index(9.0), None);
+    }
+
+    #[test]
+    fn test_mad_conformity() {
+        assert_eq!(DigitTest::First.conformity(0.5), Conformity::Close);
+        assert_eq!(DigitTest::First.conformity(1.0), Conformity::Acceptable);
+        assert_eq!(DigitTest::First.conformity(1.4), Conformity::Marginal);
+        assert_eq!(DigitTest::First.conformity(1.6), Conformity::Nonconformity);
+        assert_eq!(DigitTest::FirstTwo.conformity(0.15), Conformity::Acceptable);
+        assert_eq!(
+            DigitTest::FirstThree.conformity(0.06),
+            Conformity::Nonconformity
+        );
+        assert_eq!(Conformity::Marginal.risk_level(), RiskLevel::High);
+    }
+
+    #[test]
+    fn test_parse_list() {
+        assert_eq!(
+            DigitTest::parse_list("first, first-two").unwrap(),
+            vec![DigitTest::First, DigitTest::FirstTwo]
+        );
+        assert_eq!(
+            DigitTest::parse_list("both").unwrap(),
+            vec![DigitTest::First, DigitTest::Second]
+        );
+        assert_eq!(DigitTest::parse_list("all").unwrap().len(), 5);
+        assert!(DigitTest::parse_list("third").is_err());
+        assert!(DigitTest::parse_list("").is_err());
+    }
+}
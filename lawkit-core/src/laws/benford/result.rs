@@ -1,7 +1,9 @@
-use crate::common::{filtering::RiskThreshold, risk::RiskLevel};
+use super::analysis::{Conformity, DigitTest};
+use crate::common::{filtering::RiskThreshold, input::NumberRecord, risk::RiskLevel};
 use crate::error::{BenfError, Result};
+use serde::Serialize;
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Serialize)]
 pub struct BenfordResult {
     pub dataset_name: String,
     pub numbers_analyzed: usize,
@@ -10,8 +12,145 @@ pub struct BenfordResult {
     pub chi_square: f64,
     pub p_value: f64,
     pub mean_absolute_deviation: f64,
+    pub conformity: Conformity, // Nigrini MAD conformity of the first-digit test
     pub risk_level: RiskLevel,
     pub verdict: String,
+    pub z_statistics: Vec<DigitZStatistic>, // Per-digit Z-statistics of the first-digit test
+    pub digit_tests: Vec<DigitTestResult>,  // Requested digit tests (see `with_digit_tests`)
+    pub drill_down: Vec<DigitDrillDown>, // Records behind significant digits (see `with_drill_down`)
+}
+
+/// Result of a single Nigrini digit test
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct DigitTestResult {
+    pub test: DigitTest,
+    pub numbers_analyzed: usize, // Numbers large enough for this test
+    pub observed_distribution: Vec<f64>, // Observed percentages, one per bin
+    pub expected_distribution: Vec<f64>, // Expected percentages, one per bin
+    pub chi_square: f64,
+    pub p_value: f64,
+    pub mean_absolute_deviation: f64,
+    pub conformity: Conformity, // Nigrini MAD conformity
+    pub risk_level: RiskLevel,
+    pub verdict: String,                    // p-value based verdict
+    pub z_statistics: Vec<DigitZStatistic>, // One per bin
+}
+
+/// Nigrini's per-bin Z-statistic (with continuity correction)
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct DigitZStatistic {
+    pub bin: String,   // Bin label, e.g. "1", "10" or "07"
+    pub observed: f64, // Observed percentage
+    pub expected: f64, // Expected percentage
+    pub z_statistic: f64,
+    pub p_value: f64,      // Two-sided
+    pub significant: bool, // p_value ≤ significance level
+}
+
+/// Input records whose digits fall into a significant bin
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct DigitDrillDown {
+    pub test: DigitTest,
+    pub bin: String,
+    pub z_statistic: f64,
+    pub records: Vec<NumberRecord>,
+}
+
+impl DigitTestResult {
+    pub fn new(
+        test: DigitTest,
+        numbers: &[f64],
+        threshold: &RiskThreshold,
+        significance_level: f64,
+    ) -> Self {
+        Self::from_counts(
+            test,
+            &test.count_bins(numbers),
+            threshold,
+            significance_level,
+        )
+    }
+
+    /// Build the test result from per-bin counts (`counts.len()` must equal `test.bin_count()`)
+    pub fn from_counts(
+        test: DigitTest,
+        counts: &[usize],
+        threshold: &RiskThreshold,
+        significance_level: f64,
+    ) -> Self {
+        let total_valid: usize = counts.iter().sum();
+        let observed_distribution: Vec<f64> = counts
+            .iter()
+            .map(|&count| {
+                if total_valid > 0 {
+                    count as f64 / total_valid as f64 * 100.0
+                } else {
+                    0.0
+                }
+            })
+            .collect();
+        let expected_distribution = test.expected_distribution();
+        let chi_square = crate::common::statistics::calculate_chi_square_from_percentages(
+            &observed_distribution,
+            &expected_distribution,
+            total_valid,
+        );
+        let p_value =
+            crate::common::statistics::calculate_p_value(chi_square, test.degrees_of_freedom());
+        let z_statistics = observed_distribution
+            .iter()
+            .zip(&expected_distribution)
+            .enumerate()
+            .map(|(i, (&observed, &expected))| {
+                let z_statistic = crate::common::statistics::digit_z_statistic(
+                    observed / 100.0,
+                    expected / 100.0,
+                    total_valid,
+                );
+                let z_p_value = crate::common::statistics::two_sided_normal_p_value(z_statistic);
+                DigitZStatistic {
+                    bin: test.bin_label(i),
+                    observed,
+                    expected,
+                    z_statistic,
+                    p_value: z_p_value,
+                    significant: total_valid > 0 && z_p_value <= significance_level,
+                }
+            })
+            .collect();
+        let mean_absolute_deviation = crate::common::statistics::calculate_mad(
+            &observed_distribution,
+            &expected_distribution,
+        );
+
+        // The verdict always reflects the p-value; the risk level follows the selected threshold
+        let p_value_risk = threshold.evaluate_risk_with_significance(p_value, significance_level);
+        let conformity = test.conformity(mean_absolute_deviation);
+        let risk_level = if threshold.uses_mad_conformity() {
+            conformity.risk_level()
+        } else {
+            p_value_risk
+        };
+
+        DigitTestResult {
+            test,
+            numbers_analyzed: total_valid,
+            observed_distribution,
+            expected_distribution,
+            chi_square,
+            p_value,
+            mean_absolute_deviation,
+            conformity,
+            risk_level,
+            verdict: verdict_for(p_value_risk),
+            z_statistics,
+        }
+    }
+
+    /// Bins whose Z-statistic is significant
+    pub fn significant_bins(&self) -> impl Iterator<Item = &DigitZStatistic> {
+        self.z_statistics.iter().filter(|z| z.significant)
+    }
 }
 
 impl BenfordResult {
@@ -25,116 +164,281 @@ impl BenfordResult {
         threshold: &RiskThreshold,
         min_count: usize,
     ) -> Result<Self> {
-        if numbers.is_empty() {
-            return Err(BenfError::NoNumbersFound);
-        }
+        Self::new_with_significance(dataset_name, numbers, threshold, min_count, 0.05)
+    }
 
-        // Check minimum count requirement
-        if numbers.len() < min_count {
-            return Err(BenfError::InsufficientData(numbers.len()));
+    /// Auto risk bands follow the confidence level: Critical < α/4, High < α/2, Medium < α
+    /// (α = 1 - confidence_level), which also flags significant per-digit Z-statistics
+    pub fn new_with_confidence(
+        dataset_name: String,
+        numbers: &[f64],
+        threshold: &RiskThreshold,
+        min_count: usize,
+        confidence_level: f64,
+    ) -> Result<Self> {
+        let adjusted_alpha = 1.0 - confidence_level;
+        let mut result = Self::new_with_significance(
+            dataset_name,
+            numbers,
+            threshold,
+            min_count,
+            adjusted_alpha,
+        )?;
+        if threshold.p_value_threshold().is_some() {
+            return Ok(result);
         }
 
-        // Issue warning for small datasets but continue analysis
-        if numbers.len() < 30 {
-            eprintln!("Warning: {numbers_analyzed} numbers analyzed. For reliable Benford's Law analysis, 30+ numbers recommended.", numbers_analyzed=numbers.len());
+        // Adjust risk assessment based on confidence level
+        let risk_level = if result.p_value < adjusted_alpha / 4.0 {
+            RiskLevel::Critical
+        } else if result.p_value < adjusted_alpha / 2.0 {
+            RiskLevel::High
+        } else if result.p_value < adjusted_alpha {
+            RiskLevel::Medium
+        } else {
+            RiskLevel::Low
+        };
+        result.verdict = verdict_for(risk_level);
+        if !threshold.uses_mad_conformity() {
+            result.risk_level = risk_level;
         }
+        Ok(result)
+    }
 
-        let digit_distribution = super::analysis::calculate_digit_distribution(numbers);
-        let expected_distribution = super::analysis::BENFORD_EXPECTED_PERCENTAGES;
-        let chi_square = crate::common::statistics::calculate_chi_square(
-            &digit_distribution,
-            &expected_distribution,
+    /// `significance_level` scales the Auto risk bands and flags significant per-digit Z-statistics
+    pub fn new_with_significance(
+        dataset_name: String,
+        numbers: &[f64],
+        threshold: &RiskThreshold,
+        min_count: usize,
+        significance_level: f64,
+    ) -> Result<Self> {
+        let mut counts = FirstDigitCounts::new();
+        counts.add_batch(numbers);
+        counts.into_result(dataset_name, threshold, min_count, significance_level)
+    }
+
+    /// Build a result from pre-counted first digits (index 0 = digit 1)
+    ///
+    /// `significance_level` scales the Auto risk bands (explicit thresholds ignore it)
+    /// and decides which per-digit Z-statistics are significant.
+    pub fn from_digit_counts(
+        dataset_name: String,
+        numbers_analyzed: usize,
+        digit_counts: &[usize; 9],
+        threshold: &RiskThreshold,
+        significance_level: f64,
+    ) -> Self {
+        let first_digit = DigitTestResult::from_counts(
+            DigitTest::First,
+            digit_counts,
+            threshold,
+            significance_level,
         );
-        let p_value = crate::common::statistics::calculate_p_value(chi_square, 8); // 8 degrees of freedom
-        let mean_absolute_deviation =
-            crate::common::statistics::calculate_mad(&digit_distribution, &expected_distribution);
-
-        // Use custom threshold if provided, otherwise use default logic
-        let risk_level = threshold.evaluate_risk(p_value);
-
-        let verdict = match risk_level {
-            RiskLevel::Low => "NORMAL_DISTRIBUTION".to_string(),
-            RiskLevel::Medium => "SLIGHT_DEVIATION".to_string(),
-            RiskLevel::High => "SIGNIFICANT_DEVIATION".to_string(),
-            RiskLevel::Critical => "STRONG_EVIDENCE_OF_MANIPULATION".to_string(),
-        };
+        let mut digit_distribution = [0.0; 9];
+        digit_distribution.copy_from_slice(&first_digit.observed_distribution);
 
-        Ok(BenfordResult {
+        BenfordResult {
             dataset_name,
-            numbers_analyzed: numbers.len(),
+            numbers_analyzed,
             digit_distribution,
-            expected_distribution,
-            chi_square,
-            p_value,
-            mean_absolute_deviation,
-            risk_level,
-            verdict,
-        })
+            expected_distribution: super::analysis::BENFORD_EXPECTED_PERCENTAGES,
+            chi_square: first_digit.chi_square,
+            p_value: first_digit.p_value,
+            mean_absolute_deviation: first_digit.mean_absolute_deviation,
+            conformity: first_digit.conformity,
+            risk_level: first_digit.risk_level,
+            verdict: first_digit.verdict,
+            z_statistics: first_digit.z_statistics,
+            digit_tests: Vec::new(),
+            drill_down: Vec::new(),
+        }
     }
 
-    pub fn new_with_confidence(
-        dataset_name: String,
+    /// Run additional digit tests on the same numbers
+    ///
+    /// The overall risk level and verdict become the most severe among the requested tests,
+    /// so `--digits first-two` is judged on the first-two digits alone.
+    pub fn with_digit_tests(
+        self,
         numbers: &[f64],
-        _threshold: &RiskThreshold,
+        tests: &[DigitTest],
+        threshold: &RiskThreshold,
+        significance_level: f64,
+    ) -> Self {
+        let counts: Vec<(DigitTest, Vec<usize>)> = tests
+            .iter()
+            .map(|&test| (test, test.count_bins(numbers)))
+            .collect();
+        self.with_digit_test_counts(&counts, threshold, significance_level)
+    }
+
+    /// Like `with_digit_tests`, from per-bin counts gathered without keeping the numbers
+    pub fn with_digit_test_counts(
+        mut self,
+        counts: &[(DigitTest, Vec<usize>)],
+        threshold: &RiskThreshold,
+        significance_level: f64,
+    ) -> Self {
+        self.digit_tests = counts
+            .iter()
+            .map(|(test, counts)| {
+                DigitTestResult::from_counts(*test, counts, threshold, significance_level)
+            })
+            .collect();
+
+        if let Some(worst) = self.digit_tests.iter().max_by_key(|t| t.risk_level) {
+            self.risk_level = worst.risk_level;
+            self.verdict = worst.verdict.clone();
+        }
+        self
+    }
+
+    /// Collect the records behind every significant bin
+    ///
+    /// Uses the requested digit tests, or the first-digit test when none were requested.
+    /// `records` should be the same numbers that were analyzed, with their source locations.
+    pub fn with_drill_down(mut self, records: &[NumberRecord]) -> Self {
+        let tests: Vec<(DigitTest, &[DigitZStatistic])> = if self.digit_tests.is_empty() {
+            vec![(DigitTest::First, &self.z_statistics)]
+        } else {
+            self.digit_tests
+                .iter()
+                .map(|t| (t.test, t.z_statistics.as_slice()))
+                .collect()
+        };
+
+        let drill_down = tests
+            .into_iter()
+            .flat_map(|(test, z_statistics)| {
+                z_statistics
+                    .iter()
+                    .enumerate()
+                    .filter(|(_, z)| z.significant)
+                    .map(move |(index, z)| DigitDrillDown {
+                        test,
+                        bin: z.bin.clone(),
+                        z_statistic: z.z_statistic,
+                        records: records
+                            .iter()
+                            .filter(|record| test.bin_index(record.value) == Some(index))
+                            .cloned()
+                            .collect(),
+                    })
+            })
+            .collect();
+
+        self.drill_down = drill_down;
+        self
+    }
+}
+
+/// First-digit counts gathered one batch at a time, so large input need not be held in memory
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct FirstDigitCounts {
+    numbers_analyzed: usize,
+    digit_counts: [usize; 9], // index 0 = digit 1
+}
+
+impl FirstDigitCounts {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn add_batch(&mut self, numbers: &[f64]) {
+        self.numbers_analyzed += numbers.len();
+        for &number in numbers {
+            if let Some(digit) = super::analysis::get_first_digit(number) {
+                self.digit_counts[(digit - 1) as usize] += 1;
+            }
+        }
+    }
+
+    pub fn numbers_analyzed(&self) -> usize {
+        self.numbers_analyzed
+    }
+
+    /// Analyze the counts the way `BenfordResult::new_with_significance` analyzes numbers
+    pub fn into_result(
+        self,
+        dataset_name: String,
+        threshold: &RiskThreshold,
         min_count: usize,
-        confidence_level: f64,
-    ) -> Result<Self> {
-        if numbers.is_empty() {
+        significance_level: f64,
+    ) -> Result<BenfordResult> {
+        let numbers_analyzed = self.numbers_analyzed;
+        if numbers_analyzed == 0 {
             return Err(BenfError::NoNumbersFound);
         }
 
         // Check minimum count requirement
-        if numbers.len() < min_count {
-            return Err(BenfError::InsufficientData(numbers.len()));
+        if numbers_analyzed < min_count {
+            return Err(BenfError::InsufficientData(numbers_analyzed));
         }
 
         // Issue warning for small datasets but continue analysis
-        if numbers.len() < 30 {
-            eprintln!("Warning: {numbers_analyzed} numbers analyzed. For reliable Benford's Law analysis, 30+ numbers recommended.", numbers_analyzed=numbers.len());
+        if numbers_analyzed < 30 {
+            eprintln!("Warning: {numbers_analyzed} numbers analyzed. For reliable Benford's Law analysis, 30+ numbers recommended.");
         }
 
-        let digit_distribution = super::analysis::calculate_digit_distribution(numbers);
-        let expected_distribution = super::analysis::BENFORD_EXPECTED_PERCENTAGES;
-        let chi_square = crate::common::statistics::calculate_chi_square(
-            &digit_distribution,
-            &expected_distribution,
-        );
+        Ok(BenfordResult::from_digit_counts(
+            dataset_name,
+            numbers_analyzed,
+            &self.digit_counts,
+            threshold,
+            significance_level,
+        ))
+    }
+}
 
-        // Use standard p-value calculation (confidence level adjustment in interpretation)
-        let p_value = crate::common::statistics::calculate_p_value(chi_square, 8);
+fn verdict_for(risk_level: RiskLevel) -> String {
+    match risk_level {
+        RiskLevel::Low => "NORMAL_DISTRIBUTION".to_string(),
+        RiskLevel::Medium => "SLIGHT_DEVIATION".to_string(),
+        RiskLevel::High => "SIGNIFICANT_DEVIATION".to_string(),
+        RiskLevel::Critical => "STRONG_EVIDENCE_OF_MANIPULATION".to_string(),
+    }
+}
 
-        let mean_absolute_deviation =
-            crate::common::statistics::calculate_mad(&digit_distribution, &expected_distribution);
+#[cfg(test)]
+mod tests {
+    use super::*;
 
-        // Adjust risk assessment based on confidence level
-        let adjusted_alpha = 1.0 - confidence_level;
-        let risk_level = if p_value < adjusted_alpha / 4.0 {
-            RiskLevel::Critical
-        } else if p_value < adjusted_alpha / 2.0 {
-            RiskLevel::High
-        } else if p_value < adjusted_alpha {
-            RiskLevel::Medium
-        } else {
-            RiskLevel::Low
-        };
-
-        let verdict = match risk_level {
-            RiskLevel::Low => "NORMAL_DISTRIBUTION".to_string(),
-            RiskLevel::Medium => "SLIGHT_DEVIATION".to_string(),
-            RiskLevel::High => "SIGNIFICANT_DEVIATION".to_string(),
-            RiskLevel::Critical => "STRONG_EVIDENCE_OF_MANIPULATION".to_string(),
+    #[test]
+    fn test_confidence_bands() {
+        // 1 と 9 に偏らせた分布で p 値を中程度にする
+        let mut numbers = Vec::new();
+        for (digit, count) in [
+            (1, 60),
+            (2, 35),
+            (3, 25),
+            (4, 19),
+            (5, 16),
+            (6, 13),
+            (7, 12),
+            (8, 10),
+            (9, 22),
+        ] {
+            numbers.extend((0..count).map(|i| digit as f64 * 100.0 + i as f64));
+        }
+        let p_value = BenfordResult::new("bands".to_string(), &numbers)
+            .unwrap()
+            .p_value;
+        let at = |confidence: f64| {
+            BenfordResult::new_with_confidence(
+                "bands".to_string(),
+                &numbers,
+                &RiskThreshold::Auto,
+                5,
+                confidence,
+            )
+            .unwrap()
+            .risk_level
         };
 
-        Ok(BenfordResult {
-            dataset_name,
-            numbers_analyzed: numbers.len(),
-            digit_distribution,
-            expected_distribution,
-            chi_square,
-            p_value,
-            mean_absolute_deviation,
-            risk_level,
-            verdict,
-        })
+        assert_eq!(at(1.0 - p_value * 5.0), RiskLevel::Critical);
+        assert_eq!(at(1.0 - p_value * 3.0), RiskLevel::High);
+        assert_eq!(at(1.0 - p_value * 1.5), RiskLevel::Medium);
+        assert_eq!(at(1.0 - p_value / 2.0), RiskLevel::Low);
     }
 }
@@ -0,0 +1,679 @@
+use super::result::{
+    CategoryComparison, ComparedLaw, ComparisonResult, EffectMagnitude, EffectSize, TwoSampleTest,
+};
+use super::two_sample::{
+    anderson_darling_k_sample, chi_square_homogeneity, cliffs_delta, kolmogorov_smirnov_two_sample,
+    mean_and_std_dev, pool_sparse_columns, spearman_correlation,
+};
+use crate::{
+    common::{
+        risk::RiskLevel,
+        statistics::{calculate_mad, normal_quantile, two_sided_normal_p_value},
+    },
+    error::{BenfError, Result},
+    laws::{
+        benford::{DigitTest, BENFORD_EXPECTED_PERCENTAGES},
+        pareto::{calculate_inequality_indices, ConfidenceInterval, InequalityOptions},
+        zipf::analyze_text_zipf_from_frequencies,
+    },
+};
+use std::collections::{BTreeMap, HashMap, HashSet};
+
+/// 各データセットに必要な最小件数
+pub const MIN_COMPARISON_SIZE: usize = 5;
+
+/// Zipf 比較の分割表と構成比に使う上位項目の数
+const ZIPF_TOP_ITEMS: usize = 20;
+
+/// Cramér の V の目安（Cohen の w と同じ）
+const CRAMERS_V_THRESHOLDS: [f64; 3] = [0.1, 0.3, 0.5];
+/// Cohen の d の目安
+const COHENS_D_THRESHOLDS: [f64; 3] = [0.2, 0.5, 0.8];
+/// Cliff の δ の目安（Romano et al. 2006）
+const CLIFFS_DELTA_THRESHOLDS: [f64; 3] = [0.147, 0.33, 0.474];
+
+/// 2 つの数値データセットを指定した法則の観点で比較する
+///
+/// Zipf の場合は各数値を 1 つの項目（ID やコード）とみなして出現頻度を比べる。
+/// 単語の頻度を比べるには [`compare_word_frequencies`] を使う。
+pub fn compare_datasets(
+    law: ComparedLaw,
+    name_a: &str,
+    a: &[f64],
+    name_b: &str,
+    b: &[f64],
+    significance_level: f64,
+) -> Result<ComparisonResult> {
+    validate_significance_level(significance_level)?;
+    for values in [a, b] {
+        if values.is_empty() {
+            return Err(BenfError::NoNumbersFound);
+        }
+        if values.len() < MIN_COMPARISON_SIZE {
+            return Err(BenfError::InsufficientData(values.len()));
+        }
+    }
+
+    let comparison = Comparison {
+        name_a,
+        name_b,
+        significance_level,
+    };
+    match law {
+        ComparedLaw::Benford => comparison.benford(a, b),
+        ComparedLaw::Poisson => comparison.poisson(a, b),
+        ComparedLaw::Normal => Ok(comparison.normal(a, b)),
+        ComparedLaw::Pareto => Ok(comparison.pareto(a, b)),
+        ComparedLaw::Zipf => comparison.zipf(&value_frequencies(a), &value_frequencies(b)),
+    }
+}
+
+/// 2 つの項目頻度表（単語と出現回数）を Zipf の法則の観点で比較する
+pub fn compare_word_frequencies(
+    name_a: &str,
+    a: &[(String, usize)],
+    name_b: &str,
+    b: &[(String, usize)],
+    significance_level: f64,
+) -> Result<ComparisonResult> {
+    validate_significance_level(significance_level)?;
+    Comparison {
+        name_a,
+        name_b,
+        significance_level,
+    }
+    .zipf(a, b)
+}
+
+fn validate_significance_level(significance_level: f64) -> Result<()> {
+    if !(significance_level > 0.0 && significance_level < 1.0) {
+        return Err(BenfError::InvalidInput(format!(
+            "significance level must be between 0 and 1, got {significance_level}"
+        )));
+    }
+    Ok(())
+}
+
+struct Comparison<'a> {
+    name_a: &'a str,
+    name_b: &'a str,
+    significance_level: f64,
+}
+
+impl Comparison<'_> {
+    fn result(
+        &self,
+        law: ComparedLaw,
+        sizes: (usize, usize),
+        tests: Vec<TwoSampleTest>,
+        effect_sizes: Vec<EffectSize>,
+        categories: Vec<CategoryComparison>,
+    ) -> ComparisonResult {
+        ComparisonResult {
+            law,
+            dataset_a: self.name_a.to_string(),
+            dataset_b: self.name_b.to_string(),
+            size_a: sizes.0,
+            size_b: sizes.1,
+            significance_level: self.significance_level,
+            tests,
+            effect_sizes,
+            categories,
+            significant: false,
+            risk_level: RiskLevel::Low,
+        }
+        .assess()
+    }
+
+    /// 推定値 ± z·SE の信頼区間
+    fn interval(&self, estimate: f64, standard_error: f64) -> ConfidenceInterval {
+        let z = normal_quantile(1.0 - self.significance_level / 2.0);
+        ConfidenceInterval {
+            level: 1.0 - self.significance_level,
+            lower: estimate - z * standard_error,
+            upper: estimate + z * standard_error,
+        }
+    }
+
+    /// 比の推定値を対数スケールの SE で区間にする
+    fn ratio_interval(&self, ratio: f64, log_standard_error: f64) -> ConfidenceInterval {
+        let log_interval = self.interval(ratio.ln(), log_standard_error);
+        ConfidenceInterval {
+            level: log_interval.level,
+            lower: log_interval.lower.exp(),
+            upper: log_interval.upper.exp(),
+        }
+    }
+
+    /// 第 1 桁の分布の一様性
+    fn benford(&self, a: &[f64], b: &[f64]) -> Result<ComparisonResult> {
+        let counts_a = first_digit_counts(a)?;
+        let counts_b = first_digit_counts(b)?;
+        let size_a = counts_a.iter().sum::<f64>();
+        let size_b = counts_b.iter().sum::<f64>();
+
+        let (table, _) = pool_sparse_columns(&[counts_a.clone(), counts_b.clone()]);
+        let homogeneity = chi_square_homogeneity(&table);
+
+        let percentages = |counts: &[f64], total: f64| -> Vec<f64> {
+            counts.iter().map(|c| c / total * 100.0).collect()
+        };
+        let mad_a = calculate_mad(
+            &percentages(&counts_a, size_a),
+            &BENFORD_EXPECTED_PERCENTAGES,
+        );
+        let mad_b = calculate_mad(
+            &percentages(&counts_b, size_b),
+            &BENFORD_EXPECTED_PERCENTAGES,
+        );
+
+        let categories = (0..counts_a.len())
+            .map(|i| CategoryComparison {
+                category: DigitTest::First.bin_label(i),
+                proportion_a: counts_a[i] / size_a,
+                proportion_b: counts_b[i] / size_b,
+            })
+            .collect();
+
+        Ok(self.result(
+            ComparedLaw::Benford,
+            (size_a as usize, size_b as usize),
+            vec![homogeneity_test(&homogeneity)],
+            vec![
+                cramers_v_effect(homogeneity.cramers_v),
+                EffectSize {
+                    name: "MAD change vs Benford (pp)".to_string(),
+                    value: mad_b - mad_a,
+                    confidence_interval: None,
+                    magnitude: None,
+                },
+            ],
+            categories,
+        ))
+    }
+
+    /// 件数の分布の一様性と平均発生率の差
+    fn poisson(&self, a: &[f64], b: &[f64]) -> Result<ComparisonResult> {
+        for &value in a.iter().chain(b) {
+            if value < 0.0 || value.fract() != 0.0 {
+                return Err(BenfError::ParseError(
+                    "ポアソン分布分析には非負整数値が必要です".to_string(),
+                ));
+            }
+        }
+
+        // 出現した件数ごとに列を作る（件数の大きな値でも表が膨らまない）
+        let mut columns: BTreeMap<u64, [f64; 2]> = BTreeMap::new();
+        for (row, values) in [a, b].iter().enumerate() {
+            for &value in values.iter() {
+                columns.entry(value as u64).or_insert([0.0; 2])[row] += 1.0;
+            }
+        }
+        let values: Vec<u64> = columns.keys().copied().collect();
+        let table: Vec<Vec<f64>> = (0..2)
+            .map(|row| columns.values().map(|counts| counts[row]).collect())
+            .collect();
+        let (pooled, groups) = pool_sparse_columns(&table);
+        let homogeneity = chi_square_homogeneity(&pooled);
+
+        let (n_a, n_b) = (a.len() as f64, b.len() as f64);
+        let (sum_a, sum_b) = (a.iter().sum::<f64>(), b.iter().sum::<f64>());
+        let (lambda_a, lambda_b) = (sum_a / n_a, sum_b / n_b);
+        let pooled_lambda = (sum_a + sum_b) / (n_a + n_b);
+        let (rate_z, rate_p) = if pooled_lambda > 0.0 {
+            let z = (lambda_b - lambda_a) / (pooled_lambda * (1.0 / n_a + 1.0 / n_b)).sqrt();
+            (z, two_sided_normal_p_value(z))
+        } else {
+            (0.0, 1.0)
+        };
+
+        let mut effect_sizes = vec![cramers_v_effect(homogeneity.cramers_v)];
+        if sum_a > 0.0 && sum_b > 0.0 {
+            let ratio = lambda_b / lambda_a;
+            effect_sizes.push(EffectSize {
+                name: "rate ratio (λb/λa)".to_string(),
+                value: ratio,
+                confidence_interval: Some(
+                    self.ratio_interval(ratio, (1.0 / sum_a + 1.0 / sum_b).sqrt()),
+                ),
+                magnitude: None,
+            });
+        }
+
+        let categories = groups
+            .iter()
+            .zip(&pooled[0])
+            .zip(&pooled[1])
+            .map(|((&(start, end), &count_a), &count_b)| CategoryComparison {
+                category: if start == end {
+                    values[start].to_string()
+                } else {
+                    format!("{}-{}", values[start], values[end])
+                },
+                proportion_a: count_a / n_a,
+                proportion_b: count_b / n_b,
+            })
+            .collect();
+
+        Ok(self.result(
+            ComparedLaw::Poisson,
+            (a.len(), b.len()),
+            vec![
+                homogeneity_test(&homogeneity),
+                TwoSampleTest {
+                    name: "rate z-test".to_string(),
+                    statistic: rate_z,
+                    degrees_of_freedom: None,
+                    p_value: rate_p,
+                },
+            ],
+            effect_sizes,
+            categories,
+        ))
+    }
+
+    /// 分布全体（KS・AD）と平均・分散の差
+    fn normal(&self, a: &[f64], b: &[f64]) -> ComparisonResult {
+        let (n_a, n_b) = (a.len() as f64, b.len() as f64);
+        let (mean_a, sd_a) = mean_and_std_dev(a);
+        let (mean_b, sd_b) = mean_and_std_dev(b);
+
+        let pooled_sd =
+            (((n_a - 1.0) * sd_a.powi(2) + (n_b - 1.0) * sd_b.powi(2)) / (n_a + n_b - 2.0)).sqrt();
+        let cohens_d = if pooled_sd > 0.0 {
+            (mean_b - mean_a) / pooled_sd
+        } else {
+            0.0
+        };
+        // Hedges & Olkin の近似標準誤差
+        let d_standard_error =
+            ((n_a + n_b) / (n_a * n_b) + cohens_d.powi(2) / (2.0 * (n_a + n_b))).sqrt();
+
+        let mut effect_sizes = vec![EffectSize {
+            name: "Cohen's d".to_string(),
+            value: cohens_d,
+            confidence_interval: Some(self.interval(cohens_d, d_standard_error)),
+            magnitude: Some(EffectMagnitude::classify(cohens_d, COHENS_D_THRESHOLDS)),
+        }];
+        if sd_a > 0.0 && sd_b > 0.0 {
+            let ratio = sd_b.powi(2) / sd_a.powi(2);
+            // 正規母集団での ln(s²) の分散 2/(n−1)
+            let log_standard_error = (2.0 / (n_a - 1.0) + 2.0 / (n_b - 1.0)).sqrt();
+            effect_sizes.push(EffectSize {
+                name: "variance ratio (b/a)".to_string(),
+                value: ratio,
+                confidence_interval: Some(self.ratio_interval(ratio, log_standard_error)),
+                magnitude: None,
+            });
+        }
+
+        self.result(
+            ComparedLaw::Normal,
+            (a.len(), b.len()),
+            distribution_tests(a, b),
+            effect_sizes,
+            Vec::new(),
+        )
+    }
+
+    /// 分布全体（KS・AD）と集中度の差
+    fn pareto(&self, a: &[f64], b: &[f64]) -> ComparisonResult {
+        let delta = cliffs_delta(b, a);
+        let mut effect_sizes = vec![EffectSize {
+            name: "Cliff's delta".to_string(),
+            value: delta,
+            confidence_interval: None,
+            magnitude: Some(EffectMagnitude::classify(delta, CLIFFS_DELTA_THRESHOLDS)),
+        }];
+
+        // 負の値などで不平等指標が定義できないときは集中度の差を省く
+        let options = InequalityOptions {
+            top_share_percentiles: vec![20.0],
+            confidence_level: 1.0 - self.significance_level,
+            ..InequalityOptions::default()
+        };
+        if let (Ok(indices_a), Ok(indices_b)) = (
+            calculate_inequality_indices(a, &options),
+            calculate_inequality_indices(b, &options),
+        ) {
+            let difference = indices_b.gini - indices_a.gini;
+            // 各標本のブートストラップ区間の幅から標準誤差を逆算して合成する
+            let z = normal_quantile(1.0 - self.significance_level / 2.0);
+            let standard_error = |ci: &Option<ConfidenceInterval>| {
+                ci.as_ref()
+                    .map(|ci| (ci.upper - ci.lower) / (2.0 * z))
+                    .unwrap_or(0.0)
+            };
+            let combined = (standard_error(&indices_a.gini_confidence_interval).powi(2)
+                + standard_error(&indices_b.gini_confidence_interval).powi(2))
+            .sqrt();
+            effect_sizes.push(EffectSize {
+                name: "Gini difference (b−a)".to_string(),
+                value: difference,
+                confidence_interval: Some(self.interval(difference, combined)),
+                magnitude: None,
+            });
+            if let (Some(top_a), Some(top_b)) =
+                (indices_a.top_shares.first(), indices_b.top_shares.first())
+            {
+                effect_sizes.push(EffectSize {
+                    name: "top 20% share difference (pp)".to_string(),
+                    value: top_b.share - top_a.share,
+                    confidence_interval: None,
+                    magnitude: None,
+                });
+            }
+        }
+
+        self.result(
+            ComparedLaw::Pareto,
+            (a.len(), b.len()),
+            distribution_tests(a, b),
+            effect_sizes,
+            Vec::new(),
+        )
+    }
+
+    /// 共通項目の順位の一致度と Zipf 指数の差
+    fn zipf(&self, a: &[(String, usize)], b: &[(String, usize)]) -> Result<ComparisonResult> {
+        let fit_a = analyze_text_zipf_from_frequencies(a, self.name_a)?.zipf_fit;
+        let fit_b = analyze_text_zipf_from_frequencies(b, self.name_b)?.zipf_fit;
+
+        let counts_a: HashMap<&str, f64> = a
+            .iter()
+            .map(|(item, n)| (item.as_str(), *n as f64))
+            .collect();
+        let counts_b: HashMap<&str, f64> = b
+            .iter()
+            .map(|(item, n)| (item.as_str(), *n as f64))
+            .collect();
+        let total_a: f64 = counts_a.values().sum();
+        let total_b: f64 = counts_b.values().sum();
+
+        // 両方を合わせた頻度の上位項目 + その他で分割表を作る
+        let mut combined: Vec<(&str, f64)> = counts_a
+            .keys()
+            .chain(counts_b.keys())
+            .collect::<HashSet<_>>()
+            .into_iter()
+            .map(|&item| {
+                let frequency = counts_a.get(item).unwrap_or(&0.0) / total_a
+                    + counts_b.get(item).unwrap_or(&0.0) / total_b;
+                (item, frequency)
+            })
+            .collect();
+        combined.sort_by(|x, y| y.1.total_cmp(&x.1).then_with(|| x.0.cmp(y.0)));
+        let union_size = combined.len();
+        combined.truncate(ZIPF_TOP_ITEMS);
+
+        let mut table = vec![Vec::new(), Vec::new()];
+        for (row, (counts, total)) in [(&counts_a, total_a), (&counts_b, total_b)]
+            .into_iter()
+            .enumerate()
+        {
+            table[row] = combined
+                .iter()
+                .map(|(item, _)| counts.get(item).copied().unwrap_or(0.0))
+                .collect();
+            let top: f64 = table[row].iter().sum();
+            table[row].push(total - top);
+        }
+        let (pooled, _) = pool_sparse_columns(&table);
+        let homogeneity = chi_square_homogeneity(&pooled);
+
+        let exponent_difference = fit_b.exponent - fit_a.exponent;
+        let exponent_standard_error =
+            (fit_a.standard_error.powi(2) + fit_b.standard_error.powi(2)).sqrt();
+        let (exponent_z, exponent_p) = if exponent_standard_error > 0.0 {
+            let z = exponent_difference / exponent_standard_error;
+            (z, two_sided_normal_p_value(z))
+        } else {
+            (0.0, 1.0)
+        };
+
+        let shared: Vec<&str> = a
+            .iter()
+            .map(|(item, _)| item.as_str())
+            .filter(|item| counts_b.contains_key(item))
+            .collect();
+        let mut effect_sizes = Vec::new();
+        let shared_a: Vec<f64> = shared.iter().map(|item| counts_a[item]).collect();
+        let shared_b: Vec<f64> = shared.iter().map(|item| counts_b[item]).collect();
+        if let Some(rho) = spearman_correlation(&shared_a, &shared_b) {
+            // Fisher の z 変換（SE ≈ 1/√(n−3)）
+            let confidence_interval = (shared.len() > 3).then(|| {
+                let rho = rho.clamp(-0.999_999, 0.999_999);
+                let interval = self.interval(rho.atanh(), 1.0 / (shared.len() as f64 - 3.0).sqrt());
+                ConfidenceInterval {
+                    level: interval.level,
+                    lower: interval.lower.tanh(),
+                    upper: interval.upper.tanh(),
+                }
+            });
+            effect_sizes.push(EffectSize {
+                name: "Spearman rank correlation (shared items)".to_string(),
+                value: rho,
+                confidence_interval,
+                magnitude: Some(rank_agreement_magnitude(rho)),
+            });
+        }
+        effect_sizes.push(EffectSize {
+            name: "vocabulary overlap (Jaccard)".to_string(),
+            value: shared.len() as f64 / union_size as f64,
+            confidence_interval: None,
+            magnitude: None,
+        });
+        effect_sizes.push(EffectSize {
+            name: "Zipf exponent difference (b−a)".to_string(),
+            value: exponent_difference,
+            confidence_interval: Some(self.interval(exponent_difference, exponent_standard_error)),
+            magnitude: None,
+        });
+
+        let categories = combined
+            .iter()
+            .map(|(item, _)| CategoryComparison {
+                category: item.to_string(),
+                proportion_a: counts_a.get(item).unwrap_or(&0.0) / total_a,
+                proportion_b: counts_b.get(item).unwrap_or(&0.0) / total_b,
+            })
+            .collect();
+
+        Ok(self.result(
+            ComparedLaw::Zipf,
+            (total_a as usize, total_b as usize),
+            vec![
+                homogeneity_test(&homogeneity),
+                TwoSampleTest {
+                    name: "Zipf exponent z-test".to_string(),
+                    statistic: exponent_z,
+                    degrees_of_freedom: None,
+                    p_value: exponent_p,
+                },
+            ],
+            effect_sizes,
+            categories,
+        ))
+    }
+}
+
+fn first_digit_counts(values: &[f64]) -> Result<Vec<f64>> {
+    let counts: Vec<f64> = DigitTest::First
+        .count_bins(values)
+        .into_iter()
+        .map(|count| count as f64)
+        .collect();
+    let total = counts.iter().sum::<f64>() as usize;
+    if total < MIN_COMPARISON_SIZE {
+        return Err(BenfError::InsufficientData(total));
+    }
+    Ok(counts)
+}
+
+/// 数値ごとの出現回数（頻度の降順、同数は値の順）
+fn value_frequencies(values: &[f64]) -> Vec<(String, usize)> {
+    let mut counts: BTreeMap<String, usize> = BTreeMap::new();
+    for value in values {
+        *counts.entry(value.to_string()).or_insert(0) += 1;
+    }
+    let mut frequencies: Vec<(String, usize)> = counts.into_iter().collect();
+    frequencies.sort_by_key(|(_, count)| std::cmp::Reverse(*count));
+    frequencies
+}
+
+fn distribution_tests(a: &[f64], b: &[f64]) -> Vec<TwoSampleTest> {
+    let ks = kolmogorov_smirnov_two_sample(a, b);
+    let mut tests = vec![TwoSampleTest {
+        name: "Kolmogorov-Smirnov".to_string(),
+        statistic: ks.statistic,
+        degrees_of_freedom: None,
+        p_value: ks.p_value,
+    }];
+    if let Some(ad) = anderson_darling_k_sample(&[a, b]) {
+        tests.push(TwoSampleTest {
+            name: "Anderson-Darling".to_string(),
+            statistic: ad.statistic,
+            degrees_of_freedom: None,
+            p_value: ad.p_value,
+        });
+    }
+    tests
+}
+
+fn homogeneity_test(homogeneity: &super::two_sample::HomogeneityTest) -> TwoSampleTest {
+    TwoSampleTest {
+        name: "chi-square homogeneity".to_string(),
+        statistic: homogeneity.chi_square,
+        degrees_of_freedom: Some(homogeneity.degrees_of_freedom),
+        p_value: homogeneity.p_value,
+    }
+}
+
+fn cramers_v_effect(cramers_v: f64) -> EffectSize {
+    EffectSize {
+        name: "Cramér's V".to_string(),
+        value: cramers_v,
+        confidence_interval: None,
+        magnitude: Some(EffectMagnitude::classify(cramers_v, CRAMERS_V_THRESHOLDS)),
+    }
+}
+
+/// 順位相関が高いほど差は小さい（0.9 以上で無視できる程度）
+fn rank_agreement_magnitude(rho: f64) -> EffectMagnitude {
+    if rho >= 0.9 {
+        EffectMagnitude::Negligible
+    } else if rho >= 0.7 {
+        EffectMagnitude::Small
+    } else if rho >= 0.4 {
+        EffectMagnitude::Medium
+    } else {
+        EffectMagnitude::Large
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn benford_sample(size: usize) -> Vec<f64> {
+        (0..size).map(|i| 10f64.powf(i as f64 * 0.0137)).collect()
+    }
+
+    #[test]
+    fn test_identical_datasets_are_low_risk() {
+        let data = benford_sample(300);
+        for law in [
+            ComparedLaw::Benford,
+            ComparedLaw::Normal,
+            ComparedLaw::Pareto,
+        ] {
+            let result = compare_datasets(law, "a", &data, "b", &data, 0.05).unwrap();
+            assert!(!result.significant, "{law:?}");
+            assert_eq!(result.risk_level, RiskLevel::Low);
+        }
+    }
+
+    #[test]
+    fn test_benford_shift_is_detected() {
+        let a = benford_sample(500);
+        let b: Vec<f64> = (0..500).map(|i| 500.0 + (i % 400) as f64).collect();
+        let result = compare_datasets(ComparedLaw::Benford, "a", &a, "b", &b, 0.05).unwrap();
+        assert!(result.significant);
+        assert_eq!(result.categories.len(), 9);
+        assert_eq!(
+            result.effect_sizes[0].magnitude,
+            Some(EffectMagnitude::Large)
+        );
+        assert!(result.effect_sizes[1].value > 0.0);
+        assert_eq!(result.risk_level, RiskLevel::Critical);
+    }
+
+    #[test]
+    fn test_normal_mean_shift_effect_size() {
+        let base: Vec<f64> = (0..200).map(|i| ((i * 37) % 200) as f64 / 20.0).collect();
+        let shifted: Vec<f64> = base.iter().map(|v| v + 2.9).collect();
+        let result =
+            compare_datasets(ComparedLaw::Normal, "a", &base, "b", &shifted, 0.05).unwrap();
+        let d = &result.effect_sizes[0];
+        assert_eq!(d.name, "Cohen's d");
+        assert!((d.value - 1.0).abs() < 0.01, "d = {}", d.value);
+        assert_eq!(d.magnitude, Some(EffectMagnitude::Large));
+        let ci = d.confidence_interval.as_ref().unwrap();
+        assert!(ci.lower < d.value && d.value < ci.upper);
+        assert!((result.effect_sizes[1].value - 1.0).abs() < 1e-9);
+        assert!(result.tests.iter().all(|t| t.p_value < 0.01));
+    }
+
+    #[test]
+    fn test_poisson_rate_ratio() {
+        let a: Vec<f64> = (0..100).map(|i| (i % 4) as f64).collect();
+        let b: Vec<f64> = a.iter().map(|v| v * 2.0).collect();
+        let result = compare_datasets(ComparedLaw::Poisson, "a", &a, "b", &b, 0.05).unwrap();
+        let ratio = &result.effect_sizes[1];
+        assert!((ratio.value - 2.0).abs() < 1e-12);
+        assert!(result.significant);
+        let total_a: f64 = result.categories.iter().map(|c| c.proportion_a).sum();
+        assert!((total_a - 1.0).abs() < 1e-12);
+
+        assert!(compare_datasets(ComparedLaw::Poisson, "a", &[1.5; 10], "b", &a, 0.05).is_err());
+    }
+
+    #[test]
+    fn test_zipf_shared_item_rank_correlation() {
+        let words = |counts: &[(&str, usize)]| -> Vec<(String, usize)> {
+            counts.iter().map(|(w, n)| (w.to_string(), *n)).collect()
+        };
+        let a = words(&[
+            ("the", 60),
+            ("of", 30),
+            ("and", 20),
+            ("to", 15),
+            ("a", 12),
+            ("in", 10),
+        ]);
+        let b = words(&[
+            ("the", 58),
+            ("of", 31),
+            ("and", 19),
+            ("to", 16),
+            ("a", 11),
+            ("is", 9),
+        ]);
+        let result = compare_word_frequencies("a", &a, "b", &b, 0.05).unwrap();
+        let rho = &result.effect_sizes[0];
+        assert!((rho.value - 1.0).abs() < 1e-12);
+        assert_eq!(rho.magnitude, Some(EffectMagnitude::Negligible));
+        assert!((result.effect_sizes[1].value - 5.0 / 7.0).abs() < 1e-12);
+        assert_eq!(result.risk_level, RiskLevel::Low);
+    }
+
+    #[test]
+    fn test_insufficient_data() {
+        let small = [1.0, 2.0, 3.0];
+        let data = benford_sample(50);
+        assert!(matches!(
+            compare_datasets(ComparedLaw::Normal, "a", &small, "b", &data, 0.05),
+            Err(BenfError::InsufficientData(3))
+        ));
+    }
+}
@@ -0,0 +1,7 @@
+mod analysis;
+mod result;
+mod two_sample;
+
+pub use analysis::*;
+pub use result::*;
+pub use two_sample::*;
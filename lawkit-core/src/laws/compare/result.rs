@@ -0,0 +1,166 @@
+use crate::{
+    common::{output::formatter::FormattableResult, risk::RiskLevel},
+    error::{BenfError, Result},
+    laws::pareto::ConfidenceInterval,
+};
+use serde::Serialize;
+
+/// 比較に使う法則
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ComparedLaw {
+    #[serde(rename = "benf")]
+    Benford,
+    Pareto,
+    Zipf,
+    Normal,
+    Poisson,
+}
+
+impl ComparedLaw {
+    pub const ALL: [ComparedLaw; 5] = [
+        ComparedLaw::Benford,
+        ComparedLaw::Pareto,
+        ComparedLaw::Zipf,
+        ComparedLaw::Normal,
+        ComparedLaw::Poisson,
+    ];
+
+    /// `--law` で受け付ける名前
+    pub fn name(&self) -> &'static str {
+        match self {
+            ComparedLaw::Benford => "benf",
+            ComparedLaw::Pareto => "pareto",
+            ComparedLaw::Zipf => "zipf",
+            ComparedLaw::Normal => "normal",
+            ComparedLaw::Poisson => "poisson",
+        }
+    }
+
+    pub fn parse(name: &str) -> Result<Self> {
+        match name.to_lowercase().as_str() {
+            "benf" | "benford" => Ok(ComparedLaw::Benford),
+            "pareto" => Ok(ComparedLaw::Pareto),
+            "zipf" => Ok(ComparedLaw::Zipf),
+            "normal" => Ok(ComparedLaw::Normal),
+            "poisson" => Ok(ComparedLaw::Poisson),
+            _ => Err(BenfError::InvalidInput(format!(
+                "unknown law '{name}' (expected benf, pareto, zipf, normal or poisson)"
+            ))),
+        }
+    }
+}
+
+/// 効果量の大きさの目安
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum EffectMagnitude {
+    Negligible,
+    Small,
+    Medium,
+    Large,
+}
+
+impl EffectMagnitude {
+    /// |value| を small・medium・large の境界と比べる
+    pub fn classify(value: f64, thresholds: [f64; 3]) -> Self {
+        let value = value.abs();
+        if value >= thresholds[2] {
+            EffectMagnitude::Large
+        } else if value >= thresholds[1] {
+            EffectMagnitude::Medium
+        } else if value >= thresholds[0] {
+            EffectMagnitude::Small
+        } else {
+            EffectMagnitude::Negligible
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            EffectMagnitude::Negligible => "negligible",
+            EffectMagnitude::Small => "small",
+            EffectMagnitude::Medium => "medium",
+            EffectMagnitude::Large => "large",
+        }
+    }
+}
+
+/// 2 つのデータセットが同じ分布に従うかの検定
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct TwoSampleTest {
+    pub name: String,
+    pub statistic: f64,
+    pub degrees_of_freedom: Option<usize>,
+    pub p_value: f64,
+}
+
+/// 差の大きさ（p 値と違い標本サイズに依存しない）
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct EffectSize {
+    pub name: String,
+    pub value: f64,
+    pub confidence_interval: Option<ConfidenceInterval>,
+    /// 慣例的な目安がある効果量のみ
+    pub magnitude: Option<EffectMagnitude>,
+}
+
+/// カテゴリ（桁・件数・語）ごとの構成比
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct CategoryComparison {
+    pub category: String,
+    pub proportion_a: f64,
+    pub proportion_b: f64,
+}
+
+/// 同じ法則の下での 2 データセットの比較結果
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ComparisonResult {
+    pub law: ComparedLaw,
+    pub dataset_a: String,
+    pub dataset_b: String,
+    pub size_a: usize,
+    pub size_b: usize,
+    pub significance_level: f64,
+    pub tests: Vec<TwoSampleTest>,
+    /// 先頭がリスク判定に使う主な効果量
+    pub effect_sizes: Vec<EffectSize>,
+    pub categories: Vec<CategoryComparison>,
+    /// いずれかの検定で p < 有意水準
+    pub significant: bool,
+    pub risk_level: RiskLevel,
+}
+
+impl FormattableResult for ComparisonResult {
+    const TITLE: &'static str = "Dataset Comparison Results";
+    const ROOT: &'static str = "comparison_result";
+}
+
+impl ComparisonResult {
+    /// 有意性と主な効果量の大きさからリスクを決める
+    ///
+    /// 有意でなければ Low。有意なら効果量が無視できる程度で Low、小で Medium、
+    /// 中で High、大で Critical（目安のない効果量なら Medium）。
+    pub(crate) fn assess(mut self) -> Self {
+        self.significant = self
+            .tests
+            .iter()
+            .any(|test| test.p_value < self.significance_level);
+        let magnitude = self.effect_sizes.iter().find_map(|effect| effect.magnitude);
+        self.risk_level = match (self.significant, magnitude) {
+            (false, _) | (true, Some(EffectMagnitude::Negligible)) => RiskLevel::Low,
+            (true, None) | (true, Some(EffectMagnitude::Small)) => RiskLevel::Medium,
+            (true, Some(EffectMagnitude::Medium)) => RiskLevel::High,
+            (true, Some(EffectMagnitude::Large)) => RiskLevel::Critical,
+        };
+        self
+    }
+
+    /// 最小の p 値
+    pub fn min_p_value(&self) -> Option<f64> {
+        self.tests
+            .iter()
+            .map(|test| test.p_value)
+            .min_by(|a, b| a.total_cmp(b))
+    }
+}
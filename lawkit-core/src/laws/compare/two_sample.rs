@@ -0,0 +1,521 @@
+use crate::common::statistics::{calculate_p_value, kolmogorov_p_value};
+use serde::Serialize;
+
+/// カイ二乗近似に必要な期待度数の下限
+const MIN_EXPECTED: f64 = 5.0;
+
+/// 2 標本以上の分割表による一様性（homogeneity）のカイ二乗検定の結果
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct HomogeneityTest {
+    pub chi_square: f64,
+    pub degrees_of_freedom: usize,
+    pub p_value: f64,
+    /// Cramér の V（0 = 同じ分布、1 = 完全に分かれる）
+    pub cramers_v: f64,
+}
+
+/// 分割表（行 = 標本、列 = カテゴリ）のカイ二乗一様性検定
+///
+/// 合計が 0 の列は無視する。期待度数が小さい列は事前に [`pool_sparse_columns`] で
+/// まとめておくこと。
+pub fn chi_square_homogeneity(table: &[Vec<f64>]) -> HomogeneityTest {
+    let row_totals: Vec<f64> = table.iter().map(|row| row.iter().sum()).collect();
+    let columns = table.iter().map(Vec::len).max().unwrap_or(0);
+    let column_totals: Vec<f64> = (0..columns)
+        .map(|j| {
+            table
+                .iter()
+                .map(|row| row.get(j).copied().unwrap_or(0.0))
+                .sum()
+        })
+        .collect();
+    let total: f64 = row_totals.iter().sum();
+
+    let rows = row_totals.iter().filter(|&&t| t > 0.0).count();
+    let used_columns = column_totals.iter().filter(|&&t| t > 0.0).count();
+    if total <= 0.0 || rows < 2 || used_columns < 2 {
+        return HomogeneityTest {
+            chi_square: 0.0,
+            degrees_of_freedom: 0,
+            p_value: 1.0,
+            cramers_v: 0.0,
+        };
+    }
+
+    let mut chi_square = 0.0;
+    for (row, &row_total) in table.iter().zip(&row_totals) {
+        for (j, &column_total) in column_totals.iter().enumerate() {
+            let expected = row_total * column_total / total;
+            if expected > 0.0 {
+                let observed = row.get(j).copied().unwrap_or(0.0);
+                chi_square += (observed - expected).powi(2) / expected;
+            }
+        }
+    }
+
+    let degrees_of_freedom = (rows - 1) * (used_columns - 1);
+    let smaller_dimension = rows.min(used_columns) - 1;
+    HomogeneityTest {
+        chi_square,
+        degrees_of_freedom,
+        p_value: calculate_p_value(chi_square, degrees_of_freedom as i32),
+        cramers_v: (chi_square / (total * smaller_dimension as f64)).sqrt(),
+    }
+}
+
+/// 隣り合う列をまとめて、どの標本でも期待度数が 5 以上になるようにする
+///
+/// 戻り値は、まとめた後の分割表と、元の列の範囲（両端を含む）のリスト。
+/// 端数の列は直前のまとまりに加える。
+pub fn pool_sparse_columns(table: &[Vec<f64>]) -> (Vec<Vec<f64>>, Vec<(usize, usize)>) {
+    let columns = table.iter().map(Vec::len).max().unwrap_or(0);
+    let row_totals: Vec<f64> = table.iter().map(|row| row.iter().sum()).collect();
+    let total: f64 = row_totals.iter().sum();
+    let smallest_row = row_totals
+        .iter()
+        .copied()
+        .filter(|&t| t > 0.0)
+        .fold(f64::INFINITY, f64::min);
+    // 列合計 C について、最小の期待度数は C * (最小の行合計) / 総数
+    let required = if smallest_row.is_finite() {
+        MIN_EXPECTED * total / smallest_row
+    } else {
+        0.0
+    };
+
+    let column_total = |j: usize| -> f64 {
+        table
+            .iter()
+            .map(|row| row.get(j).copied().unwrap_or(0.0))
+            .sum()
+    };
+
+    let mut groups: Vec<(usize, usize)> = Vec::new();
+    let mut start = 0;
+    let mut accumulated = 0.0;
+    for j in 0..columns {
+        accumulated += column_total(j);
+        if accumulated >= required && accumulated > 0.0 {
+            groups.push((start, j));
+            start = j + 1;
+            accumulated = 0.0;
+        }
+    }
+    if start < columns {
+        match groups.last_mut() {
+            Some(last) => last.1 = columns - 1,
+            None => groups.push((start, columns - 1)),
+        }
+    }
+
+    let pooled = table
+        .iter()
+        .map(|row| {
+            groups
+                .iter()
+                .map(|&(first, last)| (first..=last).filter_map(|j| row.get(j)).sum())
+                .collect()
+        })
+        .collect();
+    (pooled, groups)
+}
+
+/// 2 標本 Kolmogorov–Smirnov 検定の結果
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct KolmogorovSmirnovTest {
+    /// 経験分布関数の差の最大値 D
+    pub statistic: f64,
+    pub p_value: f64,
+}
+
+/// 2 標本 Kolmogorov–Smirnov 検定（漸近 p 値、Stephens の補正つき）
+pub fn kolmogorov_smirnov_two_sample(a: &[f64], b: &[f64]) -> KolmogorovSmirnovTest {
+    let a = sorted(a);
+    let b = sorted(b);
+    if a.is_empty() || b.is_empty() {
+        return KolmogorovSmirnovTest {
+            statistic: 0.0,
+            p_value: 1.0,
+        };
+    }
+
+    let (n, m) = (a.len() as f64, b.len() as f64);
+    let (mut i, mut j) = (0, 0);
+    let mut statistic: f64 = 0.0;
+    while i < a.len() && j < b.len() {
+        // 同じ値は両方の標本でまとめて進める
+        let value = a[i].min(b[j]);
+        while i < a.len() && a[i] <= value {
+            i += 1;
+        }
+        while j < b.len() && b[j] <= value {
+            j += 1;
+        }
+        statistic = statistic.max((i as f64 / n - j as f64 / m).abs());
+    }
+
+    let effective = (n * m / (n + m)).sqrt();
+    let lambda = (effective + 0.12 + 0.11 / effective) * statistic;
+    KolmogorovSmirnovTest {
+        statistic,
+        p_value: kolmogorov_p_value(lambda),
+    }
+}
+
+/// k 標本 Anderson–Darling 検定の結果
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct AndersonDarlingTest {
+    /// 同順位を中間順位で扱う A²_akN
+    pub statistic: f64,
+    /// (A² − (k−1)) / σ_N
+    pub standardized: f64,
+    /// 0.001〜0.25 の範囲で補間した p 値（範囲外は端の値）
+    pub p_value: f64,
+}
+
+/// k 標本 Anderson–Darling 検定（Scholz & Stephens 1987）
+///
+/// 同順位のある離散データにも使える中間順位版の統計量を、帰無仮説の下での分散で
+/// 標準化し、同論文の表 2 の臨界値を log p の二次式で補間して p 値を求める。
+/// 合計 4 点未満、または標本が 2 つ未満なら `None`。
+pub fn anderson_darling_k_sample(samples: &[&[f64]]) -> Option<AndersonDarlingTest> {
+    let samples: Vec<Vec<f64>> = samples
+        .iter()
+        .map(|sample| sorted(sample))
+        .filter(|sample| !sample.is_empty())
+        .collect();
+    let k = samples.len();
+    let pooled = sorted(&samples.concat());
+    let n_total = pooled.len();
+    if k < 2 || n_total < 4 {
+        return None;
+    }
+    let big_n = n_total as f64;
+
+    // 異なる値 z*_j と、その同順位の数 l_j
+    let mut distinct: Vec<(f64, f64)> = Vec::new();
+    for &value in &pooled {
+        match distinct.last_mut() {
+            Some((last, count)) if *last == value => *count += 1.0,
+            _ => distinct.push((value, 1.0)),
+        }
+    }
+
+    let mut statistic = 0.0;
+    for sample in &samples {
+        let n_i = sample.len() as f64;
+        let mut inner = 0.0;
+        let mut below_sample = 0.0; // この標本で z*_j 未満の数
+        let mut below_pooled = 0.0; // 全体で z*_j 未満の数
+        let mut position = 0;
+        for &(value, ties) in &distinct {
+            let mut at_value = 0.0;
+            while position < sample.len() && sample[position] == value {
+                at_value += 1.0;
+                position += 1;
+            }
+            let m_ij = below_sample + at_value / 2.0;
+            let b_j = below_pooled + ties / 2.0;
+            let denominator = b_j * (big_n - b_j) - big_n * ties / 4.0;
+            if denominator > 0.0 {
+                inner += ties / big_n * (big_n * m_ij - n_i * b_j).powi(2) / denominator;
+            }
+            below_sample += at_value;
+            below_pooled += ties;
+        }
+        statistic += inner / n_i;
+    }
+    statistic *= (big_n - 1.0) / big_n;
+
+    let sigma = anderson_darling_sigma(&samples, n_total);
+    let m = (k - 1) as f64;
+    let standardized = (statistic - m) / sigma;
+    Some(AndersonDarlingTest {
+        statistic,
+        standardized,
+        p_value: anderson_darling_p_value(standardized, m),
+    })
+}
+
+/// 帰無仮説の下での A²_kN の標準偏差 σ_N（Scholz & Stephens 1987, 式 4）
+fn anderson_darling_sigma(samples: &[Vec<f64>], n_total: usize) -> f64 {
+    let big_n = n_total as f64;
+    let k = samples.len() as f64;
+    let h_sum: f64 = samples.iter().map(|s| 1.0 / s.len() as f64).sum();
+    let h: f64 = (1..n_total).map(|i| 1.0 / i as f64).sum();
+    // g = Σ_{i=1}^{N-2} Σ_{j=i+1}^{N-1} 1 / ((N-i) j)
+    let mut g = 0.0;
+    let mut tail = 0.0; // Σ_{j=N-t}^{N-1} 1/j
+    for t in 1..n_total.saturating_sub(1) {
+        tail += 1.0 / (n_total - t) as f64;
+        g += tail / (t + 1) as f64;
+    }
+
+    let a = (4.0 * g - 6.0) * (k - 1.0) + (10.0 - 6.0 * g) * h_sum;
+    let b = (2.0 * g - 4.0) * k * k + 8.0 * h * k + (2.0 * g - 14.0 * h - 4.0) * h_sum - 8.0 * h
+        + 4.0 * g
+        - 6.0;
+    let c = (6.0 * h + 2.0 * g - 2.0) * k * k
+        + (4.0 * h - 4.0 * g + 6.0) * k
+        + (2.0 * h - 6.0) * h_sum
+        + 4.0 * h;
+    let d = (2.0 * h + 6.0) * k * k - 4.0 * h * k;
+    let variance = (a * big_n.powi(3) + b * big_n * big_n + c * big_n + d)
+        / ((big_n - 1.0) * (big_n - 2.0) * (big_n - 3.0));
+    variance.sqrt()
+}
+
+/// 標準化統計量の p 値（表 2 の臨界値 t_m(α) = b0 + b1/√m + b2/m を二次補間）
+fn anderson_darling_p_value(standardized: f64, m: f64) -> f64 {
+    const SIGNIFICANCE: [f64; 7] = [0.25, 0.1, 0.05, 0.025, 0.01, 0.005, 0.001];
+    const B0: [f64; 7] = [0.675, 1.281, 1.645, 1.96, 2.326, 2.573, 3.085];
+    const B1: [f64; 7] = [-0.245, 0.25, 0.678, 1.149, 1.822, 2.364, 3.615];
+    const B2: [f64; 7] = [-0.105, -0.305, -0.362, -0.391, -0.396, -0.345, -0.154];
+
+    let critical: Vec<f64> = (0..7)
+        .map(|i| B0[i] + B1[i] / m.sqrt() + B2[i] / m)
+        .collect();
+    // 二次式は表の外で折り返すので、範囲外は端の値にする
+    if standardized >= critical[6] {
+        return SIGNIFICANCE[6];
+    }
+    if standardized <= critical[0] {
+        return SIGNIFICANCE[0];
+    }
+    let log_significance: Vec<f64> = SIGNIFICANCE.iter().map(|p| p.ln()).collect();
+    let [c0, c1, c2] = quadratic_fit(&critical, &log_significance);
+    let p_value = (c0 + c1 * standardized + c2 * standardized * standardized).exp();
+    p_value.clamp(SIGNIFICANCE[6], SIGNIFICANCE[0])
+}
+
+/// 最小二乗法で y ≈ c0 + c1 x + c2 x² を当てはめる
+fn quadratic_fit(x: &[f64], y: &[f64]) -> [f64; 3] {
+    // 正規方程式 (XᵀX) c = Xᵀy を Cramer の公式で解く
+    let moment = |power: i32| x.iter().map(|v| v.powi(power)).sum::<f64>();
+    let weighted = |power: i32| x.iter().zip(y).map(|(v, w)| v.powi(power) * w).sum::<f64>();
+    let matrix = [
+        [x.len() as f64, moment(1), moment(2)],
+        [moment(1), moment(2), moment(3)],
+        [moment(2), moment(3), moment(4)],
+    ];
+    let rhs = [weighted(0), weighted(1), weighted(2)];
+    let determinant = |m: &[[f64; 3]; 3]| {
+        m[0][0] * (m[1][1] * m[2][2] - m[1][2] * m[2][1])
+            - m[0][1] * (m[1][0] * m[2][2] - m[1][2] * m[2][0])
+            + m[0][2] * (m[1][0] * m[2][1] - m[1][1] * m[2][0])
+    };
+    let base = determinant(&matrix);
+    let mut coefficients = [0.0; 3];
+    for (column, coefficient) in coefficients.iter_mut().enumerate() {
+        let mut replaced = matrix;
+        for row in 0..3 {
+            replaced[row][column] = rhs[row];
+        }
+        *coefficient = determinant(&replaced) / base;
+    }
+    coefficients
+}
+
+/// Spearman の順位相関係数（同順位は平均順位）
+///
+/// 2 点未満、またはどちらかの順位が一定なら `None`。
+pub fn spearman_correlation(x: &[f64], y: &[f64]) -> Option<f64> {
+    if x.len() != y.len() || x.len() < 2 {
+        return None;
+    }
+    pearson(&average_ranks(x), &average_ranks(y))
+}
+
+/// Cliff の δ = P(A > B) − P(A < B)（順序尺度の効果量、−1〜1）
+pub fn cliffs_delta(a: &[f64], b: &[f64]) -> f64 {
+    if a.is_empty() || b.is_empty() {
+        return 0.0;
+    }
+    let b = sorted(b);
+    let mut dominance = 0.0;
+    for &value in a {
+        let below = b.partition_point(|&other| other < value);
+        let not_above = b.partition_point(|&other| other <= value);
+        dominance += below as f64 - (b.len() - not_above) as f64;
+    }
+    dominance / (a.len() * b.len()) as f64
+}
+
+/// 平均と不偏標準偏差
+pub fn mean_and_std_dev(values: &[f64]) -> (f64, f64) {
+    let n = values.len() as f64;
+    let mean = values.iter().sum::<f64>() / n;
+    let variance = if values.len() > 1 {
+        values.iter().map(|v| (v - mean).powi(2)).sum::<f64>() / (n - 1.0)
+    } else {
+        0.0
+    };
+    (mean, variance.sqrt())
+}
+
+fn sorted(values: &[f64]) -> Vec<f64> {
+    let mut values: Vec<f64> = values.iter().copied().filter(|v| !v.is_nan()).collect();
+    values.sort_by(|a, b| a.total_cmp(b));
+    values
+}
+
+fn average_ranks(values: &[f64]) -> Vec<f64> {
+    let mut order: Vec<usize> = (0..values.len()).collect();
+    order.sort_by(|&a, &b| values[a].total_cmp(&values[b]));
+    let mut ranks = vec![0.0; values.len()];
+    let mut start = 0;
+    while start < order.len() {
+        let mut end = start;
+        while end + 1 < order.len() && values[order[end + 1]] == values[order[start]] {
+            end += 1;
+        }
+        let rank = (start + end) as f64 / 2.0 + 1.0;
+        for &index in &order[start..=end] {
+            ranks[index] = rank;
+        }
+        start = end + 1;
+    }
+    ranks
+}
+
+fn pearson(x: &[f64], y: &[f64]) -> Option<f64> {
+    let n = x.len() as f64;
+    let mean_x = x.iter().sum::<f64>() / n;
+    let mean_y = y.iter().sum::<f64>() / n;
+    let sxy: f64 = x
+        .iter()
+        .zip(y)
+        .map(|(a, b)| (a - mean_x) * (b - mean_y))
+        .sum();
+    let sxx: f64 = x.iter().map(|a| (a - mean_x).powi(2)).sum();
+    let syy: f64 = y.iter().map(|b| (b - mean_y).powi(2)).sum();
+    if sxx <= 0.0 || syy <= 0.0 {
+        return None;
+    }
+    Some(sxy / (sxx * syy).sqrt())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assert_close(actual: f64, expected: f64, tolerance: f64) {
+        assert!(
+            (actual - expected).abs() < tolerance,
+            "expected {expected}, got {actual}"
+        );
+    }
+
+    #[test]
+    fn test_chi_square_homogeneity() {
+        // 2x2 表: χ² = N (ad − bc)² / (行合計と列合計の積)
+        let table = vec![vec![30.0, 10.0], vec![20.0, 40.0]];
+        let test = chi_square_homogeneity(&table);
+        let by_hand = 100.0 * (30.0f64 * 40.0 - 10.0 * 20.0).powi(2) / (40.0 * 60.0 * 50.0 * 50.0);
+        assert_close(test.chi_square, by_hand, 1e-9);
+        assert_eq!(test.degrees_of_freedom, 1);
+        assert_close(test.cramers_v, (by_hand / 100.0).sqrt(), 1e-12);
+
+        let same = chi_square_homogeneity(&[vec![10.0, 20.0, 30.0], vec![20.0, 40.0, 60.0]]);
+        assert_close(same.chi_square, 0.0, 1e-12);
+        assert_close(same.p_value, 1.0, 1e-12);
+    }
+
+    #[test]
+    fn test_pool_sparse_columns() {
+        // 期待度数 5 以上には列合計 5 × 164 / 82 = 10 以上が必要
+        let table = vec![
+            vec![50.0, 30.0, 2.0, 1.0, 1.0, 0.0],
+            vec![40.0, 35.0, 3.0, 0.0, 2.0, 0.0],
+        ];
+        let (pooled, groups) = pool_sparse_columns(&table);
+        assert_eq!(groups, vec![(0, 0), (1, 5)]);
+        assert_eq!(pooled[0], vec![50.0, 34.0]);
+        assert_eq!(pooled[1], vec![40.0, 40.0]);
+
+        let table = vec![vec![50.0, 30.0, 8.0, 2.0], vec![40.0, 35.0, 6.0, 1.0]];
+        let (_, groups) = pool_sparse_columns(&table);
+        assert_eq!(groups, vec![(0, 0), (1, 1), (2, 3)]);
+    }
+
+    #[test]
+    fn test_kolmogorov_smirnov_two_sample() {
+        let a = [1.0, 2.0, 3.0, 4.0, 5.0];
+        let b = [6.0, 7.0, 8.0, 9.0, 10.0];
+        let test = kolmogorov_smirnov_two_sample(&a, &b);
+        assert_close(test.statistic, 1.0, 1e-12);
+        assert!(test.p_value < 0.01);
+
+        // 同順位を含む同じ標本は D = 0
+        let tied = [1.0, 1.0, 2.0, 2.0, 3.0];
+        let test = kolmogorov_smirnov_two_sample(&tied, &tied);
+        assert_close(test.statistic, 0.0, 1e-12);
+        assert_close(test.p_value, 1.0, 1e-12);
+
+        let test = kolmogorov_smirnov_two_sample(&[1.0, 2.0, 3.0, 4.0], &[2.5, 3.5]);
+        assert_close(test.statistic, 0.5, 1e-12);
+    }
+
+    #[test]
+    fn test_anderson_darling_scholz_stephens_example() {
+        // Scholz & Stephens (1987) の紙の滑らかさのデータ（4 研究所 × 8）
+        let lab1 = [38.7, 41.5, 43.8, 44.5, 45.5, 46.0, 47.7, 58.0];
+        let lab2 = [39.2, 39.3, 39.7, 41.4, 41.8, 42.9, 43.3, 45.8];
+        let lab3 = [34.0, 35.0, 39.0, 40.0, 43.0, 43.0, 44.0, 45.0];
+        let lab4 = [34.0, 34.8, 34.8, 35.4, 37.2, 37.8, 41.2, 42.8];
+        let test = anderson_darling_k_sample(&[&lab1, &lab2, &lab3, &lab4]).unwrap();
+        // 同順位を考慮した A²_akN = 8.3926、σ_N = 1.2038（T = 4.4797）
+        assert_close(test.statistic, 8.3926, 1e-4);
+        assert_close((test.statistic - 3.0) / test.standardized, 1.2038, 1e-3);
+        assert!(
+            test.p_value > 0.001 && test.p_value < 0.004,
+            "p = {}",
+            test.p_value
+        );
+
+        assert!(anderson_darling_k_sample(&[&[1.0], &[2.0]]).is_none());
+    }
+
+    #[test]
+    fn test_anderson_darling_same_distribution() {
+        let a: Vec<f64> = (0..50).map(|i| (i as f64 * 0.37).sin()).collect();
+        let b: Vec<f64> = (0..50).map(|i| (i as f64 * 0.37 + 0.1).sin()).collect();
+        let test = anderson_darling_k_sample(&[&a, &b]).unwrap();
+        assert_close(test.p_value, 0.25, 1e-12);
+
+        let shifted: Vec<f64> = a.iter().map(|v| v + 1.5).collect();
+        let test = anderson_darling_k_sample(&[&a, &shifted]).unwrap();
+        assert_close(test.p_value, 0.001, 1e-12);
+
+        // 完全に分かれた標本でも表の外挿で p が戻らない
+        let separated: Vec<f64> = a.iter().map(|v| v + 100.0).collect();
+        let test = anderson_darling_k_sample(&[&a, &separated]).unwrap();
+        assert!(test.standardized > 20.0);
+        assert_close(test.p_value, 0.001, 1e-12);
+    }
+
+    #[test]
+    fn test_rank_statistics() {
+        assert_close(
+            spearman_correlation(&[1.0, 2.0, 3.0, 4.0], &[10.0, 20.0, 30.0, 40.0]).unwrap(),
+            1.0,
+            1e-12,
+        );
+        assert_close(
+            spearman_correlation(&[1.0, 2.0, 3.0, 4.0], &[4.0, 3.0, 2.0, 1.0]).unwrap(),
+            -1.0,
+            1e-12,
+        );
+        // 同順位: x の順位 [1, 2.5, 2.5, 4]
+        assert_close(
+            spearman_correlation(&[1.0, 2.0, 2.0, 3.0], &[1.0, 2.0, 3.0, 4.0]).unwrap(),
+            4.5 / (4.5f64 * 5.0).sqrt(),
+            1e-12,
+        );
+        assert!(spearman_correlation(&[1.0, 1.0], &[1.0, 2.0]).is_none());
+
+        assert_close(cliffs_delta(&[5.0, 6.0], &[1.0, 2.0]), 1.0, 1e-12);
+        assert_close(cliffs_delta(&[1.0, 2.0], &[5.0, 6.0]), -1.0, 1e-12);
+        assert_close(cliffs_delta(&[1.0, 3.0], &[2.0, 3.0]), -0.25, 1e-12);
+    }
+}
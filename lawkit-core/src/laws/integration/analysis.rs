@@ -1,5 +1,5 @@
 use super::result::*;
-use crate::diffx_core_mock::{diff, DiffResult};
+use crate::common::diff::{diff, DiffResult};
 use crate::error::Result;
 use crate::laws::benford::analyze_benford_law;
 use crate::laws::normal::analyze_normal_distribution;
@@ -297,26 +297,21 @@ fn calculate_fold_consistency(
     train_result: &IntegrationResult,
     test_result: &IntegrationResult,
 ) -> f64 {
-    // diffx-coreを使用した一貫性分析
-    calculate_enhanced_consistency_with_diffx(train_result, test_result)
+    // 構造的差分による一貫性分析
+    calculate_structural_consistency(train_result, test_result)
 }
 
-/// diffx-coreを使用した一貫性計算
-fn calculate_enhanced_consistency_with_diffx(
+/// 法則スコアの構造的差分による一貫性計算
+fn calculate_structural_consistency(
     train_result: &IntegrationResult,
     test_result: &IntegrationResult,
 ) -> f64 {
-    // HashMap<String, f64>をJSONに変換してdiffx-coreで比較
+    // HashMap<String, f64>をJSONに変換して比較
     let train_json = serde_json::to_value(&train_result.law_scores).unwrap_or_default();
     let test_json = serde_json::to_value(&test_result.law_scores).unwrap_or_default();
 
-    // diffx-coreで構造的差分を検出
-    let diff_results = diff(&train_json, &test_json, None);
-
-    let results = match diff_results {
-        Ok(results) => results,
-        Err(_) => return 0.0, // エラーの場合は一致度0
-    };
+    // 構造的差分を検出
+    let results = diff(&train_json, &test_json);
 
     if results.is_empty() {
         return 1.0; // 完全一致
@@ -347,7 +342,6 @@ fn calculate_enhanced_consistency_with_diffx(
                 }
             }
             DiffResult::TypeChanged(_, _, _) => 1.0, // 型変更は最大影響
-            DiffResult::Unchanged => 0.0,            // 変更なし
         };
         total_diff_impact += impact;
     }
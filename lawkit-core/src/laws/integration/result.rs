@@ -1,10 +1,11 @@
+use crate::common::diff::{diff, DiffResult};
 use crate::common::risk::RiskLevel;
-use crate::diffx_core_mock::{diff, DiffResult};
 use crate::laws::benford::BenfordResult;
 use crate::laws::normal::NormalResult;
 use crate::laws::pareto::ParetoResult;
 use crate::laws::poisson::PoissonResult;
 use crate::laws::zipf::ZipfResult;
+use serde::Serialize;
 use std::collections::HashMap;
 
 /// 法則名を短縮形からフルネーム（アポストロフィなし）に変換
@@ -20,7 +21,7 @@ fn get_law_display_name(law: &str) -> &str {
 }
 
 /// 統合分析結果
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize)]
 pub struct IntegrationResult {
     pub dataset_name: String,
     pub numbers_analyzed: usize,
@@ -54,7 +55,7 @@ pub struct IntegrationResult {
 }
 
 /// 法則間矛盾
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize)]
 pub struct Conflict {
     pub conflict_type: ConflictType,
     pub laws_involved: Vec<String>,
@@ -65,19 +66,19 @@ pub struct Conflict {
 }
 
 /// 矛盾タイプ
-#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize)]
 pub enum ConflictType {
     DistributionMismatch,   // 分布適合性の不一致
     QualityDisagreement,    // 品質評価の不一致
     RiskLevelConflict,      // リスクレベルの不一致
     ScaleIncompatibility,   // スケール不適合
     MethodologicalConflict, // 手法論的矛盾
-    ScoreDeviation,         // スコア乖離（diffx-core検出）
-    UnexpectedConsistency,  // 異常な一致（diffx-core検出）
+    ScoreDeviation,         // スコア乖離（構造的差分で検出）
+    UnexpectedConsistency,  // 異常な一致（構造的差分で検出）
 }
 
 /// 推奨システム結果
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize)]
 pub struct Recommendation {
     pub primary_law: String,                           // 主要推奨法則
     pub secondary_laws: Vec<String>,                   // 補助推奨法則
@@ -87,7 +88,7 @@ pub struct Recommendation {
 }
 
 /// 法則組み合わせ
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize)]
 pub struct LawCombination {
     pub laws: Vec<String>,
     pub purpose: String,
@@ -96,7 +97,7 @@ pub struct LawCombination {
 }
 
 /// データ特性
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize)]
 pub struct DataCharacteristics {
     pub data_type: DataType,
     pub distribution_shape: DistributionShape,
@@ -107,7 +108,7 @@ pub struct DataCharacteristics {
 }
 
 /// データタイプ
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Serialize)]
 pub enum DataType {
     Continuous, // 連続データ
     Discrete,   // 離散データ
@@ -117,7 +118,7 @@ pub enum DataType {
 }
 
 /// 分布形状
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Serialize)]
 pub enum DistributionShape {
     Normal,      // 正規分布様
     Skewed,      // 歪み分布
@@ -129,7 +130,7 @@ pub enum DistributionShape {
 }
 
 /// 外れ値レベル
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Serialize)]
 pub enum OutlierLevel {
     None,     // 外れ値なし
     Low,      // 軽微
@@ -139,7 +140,7 @@ pub enum OutlierLevel {
 }
 
 /// スケール範囲
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Serialize)]
 pub enum ScaleRange {
     Narrow, // 狭い範囲 (1-2桁)
     Medium, // 中程度 (3-4桁)
@@ -148,7 +149,7 @@ pub enum ScaleRange {
 }
 
 /// 分析目的
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Serialize)]
 pub enum AnalysisPurpose {
     QualityAudit,          // 品質監査
     FraudDetection,        // 不正検知
@@ -159,7 +160,7 @@ pub enum AnalysisPurpose {
 }
 
 /// サンプルサイズカテゴリ
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Serialize)]
 pub enum SampleSizeCategory {
     Small,     // < 30
     Medium,    // 30-300
@@ -168,7 +169,7 @@ pub enum SampleSizeCategory {
 }
 
 /// 総合評価
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Serialize)]
 pub enum OverallAssessment {
     Excellent,   // 優秀 - 全法則で一貫して高評価
     Good,        // 良好 - 大部分の法則で良評価
@@ -308,8 +309,8 @@ impl IntegrationResult {
     fn detect_conflicts(&mut self) {
         self.conflicts.clear();
 
-        // diffx-coreを使用してより詳細な矛盾分析を実行
-        self.detect_conflicts_with_diffx();
+        // 構造的差分によるより詳細な矛盾分析を実行
+        self.detect_structural_conflicts();
 
         // 従来の手法も併用（スコア差分の詳細分析）
         self.detect_score_conflicts();
@@ -317,8 +318,8 @@ impl IntegrationResult {
         self.conflicts_detected = self.conflicts.len();
     }
 
-    /// diffx-coreを使用した構造的矛盾検出
-    fn detect_conflicts_with_diffx(&mut self) {
+    /// 構造的差分による矛盾検出
+    fn detect_structural_conflicts(&mut self) {
         if self.law_scores.is_empty() {
             return;
         }
@@ -332,17 +333,12 @@ impl IntegrationResult {
             expected_scores.insert(law.clone(), average_score);
         }
 
-        // JSONに変換してdiffx-coreで比較
+        // JSONに変換して比較
         let expected_json = serde_json::to_value(&expected_scores).unwrap_or_default();
         let actual_json = serde_json::to_value(&self.law_scores).unwrap_or_default();
 
-        // diffx-coreで構造的差分を分析
-        let diff_results = diff(&expected_json, &actual_json, None);
-
-        let results = match diff_results {
-            Ok(results) => results,
-            Err(_) => return, // エラーの場合は早期リターン
-        };
+        // 構造的差分を分析
+        let results = diff(&expected_json, &actual_json);
 
         if results.is_empty() {
             // 全てのスコアが期待値と一致（疑わしい一致）
@@ -427,19 +423,16 @@ impl IntegrationResult {
                         };
                         self.conflicts.push(conflict);
                     }
-                    DiffResult::Unchanged => {
-                        // 変更なし - 何もしない
-                    }
                 }
             }
         }
     }
 
-    /// diffx-core強化版スコア矛盾検出
+    /// 構造的差分で強化したスコア矛盾検出
     fn detect_score_conflicts(&mut self) {
         let laws: Vec<String> = self.law_scores.keys().cloned().collect();
 
-        // diffx-coreを使用した構造化比較用のJSONオブジェクト作成
+        // 構造化比較用のJSONオブジェクト作成
         for i in 0..laws.len() {
             for j in i + 1..laws.len() {
                 let law_a = &laws[i];
@@ -466,8 +459,8 @@ impl IntegrationResult {
                         "relative_rank": self.get_relative_rank(law_b)
                     });
 
-                    // diffx-coreで構造的差分を検出
-                    let diff_results = diff(&law_a_profile, &law_b_profile, None);
+                    // 構造的差分を検出
+                    let diff_results = diff(&law_a_profile, &law_b_profile);
 
                     // 従来の単純差分計算
                     let score_diff = (score_a - score_b).abs();
@@ -476,25 +469,12 @@ impl IntegrationResult {
                     if max_score > 0.0 {
                         let conflict_ratio = score_diff / max_score;
 
-                        // diffx-coreの結果と組み合わせた強化判定
-                        let has_structural_conflict = match &diff_results {
-                            Ok(results) => {
-                                !results.is_empty()
-                                    && results.iter().any(|result| {
-                                        if let DiffResult::Modified(path, _old_val, _new_val) =
-                                            result
-                                        {
-                                            if path.contains("confidence_level")
-                                                || path.contains("score_category")
-                                            {
-                                                return true;
-                                            }
-                                        }
-                                        false
-                                    })
-                            }
-                            Err(_) => false,
-                        };
+                        // 構造的差分と組み合わせた強化判定
+                        let has_structural_conflict = diff_results.iter().any(|result| {
+                            matches!(result, DiffResult::Modified(path, _, _)
+                                if path.contains("confidence_level")
+                                    || path.contains("score_category"))
+                        });
 
                         if conflict_ratio > 0.5 || has_structural_conflict {
                             let enhanced_conflict_score = if has_structural_conflict {
@@ -503,27 +483,14 @@ impl IntegrationResult {
                                 conflict_ratio
                             };
 
-                            let conflict = match &diff_results {
-                                Ok(results) => self.create_enhanced_conflict(
-                                    law_a.clone(),
-                                    law_b.clone(),
-                                    enhanced_conflict_score.min(1.0),
-                                    score_a,
-                                    score_b,
-                                    results,
-                                ),
-                                Err(_) => Conflict {
-                                    conflict_type: self.classify_conflict_type(law_a, law_b),
-                                    laws_involved: vec![law_a.clone(), law_b.clone()],
-                                    conflict_score: enhanced_conflict_score.min(1.0),
-                                    description: format!(
-                                        "Enhanced conflict detected between {} and {} (score: {:.3}, diff analysis failed)",
-                                        law_a, law_b, enhanced_conflict_score.min(1.0)
-                                    ),
-                                    likely_cause: "Diff analysis failed".to_string(),
-                                    resolution_suggestion: "Review data or retry analysis".to_string(),
-                                },
-                            };
+                            let conflict = self.create_enhanced_conflict(
+                                law_a.clone(),
+                                law_b.clone(),
+                                enhanced_conflict_score.min(1.0),
+                                score_a,
+                                score_b,
+                                &diff_results,
+                            );
                             self.conflicts.push(conflict);
                         }
                     }
@@ -532,7 +499,7 @@ impl IntegrationResult {
         }
     }
 
-    /// diffx-core結果を含む強化版矛盾オブジェクト作成
+    /// 構造的差分を含む強化版矛盾オブジェクト作成
     fn create_enhanced_conflict(
         &self,
         law_a: String,
@@ -544,7 +511,7 @@ impl IntegrationResult {
     ) -> Conflict {
         let conflict_type = self.classify_conflict_type(&law_a, &law_b);
 
-        // diffx-coreの差分情報から詳細な説明を生成
+        // 差分情報から詳細な説明を生成
         let mut detailed_description = format!(
             "{} and {} show significantly different evaluations (difference: {:.3})",
             get_law_display_name(&law_a),
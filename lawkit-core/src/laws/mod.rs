@@ -1,4 +1,5 @@
 pub mod benford;
+pub mod compare;
 pub mod integration;
 pub mod normal;
 pub mod pareto;
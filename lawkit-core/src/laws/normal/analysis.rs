@@ -1,5 +1,7 @@
 use super::result::NormalResult;
-use crate::error::Result;
+use super::shapiro_wilk::{SHAPIRO_WILK_MAX_SIZE, SHAPIRO_WILK_MIN_SIZE};
+use crate::common::input::NumberRecord;
+use crate::error::{BenfError, Result};
 
 /// 正規分布分析を実行
 pub fn analyze_normal_distribution(numbers: &[f64], dataset_name: &str) -> Result<NormalResult> {
@@ -11,13 +13,23 @@ pub fn test_normality(numbers: &[f64], test_type: NormalityTest) -> Result<Norma
     let result = NormalResult::new("normality_test".to_string(), numbers)?;
 
     match test_type {
-        NormalityTest::ShapiroWilk => Ok(NormalityTestResult {
-            test_name: "Shapiro-Wilk".to_string(),
-            statistic: result.shapiro_wilk_statistic,
-            p_value: result.shapiro_wilk_p_value,
-            critical_value: 0.05,
-            is_normal: result.shapiro_wilk_p_value > 0.05,
-        }),
+        NormalityTest::ShapiroWilk => {
+            let (Some(statistic), Some(p_value)) =
+                (result.shapiro_wilk_statistic, result.shapiro_wilk_p_value)
+            else {
+                return Err(BenfError::InvalidInput(format!(
+                    "Shapiro-Wilk requires {SHAPIRO_WILK_MIN_SIZE}-{SHAPIRO_WILK_MAX_SIZE} non-constant values, got {}",
+                    numbers.len()
+                )));
+            };
+            Ok(NormalityTestResult {
+                test_name: "Shapiro-Wilk".to_string(),
+                statistic,
+                p_value,
+                critical_value: 0.05,
+                is_normal: p_value > 0.05,
+            })
+        }
         NormalityTest::AndersonDarling => Ok(NormalityTestResult {
             test_name: "Anderson-Darling".to_string(),
             statistic: result.anderson_darling_statistic,
@@ -33,11 +45,16 @@ pub fn test_normality(numbers: &[f64], test_type: NormalityTest) -> Result<Norma
             is_normal: result.kolmogorov_smirnov_p_value > 0.05,
         }),
         NormalityTest::All => {
-            // 複数検定の統合結果
-            let overall_p = (result.shapiro_wilk_p_value
-                + result.anderson_darling_p_value
-                + result.kolmogorov_smirnov_p_value)
-                / 3.0;
+            // 複数検定の統合結果（Shapiro-Wilk は適用できる場合のみ）
+            let p_values: Vec<f64> = [
+                result.shapiro_wilk_p_value,
+                Some(result.anderson_darling_p_value),
+                Some(result.kolmogorov_smirnov_p_value),
+            ]
+            .into_iter()
+            .flatten()
+            .collect();
+            let overall_p = p_values.iter().sum::<f64>() / p_values.len() as f64;
             Ok(NormalityTestResult {
                 test_name: "Combined Test".to_string(),
                 statistic: result.normality_score,
@@ -67,6 +84,7 @@ pub fn detect_outliers(
                     value: val,
                     score,
                     is_outlier: score.abs() > 2.5,
+                    record: None,
                 })
                 .collect(),
             threshold: 2.5,
@@ -81,6 +99,7 @@ pub fn detect_outliers(
                     value: val,
                     score,
                     is_outlier: score.abs() > 3.5,
+                    record: None,
                 })
                 .collect(),
             threshold: 3.5,
@@ -95,6 +114,7 @@ pub fn detect_outliers(
                     value: val,
                     score: 0.0, // IQR法ではスコアなし
                     is_outlier: true,
+                    record: None,
                 })
                 .collect(),
             threshold: 1.5, // IQR倍数
@@ -262,6 +282,16 @@ pub struct OutlierDetectionResult {
     pub threshold: f64,
 }
 
+impl OutlierDetectionResult {
+    /// 検出に使った数値と同じ順序のレコードから、各外れ値の出所を付与
+    pub fn with_records(mut self, records: &[NumberRecord]) -> Self {
+        for outlier in &mut self.outliers {
+            outlier.record = records.get(outlier.index).cloned();
+        }
+        self
+    }
+}
+
 /// 外れ値情報
 #[derive(Debug, Clone)]
 pub struct OutlierInfo {
@@ -269,6 +299,7 @@ pub struct OutlierInfo {
     pub value: f64,
     pub score: f64,
     pub is_outlier: bool,
+    pub record: Option<NumberRecord>, // 入力中の出所（with_records で付与）
 }
 
 /// 品質管理分析結果
@@ -1,5 +1,6 @@
 mod analysis;
 mod result;
+mod shapiro_wilk;
 
 pub use analysis::{
     analyze_normal_distribution, detect_outliers, quality_control_analysis, test_normality,
@@ -7,3 +8,6 @@ pub use analysis::{
     ProcessCapability, QualityControlResult,
 };
 pub use result::NormalResult;
+pub use shapiro_wilk::{
+    shapiro_wilk, ShapiroWilkTest, SHAPIRO_WILK_MAX_SIZE, SHAPIRO_WILK_MIN_SIZE,
+};
@@ -1,10 +1,12 @@
+use super::shapiro_wilk::shapiro_wilk;
 use crate::{
-    common::risk::RiskLevel,
+    common::{filtering::RiskThreshold, risk::RiskLevel},
     error::{BenfError, Result},
 };
+use serde::Serialize;
 
 /// 正規分布解析結果
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Serialize)]
 pub struct NormalResult {
     pub dataset_name: String,
     pub numbers_analyzed: usize,
@@ -18,12 +20,12 @@ pub struct NormalResult {
     pub kurtosis: f64, // 尖度（分布の尖り）
 
     // 正規性検定結果
-    pub shapiro_wilk_statistic: f64,       // Shapiro-Wilk検定統計量
-    pub shapiro_wilk_p_value: f64,         // Shapiro-Wilk p値
-    pub anderson_darling_statistic: f64,   // Anderson-Darling検定統計量
-    pub anderson_darling_p_value: f64,     // Anderson-Darling p値
-    pub kolmogorov_smirnov_statistic: f64, // Kolmogorov-Smirnov検定統計量
-    pub kolmogorov_smirnov_p_value: f64,   // Kolmogorov-Smirnov p値
+    pub shapiro_wilk_statistic: Option<f64>, // Shapiro-Wilk検定統計量（3〜5000件の範囲外は None）
+    pub shapiro_wilk_p_value: Option<f64>,   // Shapiro-Wilk p値（Royston 1995）
+    pub anderson_darling_statistic: f64,     // Anderson-Darling検定統計量
+    pub anderson_darling_p_value: f64,       // Anderson-Darling p値
+    pub kolmogorov_smirnov_statistic: f64,   // Kolmogorov-Smirnov検定統計量
+    pub kolmogorov_smirnov_p_value: f64,     // Kolmogorov-Smirnov p値
 
     // 適合度評価
     pub normality_score: f64,      // 正規性総合スコア（0-1）
@@ -48,6 +50,19 @@ pub struct NormalResult {
 
 impl NormalResult {
     pub fn new(dataset_name: String, numbers: &[f64]) -> Result<Self> {
+        Self::new_with_significance(dataset_name, numbers, &RiskThreshold::Auto, None)
+    }
+
+    /// A `significance_level` adds its Auto risk bands for the normality p-value
+    /// (Shapiro-Wilk, or Anderson-Darling outside 3-5000 values) to the shape-based risk;
+    /// `None` keeps the shape-based risk of [`Self::new`]. An explicit threshold decides
+    /// the risk on that p-value alone.
+    pub fn new_with_significance(
+        dataset_name: String,
+        numbers: &[f64],
+        threshold: &RiskThreshold,
+        significance_level: Option<f64>,
+    ) -> Result<Self> {
         if numbers.len() < 8 {
             return Err(BenfError::InsufficientData(numbers.len()));
         }
@@ -62,14 +77,14 @@ impl NormalResult {
         let kurtosis = calculate_kurtosis(numbers, mean, std_dev);
 
         // 正規性検定
-        let shapiro_result = shapiro_wilk_test(numbers);
+        let shapiro_result = shapiro_wilk(numbers);
         let anderson_result = anderson_darling_test(numbers, mean, std_dev);
         let ks_result = kolmogorov_smirnov_test(numbers, mean, std_dev);
 
         // 適合度評価
         let qq_correlation = calculate_qq_correlation(numbers, mean, std_dev);
         let normality_score = calculate_normality_score(
-            shapiro_result.1,
+            shapiro_result.map(|test| test.p_value),
             anderson_result.1,
             ks_result.1,
             qq_correlation,
@@ -93,8 +108,22 @@ impl NormalResult {
         let within_3_sigma_percent = calculate_within_sigma_percent(numbers, mean, std_dev, 3.0);
 
         // リスクレベル判定
-        let risk_level =
-            determine_risk_level(normality_score, &outliers_z_score, skewness, kurtosis);
+        let normality_p_value = shapiro_result
+            .map(|test| test.p_value)
+            .unwrap_or(anderson_result.1);
+        let risk_level = if threshold.p_value_threshold().is_some() {
+            threshold.evaluate_risk(normality_p_value)
+        } else {
+            let shape_risk =
+                determine_risk_level(normality_score, &outliers_z_score, skewness, kurtosis);
+            match significance_level {
+                Some(alpha) => shape_risk.max(RiskLevel::from_p_value_with_significance(
+                    normality_p_value,
+                    alpha,
+                )),
+                None => shape_risk,
+            }
+        };
 
         Ok(NormalResult {
             dataset_name,
@@ -105,8 +134,8 @@ impl NormalResult {
             variance,
             skewness,
             kurtosis,
-            shapiro_wilk_statistic: shapiro_result.0,
-            shapiro_wilk_p_value: shapiro_result.1,
+            shapiro_wilk_statistic: shapiro_result.map(|test| test.statistic),
+            shapiro_wilk_p_value: shapiro_result.map(|test| test.p_value),
             anderson_darling_statistic: anderson_result.0,
             anderson_darling_p_value: anderson_result.1,
             kolmogorov_smirnov_statistic: ks_result.0,
@@ -160,38 +189,6 @@ fn calculate_kurtosis(numbers: &[f64], mean: f64, std_dev: f64) -> f64 {
     (sum_fourth / n) - 3.0 // 正規分布の尖度3を基準とした超過尖度
 }
 
-/// Shapiro-Wilk検定（簡易版）
-fn shapiro_wilk_test(numbers: &[f64]) -> (f64, f64) {
-    let n = numbers.len();
-    if !(8..=5000).contains(&n) {
-        return (0.0, 1.0); // 適用範囲外
-    }
-
-    let mut sorted = numbers.to_vec();
-    sorted.sort_by(|a, b| a.partial_cmp(b).unwrap());
-
-    // 簡易実装: 分位数相関係数を使用
-    let qq_corr = calculate_qq_correlation(
-        &sorted,
-        calculate_mean(&sorted),
-        calculate_variance(&sorted, calculate_mean(&sorted)).sqrt(),
-    );
-    let w_statistic = qq_corr * qq_corr;
-
-    // 簡易p値推定（厳密な計算は複雑な積分が必要）
-    let p_value = if w_statistic > 0.95 {
-        0.5
-    } else if w_statistic > 0.90 {
-        0.1
-    } else if w_statistic > 0.80 {
-        0.01
-    } else {
-        0.001
-    };
-
-    (w_statistic, p_value)
-}
-
 /// Anderson-Darling検定（簡易版）
 fn anderson_darling_test(numbers: &[f64], mean: f64, std_dev: f64) -> (f64, f64) {
     let mut sorted = numbers.to_vec();
@@ -359,9 +356,14 @@ fn pearson_correlation(x: &[f64], y: &[f64]) -> f64 {
 }
 
 /// 正規性総合スコア計算
-fn calculate_normality_score(sw_p: f64, ad_p: f64, ks_p: f64, qq_corr: f64) -> f64 {
+fn calculate_normality_score(sw_p: Option<f64>, ad_p: f64, ks_p: f64, qq_corr: f64) -> f64 {
     // p値は高いほど正規分布に近い、相関係数も高いほど良い
-    let p_score = (sw_p * 0.4 + ad_p * 0.3 + ks_p * 0.3).min(1.0);
+    // Shapiro-Wilk が適用できない標本サイズでは残りの検定だけで評価する
+    let p_score = match sw_p {
+        Some(sw_p) => sw_p * 0.4 + ad_p * 0.3 + ks_p * 0.3,
+        None => (ad_p + ks_p) * 0.5,
+    }
+    .min(1.0);
     let corr_score = qq_corr.abs();
 
     (p_score * 0.6 + corr_score * 0.4).clamp(0.0, 1.0)
@@ -524,6 +526,24 @@ mod tests {
         assert!(result.is_err());
     }
 
+    #[test]
+    fn test_shapiro_wilk_applicability() {
+        // Shapiro & Wilk (1965) の体重の例（R: W = 0.78881, p-value = 0.006704）
+        let weights = vec![
+            148.0, 154.0, 158.0, 160.0, 161.0, 162.0, 166.0, 170.0, 182.0, 195.0, 236.0,
+        ];
+        let result = NormalResult::new("weights".to_string(), &weights).unwrap();
+        assert!((result.shapiro_wilk_statistic.unwrap() - 0.78881).abs() < 5e-6);
+        assert!((result.shapiro_wilk_p_value.unwrap() - 0.006704).abs() < 5e-7);
+
+        // 5000 件を超えると Shapiro-Wilk は報告せず、残りの検定で評価する
+        let large: Vec<f64> = (0..5001).map(|i| (i as f64 * 0.37).sin()).collect();
+        let result = NormalResult::new("large".to_string(), &large).unwrap();
+        assert_eq!(result.shapiro_wilk_statistic, None);
+        assert_eq!(result.shapiro_wilk_p_value, None);
+        assert!((0.0..=1.0).contains(&result.normality_score));
+    }
+
     #[test]
     fn test_outlier_detection() {
         let numbers = vec![1.0, 2.0, 3.0, 4.0, 5.0, 6.0, 7.0, 8.0, 100.0]; // 100.0が外れ値
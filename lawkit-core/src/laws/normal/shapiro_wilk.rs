@@ -0,0 +1,220 @@
+use crate::common::statistics::{normal_quantile, normal_upper_tail};
+use serde::Serialize;
+
+/// Royston の近似が有効な標本サイズの範囲
+pub const SHAPIRO_WILK_MIN_SIZE: usize = 3;
+pub const SHAPIRO_WILK_MAX_SIZE: usize = 5000;
+
+/// 係数 a_n, a_{n-1} の多項式補正（u = 1/√n）
+const C1: [f64; 6] = [0.0, 0.221157, -0.147981, -2.07119, 4.434685, -2.706056];
+const C2: [f64; 6] = [0.0, 0.042981, -0.293762, -1.752461, 5.682633, -3.582633];
+/// n ≤ 11: ln(1 - W) の変換 γ(n) と、変換後の平均・log 標準偏差（n の多項式）
+const GAMMA: [f64; 2] = [-2.273, 0.459];
+const C3: [f64; 4] = [0.544, -0.39978, 0.025054, -6.714e-4];
+const C4: [f64; 4] = [1.3822, -0.77857, 0.062767, -0.0020322];
+/// n ≥ 12: ln(1 - W) の平均・log 標準偏差（ln n の多項式）
+const C5: [f64; 4] = [-1.5861, -0.31082, -0.083751, 0.0038915];
+const C6: [f64; 3] = [-0.4803, -0.082676, 0.0030302];
+
+/// Shapiro-Wilk 検定の結果
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+pub struct ShapiroWilkTest {
+    /// W 統計量（1 に近いほど正規分布に近い）
+    pub statistic: f64,
+    pub p_value: f64,
+}
+
+/// Shapiro-Wilk 検定（Royston 1995, Algorithm AS R94）
+///
+/// 係数は期待正規順序統計量に Royston の多項式補正を加えた近似、p 値は ln(1 - W) の
+/// 正規近似（n = 3 は厳密分布）で求める。R の `shapiro.test` や SciPy の `shapiro` と同じ
+/// アルゴリズム。標本サイズが 3〜5000 の範囲外、非有限値を含む、またはすべて同じ値なら `None`。
+pub fn shapiro_wilk(values: &[f64]) -> Option<ShapiroWilkTest> {
+    let n = values.len();
+    if !(SHAPIRO_WILK_MIN_SIZE..=SHAPIRO_WILK_MAX_SIZE).contains(&n)
+        || values.iter().any(|v| !v.is_finite())
+    {
+        return None;
+    }
+
+    let mut sorted = values.to_vec();
+    sorted.sort_by(|a, b| a.total_cmp(b));
+    let range = sorted[n - 1] - sorted[0];
+    if range <= 0.0 {
+        return None;
+    }
+
+    // 範囲で割ってから計算し、桁の大きなデータでの桁落ちを防ぐ
+    let scaled: Vec<f64> = sorted.iter().map(|x| (x - sorted[0]) / range).collect();
+    let mean = scaled.iter().sum::<f64>() / n as f64;
+    let sum_of_squares: f64 = scaled.iter().map(|x| (x - mean).powi(2)).sum();
+    let numerator: f64 = shapiro_wilk_coefficients(n)
+        .iter()
+        .enumerate()
+        .map(|(i, a)| a * (scaled[n - 1 - i] - scaled[i]))
+        .sum();
+    let statistic = (numerator * numerator / sum_of_squares).min(1.0);
+
+    Some(ShapiroWilkTest {
+        statistic,
+        p_value: shapiro_wilk_p_value(statistic, n),
+    })
+}
+
+/// 係数 a_1 ≥ a_2 ≥ … ≥ a_{n/2}（上位と下位の順序統計量の差に掛ける正の値）
+fn shapiro_wilk_coefficients(n: usize) -> Vec<f64> {
+    if n == 3 {
+        return vec![std::f64::consts::FRAC_1_SQRT_2];
+    }
+
+    let size = n as f64;
+    // Blom の近似による期待正規順序統計量（上位側を正で持つ）
+    let m: Vec<f64> = (1..=n / 2)
+        .map(|i| -normal_quantile((i as f64 - 0.375) / (size + 0.25)))
+        .collect();
+    let sum_m2 = 2.0 * m.iter().map(|v| v * v).sum::<f64>();
+    let norm = sum_m2.sqrt();
+    let u = 1.0 / size.sqrt();
+
+    let mut coefficients = vec![0.0; m.len()];
+    coefficients[0] = m[0] / norm + polynomial(&C1, u);
+    // 端の係数を補正した分だけ、残りの係数を正規化し直す
+    let (fixed, scale) = if n > 5 {
+        coefficients[1] = m[1] / norm + polynomial(&C2, u);
+        let (a1, a2) = (coefficients[0], coefficients[1]);
+        (
+            2,
+            ((sum_m2 - 2.0 * m[0] * m[0] - 2.0 * m[1] * m[1])
+                / (1.0 - 2.0 * a1 * a1 - 2.0 * a2 * a2))
+                .sqrt(),
+        )
+    } else {
+        let a1 = coefficients[0];
+        (
+            1,
+            ((sum_m2 - 2.0 * m[0] * m[0]) / (1.0 - 2.0 * a1 * a1)).sqrt(),
+        )
+    };
+    for i in fixed..m.len() {
+        coefficients[i] = m[i] / scale;
+    }
+    coefficients
+}
+
+/// W の上側 p 値
+fn shapiro_wilk_p_value(statistic: f64, n: usize) -> f64 {
+    if n == 3 {
+        // n = 3 の厳密分布: P = (6/π)(arcsin √W − π/3)
+        let p =
+            6.0 / std::f64::consts::PI * (statistic.sqrt().asin() - std::f64::consts::FRAC_PI_3);
+        return p.clamp(0.0, 1.0);
+    }
+
+    let size = n as f64;
+    let y = (1.0 - statistic).ln();
+    let (transformed, mean, std_dev) = if n <= 11 {
+        let gamma = polynomial(&GAMMA, size);
+        if y >= gamma {
+            return 0.0;
+        }
+        (
+            -(gamma - y).ln(),
+            polynomial(&C3, size),
+            polynomial(&C4, size).exp(),
+        )
+    } else {
+        let ln_n = size.ln();
+        (y, polynomial(&C5, ln_n), polynomial(&C6, ln_n).exp())
+    };
+    normal_upper_tail((transformed - mean) / std_dev)
+}
+
+/// c0 + c1 x + c2 x² + …
+fn polynomial(coefficients: &[f64], x: f64) -> f64 {
+    coefficients.iter().rev().fold(0.0, |acc, c| acc * x + c)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assert_close(actual: f64, expected: f64, tolerance: f64) {
+        assert!(
+            (actual - expected).abs() <= tolerance,
+            "expected {expected}, got {actual}"
+        );
+    }
+
+    #[test]
+    fn test_exact_distribution_for_three_values() {
+        // W = 4.5 / (42/9)、R: W = 0.96429, p-value = 0.6369
+        let test = shapiro_wilk(&[1.0, 2.0, 4.0]).unwrap();
+        assert_close(test.statistic, 27.0 / 28.0, 1e-12);
+        assert_close(test.p_value, 0.6369, 1e-4);
+
+        // 等間隔なら W = 1, p = 1
+        let test = shapiro_wilk(&[1.0, 2.0, 3.0]).unwrap();
+        assert_close(test.statistic, 1.0, 1e-12);
+        assert_close(test.p_value, 1.0, 1e-12);
+    }
+
+    #[test]
+    fn test_shapiro_wilk_1965_weights() {
+        // Shapiro & Wilk (1965) の体重の例（n = 11）。R: W = 0.78881, p-value = 0.006704
+        let weights = [
+            148.0, 154.0, 158.0, 160.0, 161.0, 162.0, 166.0, 170.0, 182.0, 195.0, 236.0,
+        ];
+        let test = shapiro_wilk(&weights).unwrap();
+        assert_close(test.statistic, 0.78881, 5e-6);
+        assert_close(test.p_value, 0.006704, 5e-7);
+    }
+
+    #[test]
+    fn test_reference_values_from_r() {
+        // R: shapiro.test(mtcars$mpg) → W = 0.94756, p-value = 0.1229
+        let mpg = [
+            21.0, 21.0, 22.8, 21.4, 18.7, 18.1, 14.3, 24.4, 22.8, 19.2, 17.8, 16.4, 17.3, 15.2,
+            10.4, 10.4, 14.7, 32.4, 30.4, 33.9, 21.5, 15.5, 15.2, 13.3, 19.2, 27.3, 26.0, 30.4,
+            15.8, 19.7, 15.0, 21.4,
+        ];
+        let test = shapiro_wilk(&mpg).unwrap();
+        assert_close(test.statistic, 0.94756, 5e-6);
+        assert_close(test.p_value, 0.1229, 5e-5);
+
+        // R: shapiro.test(ToothGrowth$len) → W = 0.96743, p-value = 0.1091
+        let len = [
+            4.2, 11.5, 7.3, 5.8, 6.4, 10.0, 11.2, 11.2, 5.2, 7.0, 16.5, 16.5, 15.2, 17.3, 22.5,
+            17.3, 13.6, 14.5, 18.8, 15.5, 23.6, 18.5, 33.9, 25.5, 26.4, 32.5, 26.7, 21.5, 23.3,
+            29.5, 15.2, 21.5, 17.6, 9.7, 14.5, 10.0, 8.2, 9.4, 16.5, 9.7, 19.7, 23.3, 23.6, 26.4,
+            20.0, 25.2, 25.8, 21.2, 14.5, 27.3, 25.5, 26.4, 22.4, 24.5, 24.8, 30.9, 26.4, 27.3,
+            29.4, 23.0,
+        ];
+        let test = shapiro_wilk(&len).unwrap();
+        assert_close(test.statistic, 0.96743, 5e-6);
+        assert_close(test.p_value, 0.1091, 5e-5);
+    }
+
+    #[test]
+    fn test_coefficients_are_normalized() {
+        for n in [4, 5, 6, 11, 12, 50, 1000, 5000] {
+            let coefficients = shapiro_wilk_coefficients(n);
+            let sum_of_squares = 2.0 * coefficients.iter().map(|a| a * a).sum::<f64>();
+            assert_close(sum_of_squares, 1.0, 1e-12);
+            assert!(coefficients.windows(2).all(|w| w[0] >= w[1]), "n = {n}");
+        }
+    }
+
+    #[test]
+    fn test_invariance_and_applicability() {
+        let data: Vec<f64> = (1..=40).map(|i| (i as f64 * 0.7).sin() * 3.0).collect();
+        let shifted: Vec<f64> = data.iter().map(|v| v * 1000.0 + 1.0e6).collect();
+        let test = shapiro_wilk(&data).unwrap();
+        let scaled = shapiro_wilk(&shifted).unwrap();
+        assert_close(test.statistic, scaled.statistic, 1e-9);
+        assert_close(test.p_value, scaled.p_value, 1e-9);
+
+        assert!(shapiro_wilk(&[1.0, 2.0]).is_none());
+        assert!(shapiro_wilk(&[5.0; 10]).is_none());
+        assert!(shapiro_wilk(&vec![1.0; SHAPIRO_WILK_MAX_SIZE + 1]).is_none());
+    }
+}
@@ -0,0 +1,476 @@
+use super::result::ParetoResult;
+use crate::common::input::NumberRecord;
+use crate::common::output::formatter::FormattableResult;
+use crate::common::risk::RiskLevel;
+use crate::error::{BenfError, Result};
+use serde::Serialize;
+use std::collections::HashMap;
+use std::fmt;
+
+/// ABC分析のクラス（A が最も重要）
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize)]
+pub enum AbcClass {
+    A,
+    B,
+    C,
+    D,
+}
+
+impl fmt::Display for AbcClass {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            AbcClass::A => "A",
+            AbcClass::B => "B",
+            AbcClass::C => "C",
+            AbcClass::D => "D",
+        };
+        write!(f, "{name}")
+    }
+}
+
+/// クラスの境界となる累積シェア（%）
+///
+/// 2つなら ABC、3つなら ABCD に分類する。既定値は 80,95（A が累積80%まで、
+/// B が95%まで、残りが C）。
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct AbcCutoffs(Vec<f64>);
+
+impl AbcCutoffs {
+    pub fn new(cutoffs: Vec<f64>) -> Result<Self> {
+        if !(2..=3).contains(&cutoffs.len()) {
+            return Err(BenfError::InvalidInput(format!(
+                "ABC cut-offs need 2 (ABC) or 3 (ABCD) values, got {}",
+                cutoffs.len()
+            )));
+        }
+        if cutoffs.iter().any(|&c| !(c > 0.0 && c < 100.0))
+            || cutoffs.windows(2).any(|pair| pair[0] >= pair[1])
+        {
+            return Err(BenfError::InvalidInput(
+                "ABC cut-offs must be increasing cumulative percentages between 0 and 100"
+                    .to_string(),
+            ));
+        }
+        Ok(AbcCutoffs(cutoffs))
+    }
+
+    /// 境界値（%）
+    pub fn values(&self) -> &[f64] {
+        &self.0
+    }
+
+    /// 使うクラス（ABC または ABCD）
+    pub fn classes(&self) -> &'static [AbcClass] {
+        const CLASSES: [AbcClass; 4] = [AbcClass::A, AbcClass::B, AbcClass::C, AbcClass::D];
+        &CLASSES[..=self.0.len()]
+    }
+
+    /// 手前の品目までの累積シェアが `preceding_share` の品目のクラス
+    ///
+    /// 境界をまたぐ品目は上位のクラスに入る（先頭の品目は常に A）。
+    fn classify(&self, preceding_share: f64) -> AbcClass {
+        let index = self
+            .0
+            .iter()
+            .position(|&cutoff| preceding_share < cutoff)
+            .unwrap_or(self.0.len());
+        self.classes()[index]
+    }
+}
+
+impl Default for AbcCutoffs {
+    fn default() -> Self {
+        AbcCutoffs(vec![80.0, 95.0])
+    }
+}
+
+impl std::str::FromStr for AbcCutoffs {
+    type Err = BenfError;
+
+    /// `80,95` や `70,90,98` の形式
+    fn from_str(s: &str) -> Result<Self> {
+        let cutoffs = s
+            .split(',')
+            .map(|part| part.trim().trim_end_matches('%').parse::<f64>())
+            .collect::<std::result::Result<Vec<f64>, _>>()
+            .map_err(|_| BenfError::ParseError(format!("invalid ABC cut-offs: {s}")))?;
+        AbcCutoffs::new(cutoffs)
+    }
+}
+
+/// ABC分析の1品目
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct AbcItem {
+    pub rank: usize,
+    pub label: String,
+    pub value: f64,
+    /// 合計に占める割合（%）
+    pub share: f64,
+    /// この品目までの累積シェア（%）
+    pub cumulative_share: f64,
+    pub class: AbcClass,
+}
+
+/// クラスごとの集計
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct AbcClassSummary {
+    pub class: AbcClass,
+    pub items: usize,
+    /// 品目数に占める割合（%）
+    pub item_share: f64,
+    pub value: f64,
+    /// 合計に占める割合（%）
+    pub value_share: f64,
+}
+
+/// ラベル付きの値に対するABC分析の結果
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct AbcResult {
+    pub dataset_name: String,
+    pub items_analyzed: usize,
+    pub total: f64,
+    pub cutoffs: AbcCutoffs,
+    pub top_20_percent_share: f64,
+    pub concentration_index: f64,
+    pub risk_level: RiskLevel,
+    pub classes: Vec<AbcClassSummary>,
+    /// 値の大きい順
+    pub items: Vec<AbcItem>,
+    /// ラベルが空だったため除いた数値の個数
+    pub unlabelled: usize,
+}
+
+impl FormattableResult for AbcResult {
+    const TITLE: &'static str = "Pareto ABC Analysis Results";
+    const ROOT: &'static str = "abc_result";
+
+    /// テキストではクラスの集計と品目表を出力する
+    fn format_text(&self) -> Result<String> {
+        Ok(format_abc_text(self))
+    }
+
+    /// CSVでは品目表を1品目1行で出力する
+    fn format_csv(&self) -> Result<String> {
+        format_abc_csv(self)
+    }
+}
+
+/// (ラベル, 値) の組をABC分析する
+///
+/// 同じラベルの値は合計して1品目にする。品目を値の大きい順に並べ、手前の品目までの
+/// 累積シェアで `cutoffs` のクラスに分ける。集中度とリスクレベルは品目の値に対する
+/// パレート分析（`ParetoResult`）による。
+pub fn analyze_abc(
+    items: &[(String, f64)],
+    dataset_name: &str,
+    cutoffs: &AbcCutoffs,
+) -> Result<AbcResult> {
+    analyze_abc_with_unlabelled(items, dataset_name, cutoffs, 0)
+}
+
+/// レコードのラベル（`NumberRecord.group`）と値をABC分析する
+///
+/// ラベル列は `InputOptions.group_by` で指定して読み込む。ラベルのないレコードは除き、
+/// その個数を `AbcResult.unlabelled` に記録する。
+pub fn analyze_abc_records(
+    records: &[NumberRecord],
+    dataset_name: &str,
+    cutoffs: &AbcCutoffs,
+) -> Result<AbcResult> {
+    let items: Vec<(String, f64)> = records
+        .iter()
+        .filter_map(|record| record.group.clone().map(|label| (label, record.value)))
+        .collect();
+    analyze_abc_with_unlabelled(&items, dataset_name, cutoffs, records.len() - items.len())
+}
+
+fn analyze_abc_with_unlabelled(
+    items: &[(String, f64)],
+    dataset_name: &str,
+    cutoffs: &AbcCutoffs,
+    unlabelled: usize,
+) -> Result<AbcResult> {
+    if let Some((label, value)) = items.iter().find(|(_, value)| !value.is_finite()) {
+        return Err(BenfError::InvalidInput(format!(
+            "ABC analysis needs finite values ('{label}' has {value})"
+        )));
+    }
+    if let Some((label, value)) = items.iter().find(|(_, value)| *value < 0.0) {
+        return Err(BenfError::InvalidInput(format!(
+            "ABC analysis needs non-negative values ('{label}' has {value})"
+        )));
+    }
+
+    // 同じラベルを合計（初出順）
+    let mut index = HashMap::new();
+    let mut totals: Vec<(String, f64)> = Vec::new();
+    for (label, value) in items {
+        let position = *index.entry(label.clone()).or_insert_with(|| {
+            totals.push((label.clone(), 0.0));
+            totals.len() - 1
+        });
+        totals[position].1 += value;
+    }
+    // 値の大きい順（同じ値は初出順）
+    totals.sort_by(|a, b| b.1.total_cmp(&a.1));
+
+    let values: Vec<f64> = totals.iter().map(|(_, value)| *value).collect();
+    let pareto = ParetoResult::new(dataset_name.to_string(), &values)?;
+    let total: f64 = values.iter().sum();
+    if total == 0.0 {
+        return Err(BenfError::InvalidInput(
+            "ABC analysis needs a positive total".to_string(),
+        ));
+    }
+
+    let mut cumulative = 0.0;
+    let items: Vec<AbcItem> = totals
+        .into_iter()
+        .enumerate()
+        .map(|(i, (label, value))| {
+            let class = cutoffs.classify(cumulative);
+            let share = value / total * 100.0;
+            cumulative += share;
+            AbcItem {
+                rank: i + 1,
+                label,
+                value,
+                share,
+                cumulative_share: cumulative.min(100.0),
+                class,
+            }
+        })
+        .collect();
+
+    let classes = cutoffs
+        .classes()
+        .iter()
+        .map(|&class| {
+            let members: Vec<&AbcItem> = items.iter().filter(|item| item.class == class).collect();
+            let value: f64 = members.iter().map(|item| item.value).sum();
+            AbcClassSummary {
+                class,
+                items: members.len(),
+                item_share: members.len() as f64 / items.len() as f64 * 100.0,
+                value,
+                value_share: value / total * 100.0,
+            }
+        })
+        .collect();
+
+    Ok(AbcResult {
+        dataset_name: dataset_name.to_string(),
+        items_analyzed: items.len(),
+        total,
+        cutoffs: cutoffs.clone(),
+        top_20_percent_share: pareto.top_20_percent_share,
+        concentration_index: pareto.concentration_index,
+        risk_level: pareto.risk_level,
+        classes,
+        items,
+        unlabelled,
+    })
+}
+
+/// ABC分析結果のCSV表現（列は rank,label,value,share,cumulative_share,class）
+pub fn format_abc_csv(result: &AbcResult) -> Result<String> {
+    let serialization_error = |e: csv::Error| BenfError::SerializationError(e.to_string());
+
+    let mut writer = csv::WriterBuilder::new()
+        .has_headers(false)
+        .from_writer(Vec::new());
+    writer
+        .write_record([
+            "rank",
+            "label",
+            "value",
+            "share",
+            "cumulative_share",
+            "class",
+        ])
+        .map_err(serialization_error)?;
+    for item in &result.items {
+        writer.serialize(item).map_err(serialization_error)?;
+    }
+
+    let bytes = writer
+        .into_inner()
+        .map_err(|e| BenfError::SerializationError(e.to_string()))?;
+    String::from_utf8(bytes).map_err(|e| BenfError::SerializationError(e.to_string()))
+}
+
+/// ABC分析結果のテキスト表現（クラスの集計と品目表）
+pub fn format_abc_text(result: &AbcResult) -> String {
+    let cutoffs: Vec<String> = result
+        .cutoffs
+        .values()
+        .iter()
+        .map(|cutoff| format!("{cutoff}%"))
+        .collect();
+    let mut output = format!("{}\n\n", AbcResult::TITLE);
+    output.push_str(&format!("Dataset: {}\n", result.dataset_name));
+    output.push_str(&format!("Items analyzed: {}\n", result.items_analyzed));
+    output.push_str(&format!("Cut-offs: {}\n", cutoffs.join(", ")));
+    output.push_str(&format!(
+        "Top 20% share: {:.1}%\n",
+        result.top_20_percent_share
+    ));
+    output.push_str(&format!(
+        "Concentration index: {:.3}\n",
+        result.concentration_index
+    ));
+    output.push_str(&format!("Risk level: {}\n\n", result.risk_level));
+
+    output.push_str(&format!(
+        "{:<5}  {:>6}  {:>7}  {:>7}\n",
+        "Class", "Items", "Items%", "Value%"
+    ));
+    for class in &result.classes {
+        output.push_str(&format!(
+            "{:<5}  {:>6}  {:>6.1}%  {:>6.1}%\n",
+            class.class.to_string(),
+            class.items,
+            class.item_share,
+            class.value_share
+        ));
+    }
+
+    output.push_str(&format!(
+        "\n{:>4}  {:<5}  {:>14}  {:>6}  {:>10}  Label\n",
+        "Rank", "Class", "Value", "Share", "Cumulative"
+    ));
+    for item in &result.items {
+        output.push_str(&format!(
+            "{:>4}  {:<5}  {:>14.2}  {:>5.1}%  {:>9.1}%  {}\n",
+            item.rank,
+            item.class.to_string(),
+            item.value,
+            item.share,
+            item.cumulative_share,
+            item.label
+        ));
+    }
+
+    if result.unlabelled > 0 {
+        output.push_str(&format!(
+            "\nNumbers without a label: {}\n",
+            result.unlabelled
+        ));
+    }
+    output
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::input::Provenance;
+
+    fn items(pairs: &[(&str, f64)]) -> Vec<(String, f64)> {
+        pairs
+            .iter()
+            .map(|(label, value)| (label.to_string(), *value))
+            .collect()
+    }
+
+    #[test]
+    fn test_abc_classes_and_cumulative_share() {
+        let sales = items(&[
+            ("c", 100.0),
+            ("a", 500.0),
+            ("e", 50.0),
+            ("b", 250.0),
+            ("d", 60.0),
+            ("f", 40.0),
+        ]);
+        let result = analyze_abc(&sales, "sales", &AbcCutoffs::default()).unwrap();
+
+        let labels: Vec<&str> = result.items.iter().map(|i| i.label.as_str()).collect();
+        assert_eq!(labels, ["a", "b", "c", "d", "e", "f"]);
+        let classes: Vec<AbcClass> = result.items.iter().map(|i| i.class).collect();
+        // 累積シェア 50, 75, 85, 91, 96, 100（境界をまたぐ c は A）
+        assert_eq!(
+            classes,
+            [
+                AbcClass::A,
+                AbcClass::A,
+                AbcClass::A,
+                AbcClass::B,
+                AbcClass::B,
+                AbcClass::C
+            ]
+        );
+        assert!((result.items[1].cumulative_share - 75.0).abs() < 1e-9);
+        assert!((result.items[5].cumulative_share - 100.0).abs() < 1e-9);
+        assert_eq!(result.total, 1000.0);
+
+        assert_eq!(result.classes.len(), 3);
+        assert_eq!(result.classes[0].items, 3);
+        assert!((result.classes[0].value_share - 85.0).abs() < 1e-9);
+        assert!((result.classes[0].item_share - 50.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_abcd_cutoffs_and_label_totals() {
+        let cutoffs: AbcCutoffs = "50,80,95".parse().unwrap();
+        let sales = items(&[
+            ("x", 40.0),
+            ("y", 30.0),
+            ("x", 20.0),
+            ("z", 5.0),
+            ("w", 3.0),
+            ("v", 1.0),
+            ("u", 1.0),
+        ]);
+        let result = analyze_abc(&sales, "sales", &cutoffs).unwrap();
+
+        assert_eq!(result.items_analyzed, 6);
+        assert_eq!(result.items[0].label, "x");
+        assert_eq!(result.items[0].value, 60.0);
+        let classes: Vec<AbcClass> = result.items.iter().map(|i| i.class).collect();
+        // 累積シェア 60, 90, 95, 98, 99, 100
+        assert_eq!(
+            classes,
+            [
+                AbcClass::A,
+                AbcClass::B,
+                AbcClass::C,
+                AbcClass::D,
+                AbcClass::D,
+                AbcClass::D
+            ]
+        );
+        assert_eq!(result.classes.len(), 4);
+    }
+
+    #[test]
+    fn test_abc_records_and_invalid_input() {
+        let record = |label: Option<&str>, value: f64| {
+            NumberRecord::new(value, Provenance::default(), value.to_string())
+                .with_group(label.map(str::to_string))
+        };
+        let records: Vec<NumberRecord> = (0..5)
+            .map(|i| record(Some(&format!("sku-{i}")), 10.0 * (i + 1) as f64))
+            .chain(std::iter::once(record(None, 99.0)))
+            .collect();
+        let result = analyze_abc_records(&records, "stock", &AbcCutoffs::default()).unwrap();
+        assert_eq!(result.items_analyzed, 5);
+        assert_eq!(result.unlabelled, 1);
+        assert_eq!(result.items[0].label, "sku-4");
+
+        assert!("80".parse::<AbcCutoffs>().is_err());
+        assert!("95,80".parse::<AbcCutoffs>().is_err());
+        assert!("80,100".parse::<AbcCutoffs>().is_err());
+        let negative = items(&[("a", 1.0), ("b", -1.0)]);
+        assert!(analyze_abc(&negative, "bad", &AbcCutoffs::default()).is_err());
+        let nan = items(&[
+            ("a", 1.0),
+            ("b", f64::NAN),
+            ("c", 3.0),
+            ("d", 4.0),
+            ("e", 5.0),
+        ]);
+        assert!(analyze_abc(&nan, "nan", &AbcCutoffs::default()).is_err());
+        let few = items(&[("a", 1.0), ("b", 2.0)]);
+        assert!(analyze_abc(&few, "few", &AbcCutoffs::default()).is_err());
+    }
+}
@@ -0,0 +1,452 @@
+use crate::error::{BenfError, Result};
+use rand::{rngs::StdRng, Rng, SeedableRng};
+use rayon::prelude::*;
+use serde::Serialize;
+use std::str::FromStr;
+
+/// 不平等指標の設定
+#[derive(Debug, Clone, PartialEq)]
+pub struct InequalityOptions {
+    /// Atkinson 指数の不平等回避度 ε（0 以上）
+    pub atkinson_epsilons: Vec<f64>,
+    /// シェアを求める上位パーセント（0 より大きく 100 以下）
+    pub top_share_percentiles: Vec<f64>,
+    /// ジニ係数の信頼区間のためのブートストラップ回数（0 なら区間を計算しない）
+    pub bootstrap: usize,
+    /// 信頼区間の信頼水準
+    pub confidence_level: f64,
+    /// ブートストラップ乱数のシード
+    pub seed: u64,
+}
+
+impl Default for InequalityOptions {
+    fn default() -> Self {
+        InequalityOptions {
+            atkinson_epsilons: vec![0.5, 1.0, 2.0],
+            top_share_percentiles: vec![1.0, 10.0, 20.0, 50.0],
+            bootstrap: 200,
+            confidence_level: 0.95,
+            seed: 0,
+        }
+    }
+}
+
+/// パーセンタイル分位法によるブートストラップ信頼区間
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ConfidenceInterval {
+    pub level: f64,
+    pub lower: f64,
+    pub upper: f64,
+}
+
+/// 不平等回避度 ε ごとの Atkinson 指数
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct AtkinsonIndex {
+    pub epsilon: f64,
+    pub value: f64,
+}
+
+/// 上位 percentile% が合計に占める割合（%）
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct TopShare {
+    pub percentile: f64,
+    pub share: f64,
+}
+
+/// 不平等・集中度指標の一式
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct InequalityIndices {
+    pub numbers_analyzed: usize,
+    /// ジニ係数（0=完全平等、(n-1)/n=1人が全体を占有）
+    pub gini: f64,
+    pub gini_confidence_interval: Option<ConfidenceInterval>,
+    pub bootstrap_samples: usize,
+    /// Theil T 指数（GE(1)）
+    pub theil_t: f64,
+    /// Theil L 指数（平均対数偏差、GE(0)）。0 を含む場合は定義されない
+    pub theil_l: Option<f64>,
+    pub atkinson: Vec<AtkinsonIndex>,
+    /// ハーフィンダール・ハーシュマン指数（シェアの二乗和、0–1）
+    pub herfindahl_hirschman: f64,
+    /// (HHI - 1/n) / (1 - 1/n) で 0–1 に正規化した HHI
+    pub normalized_hhi: f64,
+    /// 上位 10% のシェア / 下位 40% のシェア。下位 40% の合計が 0 なら None
+    pub palma_ratio: Option<f64>,
+    /// フーバー指数（均等にするために移転が必要な合計の割合）
+    pub hoover_index: f64,
+    pub top_shares: Vec<TopShare>,
+}
+
+/// ジニ係数（母集団版）
+///
+/// 昇順に並べた x_(i) について G = Σ(2i - n - 1) x_(i) / (n Σx)。ローレンツ曲線と
+/// 完全平等線の間の面積の 2 倍に一致する。合計が 0 なら 0、NaN を含む場合は NaN。
+pub fn gini_coefficient(values: &[f64]) -> f64 {
+    let mut sorted = values.to_vec();
+    sorted.sort_by(|a, b| a.total_cmp(b));
+    gini_of_sorted(&sorted)
+}
+
+/// 不平等指標をまとめて計算する
+///
+/// 数値は 0 以上で合計が正でなければならない。ジニ係数の信頼区間は数値を復元抽出した
+/// ブートストラップ標本のパーセンタイルで求める。
+pub fn calculate_inequality_indices(
+    values: &[f64],
+    options: &InequalityOptions,
+) -> Result<InequalityIndices> {
+    validate_options(options)?;
+    if values.is_empty() {
+        return Err(BenfError::NoNumbersFound);
+    }
+    if values.len() < 2 {
+        return Err(BenfError::InsufficientData(values.len()));
+    }
+    if values.iter().any(|v| !v.is_finite() || *v < 0.0) {
+        return Err(BenfError::InvalidInput(
+            "Inequality indices require finite, non-negative numbers".to_string(),
+        ));
+    }
+
+    let mut sorted = values.to_vec();
+    sorted.sort_by(|a, b| a.total_cmp(b));
+    let n = sorted.len() as f64;
+    let total: f64 = sorted.iter().sum();
+    if total <= 0.0 {
+        return Err(BenfError::InvalidInput(
+            "Inequality indices require a positive total".to_string(),
+        ));
+    }
+    let mean = total / n;
+
+    let theil_t = sorted
+        .iter()
+        .filter(|&&x| x > 0.0)
+        .map(|&x| (x / mean) * (x / mean).ln())
+        .sum::<f64>()
+        / n;
+    let theil_l = sorted
+        .iter()
+        .all(|&x| x > 0.0)
+        .then(|| sorted.iter().map(|&x| (mean / x).ln()).sum::<f64>() / n);
+
+    let atkinson = options
+        .atkinson_epsilons
+        .iter()
+        .map(|&epsilon| AtkinsonIndex {
+            epsilon,
+            value: atkinson_index(&sorted, mean, epsilon),
+        })
+        .collect();
+
+    let herfindahl_hirschman: f64 = sorted.iter().map(|&x| (x / total).powi(2)).sum();
+    let normalized_hhi = (herfindahl_hirschman - 1.0 / n) / (1.0 - 1.0 / n);
+
+    let bottom_40 = lorenz(&sorted, 0.4);
+    let top_10 = total - lorenz(&sorted, 0.9);
+    let palma_ratio = (bottom_40 > 0.0).then(|| top_10 / bottom_40);
+
+    let hoover_index = 0.5
+        * sorted
+            .iter()
+            .map(|&x| (x / total - 1.0 / n).abs())
+            .sum::<f64>();
+
+    let top_shares = options
+        .top_share_percentiles
+        .iter()
+        .map(|&percentile| TopShare {
+            percentile,
+            share: (1.0 - lorenz(&sorted, 1.0 - percentile / 100.0) / total) * 100.0,
+        })
+        .collect();
+
+    Ok(InequalityIndices {
+        numbers_analyzed: sorted.len(),
+        gini: gini_of_sorted(&sorted),
+        gini_confidence_interval: bootstrap_gini_interval(&sorted, options),
+        bootstrap_samples: options.bootstrap,
+        theil_t,
+        theil_l,
+        atkinson,
+        herfindahl_hirschman,
+        normalized_hhi,
+        palma_ratio,
+        hoover_index,
+        top_shares,
+    })
+}
+
+fn validate_options(options: &InequalityOptions) -> Result<()> {
+    if let Some(epsilon) = options
+        .atkinson_epsilons
+        .iter()
+        .find(|e| !(e.is_finite() && **e >= 0.0))
+    {
+        return Err(BenfError::InvalidInput(format!(
+            "Atkinson epsilon must be non-negative: {epsilon}"
+        )));
+    }
+    if let Some(percentile) = options
+        .top_share_percentiles
+        .iter()
+        .find(|p| !(**p > 0.0 && **p <= 100.0))
+    {
+        return Err(BenfError::InvalidInput(format!(
+            "Top-share percentile must be in (0, 100]: {percentile}"
+        )));
+    }
+    if !(options.confidence_level > 0.0 && options.confidence_level < 1.0) {
+        return Err(BenfError::InvalidInput(format!(
+            "Confidence level must be between 0 and 1 (exclusive): {}",
+            options.confidence_level
+        )));
+    }
+    Ok(())
+}
+
+/// 昇順ソート済みの数値のジニ係数
+fn gini_of_sorted(sorted: &[f64]) -> f64 {
+    let n = sorted.len() as f64;
+    let total: f64 = sorted.iter().sum();
+    if sorted.len() < 2 || total == 0.0 {
+        return 0.0;
+    }
+    let weighted: f64 = sorted
+        .iter()
+        .enumerate()
+        .map(|(i, &x)| (2.0 * (i + 1) as f64 - n - 1.0) * x)
+        .sum();
+    weighted / (n * total)
+}
+
+/// 下位 fraction（0–1）の人数が持つ合計（ローレンツ曲線を線形補間、昇順ソート済み）
+fn lorenz(sorted: &[f64], fraction: f64) -> f64 {
+    let position = fraction.clamp(0.0, 1.0) * sorted.len() as f64;
+    let whole = (position.floor() as usize).min(sorted.len());
+    let partial = if whole < sorted.len() {
+        sorted[whole] * (position - whole as f64)
+    } else {
+        0.0
+    };
+    sorted[..whole].iter().sum::<f64>() + partial
+}
+
+/// Atkinson 指数 A(ε) = 1 - (一般化平均_{1-ε}) / 平均
+///
+/// ε = 1 では幾何平均を使う。ε ≥ 1 で 0 を含む場合、等価平等所得が 0 になるので 1。
+fn atkinson_index(sorted: &[f64], mean: f64, epsilon: f64) -> f64 {
+    let n = sorted.len() as f64;
+    if epsilon == 0.0 {
+        return 0.0;
+    }
+    if epsilon >= 1.0 && sorted.contains(&0.0) {
+        return 1.0;
+    }
+    let equally_distributed = if (epsilon - 1.0).abs() < f64::EPSILON {
+        (sorted.iter().map(|&x| (x / mean).ln()).sum::<f64>() / n).exp() * mean
+    } else {
+        let power = 1.0 - epsilon;
+        (sorted.iter().map(|&x| (x / mean).powf(power)).sum::<f64>() / n).powf(1.0 / power) * mean
+    };
+    1.0 - equally_distributed / mean
+}
+
+/// ジニ係数のブートストラップ・パーセンタイル信頼区間（回数 0 なら None）
+fn bootstrap_gini_interval(
+    sorted: &[f64],
+    options: &InequalityOptions,
+) -> Option<ConfidenceInterval> {
+    if options.bootstrap == 0 {
+        return None;
+    }
+    let mut replicates: Vec<f64> = (0..options.bootstrap)
+        .into_par_iter()
+        .map(|replicate| {
+            let mut rng = StdRng::seed_from_u64(options.seed.wrapping_add(replicate as u64));
+            let mut resample: Vec<f64> = (0..sorted.len())
+                .map(|_| sorted[rng.gen_range(0..sorted.len())])
+                .collect();
+            resample.sort_by(|a, b| a.total_cmp(b));
+            gini_of_sorted(&resample)
+        })
+        .collect();
+    replicates.sort_by(|a, b| a.total_cmp(b));
+
+    let tail = (1.0 - options.confidence_level) / 2.0;
+    Some(ConfidenceInterval {
+        level: options.confidence_level,
+        lower: quantile(&replicates, tail),
+        upper: quantile(&replicates, 1.0 - tail),
+    })
+}
+
+/// ソート済み標本の分位点（線形補間）
+fn quantile(sorted: &[f64], probability: f64) -> f64 {
+    let position = probability * (sorted.len() - 1) as f64;
+    let lower = position.floor() as usize;
+    let upper = position.ceil() as usize;
+    sorted[lower] + (sorted[upper] - sorted[lower]) * (position - lower as f64)
+}
+
+/// "0.5,1,2" のようなカンマ区切りの数値リスト
+pub fn parse_number_list(text: &str) -> Result<Vec<f64>> {
+    text.split(',')
+        .map(|part| {
+            f64::from_str(part.trim())
+                .map_err(|_| BenfError::ParseError(format!("Invalid number in list: {part}")))
+        })
+        .collect()
+}
+
+/// 不平等指標のテキスト表現
+pub fn format_inequality_text(indices: &InequalityIndices) -> String {
+    let mut output = String::from("Inequality Indices:\n");
+    match &indices.gini_confidence_interval {
+        Some(interval) => output.push_str(&format!(
+            "  Gini: {:.4} ({:.0}% CI {:.4}-{:.4}, {} bootstrap samples)\n",
+            indices.gini,
+            interval.level * 100.0,
+            interval.lower,
+            interval.upper,
+            indices.bootstrap_samples
+        )),
+        None => output.push_str(&format!("  Gini: {:.4}\n", indices.gini)),
+    }
+    output.push_str(&format!("  Theil T: {:.4}\n", indices.theil_t));
+    match indices.theil_l {
+        Some(theil_l) => output.push_str(&format!("  Theil L: {theil_l:.4}\n")),
+        None => output.push_str("  Theil L: undefined (zero values)\n"),
+    }
+    for atkinson in &indices.atkinson {
+        output.push_str(&format!(
+            "  Atkinson (e={}): {:.4}\n",
+            atkinson.epsilon, atkinson.value
+        ));
+    }
+    output.push_str(&format!(
+        "  Herfindahl-Hirschman: {:.4} (normalized {:.4})\n",
+        indices.herfindahl_hirschman, indices.normalized_hhi
+    ));
+    match indices.palma_ratio {
+        Some(palma) => output.push_str(&format!("  Palma ratio: {palma:.3}\n")),
+        None => output.push_str("  Palma ratio: undefined (bottom 40% total is zero)\n"),
+    }
+    output.push_str(&format!("  Hoover index: {:.4}\n", indices.hoover_index));
+    for top in &indices.top_shares {
+        output.push_str(&format!(
+            "  Top {}% share: {:.1}%\n",
+            top.percentile, top.share
+        ));
+    }
+    output
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn no_bootstrap() -> InequalityOptions {
+        InequalityOptions {
+            bootstrap: 0,
+            ..InequalityOptions::default()
+        }
+    }
+
+    #[test]
+    fn test_equal_values_have_no_inequality() {
+        let indices = calculate_inequality_indices(&[5.0; 10], &no_bootstrap()).unwrap();
+        assert!(indices.gini.abs() < 1e-12);
+        assert!(indices.theil_t.abs() < 1e-12);
+        assert!(indices.theil_l.unwrap().abs() < 1e-12);
+        assert!(indices.atkinson.iter().all(|a| a.value.abs() < 1e-12));
+        assert!((indices.herfindahl_hirschman - 0.1).abs() < 1e-12);
+        assert!(indices.normalized_hhi.abs() < 1e-12);
+        assert!((indices.palma_ratio.unwrap() - 0.25).abs() < 1e-12);
+        assert!(indices.hoover_index.abs() < 1e-12);
+        let top_10 = &indices.top_shares[1];
+        assert_eq!(top_10.percentile, 10.0);
+        assert!((top_10.share - 10.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_known_values() {
+        // 1..=4: 平均 2.5、合計 10
+        let values = [1.0, 2.0, 3.0, 4.0];
+        let options = InequalityOptions {
+            atkinson_epsilons: vec![1.0, 2.0],
+            top_share_percentiles: vec![25.0, 50.0],
+            ..no_bootstrap()
+        };
+        let indices = calculate_inequality_indices(&values, &options).unwrap();
+        assert!((indices.gini - 0.25).abs() < 1e-12);
+        let theil_t = [1.0f64, 2.0, 3.0, 4.0]
+            .iter()
+            .map(|x| x / 2.5 * (x / 2.5).ln())
+            .sum::<f64>()
+            / 4.0;
+        assert!((indices.theil_t - theil_t).abs() < 1e-12);
+        let geometric_mean = 24f64.powf(0.25);
+        assert!((indices.atkinson[0].value - (1.0 - geometric_mean / 2.5)).abs() < 1e-12);
+        let harmonic_mean = 4.0 / (1.0 + 0.5 + 1.0 / 3.0 + 0.25);
+        assert!((indices.atkinson[1].value - (1.0 - harmonic_mean / 2.5)).abs() < 1e-12);
+        assert!((indices.herfindahl_hirschman - 0.3).abs() < 1e-12);
+        assert!((indices.hoover_index - 0.2).abs() < 1e-12);
+        // 上位 10% = 4 の 0.4 件分、下位 40% = 1 + 2 の 0.6 件分
+        assert!((indices.palma_ratio.unwrap() - 1.6 / 2.2).abs() < 1e-12);
+        assert!((indices.top_shares[0].share - 40.0).abs() < 1e-9);
+        assert!((indices.top_shares[1].share - 70.0).abs() < 1e-9);
+        assert_eq!(gini_coefficient(&[4.0, 1.0, 3.0, 2.0]), indices.gini);
+    }
+
+    #[test]
+    fn test_non_finite_values() {
+        assert!(gini_coefficient(&[1.0, f64::NAN, 3.0]).is_nan());
+        for value in [f64::NAN, f64::INFINITY] {
+            assert!(calculate_inequality_indices(&[1.0, value, 3.0], &no_bootstrap()).is_err());
+        }
+    }
+
+    #[test]
+    fn test_zero_values() {
+        let indices =
+            calculate_inequality_indices(&[0.0, 0.0, 0.0, 10.0], &no_bootstrap()).unwrap();
+        assert!((indices.gini - 0.75).abs() < 1e-12);
+        assert_eq!(indices.theil_l, None);
+        assert_eq!(indices.palma_ratio, None);
+        assert!((indices.theil_t - 4f64.ln()).abs() < 1e-12);
+        assert_eq!(indices.atkinson.last().unwrap().value, 1.0);
+        assert!((indices.normalized_hhi - 1.0).abs() < 1e-12);
+
+        assert!(calculate_inequality_indices(&[0.0, 0.0], &no_bootstrap()).is_err());
+        assert!(calculate_inequality_indices(&[1.0, -1.0, 2.0], &no_bootstrap()).is_err());
+    }
+
+    #[test]
+    fn test_bootstrap_interval_contains_estimate() {
+        let mut rng = StdRng::seed_from_u64(9);
+        let values: Vec<f64> = (0..500)
+            .map(|_| (1.0 - rng.gen::<f64>()).powf(-1.0 / 1.5))
+            .collect();
+        let options = InequalityOptions::default();
+        let indices = calculate_inequality_indices(&values, &options).unwrap();
+        let interval = indices.gini_confidence_interval.clone().unwrap();
+        assert!(interval.lower < indices.gini && indices.gini < interval.upper);
+        assert_eq!(interval.level, 0.95);
+
+        // 同じシードなら同じ区間
+        let again = calculate_inequality_indices(&values, &options).unwrap();
+        assert_eq!(again.gini_confidence_interval, Some(interval));
+    }
+
+    #[test]
+    fn test_invalid_options() {
+        let options = InequalityOptions {
+            top_share_percentiles: vec![0.0],
+            ..no_bootstrap()
+        };
+        assert!(calculate_inequality_indices(&[1.0, 2.0], &options).is_err());
+        assert_eq!(parse_number_list("0.5, 1,2").unwrap(), vec![0.5, 1.0, 2.0]);
+        assert!(parse_number_list("a").is_err());
+    }
+}
@@ -1,5 +1,11 @@
+pub mod abc;
 pub mod analysis;
+pub mod inequality;
+pub mod power_law;
 pub mod result;
 
+pub use abc::*;
 pub use analysis::*;
+pub use inequality::*;
+pub use power_law::*;
 pub use result::*;
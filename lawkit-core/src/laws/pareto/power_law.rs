@@ -0,0 +1,738 @@
+use crate::common::statistics::{
+    ln_hurwitz_zeta, ln_normal_upper_tail, minimize_nelder_mead, two_sided_normal_p_value,
+};
+use crate::error::{BenfError, Result};
+use rand::{rngs::StdRng, Rng, SeedableRng};
+use rayon::prelude::*;
+use serde::Serialize;
+use std::collections::BTreeMap;
+
+/// x_min の候補にする裾の最小サイズ
+const MIN_TAIL_SIZE: usize = 10;
+/// x_min の候補数の上限（超える場合は等間隔に間引く）
+const MAX_XMIN_CANDIDATES: usize = 200;
+/// 尤度比検定でどちらかのモデルを支持する有意水準（Clauset et al. 2009 と同じ）
+const LIKELIHOOD_RATIO_SIGNIFICANCE: f64 = 0.1;
+/// ブートストラップ p 値がこれ以上ならべき乗則として妥当とみなす
+const PLAUSIBILITY_THRESHOLD: f64 = 0.1;
+/// 代替分布の最尤推定の反復回数
+const ALTERNATIVE_FIT_ITERATIONS: usize = 1000;
+
+/// べき乗則フィットの設定
+#[derive(Debug, Clone, PartialEq)]
+pub struct PowerLawOptions {
+    /// 適合度 p 値のためのブートストラップ回数（0 なら p 値を計算しない）
+    pub bootstrap: usize,
+    /// ブートストラップ乱数のシード（同じシードなら同じ p 値）
+    pub seed: u64,
+}
+
+impl Default for PowerLawOptions {
+    fn default() -> Self {
+        PowerLawOptions {
+            bootstrap: 100,
+            seed: 0,
+        }
+    }
+}
+
+/// 連続値（x ≥ x_min の実数）か離散値（x ≥ x_min の整数）か
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PowerLawSupport {
+    Continuous,
+    Discrete,
+}
+
+/// べき乗則と比較する代替分布（いずれも x_min で切断）
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AlternativeDistribution {
+    Lognormal,
+    Exponential,
+    StretchedExponential,
+}
+
+impl AlternativeDistribution {
+    pub const ALL: [AlternativeDistribution; 3] = [
+        AlternativeDistribution::Lognormal,
+        AlternativeDistribution::Exponential,
+        AlternativeDistribution::StretchedExponential,
+    ];
+}
+
+impl std::fmt::Display for AlternativeDistribution {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let name = match self {
+            AlternativeDistribution::Lognormal => "lognormal",
+            AlternativeDistribution::Exponential => "exponential",
+            AlternativeDistribution::StretchedExponential => "stretched exponential",
+        };
+        write!(f, "{name}")
+    }
+}
+
+/// 尤度比検定が支持するモデル
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum FavoredModel {
+    PowerLaw,
+    Alternative,
+    /// 有意な差がない
+    Inconclusive,
+}
+
+/// べき乗則と代替分布の尤度比検定（Vuong 検定）
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct LikelihoodRatioTest {
+    pub alternative: AlternativeDistribution,
+    /// 裾に最尤推定した代替分布のパラメータ
+    pub parameters: BTreeMap<String, f64>,
+    /// 対数尤度比 R（正ならべき乗則の方が尤もらしい）
+    pub log_likelihood_ratio: f64,
+    /// R / (σ √n)
+    pub normalized_ratio: f64,
+    /// R の符号の有意性（両側）
+    pub p_value: f64,
+    pub favored: FavoredModel,
+}
+
+/// Clauset–Shalizi–Newman の方法によるべき乗則の最尤フィット
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct PowerLawFit {
+    pub support: PowerLawSupport,
+    /// KS 距離を最小にする裾の下限
+    pub x_min: f64,
+    /// p(x) ∝ x^(-alpha) の指数（最尤推定）
+    pub alpha: f64,
+    pub alpha_standard_error: f64,
+    /// x ≥ x_min の数値の個数
+    pub tail_size: usize,
+    /// フィットに使った正の数値の個数
+    pub numbers_fitted: usize,
+    /// 裾と当てはめたべき乗則の KS 距離
+    pub ks_statistic: f64,
+    pub bootstrap_samples: usize,
+    /// 合成データの KS 距離が観測値以上になる割合（ブートストラップなしなら None）
+    pub p_value: Option<f64>,
+    /// p 値が 0.1 以上か
+    pub plausible: Option<bool>,
+    pub comparisons: Vec<LikelihoodRatioTest>,
+}
+
+/// 連続値のべき乗則フィット
+///
+/// 正の数値について、x_min の各候補で α を最尤推定（α = 1 + n / Σ ln(x/x_min)）し、
+/// 裾の経験分布との KS 距離が最小になる x_min を選ぶ。適合度 p 値はセミパラメトリック
+/// ブートストラップ（x_min 未満は観測値から、裾はフィットしたべき乗則から生成し、
+/// x_min の選択からやり直す）で求め、裾で対数正規・指数・引き伸ばし指数分布との
+/// 尤度比検定を行う。正の数値が 10 個未満ならエラー。
+pub fn fit_power_law(values: &[f64], options: &PowerLawOptions) -> Result<PowerLawFit> {
+    let sorted = sorted_positive(values);
+    fit_sorted(&sorted, PowerLawSupport::Continuous, options)
+}
+
+/// 離散値（正の整数）のべき乗則フィット
+///
+/// p(x) = x^(-α) / ζ(α, x_min) として α を数値的に最尤推定する。それ以外は
+/// `fit_power_law` と同じ。整数でない数値があればエラー。
+pub fn fit_discrete_power_law(values: &[f64], options: &PowerLawOptions) -> Result<PowerLawFit> {
+    let sorted = sorted_positive(values);
+    if let Some(value) = sorted.iter().find(|value| value.fract() != 0.0) {
+        return Err(BenfError::InvalidInput(format!(
+            "discrete power-law fit needs integer values, got {value}"
+        )));
+    }
+    fit_sorted(&sorted, PowerLawSupport::Discrete, options)
+}
+
+/// 正の有限値を昇順に並べる
+fn sorted_positive(values: &[f64]) -> Vec<f64> {
+    let mut sorted: Vec<f64> = values
+        .iter()
+        .copied()
+        .filter(|value| value.is_finite() && *value > 0.0)
+        .collect();
+    sorted.sort_by(|a, b| a.total_cmp(b));
+    sorted
+}
+
+fn fit_sorted(
+    sorted: &[f64],
+    support: PowerLawSupport,
+    options: &PowerLawOptions,
+) -> Result<PowerLawFit> {
+    if sorted.len() < MIN_TAIL_SIZE {
+        return Err(BenfError::InsufficientData(sorted.len()));
+    }
+    let fit = scan_x_min(sorted, support).ok_or_else(|| {
+        BenfError::InvalidInput(
+            "power-law fit needs at least 10 values above x_min that are not all equal".to_string(),
+        )
+    })?;
+    let tail = &sorted[fit.tail_start..];
+
+    // セミパラメトリック・ブートストラップ
+    let below = &sorted[..fit.tail_start];
+    let tail_fraction = tail.len() as f64 / sorted.len() as f64;
+    let synthetic_distances: Vec<f64> = (0..options.bootstrap)
+        .into_par_iter()
+        .filter_map(|replicate| {
+            let mut rng = StdRng::seed_from_u64(options.seed.wrapping_add(replicate as u64));
+            let mut synthetic: Vec<f64> = (0..sorted.len())
+                .map(|_| {
+                    if below.is_empty() || rng.gen::<f64>() < tail_fraction {
+                        sample_power_law(&mut rng, &fit, support)
+                    } else {
+                        below[rng.gen_range(0..below.len())]
+                    }
+                })
+                .collect();
+            synthetic.sort_by(|a, b| a.total_cmp(b));
+            scan_x_min(&synthetic, support).map(|synthetic_fit| synthetic_fit.ks_statistic)
+        })
+        .collect();
+    let p_value = (!synthetic_distances.is_empty()).then(|| {
+        synthetic_distances
+            .iter()
+            .filter(|&&distance| distance >= fit.ks_statistic)
+            .count() as f64
+            / synthetic_distances.len() as f64
+    });
+
+    let power_law_log_likelihoods: Vec<f64> = tail
+        .iter()
+        .map(|&x| power_law_log_density(x, fit.alpha, fit.x_min, support))
+        .collect();
+    let comparisons = AlternativeDistribution::ALL
+        .iter()
+        .map(|&alternative| {
+            compare_alternative(
+                alternative,
+                tail,
+                fit.x_min,
+                support,
+                &power_law_log_likelihoods,
+            )
+        })
+        .collect();
+
+    let m = tail.len() as f64;
+    let alpha_standard_error = match support {
+        PowerLawSupport::Continuous => (fit.alpha - 1.0) / m.sqrt(),
+        PowerLawSupport::Discrete => {
+            // 1 / √(n · d²ln ζ(α, x_min)/dα²)
+            let h = 1e-3;
+            let curvature = (ln_hurwitz_zeta(fit.alpha + h, fit.x_min)
+                - 2.0 * ln_hurwitz_zeta(fit.alpha, fit.x_min)
+                + ln_hurwitz_zeta(fit.alpha - h, fit.x_min))
+                / (h * h);
+            1.0 / (m * curvature).sqrt()
+        }
+    };
+
+    Ok(PowerLawFit {
+        support,
+        x_min: fit.x_min,
+        alpha: fit.alpha,
+        alpha_standard_error,
+        tail_size: tail.len(),
+        numbers_fitted: sorted.len(),
+        ks_statistic: fit.ks_statistic,
+        bootstrap_samples: synthetic_distances.len(),
+        p_value,
+        plausible: p_value.map(|p| p >= PLAUSIBILITY_THRESHOLD),
+        comparisons,
+    })
+}
+
+/// x_min の1候補に対するフィット
+struct TailFit {
+    x_min: f64,
+    alpha: f64,
+    tail_start: usize,
+    ks_statistic: f64,
+}
+
+/// KS 距離が最小になる x_min を探す（昇順ソート済みの入力）
+fn scan_x_min(sorted: &[f64], support: PowerLawSupport) -> Option<TailFit> {
+    let n = sorted.len();
+    let mut suffix_ln = vec![0.0; n + 1];
+    for i in (0..n).rev() {
+        suffix_ln[i] = suffix_ln[i + 1] + sorted[i].ln();
+    }
+
+    let mut candidates: Vec<usize> = (0..n)
+        .filter(|&i| {
+            (i == 0 || sorted[i] != sorted[i - 1])
+                && n - i >= MIN_TAIL_SIZE
+                && sorted[n - 1] > sorted[i]
+        })
+        .collect();
+    if candidates.len() > MAX_XMIN_CANDIDATES {
+        candidates = (0..MAX_XMIN_CANDIDATES)
+            .map(|k| candidates[k * candidates.len() / MAX_XMIN_CANDIDATES])
+            .collect();
+    }
+
+    candidates
+        .into_iter()
+        .filter_map(|start| {
+            let x_min = sorted[start];
+            let alpha = estimate_alpha(x_min, n - start, suffix_ln[start], support)?;
+            let ks_statistic = ks_distance(&sorted[start..], alpha, x_min, support);
+            Some(TailFit {
+                x_min,
+                alpha,
+                tail_start: start,
+                ks_statistic,
+            })
+        })
+        .min_by(|a, b| a.ks_statistic.total_cmp(&b.ks_statistic))
+}
+
+/// 裾（`count` 個、ln x の和が `sum_ln`）に対する α の最尤推定
+fn estimate_alpha(x_min: f64, count: usize, sum_ln: f64, support: PowerLawSupport) -> Option<f64> {
+    let m = count as f64;
+    let alpha = match support {
+        PowerLawSupport::Continuous => 1.0 + m / (sum_ln - m * x_min.ln()),
+        PowerLawSupport::Discrete => {
+            // 近似式 α ≈ 1 + n / Σ ln(x / (x_min - 1/2)) から α = 1 + e^t を探索
+            let start = 1.0 + m / (sum_ln - m * (x_min - 0.5).ln());
+            let negative_log_likelihood = |t: &[f64]| {
+                let alpha = 1.0 + t[0].exp();
+                m * ln_hurwitz_zeta(alpha, x_min) + alpha * sum_ln
+            };
+            let t = minimize_nelder_mead(
+                negative_log_likelihood,
+                &[(start - 1.0).max(1e-3).ln()],
+                0.1,
+                200,
+            );
+            1.0 + t[0].exp()
+        }
+    };
+    (alpha.is_finite() && alpha > 1.0).then_some(alpha)
+}
+
+/// 裾（昇順）の経験分布とべき乗則の累積分布関数の最大差
+fn ks_distance(tail: &[f64], alpha: f64, x_min: f64, support: PowerLawSupport) -> f64 {
+    let m = tail.len() as f64;
+    let ln_zeta_min = ln_hurwitz_zeta(alpha, x_min);
+    let mut distance: f64 = 0.0;
+    let mut i = 0;
+    while i < tail.len() {
+        let value = tail[i];
+        let mut j = i;
+        while j < tail.len() && tail[j] == value {
+            j += 1;
+        }
+        let (empirical_before, empirical_after) = (i as f64 / m, j as f64 / m);
+
+        // 値の直前と値での理論累積確率（連続値では等しい）
+        let (model_before, model_at) = match support {
+            PowerLawSupport::Continuous => {
+                let cdf = 1.0 - (value / x_min).powf(1.0 - alpha);
+                (cdf, cdf)
+            }
+            PowerLawSupport::Discrete => (
+                1.0 - (ln_hurwitz_zeta(alpha, value) - ln_zeta_min).exp(),
+                1.0 - (ln_hurwitz_zeta(alpha, value + 1.0) - ln_zeta_min).exp(),
+            ),
+        };
+        distance = distance
+            .max((model_before - empirical_before).abs())
+            .max((model_at - empirical_after).abs());
+        i = j;
+    }
+    distance
+}
+
+/// フィットしたべき乗則（x ≥ x_min）から1つ生成する
+fn sample_power_law(rng: &mut StdRng, fit: &TailFit, support: PowerLawSupport) -> f64 {
+    let u: f64 = rng.gen();
+    let exponent = -1.0 / (fit.alpha - 1.0);
+    match support {
+        PowerLawSupport::Continuous => fit.x_min * (1.0 - u).powf(exponent),
+        PowerLawSupport::Discrete => {
+            // 連続近似から始めて累積分布関数で正確な逆関数値に合わせる
+            let mut x = ((fit.x_min - 0.5) * (1.0 - u).powf(exponent) + 0.5)
+                .floor()
+                .max(fit.x_min);
+            if x < 1e12 {
+                let ln_zeta_min = ln_hurwitz_zeta(fit.alpha, fit.x_min);
+                let cdf = |x: f64| 1.0 - (ln_hurwitz_zeta(fit.alpha, x + 1.0) - ln_zeta_min).exp();
+                for _ in 0..1000 {
+                    if cdf(x) >= u {
+                        break;
+                    }
+                    x += 1.0;
+                }
+                for _ in 0..1000 {
+                    if x <= fit.x_min || cdf(x - 1.0) < u {
+                        break;
+                    }
+                    x -= 1.0;
+                }
+            }
+            x
+        }
+    }
+}
+
+/// べき乗則の対数密度（離散値では対数確率）
+fn power_law_log_density(x: f64, alpha: f64, x_min: f64, support: PowerLawSupport) -> f64 {
+    match support {
+        PowerLawSupport::Continuous => (alpha - 1.0).ln() - x_min.ln() - alpha * (x / x_min).ln(),
+        PowerLawSupport::Discrete => -alpha * x.ln() - ln_hurwitz_zeta(alpha, x_min),
+    }
+}
+
+/// 代替分布を裾に最尤推定し、べき乗則との Vuong 検定を行う
+fn compare_alternative(
+    alternative: AlternativeDistribution,
+    tail: &[f64],
+    x_min: f64,
+    support: PowerLawSupport,
+    power_law_log_likelihoods: &[f64],
+) -> LikelihoodRatioTest {
+    let m = tail.len() as f64;
+    let log_likelihoods = |theta: &[f64]| -> Vec<f64> {
+        tail.iter()
+            .map(|&x| alternative_log_density(alternative, theta, x, x_min, support))
+            .collect()
+    };
+
+    let theta = minimize_nelder_mead(
+        |theta| -log_likelihoods(theta).iter().sum::<f64>(),
+        &initial_parameters(alternative, tail, x_min, support),
+        0.5,
+        ALTERNATIVE_FIT_ITERATIONS,
+    );
+
+    let differences: Vec<f64> = power_law_log_likelihoods
+        .iter()
+        .zip(log_likelihoods(&theta))
+        .map(|(power_law, alternative)| power_law - alternative)
+        .collect();
+    let log_likelihood_ratio: f64 = differences.iter().sum();
+    let mean = log_likelihood_ratio / m;
+    let variance = differences.iter().map(|d| (d - mean).powi(2)).sum::<f64>() / m;
+    let normalized_ratio = if variance > 0.0 {
+        log_likelihood_ratio / (variance * m).sqrt()
+    } else {
+        0.0
+    };
+    let p_value = two_sided_normal_p_value(normalized_ratio);
+    let favored = if !log_likelihood_ratio.is_finite() {
+        // 代替分布のフィットが発散した
+        if log_likelihood_ratio > 0.0 {
+            FavoredModel::PowerLaw
+        } else {
+            FavoredModel::Alternative
+        }
+    } else if p_value >= LIKELIHOOD_RATIO_SIGNIFICANCE {
+        FavoredModel::Inconclusive
+    } else if log_likelihood_ratio > 0.0 {
+        FavoredModel::PowerLaw
+    } else {
+        FavoredModel::Alternative
+    };
+
+    LikelihoodRatioTest {
+        alternative,
+        parameters: reported_parameters(alternative, &theta, x_min),
+        log_likelihood_ratio,
+        normalized_ratio,
+        p_value,
+        favored,
+    }
+}
+
+/// 最適化の初期値（指数分布の λ は平均から、対数正規分布は ln x の平均と標準偏差）
+///
+/// パラメータは指数: [ln λ]、引き伸ばし指数: [ln λ', ln β]（y = x/x_min に対し
+/// S(y) = exp(-λ' y^β)）、対数正規: [μ, ln σ]。
+fn initial_parameters(
+    alternative: AlternativeDistribution,
+    tail: &[f64],
+    x_min: f64,
+    support: PowerLawSupport,
+) -> Vec<f64> {
+    let m = tail.len() as f64;
+    let excess = tail.iter().map(|x| x - x_min).sum::<f64>() / m;
+    let rate = match support {
+        PowerLawSupport::Continuous => 1.0 / excess,
+        PowerLawSupport::Discrete => (1.0 + 1.0 / excess).ln(),
+    };
+    match alternative {
+        AlternativeDistribution::Exponential => vec![rate.ln()],
+        AlternativeDistribution::StretchedExponential => vec![(rate * x_min).ln(), 0.0],
+        AlternativeDistribution::Lognormal => {
+            let mean = tail.iter().map(|x| x.ln()).sum::<f64>() / m;
+            let variance = tail.iter().map(|x| (x.ln() - mean).powi(2)).sum::<f64>() / m;
+            vec![mean, variance.sqrt().max(0.1).ln()]
+        }
+    }
+}
+
+/// 代替分布の対数生存関数 ln S(x)（切断前）
+fn alternative_log_survival(
+    alternative: AlternativeDistribution,
+    theta: &[f64],
+    x: f64,
+    x_min: f64,
+) -> f64 {
+    match alternative {
+        AlternativeDistribution::Exponential => -theta[0].exp() * x,
+        AlternativeDistribution::StretchedExponential => {
+            -theta[0].exp() * (x / x_min).powf(theta[1].exp())
+        }
+        AlternativeDistribution::Lognormal => {
+            ln_normal_upper_tail((x.ln() - theta[0]) / theta[1].exp())
+        }
+    }
+}
+
+/// x_min で切断した代替分布の対数密度（離散値では S(x) - S(x+1) による対数確率）
+fn alternative_log_density(
+    alternative: AlternativeDistribution,
+    theta: &[f64],
+    x: f64,
+    x_min: f64,
+    support: PowerLawSupport,
+) -> f64 {
+    let log_survival = |x: f64| alternative_log_survival(alternative, theta, x, x_min);
+    let log_normalizer = log_survival(x_min);
+    match support {
+        PowerLawSupport::Discrete => {
+            let (here, next) = (log_survival(x), log_survival(x + 1.0));
+            here + (-(next - here).exp_m1()).ln() - log_normalizer
+        }
+        PowerLawSupport::Continuous => {
+            let log_density = match alternative {
+                AlternativeDistribution::Exponential => theta[0] - theta[0].exp() * x,
+                AlternativeDistribution::StretchedExponential => {
+                    let beta = theta[1].exp();
+                    let y = x / x_min;
+                    theta[1] + theta[0] + (beta - 1.0) * y.ln()
+                        - x_min.ln()
+                        - theta[0].exp() * y.powf(beta)
+                }
+                AlternativeDistribution::Lognormal => {
+                    let sigma = theta[1].exp();
+                    let z = (x.ln() - theta[0]) / sigma;
+                    -x.ln() - theta[1] - 0.5 * (2.0 * std::f64::consts::PI).ln() - 0.5 * z * z
+                }
+            };
+            log_density - log_normalizer
+        }
+    }
+}
+
+/// 最適化したパラメータを元の尺度で表す
+fn reported_parameters(
+    alternative: AlternativeDistribution,
+    theta: &[f64],
+    x_min: f64,
+) -> BTreeMap<String, f64> {
+    let parameters: Vec<(&str, f64)> = match alternative {
+        AlternativeDistribution::Exponential => vec![("lambda", theta[0].exp())],
+        AlternativeDistribution::StretchedExponential => {
+            let beta = theta[1].exp();
+            vec![
+                ("lambda", theta[0].exp() / x_min.powf(beta)),
+                ("beta", beta),
+            ]
+        }
+        AlternativeDistribution::Lognormal => vec![("mu", theta[0]), ("sigma", theta[1].exp())],
+    };
+    parameters
+        .into_iter()
+        .map(|(name, value)| (name.to_string(), value))
+        .collect()
+}
+
+/// べき乗則フィットのテキスト表現
+pub fn format_power_law_text(fit: &PowerLawFit) -> String {
+    let support = match fit.support {
+        PowerLawSupport::Continuous => "continuous",
+        PowerLawSupport::Discrete => "discrete",
+    };
+    let mut output = format!("Power-Law Fit (Clauset-Shalizi-Newman, {support}):\n");
+    output.push_str(&format!(
+        "  alpha: {:.3} ± {:.3}\n",
+        fit.alpha, fit.alpha_standard_error
+    ));
+    output.push_str(&format!(
+        "  x_min: {} (tail: {} of {} numbers)\n",
+        fit.x_min, fit.tail_size, fit.numbers_fitted
+    ));
+    output.push_str(&format!("  KS distance: {:.4}\n", fit.ks_statistic));
+    match (fit.p_value, fit.plausible) {
+        (Some(p_value), Some(plausible)) => output.push_str(&format!(
+            "  Goodness of fit: p = {:.3} ({} bootstrap samples) - {}\n",
+            p_value,
+            fit.bootstrap_samples,
+            if plausible {
+                "power law plausible"
+            } else {
+                "power law rejected"
+            }
+        )),
+        _ => output.push_str("  Goodness of fit: not computed (no bootstrap samples)\n"),
+    }
+    for test in &fit.comparisons {
+        let favored = match test.favored {
+            FavoredModel::PowerLaw => "power law favored".to_string(),
+            FavoredModel::Alternative => format!("{} favored", test.alternative),
+            FavoredModel::Inconclusive => "inconclusive".to_string(),
+        };
+        output.push_str(&format!(
+            "  vs {}: R = {:.3}, p = {:.3} ({favored})\n",
+            test.alternative, test.log_likelihood_ratio, test.p_value
+        ));
+    }
+    output
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn pareto_sample(count: usize, x_min: f64, alpha: f64, seed: u64) -> Vec<f64> {
+        let mut rng = StdRng::seed_from_u64(seed);
+        (0..count)
+            .map(|_| x_min * (1.0 - rng.gen::<f64>()).powf(-1.0 / (alpha - 1.0)))
+            .collect()
+    }
+
+    fn comparison(fit: &PowerLawFit, alternative: AlternativeDistribution) -> &LikelihoodRatioTest {
+        fit.comparisons
+            .iter()
+            .find(|test| test.alternative == alternative)
+            .unwrap()
+    }
+
+    #[test]
+    fn test_continuous_fit_recovers_alpha() {
+        let values = pareto_sample(2000, 3.0, 2.5, 7);
+        let fit = fit_power_law(
+            &values,
+            &PowerLawOptions {
+                bootstrap: 20,
+                seed: 1,
+            },
+        )
+        .unwrap();
+
+        assert_eq!(fit.support, PowerLawSupport::Continuous);
+        assert!((fit.alpha - 2.5).abs() < 0.15, "alpha = {}", fit.alpha);
+        assert!(fit.x_min >= 3.0 && fit.x_min < 6.0, "x_min = {}", fit.x_min);
+        assert!(fit.ks_statistic < 0.05);
+        assert!(fit.p_value.unwrap() >= 0.1);
+        assert_eq!(fit.plausible, Some(true));
+        assert_eq!(fit.bootstrap_samples, 20);
+        assert!(
+            comparison(&fit, AlternativeDistribution::Exponential).favored
+                == FavoredModel::PowerLaw
+        );
+        assert_eq!(fit.comparisons.len(), 3);
+    }
+
+    #[test]
+    fn test_x_min_skips_non_power_law_body() {
+        // 1-2 の一様な本体の上に x_min = 10, α = 2 のべき乗則の裾
+        let mut rng = StdRng::seed_from_u64(3);
+        let mut values: Vec<f64> = (0..500).map(|_| 1.0 + rng.gen::<f64>()).collect();
+        values.extend(pareto_sample(1000, 10.0, 2.0, 4));
+        let fit = fit_power_law(
+            &values,
+            &PowerLawOptions {
+                bootstrap: 0,
+                seed: 0,
+            },
+        )
+        .unwrap();
+
+        assert!(
+            fit.x_min >= 9.0 && fit.x_min < 15.0,
+            "x_min = {}",
+            fit.x_min
+        );
+        assert!((fit.alpha - 2.0).abs() < 0.15, "alpha = {}", fit.alpha);
+        assert_eq!(fit.p_value, None);
+        assert_eq!(fit.plausible, None);
+    }
+
+    #[test]
+    fn test_exponential_data_is_rejected() {
+        let mut rng = StdRng::seed_from_u64(11);
+        let values: Vec<f64> = (0..1000)
+            .map(|_| 1.0 - (1.0 - rng.gen::<f64>()).ln() * 5.0)
+            .collect();
+        let fit = fit_power_law(
+            &values,
+            &PowerLawOptions {
+                bootstrap: 50,
+                seed: 2,
+            },
+        )
+        .unwrap();
+
+        assert!(fit.p_value.unwrap() < 0.1, "p = {:?}", fit.p_value);
+        assert_eq!(fit.plausible, Some(false));
+        // どの代替分布もべき乗則より尤もらしい
+        assert!(fit
+            .comparisons
+            .iter()
+            .all(|test| test.log_likelihood_ratio < 0.0 && test.favored != FavoredModel::PowerLaw));
+        let exponential = comparison(&fit, AlternativeDistribution::Exponential);
+        assert!((exponential.parameters["lambda"] - 0.2).abs() < 0.05);
+    }
+
+    #[test]
+    fn test_discrete_fit_recovers_alpha() {
+        // 離散べき乗則 (x_min = 1, α = 2.2) を累積分布関数の逆関数で生成
+        let fit = TailFit {
+            x_min: 1.0,
+            alpha: 2.2,
+            tail_start: 0,
+            ks_statistic: 0.0,
+        };
+        let mut rng = StdRng::seed_from_u64(5);
+        let values: Vec<f64> = (0..3000)
+            .map(|_| sample_power_law(&mut rng, &fit, PowerLawSupport::Discrete))
+            .collect();
+        assert!(values.iter().all(|x| x.fract() == 0.0 && *x >= 1.0));
+        // x = 1 の確率は 1/ζ(2.2) ≈ 0.67
+        let ones = values.iter().filter(|&&x| x == 1.0).count() as f64 / 3000.0;
+        assert!((ones - 0.67).abs() < 0.03, "P(1) = {ones}");
+
+        let result = fit_discrete_power_law(
+            &values,
+            &PowerLawOptions {
+                bootstrap: 10,
+                seed: 0,
+            },
+        )
+        .unwrap();
+        assert_eq!(result.support, PowerLawSupport::Discrete);
+        assert!(
+            (result.alpha - 2.2).abs() < 0.15,
+            "alpha = {}",
+            result.alpha
+        );
+        assert!(result.alpha_standard_error > 0.0 && result.alpha_standard_error < 0.1);
+        assert!(result.p_value.is_some());
+    }
+
+    #[test]
+    fn test_invalid_input() {
+        let options = PowerLawOptions::default();
+        assert!(fit_power_law(&[1.0, 2.0, 3.0], &options).is_err());
+        assert!(fit_power_law(&[5.0; 50], &options).is_err());
+        let fractional: Vec<f64> = (1..=20).map(|i| i as f64 + 0.5).collect();
+        assert!(fit_discrete_power_law(&fractional, &options).is_err());
+    }
+}
@@ -1,7 +1,12 @@
+use super::inequality::{
+    calculate_inequality_indices, gini_coefficient, InequalityIndices, InequalityOptions,
+};
+use super::power_law::{fit_power_law, PowerLawFit, PowerLawOptions};
 use crate::common::risk::RiskLevel;
 use crate::error::Result;
+use serde::Serialize;
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Serialize)]
 pub struct ParetoResult {
     pub dataset_name: String,
     pub numbers_analyzed: usize,
@@ -10,6 +15,8 @@ pub struct ParetoResult {
     pub top_20_percent_share: f64,                   // 上位20%が占める割合
     pub cumulative_distribution: Vec<(f64, f64)>,    // ローレンツ曲線用データ
     pub custom_percentiles: Option<Vec<(f64, f64)>>, // カスタムパーセンタイル (パーセンタイル, シェア)
+    pub power_law: Option<PowerLawFit>,              // べき乗則の裾の最尤フィット
+    pub inequality: Option<InequalityIndices>,       // 不平等指標一式
     pub risk_level: RiskLevel,
 }
 
@@ -34,7 +41,7 @@ impl ParetoResult {
         let top_20_percent_share = (top_20_percent_sum / total_sum) * 100.0;
 
         // ジニ係数計算（集中度指標）
-        let concentration_index = calculate_gini_coefficient(&sorted_numbers);
+        let concentration_index = gini_coefficient(&sorted_numbers);
 
         // ローレンツ曲線データ生成
         let cumulative_distribution = generate_lorenz_curve(&sorted_numbers);
@@ -53,6 +60,8 @@ impl ParetoResult {
             top_20_percent_share,
             cumulative_distribution,
             custom_percentiles: None,
+            power_law: None,
+            inequality: None,
             risk_level,
         })
     }
@@ -77,23 +86,26 @@ impl ParetoResult {
         self.custom_percentiles = Some(custom_percentiles);
         self
     }
-}
-
-/// ジニ係数を計算（0=完全平等、1=完全不平等）
-fn calculate_gini_coefficient(sorted_numbers: &[f64]) -> f64 {
-    let n = sorted_numbers.len() as f64;
-    let sum: f64 = sorted_numbers.iter().sum();
 
-    if sum == 0.0 {
-        return 0.0;
+    /// べき乗則（連続値）の最尤フィットを計算してセット
+    pub fn with_power_law_fit(
+        mut self,
+        numbers: &[f64],
+        options: &PowerLawOptions,
+    ) -> Result<Self> {
+        self.power_law = Some(fit_power_law(numbers, options)?);
+        Ok(self)
     }
 
-    let mut gini_sum = 0.0;
-    for (i, &value) in sorted_numbers.iter().enumerate() {
-        gini_sum += (2.0 * (i as f64 + 1.0) - n - 1.0) * value;
+    /// 不平等指標一式（ジニ係数の信頼区間・Theil・Atkinson など）を計算してセット
+    pub fn with_inequality_indices(
+        mut self,
+        numbers: &[f64],
+        options: &InequalityOptions,
+    ) -> Result<Self> {
+        self.inequality = Some(calculate_inequality_indices(numbers, options)?);
+        Ok(self)
     }
-
-    gini_sum / (n * sum)
 }
 
 /// ローレンツ曲線のデータポイントを生成
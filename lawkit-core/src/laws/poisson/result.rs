@@ -1,9 +1,10 @@
-use crate::common::risk::RiskLevel;
+use crate::common::{filtering::RiskThreshold, risk::RiskLevel};
 use crate::error::{BenfError, Result};
+use serde::Serialize;
 use std::collections::HashMap;
 
 /// ポアソン分布分析結果
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Serialize)]
 pub struct PoissonResult {
     pub dataset_name: String,
     pub numbers_analyzed: usize,
@@ -45,7 +46,7 @@ pub struct PoissonResult {
 }
 
 /// ポアソン分布適合度評価
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Serialize)]
 pub enum PoissonAssessment {
     Excellent,  // 優秀なポアソン適合
     Good,       // 良好なポアソン適合
@@ -56,6 +57,18 @@ pub enum PoissonAssessment {
 
 impl PoissonResult {
     pub fn new(dataset_name: String, numbers: &[f64]) -> Result<Self> {
+        Self::new_with_significance(dataset_name, numbers, &RiskThreshold::Auto, None)
+    }
+
+    /// A `significance_level` adds its Auto risk bands for the chi-square p-value to the
+    /// shape-based risk; `None` keeps the shape-based risk of [`Self::new`]. An explicit
+    /// threshold decides the risk on that p-value alone.
+    pub fn new_with_significance(
+        dataset_name: String,
+        numbers: &[f64],
+        threshold: &RiskThreshold,
+        significance_level: Option<f64>,
+    ) -> Result<Self> {
         if numbers.len() < 10 {
             return Err(BenfError::InsufficientData(numbers.len()));
         }
@@ -96,7 +109,18 @@ impl PoissonResult {
             assess_poisson_distribution(goodness_of_fit_score, variance_ratio);
 
         // リスク評価
-        let risk_level = determine_risk_level(goodness_of_fit_score, &distribution_assessment);
+        let risk_level = if threshold.p_value_threshold().is_some() {
+            threshold.evaluate_risk(chi_square_p_value)
+        } else {
+            let shape_risk = determine_risk_level(goodness_of_fit_score, &distribution_assessment);
+            match significance_level {
+                Some(alpha) => shape_risk.max(RiskLevel::from_p_value_with_significance(
+                    chi_square_p_value,
+                    alpha,
+                )),
+                None => shape_risk,
+            }
+        };
 
         // 稀少事象分析
         let rare_events_threshold = calculate_rare_events_threshold(lambda);
@@ -223,8 +247,7 @@ fn chi_square_goodness_of_fit_test(
 
     degrees_of_freedom = degrees_of_freedom.saturating_sub(2); // パラメータ数（λ）を考慮
 
-    // 簡易p値推定
-    let p_value = estimate_chi_square_p_value(chi_square, degrees_of_freedom);
+    let p_value = crate::common::statistics::calculate_p_value(chi_square, degrees_of_freedom);
 
     (chi_square, p_value)
 }
@@ -340,26 +363,3 @@ fn calculate_lambda_confidence_interval(sample_mean: f64, sample_size: usize) ->
 
     ((sample_mean - margin).max(0.0), sample_mean + margin)
 }
-
-/// カイ二乗分布p値簡易推定
-fn estimate_chi_square_p_value(chi_square: f64, df: i32) -> f64 {
-    if df <= 0 {
-        return 1.0;
-    }
-
-    // 簡易推定（正確な計算には特殊関数が必要）
-    let critical_values = match df {
-        1 => vec![(3.84, 0.05), (6.64, 0.01), (10.83, 0.001)],
-        2 => vec![(5.99, 0.05), (9.21, 0.01), (13.82, 0.001)],
-        3 => vec![(7.81, 0.05), (11.34, 0.01), (16.27, 0.001)],
-        _ => vec![(9.49, 0.05), (13.28, 0.01), (18.47, 0.001)], // df=4の値を近似
-    };
-
-    for (critical, alpha) in critical_values {
-        if chi_square < critical {
-            return 1.0 - alpha;
-        }
-    }
-
-    0.001
-}
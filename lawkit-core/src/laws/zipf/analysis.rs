@@ -1,4 +1,5 @@
 use super::result::ZipfResult;
+use super::tokenizer::TextTokenizer;
 use crate::error::Result;
 use std::collections::HashMap;
 
@@ -7,14 +8,23 @@ pub fn analyze_zipf_distribution(frequencies: &[f64], dataset_name: &str) -> Res
     ZipfResult::new(dataset_name.to_string(), frequencies)
 }
 
-/// テキストデータからZipf分析を実行
+/// テキストデータからZipf分析を実行（既定のトークナイザー）
 pub fn analyze_text_zipf(text: &str, dataset_name: &str) -> Result<ZipfResult> {
-    let word_frequencies = extract_word_frequencies(text);
+    analyze_text_zipf_with(text, dataset_name, &TextTokenizer::default())
+}
+
+/// 指定したトークナイザーでテキストのZipf分析を実行
+pub fn analyze_text_zipf_with(
+    text: &str,
+    dataset_name: &str,
+    tokenizer: &TextTokenizer,
+) -> Result<ZipfResult> {
+    let word_frequencies = extract_word_frequencies_with(text, tokenizer);
     let frequencies: Vec<f64> = word_frequencies
         .into_iter()
         .map(|(_, freq)| freq as f64)
         .collect();
-    analyze_zipf_distribution(&frequencies, dataset_name)
+    Ok(analyze_zipf_distribution(&frequencies, dataset_name)?.with_tokenizer(tokenizer.info()))
 }
 
 /// 頻度データからZipf分析を実行
@@ -26,68 +36,32 @@ pub fn analyze_text_zipf_from_frequencies(
     analyze_zipf_distribution(&freq_values, dataset_name)
 }
 
-/// テキストから単語頻度を抽出
-fn extract_word_frequencies(text: &str) -> Vec<(String, usize)> {
-    let mut word_counts = HashMap::new();
+/// テキストから単語頻度を抽出（頻度の降順）
+pub fn extract_word_frequencies(text: &str) -> Vec<(String, usize)> {
+    extract_word_frequencies_with(text, &TextTokenizer::default())
+}
 
-    // 単語分割（日本語・英語・中国語対応）
-    let words = tokenize_multilingual_text(text);
+/// 指定したトークナイザーでテキストから単語頻度を抽出（頻度の降順）
+pub fn extract_word_frequencies_with(
+    text: &str,
+    tokenizer: &TextTokenizer,
+) -> Vec<(String, usize)> {
+    let mut word_counts = HashMap::new();
 
-    for word in words {
-        if !word.is_empty() && word.len() > 1 {
-            *word_counts.entry(word.to_lowercase()).or_insert(0) += 1;
-        }
+    for word in tokenizer.tokenize(text) {
+        *word_counts.entry(word).or_insert(0) += 1;
     }
 
     // 頻度順にソート
     let mut frequencies: Vec<(String, usize)> = word_counts.into_iter().collect();
-    frequencies.sort_by(|a, b| b.1.cmp(&a.1));
+    frequencies.sort_by_key(|b| std::cmp::Reverse(b.1));
 
     frequencies
 }
 
-/// 多言語テキストのトークン化
-fn tokenize_multilingual_text(text: &str) -> Vec<String> {
-    let mut tokens = Vec::new();
-    let mut current_token = String::new();
-
-    for ch in text.chars() {
-        match ch {
-            // 英語・数字の処理
-            'a'..='z' | 'A'..='Z' | '0'..='9' => {
-                current_token.push(ch);
-            }
-            // 日本語文字の処理
-            '\u{3040}'..='\u{309F}' |  // ひらがな
-            '\u{30A0}'..='\u{30FF}' |  // カタカナ
-            '\u{4E00}'..='\u{9FAF}' => { // 漢字
-                if !current_token.is_empty() {
-                    tokens.push(current_token.clone());
-                    current_token.clear();
-                }
-                tokens.push(ch.to_string());
-            }
-            // 区切り文字
-            ' ' | '\t' | '\n' | '\r' | ',' | '.' | '!' | '?' | ';' | ':' |
-            '"' | '\'' | '(' | ')' | '[' | ']' | '{' | '}' | '/' | '\\' |
-            '|' | '@' | '#' | '$' | '%' | '^' | '&' | '*' | '+' | '=' |
-            '<' | '>' | '~' | '`' => {
-                if !current_token.is_empty() {
-                    tokens.push(current_token.clone());
-                    current_token.clear();
-                }
-            }
-            _ => {
-                current_token.push(ch);
-            }
-        }
-    }
-
-    if !current_token.is_empty() {
-        tokens.push(current_token);
-    }
-
-    tokens
+/// テキストから分析対象の単語を出現順に抽出（既定のトークナイザー、小文字化済み）
+pub fn extract_words(text: &str) -> Vec<String> {
+    TextTokenizer::default().tokenize(text)
 }
 
 /// 数値データからZipf分析（頻度分布として扱う）
@@ -222,14 +196,14 @@ mod tests {
     }
 
     #[test]
-    fn test_multilingual_tokenization() {
-        let text = "Hello 世界 测试 مرحبا";
-        let tokens = tokenize_multilingual_text(text);
-
-        assert!(!tokens.is_empty());
-        assert!(tokens.contains(&"Hello".to_string()));
-        assert!(tokens.contains(&"世".to_string()));
-        assert!(tokens.contains(&"界".to_string()));
+    fn test_single_letter_words_are_counted() {
+        let frequencies = extract_word_frequencies("a cat and a dog and a bird");
+        assert_eq!(frequencies[0], ("a".to_string(), 3));
+        assert_eq!(frequencies.len(), 5);
+
+        let result = analyze_text_zipf("a cat and a dog and a bird", "text").unwrap();
+        assert_eq!(result.total_observations, 8);
+        assert_eq!(result.tokenizer.unwrap().name, "unicode-words");
     }
 
     #[test]
@@ -0,0 +1,411 @@
+use crate::{
+    common::statistics::{calculate_p_value, minimize_nelder_mead, normal_quantile},
+    error::{BenfError, Result},
+    laws::pareto::inequality::ConfidenceInterval,
+};
+use serde::Serialize;
+
+/// カイ二乗検定で 1 ビンに必要な期待度数（満たない裾のランクはまとめる）
+const MIN_EXPECTED_PER_BIN: f64 = 5.0;
+/// 指数の探索範囲の上限
+const MAX_EXPONENT: f64 = 50.0;
+/// Zipf–Mandelbrot の最尤推定の反復回数
+const MANDELBROT_FIT_ITERATIONS: usize = 500;
+
+/// ランク分布の適合度（カイ二乗検定と KS 距離）
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct RankGoodnessOfFit {
+    /// 期待度数 5 未満の裾のランクをまとめたカイ二乗統計量
+    pub chi_square: f64,
+    pub degrees_of_freedom: usize,
+    /// 自由度が 0 なら None
+    pub p_value: Option<f64>,
+    /// 観測と理論の累積ランク分布の最大差
+    pub ks_statistic: f64,
+}
+
+/// 離散 Zipf 分布 p(r) = r^(-s) / H(N, s) の最尤推定
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ZipfExponentFit {
+    pub exponent: f64,
+    /// フィッシャー情報量 M·Var(ln r) による標準誤差
+    pub standard_error: f64,
+    /// 指数の Wald 信頼区間
+    pub confidence_interval: ConfidenceInterval,
+    pub log_likelihood: f64,
+    pub goodness_of_fit: RankGoodnessOfFit,
+}
+
+/// Zipf–Mandelbrot 分布 p(r) = (r + q)^(-s) / Σ(k + q)^(-s) の最尤推定
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ZipfMandelbrotFit {
+    pub exponent: f64,
+    /// ランクのオフセット（q > -1、0 なら Zipf 分布）
+    pub q: f64,
+    pub log_likelihood: f64,
+    /// 2 (ln L_ZM - ln L_Zipf)。q = 0 の Zipf 分布に対する尤度比
+    pub likelihood_ratio: f64,
+    /// 尤度比の χ²(1) による p 値（小さければ q が有意）
+    pub likelihood_ratio_p_value: f64,
+    pub goodness_of_fit: RankGoodnessOfFit,
+}
+
+/// ランク-頻度データに離散 Zipf 分布を最尤フィットする
+///
+/// 頻度を降順に並べ、各観測のランク r ∈ {1..N} が p(r) ∝ r^(-s) に従うとみなす。
+/// 尤度方程式 E_s[ln r] = Σ f_r ln r / M は s について単調なので、ニュートン法と
+/// 二分法で解く。信頼区間は信頼水準 `confidence_level` の Wald 区間。頻度が負、
+/// ランクが 2 未満、合計が 0、信頼水準が 0 と 1 の間にない場合はエラー。
+pub fn fit_zipf_exponent(frequencies: &[f64], confidence_level: f64) -> Result<ZipfExponentFit> {
+    let sorted = sorted_frequencies(frequencies)?;
+    let total: f64 = sorted.iter().sum();
+    let mean_log_rank = weighted_log_sum(&sorted, 0.0) / total;
+
+    let exponent = solve_zipf_exponent(sorted.len(), mean_log_rank);
+    let moments = RankMoments::new(sorted.len(), exponent, 0.0);
+    let standard_error = 1.0 / (total * moments.variance).sqrt();
+    let probabilities = rank_probabilities(sorted.len(), exponent, 0.0);
+
+    Ok(ZipfExponentFit {
+        exponent,
+        standard_error,
+        confidence_interval: wald_interval(exponent, standard_error, confidence_level)?,
+        log_likelihood: log_likelihood(&sorted, exponent, 0.0),
+        goodness_of_fit: goodness_of_fit(&sorted, &probabilities, 1),
+    })
+}
+
+impl ZipfExponentFit {
+    /// 同じ推定値と標準誤差から、別の信頼水準で信頼区間を求め直す
+    pub fn with_confidence_level(mut self, confidence_level: f64) -> Result<Self> {
+        self.confidence_interval =
+            wald_interval(self.exponent, self.standard_error, confidence_level)?;
+        Ok(self)
+    }
+}
+
+/// 推定値 ± z·標準誤差 の Wald 信頼区間
+fn wald_interval(
+    estimate: f64,
+    standard_error: f64,
+    confidence_level: f64,
+) -> Result<ConfidenceInterval> {
+    if !(confidence_level > 0.0 && confidence_level < 1.0) {
+        return Err(BenfError::InvalidInput(format!(
+            "Confidence level must be between 0 and 1 (exclusive): {confidence_level}"
+        )));
+    }
+    let z = normal_quantile(0.5 + confidence_level / 2.0);
+    Ok(ConfidenceInterval {
+        level: confidence_level,
+        lower: estimate - z * standard_error,
+        upper: estimate + z * standard_error,
+    })
+}
+
+/// ランク-頻度データに Zipf–Mandelbrot 分布を最尤フィットする
+///
+/// (s, ln(1 + q)) を Nelder–Mead で最適化し、Zipf 分布（q = 0）との尤度比検定を行う。
+pub fn fit_zipf_mandelbrot(frequencies: &[f64]) -> Result<ZipfMandelbrotFit> {
+    let sorted = sorted_frequencies(frequencies)?;
+    let total: f64 = sorted.iter().sum();
+    let zipf_exponent = solve_zipf_exponent(sorted.len(), weighted_log_sum(&sorted, 0.0) / total);
+    let zipf_log_likelihood = log_likelihood(&sorted, zipf_exponent, 0.0);
+
+    let best = minimize_nelder_mead(
+        |p: &[f64]| -log_likelihood(&sorted, p[0], p[1].exp_m1()),
+        &[zipf_exponent, 0.0],
+        0.5,
+        MANDELBROT_FIT_ITERATIONS,
+    );
+    let (mut exponent, mut q) = (best[0], best[1].exp_m1());
+    let mut fitted_log_likelihood = log_likelihood(&sorted, exponent, q);
+    if fitted_log_likelihood.is_nan() || fitted_log_likelihood < zipf_log_likelihood {
+        // 最適化が Zipf 分布より悪くなることはないはず（数値誤差の保険）
+        (exponent, q, fitted_log_likelihood) = (zipf_exponent, 0.0, zipf_log_likelihood);
+    }
+
+    let likelihood_ratio = 2.0 * (fitted_log_likelihood - zipf_log_likelihood);
+    let probabilities = rank_probabilities(sorted.len(), exponent, q);
+    Ok(ZipfMandelbrotFit {
+        exponent,
+        q,
+        log_likelihood: fitted_log_likelihood,
+        likelihood_ratio,
+        likelihood_ratio_p_value: calculate_p_value(likelihood_ratio, 1),
+        goodness_of_fit: goodness_of_fit(&sorted, &probabilities, 2),
+    })
+}
+
+fn sorted_frequencies(frequencies: &[f64]) -> Result<Vec<f64>> {
+    if frequencies.len() < 2 {
+        return Err(BenfError::InsufficientData(frequencies.len()));
+    }
+    if frequencies.iter().any(|f| !f.is_finite() || *f < 0.0) {
+        return Err(BenfError::InvalidInput(
+            "Frequencies must be finite and non-negative".to_string(),
+        ));
+    }
+    let mut sorted = frequencies.to_vec();
+    sorted.sort_by(|a, b| b.partial_cmp(a).unwrap());
+    if sorted[0] == 0.0 {
+        return Err(BenfError::InvalidInput(
+            "Frequencies must not all be zero".to_string(),
+        ));
+    }
+    Ok(sorted)
+}
+
+/// Σ f_r ln(r + q)
+fn weighted_log_sum(sorted: &[f64], q: f64) -> f64 {
+    sorted
+        .iter()
+        .enumerate()
+        .map(|(i, &f)| f * ((i + 1) as f64 + q).ln())
+        .sum()
+}
+
+/// p(r) ∝ (r + q)^(-s), r = 1..N の正規化定数の対数と ln(r + q) の平均・分散
+struct RankMoments {
+    log_normalizer: f64,
+    mean: f64,
+    variance: f64,
+}
+
+impl RankMoments {
+    fn new(ranks: usize, s: f64, q: f64) -> Self {
+        // 最大の項（s ≥ 0 ならランク 1、s < 0 ならランク N）で割って桁あふれを防ぐ
+        let shift = (-s * (1.0 + q).ln()).max(-s * (ranks as f64 + q).ln());
+        let (mut sum, mut first, mut second) = (0.0, 0.0, 0.0);
+        for rank in 1..=ranks {
+            let log_rank = (rank as f64 + q).ln();
+            let weight = (-s * log_rank - shift).exp();
+            sum += weight;
+            first += weight * log_rank;
+            second += weight * log_rank * log_rank;
+        }
+        let mean = first / sum;
+        RankMoments {
+            log_normalizer: sum.ln() + shift,
+            mean,
+            variance: (second / sum - mean * mean).max(0.0),
+        }
+    }
+}
+
+/// E_s[ln r] = mean_log_rank を満たす s（E_s[ln r] は s について減少）
+fn solve_zipf_exponent(ranks: usize, mean_log_rank: f64) -> f64 {
+    let (mut low, mut high) = (0.0, MAX_EXPONENT);
+    if RankMoments::new(ranks, low, 0.0).mean <= mean_log_rank {
+        return 0.0;
+    }
+    if RankMoments::new(ranks, high, 0.0).mean >= mean_log_rank {
+        return high;
+    }
+
+    let mut s = 1.0;
+    for _ in 0..100 {
+        let moments = RankMoments::new(ranks, s, 0.0);
+        let gap = moments.mean - mean_log_rank;
+        if gap.abs() < 1e-12 {
+            break;
+        }
+        if gap > 0.0 {
+            low = s;
+        } else {
+            high = s;
+        }
+        // ニュートン法（d E_s[ln r] / ds = -Var_s(ln r)）。範囲外なら二分法
+        let newton = s + gap / moments.variance;
+        s = if newton > low && newton < high && moments.variance > 0.0 {
+            newton
+        } else {
+            (low + high) / 2.0
+        };
+        if high - low < 1e-12 {
+            break;
+        }
+    }
+    s
+}
+
+fn log_likelihood(sorted: &[f64], s: f64, q: f64) -> f64 {
+    if q <= -1.0 || !s.is_finite() || !q.is_finite() {
+        return f64::NEG_INFINITY;
+    }
+    let total: f64 = sorted.iter().sum();
+    let moments = RankMoments::new(sorted.len(), s, q);
+    -s * weighted_log_sum(sorted, q) - total * moments.log_normalizer
+}
+
+fn rank_probabilities(ranks: usize, s: f64, q: f64) -> Vec<f64> {
+    let log_normalizer = RankMoments::new(ranks, s, q).log_normalizer;
+    (1..=ranks)
+        .map(|rank| (-s * (rank as f64 + q).ln() - log_normalizer).exp())
+        .collect()
+}
+
+/// 観測頻度と理論確率の適合度。ランク順に期待度数が 5 以上になるまでまとめてビンにする
+fn goodness_of_fit(sorted: &[f64], probabilities: &[f64], parameters: usize) -> RankGoodnessOfFit {
+    let total: f64 = sorted.iter().sum();
+
+    let mut bins: Vec<(f64, f64)> = Vec::new();
+    let (mut observed, mut expected) = (0.0, 0.0);
+    for (&frequency, &probability) in sorted.iter().zip(probabilities) {
+        observed += frequency;
+        expected += probability * total;
+        if expected >= MIN_EXPECTED_PER_BIN {
+            bins.push((observed, expected));
+            (observed, expected) = (0.0, 0.0);
+        }
+    }
+    if expected > 0.0 {
+        match bins.last_mut() {
+            Some(last) => {
+                last.0 += observed;
+                last.1 += expected;
+            }
+            None => bins.push((observed, expected)),
+        }
+    }
+    let chi_square: f64 = bins.iter().map(|(o, e)| (o - e).powi(2) / e).sum();
+    let degrees_of_freedom = bins.len().saturating_sub(1 + parameters);
+
+    let mut ks_statistic: f64 = 0.0;
+    let (mut observed_cdf, mut expected_cdf) = (0.0, 0.0);
+    for (&frequency, &probability) in sorted.iter().zip(probabilities) {
+        observed_cdf += frequency / total;
+        expected_cdf += probability;
+        ks_statistic = ks_statistic.max((observed_cdf - expected_cdf).abs());
+    }
+
+    RankGoodnessOfFit {
+        chi_square,
+        degrees_of_freedom,
+        p_value: (degrees_of_freedom > 0)
+            .then(|| calculate_p_value(chi_square, degrees_of_freedom as i32)),
+        ks_statistic,
+    }
+}
+
+/// Zipf・Zipf–Mandelbrot フィットのテキスト表現（Zipf–Mandelbrot はフィットした場合のみ）
+pub fn format_zipf_fits_text(
+    zipf: &ZipfExponentFit,
+    mandelbrot: Option<&ZipfMandelbrotFit>,
+) -> String {
+    let p_value = |fit: &RankGoodnessOfFit| {
+        fit.p_value
+            .map_or("n/a".to_string(), |p_value| format!("{p_value:.3}"))
+    };
+    let mut output = String::from("Maximum-Likelihood Fits:\n");
+    output.push_str(&format!(
+        "  Zipf: s = {:.4} ± {:.4} ({}% CI {:.4}-{:.4})\n",
+        zipf.exponent,
+        zipf.standard_error,
+        (zipf.confidence_interval.level * 1000.0).round() / 10.0,
+        zipf.confidence_interval.lower,
+        zipf.confidence_interval.upper
+    ));
+    output.push_str(&format!(
+        "    chi-square = {:.2} (df {}), p = {}, KS = {:.4}\n",
+        zipf.goodness_of_fit.chi_square,
+        zipf.goodness_of_fit.degrees_of_freedom,
+        p_value(&zipf.goodness_of_fit),
+        zipf.goodness_of_fit.ks_statistic
+    ));
+    let Some(mandelbrot) = mandelbrot else {
+        return output;
+    };
+    output.push_str(&format!(
+        "  Zipf-Mandelbrot: s = {:.4}, q = {:.4} (LR vs Zipf = {:.2}, p = {:.3})\n",
+        mandelbrot.exponent,
+        mandelbrot.q,
+        mandelbrot.likelihood_ratio,
+        mandelbrot.likelihood_ratio_p_value
+    ));
+    output.push_str(&format!(
+        "    chi-square = {:.2} (df {}), p = {}, KS = {:.4}\n",
+        mandelbrot.goodness_of_fit.chi_square,
+        mandelbrot.goodness_of_fit.degrees_of_freedom,
+        p_value(&mandelbrot.goodness_of_fit),
+        mandelbrot.goodness_of_fit.ks_statistic
+    ));
+    output
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// 期待度数そのもの（丸めなし）の頻度
+    fn expected_frequencies(ranks: usize, s: f64, q: f64, total: f64) -> Vec<f64> {
+        rank_probabilities(ranks, s, q)
+            .into_iter()
+            .map(|p| p * total)
+            .collect()
+    }
+
+    #[test]
+    fn test_zipf_mle_recovers_exponent() {
+        let frequencies = expected_frequencies(200, 1.1, 0.0, 100_000.0);
+        let fit = fit_zipf_exponent(&frequencies, 0.95).unwrap();
+        assert!((fit.exponent - 1.1).abs() < 1e-6);
+        assert!(fit.confidence_interval.lower < 1.1 && 1.1 < fit.confidence_interval.upper);
+        assert!(fit.standard_error > 0.0 && fit.standard_error < 0.01);
+        assert!(fit.goodness_of_fit.chi_square < 1e-6);
+        assert!(fit.goodness_of_fit.ks_statistic < 1e-9);
+        assert!(fit.goodness_of_fit.p_value.unwrap() > 0.99);
+    }
+
+    #[test]
+    fn test_zipf_mle_confidence_level() {
+        let frequencies = expected_frequencies(50, 1.0, 0.0, 5_000.0);
+        let fit = fit_zipf_exponent(&frequencies, 0.95).unwrap();
+        let half_width = fit.confidence_interval.upper - fit.exponent;
+        assert!((half_width - 1.959_963_984_540_054 * fit.standard_error).abs() < 1e-12);
+
+        let narrower = fit.clone().with_confidence_level(0.9).unwrap();
+        assert_eq!(narrower.confidence_interval.level, 0.9);
+        let half_width_90 = narrower.confidence_interval.upper - narrower.exponent;
+        assert!((half_width_90 - 1.644_853_626_951_472 * fit.standard_error).abs() < 1e-12);
+        assert_eq!(
+            fit_zipf_exponent(&frequencies, 0.9).unwrap(),
+            narrower,
+            "refitting at 90% gives the same interval"
+        );
+        assert!(fit.with_confidence_level(1.0).is_err());
+        assert!(format_zipf_fits_text(&narrower, None).contains("(90% CI "));
+    }
+
+    #[test]
+    fn test_zipf_mle_uniform_frequencies() {
+        let fit = fit_zipf_exponent(&[10.0; 20], 0.95).unwrap();
+        assert_eq!(fit.exponent, 0.0);
+        assert!(fit_zipf_exponent(&[0.0, 0.0], 0.95).is_err());
+        assert!(fit_zipf_exponent(&[5.0], 0.95).is_err());
+    }
+
+    #[test]
+    fn test_zipf_mandelbrot_recovers_offset() {
+        let frequencies = expected_frequencies(500, 1.2, 2.7, 1_000_000.0);
+        let fit = fit_zipf_mandelbrot(&frequencies).unwrap();
+        assert!((fit.exponent - 1.2).abs() < 0.01, "s = {}", fit.exponent);
+        assert!((fit.q - 2.7).abs() < 0.05, "q = {}", fit.q);
+        assert!(fit.likelihood_ratio > 100.0);
+        assert!(fit.likelihood_ratio_p_value < 1e-6);
+        assert!(fit.goodness_of_fit.ks_statistic < 1e-4);
+
+        // Zipf 分布の推定は q を無視するので適合が悪い
+        let zipf = fit_zipf_exponent(&frequencies, 0.95).unwrap();
+        assert!(zipf.goodness_of_fit.chi_square > fit.goodness_of_fit.chi_square);
+    }
+
+    #[test]
+    fn test_zipf_mandelbrot_on_pure_zipf() {
+        let frequencies = expected_frequencies(100, 1.0, 0.0, 10_000.0);
+        let fit = fit_zipf_mandelbrot(&frequencies).unwrap();
+        assert!(fit.q.abs() < 0.05, "q = {}", fit.q);
+        assert!(fit.likelihood_ratio < 1e-3);
+        assert!(fit.likelihood_ratio_p_value > 0.9);
+    }
+}
@@ -0,0 +1,163 @@
+use super::tokenizer::TextTokenizer;
+use crate::{
+    common::memory::IncrementalZipf,
+    error::{BenfError, Result},
+};
+use serde::Serialize;
+
+/// 語彙数がトークン数とほぼ等しい序盤の点（フィットから除外する）
+const MIN_FIT_TOKENS: usize = 10;
+/// フィットに必要な曲線の点数
+const MIN_FIT_POINTS: usize = 3;
+/// これ未満の β は語彙の伸びが鈍い（反復的・テンプレート的なテキスト）
+const LOW_BETA: f64 = 0.4;
+/// これを超える β は語彙がほとんど繰り返されない（短いテキストや単語リスト）
+const HIGH_BETA: f64 = 0.8;
+
+/// Heaps の法則 V(n) = K n^β による語彙成長の分析結果
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct HeapsResult {
+    pub total_tokens: usize,
+    pub vocabulary_size: usize,
+    pub k: f64,
+    /// 自然言語では概ね 0.4〜0.6
+    pub beta: f64,
+    /// ln V と ln n の回帰の決定係数
+    pub r_squared: f64,
+    /// フィットに使った点の数
+    pub points_fitted: usize,
+    /// (トークン数, 語彙数)。トークン数の対数でほぼ等間隔
+    pub vocabulary_growth: Vec<(usize, usize)>,
+}
+
+impl HeapsResult {
+    /// 語彙成長曲線に V = K n^β を当てはめる
+    ///
+    /// ln V = ln K + β ln n を最小二乗法で推定する。語彙数がトークン数とほぼ
+    /// 等しい 10 トークン未満の点は、残りが 3 点以上あれば除外する。
+    pub fn from_growth(vocabulary_growth: Vec<(usize, usize)>) -> Result<Self> {
+        let &(total_tokens, vocabulary_size) =
+            vocabulary_growth.last().ok_or(BenfError::NoNumbersFound)?;
+
+        let mut points: Vec<(f64, f64)> = vocabulary_growth
+            .iter()
+            .filter(|&&(tokens, _)| tokens >= MIN_FIT_TOKENS)
+            .map(|&(tokens, vocabulary)| ((tokens as f64).ln(), (vocabulary as f64).ln()))
+            .collect();
+        if points.len() < MIN_FIT_POINTS {
+            points = vocabulary_growth
+                .iter()
+                .filter(|&&(tokens, vocabulary)| tokens > 0 && vocabulary > 0)
+                .map(|&(tokens, vocabulary)| ((tokens as f64).ln(), (vocabulary as f64).ln()))
+                .collect();
+        }
+        if points.len() < MIN_FIT_POINTS {
+            return Err(BenfError::InsufficientData(points.len()));
+        }
+
+        let n = points.len() as f64;
+        let mean_x = points.iter().map(|p| p.0).sum::<f64>() / n;
+        let mean_y = points.iter().map(|p| p.1).sum::<f64>() / n;
+        let sxx: f64 = points.iter().map(|p| (p.0 - mean_x).powi(2)).sum();
+        let sxy: f64 = points.iter().map(|p| (p.0 - mean_x) * (p.1 - mean_y)).sum();
+        let syy: f64 = points.iter().map(|p| (p.1 - mean_y).powi(2)).sum();
+
+        let beta = sxy / sxx;
+        let r_squared = if syy > 0.0 {
+            (sxy * sxy) / (sxx * syy)
+        } else {
+            1.0
+        };
+
+        Ok(HeapsResult {
+            total_tokens,
+            vocabulary_size,
+            k: (mean_y - beta * mean_x).exp(),
+            beta,
+            r_squared,
+            points_fitted: points.len(),
+            vocabulary_growth,
+        })
+    }
+
+    /// 単語を出現順に `IncrementalZipf` に流し込み、その語彙成長曲線を当てはめる
+    pub fn from_zipf(zipf: &IncrementalZipf) -> Result<Self> {
+        Self::from_growth(zipf.vocabulary_growth())
+    }
+}
+
+/// テキストの Heaps の法則分析（Zipf 分析と同じ単語抽出）
+pub fn analyze_text_heaps(text: &str) -> Result<HeapsResult> {
+    analyze_text_heaps_with(text, &TextTokenizer::default())
+}
+
+/// 指定したトークナイザーで分割したテキストの Heaps の法則分析
+pub fn analyze_text_heaps_with(text: &str, tokenizer: &TextTokenizer) -> Result<HeapsResult> {
+    let mut zipf = IncrementalZipf::new();
+    for word in tokenizer.tokenize(text) {
+        zipf.add_word(word);
+    }
+    HeapsResult::from_zipf(&zipf)
+}
+
+/// Heaps の法則のテキスト表現
+pub fn format_heaps_text(heaps: &HeapsResult) -> String {
+    let mut output = String::from("Heaps' Law (Vocabulary Growth):\n");
+    output.push_str(&format!(
+        "  V(n) = {:.3} * n^{:.3} (R² = {:.3}, {} points)\n",
+        heaps.k, heaps.beta, heaps.r_squared, heaps.points_fitted
+    ));
+    output.push_str(&format!(
+        "  Tokens: {}, vocabulary: {} (type-token ratio {:.3})\n",
+        heaps.total_tokens,
+        heaps.vocabulary_size,
+        heaps.vocabulary_size as f64 / heaps.total_tokens as f64
+    ));
+    if heaps.beta < LOW_BETA {
+        output.push_str("  INFO: Slow vocabulary growth - repetitive or templated text\n");
+    } else if heaps.beta > HIGH_BETA {
+        output.push_str("  INFO: Little repetition - short text or word list\n");
+    }
+    output
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_heaps_fit_recovers_parameters() {
+        // V = 5 n^0.5 の曲線そのもの
+        let growth: Vec<(usize, usize)> = (1..=60)
+            .map(|i| {
+                let tokens = (1.2f64.powi(i)).round() as usize;
+                (tokens, (5.0 * (tokens as f64).sqrt()).round() as usize)
+            })
+            .collect();
+        let heaps = HeapsResult::from_growth(growth).unwrap();
+        assert!((heaps.beta - 0.5).abs() < 0.01, "beta = {}", heaps.beta);
+        assert!((heaps.k - 5.0).abs() < 0.3, "k = {}", heaps.k);
+        assert!(heaps.r_squared > 0.999);
+    }
+
+    #[test]
+    fn test_templated_text_has_low_beta() {
+        let template = "dear customer your order has shipped and will arrive soon thank you ";
+        let templated = template.repeat(200);
+        let heaps = analyze_text_heaps(&templated).unwrap();
+        assert_eq!(heaps.vocabulary_size, 12);
+        assert!(heaps.beta < LOW_BETA, "beta = {}", heaps.beta);
+        assert!(format_heaps_text(&heaps).contains("templated"));
+
+        // 毎回新しい単語が現れるテキストは β ≈ 1
+        let unique: String = (0..500).map(|i| format!("word{i} ")).collect();
+        let heaps = analyze_text_heaps(&unique).unwrap();
+        assert!((heaps.beta - 1.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_heaps_requires_enough_points() {
+        assert!(analyze_text_heaps("").is_err());
+        assert!(analyze_text_heaps("hello world").is_err());
+    }
+}
@@ -1,8 +1,22 @@
 mod analysis;
+mod fit;
+mod heaps;
 mod result;
+mod tokenizer;
 
 pub use analysis::{
     analyze_combined_zipf, analyze_numeric_zipf, analyze_text_zipf,
-    analyze_text_zipf_from_frequencies, evaluate_zipf_quality, ZipfQualityReport,
+    analyze_text_zipf_from_frequencies, analyze_text_zipf_with, evaluate_zipf_quality,
+    extract_word_frequencies, extract_word_frequencies_with, extract_words, ZipfQualityReport,
 };
+pub use fit::{
+    fit_zipf_exponent, fit_zipf_mandelbrot, format_zipf_fits_text, RankGoodnessOfFit,
+    ZipfExponentFit, ZipfMandelbrotFit,
+};
+pub use heaps::{analyze_text_heaps, analyze_text_heaps_with, format_heaps_text, HeapsResult};
 pub use result::ZipfResult;
+pub use tokenizer::{
+    builtin_tokenizer, parse_stopwords, CjkNgramTokenizer, RegexTokenizer, TextTokenizer,
+    Tokenizer, TokenizerInfo, TokenizerOptions, UnicodeWordTokenizer, WhitespaceTokenizer,
+    DEFAULT_NGRAM,
+};
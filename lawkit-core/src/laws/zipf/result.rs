@@ -1,19 +1,30 @@
 use crate::{
     common::risk::RiskLevel,
     error::{BenfError, Result},
+    laws::pareto::{
+        inequality::gini_coefficient,
+        power_law::{fit_discrete_power_law, fit_power_law, PowerLawFit, PowerLawOptions},
+    },
 };
+use serde::Serialize;
+
+use super::fit::{fit_zipf_exponent, fit_zipf_mandelbrot, ZipfExponentFit, ZipfMandelbrotFit};
+use super::heaps::HeapsResult;
+use super::tokenizer::TokenizerInfo;
 
 /// ジップの法則（Zipf's law）解析結果
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Serialize)]
 pub struct ZipfResult {
     pub dataset_name: String,
     pub numbers_analyzed: usize,
     pub risk_level: RiskLevel,
 
     // Zipf分析メトリクス
-    pub zipf_exponent: f64,           // Zipf指数（理論値は1.0）
+    pub zipf_exponent: f64,        // Zipf指数（理論値は1.0、対数線形回帰）
+    pub zipf_fit: ZipfExponentFit, // Zipf指数の最尤推定と信頼区間
+    pub zipf_mandelbrot: Option<ZipfMandelbrotFit>, // Zipf–Mandelbrot分布の最尤推定（指定時のみ）
     pub correlation_coefficient: f64, // 相関係数（適合度）
-    pub distribution_quality: f64,    // 分布品質スコア
+    pub distribution_quality: f64, // 分布品質スコア
 
     // 頻度分析
     pub total_observations: usize,               // 総観測数
@@ -22,11 +33,17 @@ pub struct ZipfResult {
     pub rank_frequency_pairs: Vec<(usize, f64)>, // ランク-頻度ペア（上位20項目）
 
     // 分布特性
-    pub concentration_index: f64, // 集中度指数
-    pub diversity_index: f64,     // 多様性指数（Shannon entropy）
-    pub power_law_fit: f64,       // べき乗法則適合度
+    pub concentration_index: f64,         // 集中度指数
+    pub diversity_index: f64,             // 多様性指数（Shannon entropy）
+    pub power_law_fit: f64,               // べき乗法則適合度
+    pub power_law: Option<PowerLawFit>,   // 頻度分布の裾の最尤フィット
+    pub heaps: Option<HeapsResult>,       // 語彙成長（Heaps の法則、テキストのみ）
+    pub tokenizer: Option<TokenizerInfo>, // テキスト分析で使ったトークン化
 }
 
+/// Zipf 指数の信頼区間の既定の信頼水準
+const DEFAULT_CONFIDENCE_LEVEL: f64 = 0.95;
+
 impl ZipfResult {
     pub fn new(dataset_name: String, frequencies: &[f64]) -> Result<Self> {
         if frequencies.len() < 5 {
@@ -50,6 +67,9 @@ impl ZipfResult {
         // Zipf指数計算（対数線形回帰）
         let zipf_exponent = calculate_zipf_exponent(frequencies);
 
+        // 離散Zipf分布の最尤推定
+        let zipf_fit = fit_zipf_exponent(frequencies, DEFAULT_CONFIDENCE_LEVEL)?;
+
         // 相関係数計算（理論Zipf分布との適合度）
         let correlation_coefficient = calculate_correlation_with_theoretical_zipf(frequencies);
 
@@ -58,7 +78,7 @@ impl ZipfResult {
             calculate_distribution_quality(zipf_exponent, correlation_coefficient);
 
         // 集中度指数（Gini係数）
-        let concentration_index = calculate_concentration_index(frequencies);
+        let concentration_index = gini_coefficient(frequencies);
 
         // 多様性指数（Shannon entropy）
         let diversity_index = calculate_diversity_index(frequencies);
@@ -75,6 +95,8 @@ impl ZipfResult {
             numbers_analyzed,
             risk_level,
             zipf_exponent,
+            zipf_fit,
+            zipf_mandelbrot: None,
             correlation_coefficient,
             distribution_quality,
             total_observations,
@@ -84,8 +106,55 @@ impl ZipfResult {
             concentration_index,
             diversity_index,
             power_law_fit,
+            power_law: None,
+            heaps: None,
+            tokenizer: None,
         })
     }
+
+    /// Zipf 指数の信頼区間を指定の信頼水準（既定 0.95）で求め直す
+    pub fn with_confidence_level(mut self, confidence_level: f64) -> Result<Self> {
+        self.zipf_fit = self.zipf_fit.with_confidence_level(confidence_level)?;
+        Ok(self)
+    }
+
+    /// `new` に渡したのと同じ頻度に Zipf–Mandelbrot 分布を最尤フィットしてセット
+    ///
+    /// Nelder–Mead の反復で尤度を何百回も評価するため、必要なときだけ呼ぶ。
+    pub fn with_mandelbrot_fit(mut self, frequencies: &[f64]) -> Result<Self> {
+        self.zipf_mandelbrot = Some(fit_zipf_mandelbrot(frequencies)?);
+        Ok(self)
+    }
+
+    /// 頻度の分布にべき乗則を最尤フィットしてセット
+    ///
+    /// 頻度がすべて整数なら離散、そうでなければ連続のべき乗則を当てはめる。
+    /// 頻度分布の指数 α と Zipf 指数 s には α ≈ 1 + 1/s の関係がある。
+    pub fn with_power_law_fit(
+        mut self,
+        frequencies: &[f64],
+        options: &PowerLawOptions,
+    ) -> Result<Self> {
+        let fit = if frequencies.iter().all(|frequency| frequency.fract() == 0.0) {
+            fit_discrete_power_law(frequencies, options)?
+        } else {
+            fit_power_law(frequencies, options)?
+        };
+        self.power_law = Some(fit);
+        Ok(self)
+    }
+
+    /// テキストの語彙成長（Heaps の法則）をセット
+    pub fn with_heaps(mut self, heaps: HeapsResult) -> Self {
+        self.heaps = Some(heaps);
+        self
+    }
+
+    /// テキスト分析で使ったトークン化の設定を記録
+    pub fn with_tokenizer(mut self, tokenizer: TokenizerInfo) -> Self {
+        self.tokenizer = Some(tokenizer);
+        self
+    }
 }
 
 /// Zipf指数を計算（対数線形回帰）
@@ -193,30 +262,6 @@ fn calculate_distribution_quality(zipf_exponent: f64, correlation: f64) -> f64 {
     (exponent_score * 0.6 + correlation_score * 0.4).clamp(0.0, 1.0)
 }
 
-/// 集中度指数（Gini係数）を計算
-fn calculate_concentration_index(frequencies: &[f64]) -> f64 {
-    if frequencies.len() < 2 {
-        return 0.0;
-    }
-
-    let mut sorted_freqs = frequencies.to_vec();
-    sorted_freqs.sort_by(|a, b| a.partial_cmp(b).unwrap());
-
-    let n = sorted_freqs.len() as f64;
-    let sum: f64 = sorted_freqs.iter().sum();
-
-    if sum == 0.0 {
-        return 0.0;
-    }
-
-    let mut gini = 0.0;
-    for (i, &freq) in sorted_freqs.iter().enumerate() {
-        gini += freq * (2.0 * (i + 1) as f64 - n - 1.0);
-    }
-
-    gini / (n * sum)
-}
-
 /// 多様性指数（Shannon entropy）を計算
 fn calculate_diversity_index(frequencies: &[f64]) -> f64 {
     let total: f64 = frequencies.iter().sum();
@@ -309,6 +354,50 @@ mod tests {
         assert!(matches!(result.risk_level, RiskLevel::Critical));
     }
 
+    #[test]
+    fn test_power_law_fit_of_frequencies() {
+        // 頻度 f の項目数が f^(-2) に比例（Zipf 指数 s = 1 に相当）
+        let mut frequencies = Vec::new();
+        for frequency in 1..=60u32 {
+            let items = (6000.0 / (frequency as f64).powi(2)).round() as usize;
+            frequencies.extend(std::iter::repeat(frequency as f64).take(items));
+        }
+        frequencies.sort_by(|a, b| b.partial_cmp(a).unwrap());
+
+        let result = ZipfResult::new("words".to_string(), &frequencies)
+            .unwrap()
+            .with_power_law_fit(
+                &frequencies,
+                &PowerLawOptions {
+                    bootstrap: 0,
+                    seed: 0,
+                },
+            )
+            .unwrap();
+        let fit = result.power_law.unwrap();
+        assert_eq!(
+            fit.support,
+            crate::laws::pareto::power_law::PowerLawSupport::Discrete
+        );
+        assert!((fit.alpha - 2.0).abs() < 0.15, "alpha = {}", fit.alpha);
+    }
+
+    #[test]
+    fn test_optional_fits() {
+        let frequencies: Vec<f64> = (1..=30).map(|rank| 600.0 / rank as f64).collect();
+        let result = ZipfResult::new("test".to_string(), &frequencies).unwrap();
+        assert_eq!(result.zipf_fit.confidence_interval.level, 0.95);
+        assert!(result.zipf_mandelbrot.is_none());
+
+        let result = result
+            .with_confidence_level(0.99)
+            .unwrap()
+            .with_mandelbrot_fit(&frequencies)
+            .unwrap();
+        assert_eq!(result.zipf_fit.confidence_interval.level, 0.99);
+        assert!(result.zipf_mandelbrot.unwrap().q.abs() < 0.05);
+    }
+
     #[test]
     fn test_insufficient_data() {
         let frequencies = vec![1.0, 2.0]; // 5個未満
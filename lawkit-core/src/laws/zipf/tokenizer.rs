@@ -0,0 +1,393 @@
+use crate::error::{BenfError, Result};
+use regex::Regex;
+use serde::Serialize;
+use std::collections::HashSet;
+use unicode_normalization::UnicodeNormalization;
+use unicode_segmentation::UnicodeSegmentation;
+
+/// CJK 文字 n-gram の既定の n（日本語・中国語では 2-gram が一般的）
+pub const DEFAULT_NGRAM: usize = 2;
+
+/// テキストをトークン列に分割する
+///
+/// 組み込みの実装で足りない場合（形態素解析器など）はこのトレイトを実装して
+/// [`TextTokenizer::with_tokenizer`] に渡す。
+pub trait Tokenizer: Send + Sync {
+    /// 分析結果に記録する名前
+    fn name(&self) -> String;
+
+    /// トークンを出現順に返す
+    fn tokenize(&self, text: &str) -> Vec<String>;
+}
+
+/// Unicode の単語境界（UAX #29）で分割する
+///
+/// 句読点や空白だけのセグメントは捨てる。漢字・ひらがなは 1 文字ずつ、
+/// カタカナは連続した並びが 1 語になる。
+#[derive(Debug, Clone, Copy, Default)]
+pub struct UnicodeWordTokenizer;
+
+impl Tokenizer for UnicodeWordTokenizer {
+    fn name(&self) -> String {
+        "unicode-words".to_string()
+    }
+
+    fn tokenize(&self, text: &str) -> Vec<String> {
+        text.unicode_words().map(str::to_string).collect()
+    }
+}
+
+/// 空白で分割する（トークン化済みのテキスト向け）
+#[derive(Debug, Clone, Copy, Default)]
+pub struct WhitespaceTokenizer;
+
+impl Tokenizer for WhitespaceTokenizer {
+    fn name(&self) -> String {
+        "whitespace".to_string()
+    }
+
+    fn tokenize(&self, text: &str) -> Vec<String> {
+        text.split_whitespace().map(str::to_string).collect()
+    }
+}
+
+/// CJK 文字の連続を文字 n-gram に分割し、それ以外は UAX #29 の単語にする
+///
+/// 分かち書きのない日本語・中国語でも辞書なしで語彙を数えられる。
+/// n 文字に満たない CJK の並びはそのまま 1 トークンにする。
+#[derive(Debug, Clone, Copy)]
+pub struct CjkNgramTokenizer {
+    n: usize,
+}
+
+impl CjkNgramTokenizer {
+    pub fn new(n: usize) -> Result<Self> {
+        if n == 0 {
+            return Err(BenfError::InvalidInput(
+                "n-gram size must be at least 1".to_string(),
+            ));
+        }
+        Ok(CjkNgramTokenizer { n })
+    }
+
+    fn push_ngrams(&self, run: &[char], tokens: &mut Vec<String>) {
+        if run.len() <= self.n {
+            tokens.push(run.iter().collect());
+        } else {
+            tokens.extend(run.windows(self.n).map(|gram| gram.iter().collect()));
+        }
+    }
+}
+
+impl Default for CjkNgramTokenizer {
+    fn default() -> Self {
+        CjkNgramTokenizer { n: DEFAULT_NGRAM }
+    }
+}
+
+impl Tokenizer for CjkNgramTokenizer {
+    fn name(&self) -> String {
+        format!("cjk-ngram({})", self.n)
+    }
+
+    fn tokenize(&self, text: &str) -> Vec<String> {
+        let mut tokens = Vec::new();
+        let mut run: Vec<char> = Vec::new();
+        let mut segment_start = 0;
+
+        for (index, ch) in text.char_indices() {
+            if is_cjk(ch) {
+                if run.is_empty() {
+                    tokens.extend(
+                        text[segment_start..index]
+                            .unicode_words()
+                            .map(str::to_string),
+                    );
+                }
+                run.push(ch);
+            } else if !run.is_empty() {
+                self.push_ngrams(&run, &mut tokens);
+                run.clear();
+                segment_start = index;
+            }
+        }
+
+        if run.is_empty() {
+            tokens.extend(text[segment_start..].unicode_words().map(str::to_string));
+        } else {
+            self.push_ngrams(&run, &mut tokens);
+        }
+        tokens
+    }
+}
+
+/// 正規表現にマッチした部分をトークンにする
+#[derive(Debug, Clone)]
+pub struct RegexTokenizer {
+    pattern: Regex,
+}
+
+impl RegexTokenizer {
+    pub fn new(pattern: &str) -> Result<Self> {
+        let pattern = Regex::new(pattern)
+            .map_err(|e| BenfError::InvalidInput(format!("invalid token pattern: {e}")))?;
+        Ok(RegexTokenizer { pattern })
+    }
+}
+
+impl Tokenizer for RegexTokenizer {
+    fn name(&self) -> String {
+        format!("regex({})", self.pattern.as_str())
+    }
+
+    fn tokenize(&self, text: &str) -> Vec<String> {
+        self.pattern
+            .find_iter(text)
+            .map(|m| m.as_str().to_string())
+            .filter(|token| !token.is_empty())
+            .collect()
+    }
+}
+
+/// 漢字・ひらがな・カタカナ・ハングル
+fn is_cjk(ch: char) -> bool {
+    matches!(ch,
+        '\u{3005}' | '\u{3007}' |      // 々 〇
+        '\u{3040}'..='\u{309F}' |      // ひらがな
+        '\u{30A0}'..='\u{30FF}' |      // カタカナ
+        '\u{31F0}'..='\u{31FF}' |      // カタカナ拡張
+        '\u{3400}'..='\u{4DBF}' |      // CJK 統合漢字拡張 A
+        '\u{4E00}'..='\u{9FFF}' |      // CJK 統合漢字
+        '\u{AC00}'..='\u{D7AF}' |      // ハングル音節
+        '\u{F900}'..='\u{FAFF}' |      // CJK 互換漢字
+        '\u{FF66}'..='\u{FF9F}' |      // 半角カタカナ
+        '\u{20000}'..='\u{3134F}'      // CJK 統合漢字拡張 B〜G
+    )
+}
+
+/// 組み込みのトークナイザーを名前で選ぶ
+///
+/// `unicode`（既定）・`whitespace`・`cjk-ngram`・`regex` に対応する。
+/// `regex` には `pattern` が必要。
+pub fn builtin_tokenizer(
+    name: &str,
+    ngram: usize,
+    pattern: Option<&str>,
+) -> Result<Box<dyn Tokenizer>> {
+    match name {
+        "unicode" | "unicode-words" => Ok(Box::new(UnicodeWordTokenizer)),
+        "whitespace" => Ok(Box::new(WhitespaceTokenizer)),
+        "cjk-ngram" | "ngram" => Ok(Box::new(CjkNgramTokenizer::new(ngram)?)),
+        "regex" => {
+            let pattern = pattern.ok_or_else(|| {
+                BenfError::InvalidInput("regex tokenizer requires a token pattern".to_string())
+            })?;
+            Ok(Box::new(RegexTokenizer::new(pattern)?))
+        }
+        _ => Err(BenfError::InvalidInput(format!(
+            "unknown tokenizer '{name}' (expected unicode, whitespace, cjk-ngram or regex)"
+        ))),
+    }
+}
+
+/// トークン化の前後に適用する正規化とフィルタ
+#[derive(Debug, Clone, PartialEq)]
+pub struct TokenizerOptions {
+    /// トークンを小文字にそろえる
+    pub lowercase: bool,
+    /// 分割の前にテキストを NFKC 正規化する（全角英数字・半角カナの統一）
+    pub nfkc: bool,
+    /// 除外する語。トークンと同じ正規化をしてから比較する
+    pub stopwords: Vec<String>,
+    /// これより短いトークンを除外する（バイト数ではなく文字数）
+    pub min_length: usize,
+}
+
+impl Default for TokenizerOptions {
+    fn default() -> Self {
+        TokenizerOptions {
+            lowercase: true,
+            nfkc: false,
+            stopwords: Vec::new(),
+            min_length: 1,
+        }
+    }
+}
+
+/// 分析に使ったトークン化の設定（結果に記録する）
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct TokenizerInfo {
+    pub name: String,
+    pub lowercase: bool,
+    pub nfkc: bool,
+    pub stopwords: usize,
+    pub min_length: usize,
+}
+
+/// トークナイザーと正規化・フィルタを組み合わせたテキスト分割器
+pub struct TextTokenizer {
+    tokenizer: Box<dyn Tokenizer>,
+    options: TokenizerOptions,
+    stopwords: HashSet<String>,
+}
+
+impl TextTokenizer {
+    pub fn new(options: TokenizerOptions) -> Self {
+        Self::with_tokenizer(Box::new(UnicodeWordTokenizer), options)
+    }
+
+    pub fn with_tokenizer(tokenizer: Box<dyn Tokenizer>, options: TokenizerOptions) -> Self {
+        let mut text_tokenizer = TextTokenizer {
+            tokenizer,
+            options,
+            stopwords: HashSet::new(),
+        };
+        text_tokenizer.stopwords = text_tokenizer
+            .options
+            .stopwords
+            .iter()
+            .map(|word| text_tokenizer.normalize_token(word.trim()))
+            .filter(|word| !word.is_empty())
+            .collect();
+        text_tokenizer
+    }
+
+    /// テキストを正規化・分割し、フィルタを通ったトークンを出現順に返す
+    pub fn tokenize(&self, text: &str) -> Vec<String> {
+        let tokens = if self.options.nfkc {
+            self.tokenizer.tokenize(&text.nfkc().collect::<String>())
+        } else {
+            self.tokenizer.tokenize(text)
+        };
+        tokens
+            .into_iter()
+            .map(|token| {
+                if self.options.lowercase {
+                    token.to_lowercase()
+                } else {
+                    token
+                }
+            })
+            .filter(|token| {
+                token.chars().count() >= self.options.min_length.max(1)
+                    && !self.stopwords.contains(token)
+            })
+            .collect()
+    }
+
+    pub fn info(&self) -> TokenizerInfo {
+        TokenizerInfo {
+            name: self.tokenizer.name(),
+            lowercase: self.options.lowercase,
+            nfkc: self.options.nfkc,
+            stopwords: self.stopwords.len(),
+            min_length: self.options.min_length,
+        }
+    }
+
+    fn normalize_token(&self, token: &str) -> String {
+        let token: String = if self.options.nfkc {
+            token.nfkc().collect()
+        } else {
+            token.to_string()
+        };
+        if self.options.lowercase {
+            token.to_lowercase()
+        } else {
+            token
+        }
+    }
+}
+
+impl Default for TextTokenizer {
+    fn default() -> Self {
+        Self::new(TokenizerOptions::default())
+    }
+}
+
+/// ストップワードのリストを読む（空白・改行区切り、`#` 以降はコメント）
+pub fn parse_stopwords(text: &str) -> Vec<String> {
+    text.lines()
+        .map(|line| line.split('#').next().unwrap_or(""))
+        .flat_map(str::split_whitespace)
+        .map(str::to_string)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_unicode_words_keep_single_letter_tokens() {
+        let tokens = TextTokenizer::default().tokenize("I saw a cat, and I can't stop.");
+        assert_eq!(
+            tokens,
+            vec!["i", "saw", "a", "cat", "and", "i", "can't", "stop"]
+        );
+    }
+
+    #[test]
+    fn test_multilingual_tokenization() {
+        let tokens = UnicodeWordTokenizer.tokenize("Hello 世界 テスト мир مرحبا");
+        assert_eq!(tokens, vec!["Hello", "世", "界", "テスト", "мир", "مرحبا"]);
+    }
+
+    #[test]
+    fn test_cjk_ngrams() {
+        let tokenizer = CjkNgramTokenizer::new(2).unwrap();
+        assert_eq!(
+            tokenizer.tokenize("東京都に住む Tokyo人"),
+            vec!["東京", "京都", "都に", "に住", "住む", "Tokyo", "人"]
+        );
+        assert_eq!(
+            CjkNgramTokenizer::new(1).unwrap().tokenize("日本語"),
+            vec!["日", "本", "語"]
+        );
+        assert!(CjkNgramTokenizer::new(0).is_err());
+    }
+
+    #[test]
+    fn test_regex_and_whitespace_tokenizers() {
+        let tokenizer = RegexTokenizer::new(r"#\w+").unwrap();
+        assert_eq!(
+            tokenizer.tokenize("#rust is #fast, #rust"),
+            vec!["#rust", "#fast", "#rust"]
+        );
+        assert!(RegexTokenizer::new("(").is_err());
+        assert_eq!(
+            WhitespaceTokenizer.tokenize("a-b  c.d\n"),
+            vec!["a-b", "c.d"]
+        );
+        assert!(builtin_tokenizer("regex", DEFAULT_NGRAM, None).is_err());
+        assert!(builtin_tokenizer("mecab", DEFAULT_NGRAM, None).is_err());
+    }
+
+    #[test]
+    fn test_normalization_and_filters() {
+        let options = TokenizerOptions {
+            nfkc: true,
+            stopwords: parse_stopwords("THE # article\nof  a\n"),
+            min_length: 2,
+            ..TokenizerOptions::default()
+        };
+        let tokenizer = TextTokenizer::new(options);
+        // 全角英字は NFKC で半角になり、小文字化してからストップワードと比較される
+        assert_eq!(
+            tokenizer.tokenize("ＴＨＥ Ｒｕｓｔ of a x Book"),
+            vec!["rust", "book"]
+        );
+
+        let info = tokenizer.info();
+        assert_eq!(info.name, "unicode-words");
+        assert!(info.nfkc && info.lowercase);
+        assert_eq!(info.stopwords, 3);
+        assert_eq!(info.min_length, 2);
+
+        let case_sensitive = TextTokenizer::new(TokenizerOptions {
+            lowercase: false,
+            ..TokenizerOptions::default()
+        });
+        assert_eq!(case_sensitive.tokenize("The the"), vec!["The", "the"]);
+    }
+}
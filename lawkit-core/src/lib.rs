@@ -23,97 +23,70 @@ pub fn law(
     options: Option<&LawkitOptions>,
 ) -> Result<Vec<LawkitResult>> {
     let default_options = LawkitOptions::default();
-    let opts = options.unwrap_or(&default_options);
+    let opts = AnalysisSettings::from_options(options.unwrap_or(&default_options))?;
 
     match subcommand {
-        "benf" | "benford" => analyze_benford_law(data_or_config, opts),
-        "pareto" => analyze_pareto_principle(data_or_config, opts),
-        "zipf" => analyze_zipf_law(data_or_config, opts),
-        "normal" => analyze_normal_distribution(data_or_config, opts),
-        "poisson" => analyze_poisson_distribution(data_or_config, opts),
-        "analyze" => analyze_all_laws(data_or_config, opts),
-        "validate" => validate_data(data_or_config, opts),
-        "diagnose" => diagnose_data(data_or_config, opts),
-        "generate" => generate_sample_data(data_or_config, opts),
+        "benf" | "benford" => analyze_benford_law(data_or_config, &opts),
+        "pareto" => analyze_pareto_principle(data_or_config, &opts),
+        "zipf" => analyze_zipf_law(data_or_config, &opts),
+        "normal" => analyze_normal_distribution(data_or_config, &opts),
+        "poisson" => analyze_poisson_distribution(data_or_config, &opts),
+        "compare" => compare_two_datasets(data_or_config, &opts),
+        "analyze" => analyze_all_laws(data_or_config, &opts),
+        "validate" => validate_data(data_or_config, &opts),
+        "diagnose" => diagnose_data(data_or_config, &opts),
+        "generate" => generate_sample_data(data_or_config, &opts),
         _ => Err(anyhow!("Unknown subcommand: {subcommand}")),
     }
 }
 
-fn analyze_benford_law(data: &Value, _options: &LawkitOptions) -> Result<Vec<LawkitResult>> {
-    let numbers = extract_numbers_from_value(data)?;
+/// Run `law()` and render the results in `options.output_format` (default: lawkit)
+pub fn law_to_string(
+    subcommand: &str,
+    data_or_config: &Value,
+    options: Option<&LawkitOptions>,
+) -> Result<String> {
+    let results = law(subcommand, data_or_config, options)?;
+    let format = options
+        .and_then(|options| options.output_format)
+        .unwrap_or_default();
+    format_output(&results, format)
+}
+
+fn analyze_benford_law(data: &Value, options: &AnalysisSettings) -> Result<Vec<LawkitResult>> {
+    let numbers = extract_numbers_from_value(data, options)?;
 
     if numbers.is_empty() {
         return Err(anyhow!("No valid numbers found in input data"));
     }
+    options.check_sample_size(numbers.len())?;
 
-    // Calculate first digit distribution (counts)
-    let mut observed_counts = [0.0; 9];
-    let mut total = 0;
-
-    for &num in &numbers {
-        if let Some(digit) = get_first_digit(num.abs()) {
-            observed_counts[digit as usize - 1] += 1.0;
-            total += 1;
-        }
-    }
-
-    // Expected Benford proportions: P(d) = log10(1 + 1/d)
-    let expected_proportions = [
-        2.0_f64.log10(),               // log10(2) ≈ 0.301 for d=1
-        (1.0_f64 + 1.0 / 2.0).log10(), // log10(1.5) ≈ 0.176
-        (1.0_f64 + 1.0 / 3.0).log10(), // log10(1.333) ≈ 0.125
-        (1.0_f64 + 1.0 / 4.0).log10(), // log10(1.25) ≈ 0.097
-        (1.0_f64 + 1.0 / 5.0).log10(), // log10(1.2) ≈ 0.079
-        (1.0_f64 + 1.0 / 6.0).log10(), // log10(1.167) ≈ 0.067
-        (1.0_f64 + 1.0 / 7.0).log10(), // log10(1.143) ≈ 0.058
-        (1.0_f64 + 1.0 / 8.0).log10(), // log10(1.125) ≈ 0.051
-        (1.0_f64 + 1.0 / 9.0).log10(), // log10(1.111) ≈ 0.046
-    ];
-
-    // Convert proportions to counts for chi-square test
-    let total_f = total as f64;
-    let expected_counts: [f64; 9] = expected_proportions.map(|p| p * total_f);
-
-    // Convert observed counts to proportions for output
-    let observed: [f64; 9] = observed_counts.map(|c| c / total_f);
-
-    // Calculate chi-square test (using counts, not proportions)
-    let chi_square = calculate_chi_square(&observed_counts, &expected_counts);
-    let p_value = calculate_p_value(chi_square, 8);
-    let mad = calculate_mad(&observed, &expected_proportions);
-
-    // Determine risk level
-    let risk_level = if p_value < 0.05 {
-        "HIGH"
-    } else if p_value < 0.1 {
-        "MEDIUM"
-    } else {
-        "LOW"
+    // Count first digits here so batching/parallelism apply, then let the law build the result
+    let digit_counts = count_first_digits(&numbers, options);
+    let mut benford_result = BenfordResult::from_digit_counts(
+        "benford_analysis".to_string(),
+        numbers.len(),
+        &digit_counts,
+        &options.risk_threshold,
+        options.significance_level,
+    );
+    if let Some(tests) = &options.benford_digit_tests {
+        benford_result = benford_result.with_digit_tests(
+            &numbers,
+            tests,
+            &options.risk_threshold,
+            options.significance_level,
+        );
     }
-    .to_string();
-
-    let analysis_summary =
-        format!("Benford's law analysis: p-value={p_value:.4}, MAD={mad:.4}, risk={risk_level}");
-
-    let benford_data = BenfordData {
-        observed_distribution: observed,
-        expected_distribution: expected_proportions,
-        chi_square,
-        p_value,
-        mad,
-        risk_level,
-        total_numbers: total,
-        analysis_summary,
-    };
 
     Ok(vec![LawkitResult::BenfordAnalysis(
         "benford_analysis".to_string(),
-        benford_data,
+        benford_result,
     )])
 }
 
-fn analyze_pareto_principle(data: &Value, _options: &LawkitOptions) -> Result<Vec<LawkitResult>> {
-    let numbers = extract_numbers_from_value(data)?;
+fn analyze_pareto_principle(data: &Value, options: &AnalysisSettings) -> Result<Vec<LawkitResult>> {
+    let numbers = extract_numbers_from_value(data, options)?;
 
     if numbers.is_empty() {
         return Err(anyhow!("No valid numbers found in input data"));
@@ -121,64 +94,40 @@ fn analyze_pareto_principle(data: &Value, _options: &LawkitOptions) -> Result<Ve
 
     // Sort in descending order
     let mut sorted_numbers = numbers.clone();
-    sorted_numbers.sort_by(|a, b| b.partial_cmp(a).unwrap());
-
-    let total_sum: f64 = sorted_numbers.iter().sum();
-    let total_count = sorted_numbers.len();
-    let top_20_count = (total_count as f64 * 0.2).ceil() as usize;
-
-    let top_20_sum: f64 = sorted_numbers.iter().take(top_20_count).sum();
-    let top_20_percent_contribution = (top_20_sum / total_sum) * 100.0;
-
-    // Calculate Pareto ratio (how much the top 20% contributes)
-    let pareto_ratio = top_20_percent_contribution / 80.0; // Ideal is 1.0 (80/20 rule)
-
-    // Calculate concentration index (Gini-like measure)
-    let mut cumulative_sum = 0.0;
-    let mut concentration_index = 0.0;
-    for (i, &value) in sorted_numbers.iter().enumerate() {
-        cumulative_sum += value;
-        let proportion = (i + 1) as f64 / total_count as f64;
-        let cumulative_proportion = cumulative_sum / total_sum;
-        concentration_index += (proportion - cumulative_proportion).abs();
-    }
-    concentration_index /= total_count as f64;
-
-    // Determine risk level
-    let risk_level = if top_20_percent_contribution < 60.0 {
-        "LOW" // Not following Pareto principle
-    } else if top_20_percent_contribution > 95.0 {
-        "HIGH" // Extreme concentration
-    } else {
-        "MEDIUM"
+    sorted_numbers.sort_by(|a, b| b.total_cmp(a));
+    if let Some(limit) = options.pareto_category_limit {
+        sorted_numbers.truncate(limit);
     }
-    .to_string();
+    options.check_sample_size(sorted_numbers.len())?;
 
-    let analysis_summary = format!(
-        "Pareto analysis: top 20% contributes {top_20_percent_contribution:.1}%, concentration index={concentration_index:.3}, risk={risk_level}"
-    );
+    let mut pareto_result = ParetoResult::new("pareto_analysis".to_string(), &sorted_numbers)?;
 
-    let pareto_data = ParetoData {
-        top_20_percent_contribution,
-        pareto_ratio,
-        concentration_index,
-        risk_level,
-        total_items: total_count,
-        analysis_summary,
-    };
+    // A non-default ratio is reported as a custom percentile (0.9 -> top 10%)
+    if options.has_custom_pareto_ratio() {
+        let top_percent = (1.0 - options.pareto_ratio) * 100.0;
+        pareto_result = pareto_result.with_custom_percentiles(&[top_percent], &sorted_numbers);
+    }
+
+    if let Some(power_law) = &options.power_law {
+        pareto_result = pareto_result.with_power_law_fit(&sorted_numbers, power_law)?;
+    }
+    if let Some(inequality) = &options.inequality {
+        pareto_result = pareto_result.with_inequality_indices(&sorted_numbers, inequality)?;
+    }
 
     Ok(vec![LawkitResult::ParetoAnalysis(
         "pareto_analysis".to_string(),
-        pareto_data,
+        pareto_result,
     )])
 }
 
-fn analyze_zipf_law(data: &Value, _options: &LawkitOptions) -> Result<Vec<LawkitResult>> {
-    let numbers = extract_numbers_from_value(data)?;
+fn analyze_zipf_law(data: &Value, options: &AnalysisSettings) -> Result<Vec<LawkitResult>> {
+    let numbers = extract_numbers_from_value(data, options)?;
 
     if numbers.is_empty() {
         return Err(anyhow!("No valid numbers found in input data"));
     }
+    options.check_sample_size(numbers.len())?;
 
     // Count frequencies and sort by frequency (descending)
     let mut frequency_map: HashMap<String, f64> = HashMap::new();
@@ -188,250 +137,199 @@ fn analyze_zipf_law(data: &Value, _options: &LawkitOptions) -> Result<Vec<Lawkit
     }
 
     let mut frequencies: Vec<f64> = frequency_map.values().cloned().collect();
-    frequencies.sort_by(|a, b| b.partial_cmp(a).unwrap());
-
-    if frequencies.len() < 2 {
-        return Err(anyhow!("Insufficient unique values for Zipf analysis"));
+    frequencies.sort_by(|a, b| b.total_cmp(a));
+    if let Some(cutoff) = options.zipf_frequency_cutoff {
+        frequencies.retain(|&freq| freq >= cutoff);
     }
-
-    // Calculate Zipf coefficient (log-log slope)
-    let mut log_ranks: Vec<f64> = Vec::new();
-    let mut log_frequencies: Vec<f64> = Vec::new();
-
-    for (rank, &freq) in frequencies.iter().enumerate() {
-        if freq > 0.0 {
-            log_ranks.push((rank + 1) as f64);
-            log_frequencies.push(freq);
-        }
+    if let Some(limit) = options.zipf_rank_limit {
+        frequencies.truncate(limit);
     }
 
-    let zipf_coefficient = calculate_zipf_coefficient(&log_ranks, &log_frequencies);
-    let correlation = calculate_correlation(&log_ranks, &log_frequencies);
-    let deviation_score = (zipf_coefficient - 1.0).abs(); // Ideal Zipf has coefficient = -1
-
-    // Determine risk level
-    let risk_level = if deviation_score < 0.2 {
-        "LOW" // Close to ideal Zipf
-    } else if deviation_score > 0.8 {
-        "HIGH" // Far from Zipf distribution
-    } else {
-        "MEDIUM"
+    let mut zipf_result = ZipfResult::new("zipf_analysis".to_string(), &frequencies)?
+        .with_confidence_level(1.0 - options.significance_level)?;
+    if options.zipf_mandelbrot {
+        zipf_result = zipf_result.with_mandelbrot_fit(&frequencies)?;
+    }
+    if let Some(power_law) = &options.power_law {
+        zipf_result = zipf_result.with_power_law_fit(&frequencies, power_law)?;
     }
-    .to_string();
-
-    let analysis_summary = format!(
-        "Zipf analysis: coefficient={zipf_coefficient:.3}, correlation={correlation:.3}, deviation={deviation_score:.3}, risk={risk_level}"
-    );
-
-    let zipf_data = ZipfData {
-        zipf_coefficient,
-        correlation_coefficient: correlation,
-        deviation_score,
-        risk_level,
-        total_items: frequencies.len(),
-        analysis_summary,
-    };
 
     Ok(vec![LawkitResult::ZipfAnalysis(
         "zipf_analysis".to_string(),
-        zipf_data,
+        zipf_result,
     )])
 }
 
 fn analyze_normal_distribution(
     data: &Value,
-    _options: &LawkitOptions,
+    options: &AnalysisSettings,
 ) -> Result<Vec<LawkitResult>> {
-    let numbers = extract_numbers_from_value(data)?;
+    let numbers = extract_numbers_from_value(data, options)?;
 
     if numbers.is_empty() {
         return Err(anyhow!("No valid numbers found in input data"));
     }
+    options.check_sample_size(numbers.len())?;
 
-    if numbers.len() < 3 {
-        return Err(anyhow!(
-            "Insufficient data points for normal distribution analysis"
-        ));
-    }
-
-    // Calculate basic statistics
-    let mean = numbers.iter().sum::<f64>() / numbers.len() as f64;
-    let variance =
-        numbers.iter().map(|x| (x - mean).powi(2)).sum::<f64>() / (numbers.len() - 1) as f64;
-    let std_dev = variance.sqrt();
-
-    // Calculate skewness and kurtosis
-    let skewness = calculate_skewness(&numbers, mean, std_dev);
-    let kurtosis = calculate_kurtosis(&numbers, mean, std_dev);
-
-    // Simple normality test (Shapiro-Wilk approximation)
-    let normality_test_p = calculate_normality_p_value(&numbers);
-
-    // Determine risk level
-    let risk_level = if normality_test_p > 0.05 && skewness.abs() < 1.0 && kurtosis.abs() < 3.0 {
-        "LOW" // Likely normal
-    } else if normality_test_p < 0.01 || skewness.abs() > 2.0 || kurtosis.abs() > 5.0 {
-        "HIGH" // Clearly non-normal
-    } else {
-        "MEDIUM"
+    let mut normal_result = NormalResult::new_with_significance(
+        "normal_analysis".to_string(),
+        &numbers,
+        &options.risk_threshold,
+        options.requested_significance_level(),
+    )?;
+    if options.outlier_detection == Some(false) {
+        normal_result.outliers_z_score.clear();
+        normal_result.outliers_modified_z.clear();
+        normal_result.outliers_iqr.clear();
     }
-    .to_string();
-
-    let analysis_summary = format!(
-        "Normal distribution analysis: mean={mean:.3}, std={std_dev:.3}, skew={skewness:.3}, kurt={kurtosis:.3}, p={normality_test_p:.4}, risk={risk_level}"
-    );
-
-    let normal_data = NormalData {
-        mean,
-        std_dev,
-        skewness,
-        kurtosis,
-        normality_test_p,
-        risk_level,
-        total_numbers: numbers.len(),
-        analysis_summary,
-    };
 
     Ok(vec![LawkitResult::NormalAnalysis(
         "normal_analysis".to_string(),
-        normal_data,
+        normal_result,
     )])
 }
 
 fn analyze_poisson_distribution(
     data: &Value,
-    _options: &LawkitOptions,
+    options: &AnalysisSettings,
 ) -> Result<Vec<LawkitResult>> {
-    let numbers = extract_numbers_from_value(data)?;
+    let numbers = extract_numbers_from_value(data, options)?;
 
     if numbers.is_empty() {
         return Err(anyhow!("No valid numbers found in input data"));
     }
 
     // For Poisson analysis, we need non-negative integer values
-    let integers: Vec<u32> = numbers
-        .iter()
-        .filter_map(|&x| {
-            if x >= 0.0 && x.fract() == 0.0 {
-                Some(x as u32)
-            } else {
-                None
-            }
-        })
+    let event_counts: Vec<f64> = numbers
+        .into_iter()
+        .filter(|&x| x >= 0.0 && x.fract() == 0.0)
         .collect();
 
-    if integers.is_empty() {
+    if event_counts.is_empty() {
         return Err(anyhow!(
             "No valid non-negative integers found for Poisson analysis"
         ));
     }
+    options.check_sample_size(event_counts.len())?;
 
-    // Calculate lambda (mean rate)
-    let lambda = integers.iter().sum::<u32>() as f64 / integers.len() as f64;
-
-    // Calculate variance
-    let variance = integers
-        .iter()
-        .map(|&x| (x as f64 - lambda).powi(2))
-        .sum::<f64>()
-        / integers.len() as f64;
-
-    // For Poisson distribution, variance should equal lambda
-    let variance_ratio = variance / lambda;
-
-    // Simple Poisson test (variance-to-mean ratio)
-    let poisson_test_p = calculate_poisson_p_value(variance_ratio, integers.len());
-
-    // Determine risk level
-    let risk_level = if (variance_ratio - 1.0).abs() < 0.2 && poisson_test_p > 0.05 {
-        "LOW" // Likely Poisson
-    } else if (variance_ratio - 1.0).abs() > 0.8 || poisson_test_p < 0.01 {
-        "HIGH" // Clearly non-Poisson
-    } else {
-        "MEDIUM"
-    }
-    .to_string();
+    let poisson_result = PoissonResult::new_with_significance(
+        "poisson_analysis".to_string(),
+        &event_counts,
+        &options.risk_threshold,
+        options.requested_significance_level(),
+    )?;
 
-    let analysis_summary = format!(
-        "Poisson distribution analysis: lambda={lambda:.3}, var/mean={variance_ratio:.3}, p={poisson_test_p:.4}, risk={risk_level}"
-    );
+    Ok(vec![LawkitResult::PoissonAnalysis(
+        "poisson_analysis".to_string(),
+        poisson_result,
+    )])
+}
 
-    let poisson_data = PoissonData {
-        lambda,
-        variance_ratio,
-        poisson_test_p,
-        risk_level,
-        total_events: integers.len(),
-        analysis_summary,
+/// Compare two datasets under one law
+///
+/// Accepts `{"name_a": [...], "name_b": [...]}` or `[[...], [...]]` (named "a" and "b").
+fn compare_two_datasets(data: &Value, options: &AnalysisSettings) -> Result<Vec<LawkitResult>> {
+    let law = options.compare_law.ok_or_else(|| {
+        anyhow!("Comparison requires compare_law (benf, pareto, zipf, normal or poisson)")
+    })?;
+    let datasets: Vec<(String, &Value)> = match data {
+        Value::Object(map) if map.len() == 2 => {
+            map.iter().map(|(name, value)| (name.clone(), value)).collect()
+        }
+        Value::Array(items) if items.len() == 2 => vec![
+            ("a".to_string(), &items[0]),
+            ("b".to_string(), &items[1]),
+        ],
+        _ => {
+            return Err(anyhow!(
+                "Comparison needs exactly two datasets: an object with two keys or an array of two arrays"
+            ))
+        }
     };
 
-    Ok(vec![LawkitResult::PoissonAnalysis(
-        "poisson_analysis".to_string(),
-        poisson_data,
+    let mut samples = Vec::with_capacity(2);
+    for (name, value) in &datasets {
+        let numbers = extract_numbers_from_value(value, options)?;
+        if numbers.is_empty() {
+            return Err(anyhow!("No valid numbers found in dataset '{name}'"));
+        }
+        options.check_sample_size(numbers.len())?;
+        samples.push(numbers);
+    }
+
+    let comparison = crate::laws::compare::compare_datasets(
+        law,
+        &datasets[0].0,
+        &samples[0],
+        &datasets[1].0,
+        &samples[1],
+        options.significance_level,
+    )?;
+    Ok(vec![LawkitResult::ComparisonAnalysis(
+        "comparison_analysis".to_string(),
+        comparison,
     )])
 }
 
-fn analyze_all_laws(data: &Value, _options: &LawkitOptions) -> Result<Vec<LawkitResult>> {
+fn analyze_all_laws(data: &Value, options: &AnalysisSettings) -> Result<Vec<LawkitResult>> {
     let mut results = Vec::new();
     let mut laws_analyzed = Vec::new();
     let mut overall_risks = Vec::new();
 
     // Analyze all applicable laws
-    if let Ok(mut benford_results) = analyze_benford_law(data, _options) {
+    if let Ok(mut benford_results) = analyze_benford_law(data, options) {
         laws_analyzed.push("Benford".to_string());
         if let Some(LawkitResult::BenfordAnalysis(_, ref benford_data)) = benford_results.first() {
-            overall_risks.push(benford_data.risk_level.clone());
+            overall_risks.push(benford_data.risk_level);
         }
         results.append(&mut benford_results);
     }
 
-    if let Ok(mut pareto_results) = analyze_pareto_principle(data, _options) {
+    if let Ok(mut pareto_results) = analyze_pareto_principle(data, options) {
         laws_analyzed.push("Pareto".to_string());
         if let Some(LawkitResult::ParetoAnalysis(_, ref pareto_data)) = pareto_results.first() {
-            overall_risks.push(pareto_data.risk_level.clone());
+            overall_risks.push(pareto_data.risk_level);
         }
         results.append(&mut pareto_results);
     }
 
-    if let Ok(mut zipf_results) = analyze_zipf_law(data, _options) {
+    if let Ok(mut zipf_results) = analyze_zipf_law(data, options) {
         laws_analyzed.push("Zipf".to_string());
         if let Some(LawkitResult::ZipfAnalysis(_, ref zipf_data)) = zipf_results.first() {
-            overall_risks.push(zipf_data.risk_level.clone());
+            overall_risks.push(zipf_data.risk_level);
         }
         results.append(&mut zipf_results);
     }
 
-    if let Ok(mut normal_results) = analyze_normal_distribution(data, _options) {
+    if let Ok(mut normal_results) = analyze_normal_distribution(data, options) {
         laws_analyzed.push("Normal".to_string());
         if let Some(LawkitResult::NormalAnalysis(_, ref normal_data)) = normal_results.first() {
-            overall_risks.push(normal_data.risk_level.clone());
+            overall_risks.push(normal_data.risk_level);
         }
         results.append(&mut normal_results);
     }
 
-    if let Ok(mut poisson_results) = analyze_poisson_distribution(data, _options) {
+    if let Ok(mut poisson_results) = analyze_poisson_distribution(data, options) {
         laws_analyzed.push("Poisson".to_string());
         if let Some(LawkitResult::PoissonAnalysis(_, ref poisson_data)) = poisson_results.first() {
-            overall_risks.push(poisson_data.risk_level.clone());
+            overall_risks.push(poisson_data.risk_level);
         }
         results.append(&mut poisson_results);
     }
 
-    // Determine overall risk
-    let high_count = overall_risks.iter().filter(|&r| r == "HIGH").count();
-    let medium_count = overall_risks.iter().filter(|&r| r == "MEDIUM").count();
-
-    let overall_risk = if high_count > 0 {
-        "HIGH"
-    } else if medium_count > 0 {
-        "MEDIUM"
-    } else {
-        "LOW"
-    }
-    .to_string();
+    // Overall risk is the most severe individual risk
+    let overall_risk = overall_risks
+        .iter()
+        .copied()
+        .max()
+        .unwrap_or(RiskLevel::Low);
 
     // Generate recommendations
-    let recommendations = generate_recommendations(&laws_analyzed, &overall_risks);
+    let recommendations = if options.show_recommendations {
+        generate_recommendations(&laws_analyzed, &overall_risks)
+    } else {
+        Vec::new()
+    };
 
     let analysis_summary = format!(
         "Integrated analysis of {} laws completed. Overall risk: {overall_risk}",
@@ -454,8 +352,8 @@ fn analyze_all_laws(data: &Value, _options: &LawkitOptions) -> Result<Vec<Lawkit
     Ok(results)
 }
 
-fn validate_data(data: &Value, _options: &LawkitOptions) -> Result<Vec<LawkitResult>> {
-    let numbers = extract_numbers_from_value(data)?;
+fn validate_data(data: &Value, options: &AnalysisSettings) -> Result<Vec<LawkitResult>> {
+    let numbers = extract_numbers_from_value(data, options)?;
 
     let mut issues_found = Vec::new();
     let mut validation_passed = true;
@@ -467,9 +365,10 @@ fn validate_data(data: &Value, _options: &LawkitOptions) -> Result<Vec<LawkitRes
     }
 
     // Check for minimum sample size
-    if numbers.len() < 10 {
+    let min_sample_size = options.validation_sample_size();
+    if numbers.len() < min_sample_size {
         issues_found.push(format!(
-            "Small sample size: {} (recommended: 10+)",
+            "Small sample size: {} (recommended: {min_sample_size}+)",
             numbers.len()
         ));
         validation_passed = false;
@@ -516,8 +415,8 @@ fn validate_data(data: &Value, _options: &LawkitOptions) -> Result<Vec<LawkitRes
     )])
 }
 
-fn diagnose_data(data: &Value, _options: &LawkitOptions) -> Result<Vec<LawkitResult>> {
-    let numbers = extract_numbers_from_value(data)?;
+fn diagnose_data(data: &Value, options: &AnalysisSettings) -> Result<Vec<LawkitResult>> {
+    let numbers = extract_numbers_from_value(data, options)?;
 
     if numbers.is_empty() {
         return Err(anyhow!("No valid numbers found for diagnosis"));
@@ -529,7 +428,7 @@ fn diagnose_data(data: &Value, _options: &LawkitOptions) -> Result<Vec<LawkitRes
     let mean = numbers.iter().sum::<f64>() / numbers.len() as f64;
     let median = {
         let mut sorted = numbers.clone();
-        sorted.sort_by(|a, b| a.partial_cmp(b).unwrap());
+        sorted.sort_by(f64::total_cmp);
         sorted[sorted.len() / 2]
     };
 
@@ -555,21 +454,24 @@ fn diagnose_data(data: &Value, _options: &LawkitOptions) -> Result<Vec<LawkitRes
         findings.push("Distribution appears left-skewed".to_string());
     }
 
-    // Outlier detection
-    let q1 = calculate_percentile(&numbers, 0.25);
-    let q3 = calculate_percentile(&numbers, 0.75);
-    let iqr = q3 - q1;
-    let outlier_threshold_low = q1 - 1.5 * iqr;
-    let outlier_threshold_high = q3 + 1.5 * iqr;
-
-    let outliers: Vec<f64> = numbers
-        .iter()
-        .cloned()
-        .filter(|&x| x < outlier_threshold_low || x > outlier_threshold_high)
-        .collect();
+    if options.show_details {
+        let std_dev =
+            (numbers.iter().map(|x| (x - mean).powi(2)).sum::<f64>() / numbers.len() as f64).sqrt();
+        let q1 = calculate_percentile(&numbers, 0.25);
+        let q3 = calculate_percentile(&numbers, 0.75);
+        findings.push(format!("Standard deviation: {std_dev:.3}"));
+        findings.push(format!(
+            "Quartiles: Q1={q1:.3}, Q3={q3:.3} (IQR: {:.3})",
+            q3 - q1
+        ));
+    }
 
-    if !outliers.is_empty() {
-        findings.push(format!("Found {} potential outliers", outliers.len()));
+    // Outlier detection
+    if options.outlier_detection != Some(false) {
+        let outliers = detect_iqr_outliers(&numbers);
+        if !outliers.is_empty() {
+            findings.push(format!("Found {} potential outliers", outliers.len()));
+        }
     }
 
     let confidence_level = if numbers.len() >= 100 {
@@ -599,7 +501,7 @@ fn diagnose_data(data: &Value, _options: &LawkitOptions) -> Result<Vec<LawkitRes
     )])
 }
 
-fn generate_sample_data(config: &Value, _options: &LawkitOptions) -> Result<Vec<LawkitResult>> {
+fn generate_sample_data(config: &Value, options: &AnalysisSettings) -> Result<Vec<LawkitResult>> {
     use crate::generate::{
         BenfordGenerator, DataGenerator, GenerateConfig, NormalGenerator, ParetoGenerator,
         PoissonGenerator, ZipfGenerator,
@@ -611,8 +513,17 @@ fn generate_sample_data(config: &Value, _options: &LawkitOptions) -> Result<Vec<
         .and_then(|v| v.as_str())
         .unwrap_or("benford");
 
-    let count = config.get("count").and_then(|v| v.as_u64()).unwrap_or(1000) as usize;
-    let seed = config.get("seed").and_then(|v| v.as_u64());
+    // Explicit configuration wins over the generate_* options
+    let count = config
+        .get("count")
+        .and_then(|v| v.as_u64())
+        .map(|c| c as usize)
+        .or(options.generate_count)
+        .unwrap_or(1000);
+    let seed = config
+        .get("seed")
+        .and_then(|v| v.as_u64())
+        .or(options.generate_seed);
 
     let mut gen_config = GenerateConfig::new(count);
     if let Some(s) = seed {
@@ -622,10 +533,15 @@ fn generate_sample_data(config: &Value, _options: &LawkitOptions) -> Result<Vec<
     let mut parameters = HashMap::new();
     let sample_data = match data_type {
         "benford" | "benf" => {
-            let min_value = config.get("min").and_then(|v| v.as_f64()).unwrap_or(1.0);
+            let min_value = config
+                .get("min")
+                .and_then(|v| v.as_f64())
+                .or(options.generate_range_min)
+                .unwrap_or(1.0);
             let max_value = config
                 .get("max")
                 .and_then(|v| v.as_f64())
+                .or(options.generate_range_max)
                 .unwrap_or(100000.0);
             parameters.insert("min".to_string(), min_value);
             parameters.insert("max".to_string(), max_value);
@@ -698,7 +614,9 @@ use helpers::*;
 
 // Re-export parser and format functions for CLI and language bindings
 pub use helpers::format_output;
-pub use parsers::{parse_csv, parse_ini, parse_json, parse_toml, parse_xml, parse_yaml};
+pub use parsers::{
+    parse_csv, parse_ini, parse_json, parse_toml, parse_xml, parse_yaml, select_values,
+};
 
 // ============================================================================
 // MODULE DECLARATIONS - For CLI and external access
@@ -711,9 +629,6 @@ pub mod types;
 pub mod helpers;
 pub mod parsers;
 
-// Temporary diffx-core mock until diffx reboot is complete
-pub mod diffx_core_mock;
-
 pub mod common;
 pub mod core;
 pub mod error;
@@ -1,3 +1,4 @@
+use crate::common::input::selector::KeySelection;
 use anyhow::{anyhow, Result};
 use csv::ReaderBuilder;
 use serde_json::Value;
@@ -14,6 +15,16 @@ pub fn parse_json(content: &str) -> Result<Value> {
     serde_json::from_str(content).map_err(|e| anyhow!("JSON parse error: {e}"))
 }
 
+/// Keep only the values a JSONPath and key regexes select - FOR INTERNAL USE ONLY
+///
+/// Returns the selected numbers and strings as a flat array in document order, ready to
+/// pass to law(): `select_values(&parse_yaml(content)?, &selection)`.
+pub fn select_values(value: &Value, selection: &KeySelection) -> Value {
+    let mut selected = Vec::new();
+    selection.visit_json(value, &mut |_, leaf| selected.push(leaf.clone()));
+    Value::Array(selected)
+}
+
 /// Parse CSV content - FOR INTERNAL USE ONLY
 pub fn parse_csv(content: &str) -> Result<Value> {
     let mut reader = ReaderBuilder::new()
@@ -110,11 +121,11 @@ pub fn parse_ini(content: &str) -> Result<Value> {
 }
 
 /// Parse XML content - FOR INTERNAL USE ONLY
+///
+/// Elements become objects keyed by the root name, attributes `@name` keys, mixed
+/// text `#text`, and repeated elements arrays.
 pub fn parse_xml(content: &str) -> Result<Value> {
-    // Simple XML parser - for production use, consider using quick-xml
-    // This is a simplified implementation
-    Ok(Value::String(format!(
-        "XML parsing not fully implemented: {}",
-        content.len()
-    )))
+    let document = crate::common::input::formats::xml::XmlDocument::parse(content)
+        .map_err(|e| anyhow!("XML parse error: {e}"))?;
+    Ok(document.to_value())
 }
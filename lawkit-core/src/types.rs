@@ -9,26 +9,40 @@ use regex::Regex;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 
+pub use crate::common::risk::RiskLevel;
+pub use crate::laws::benford::{BenfordResult, Conformity, DigitTest, DigitTestResult};
+pub use crate::laws::compare::{ComparedLaw, ComparisonResult};
+pub use crate::laws::normal::NormalResult;
+pub use crate::laws::pareto::ParetoResult;
+pub use crate::laws::poisson::PoissonResult;
+pub use crate::laws::zipf::ZipfResult;
+
+/// Results of the unified API
+///
+/// The law variants carry the same result structs the CLI uses, so both report identical numbers.
 #[derive(Debug, PartialEq, Serialize)]
 pub enum LawkitResult {
     // Benford's law results
-    BenfordAnalysis(String, BenfordData),
+    BenfordAnalysis(String, BenfordResult),
 
     // Pareto principle results
-    ParetoAnalysis(String, ParetoData),
+    ParetoAnalysis(String, ParetoResult),
 
     // Zipf's law results
-    ZipfAnalysis(String, ZipfData),
+    ZipfAnalysis(String, ZipfResult),
 
     // Normal distribution results
-    NormalAnalysis(String, NormalData),
+    NormalAnalysis(String, NormalResult),
 
     // Poisson distribution results
-    PoissonAnalysis(String, PoissonData),
+    PoissonAnalysis(String, PoissonResult),
 
     // Integration analysis results
     IntegrationAnalysis(String, IntegrationData),
 
+    // Two-dataset comparison results
+    ComparisonAnalysis(String, ComparisonResult),
+
     // Validation results
     ValidationResult(String, ValidationData),
 
@@ -39,64 +53,10 @@ pub enum LawkitResult {
     GeneratedData(String, GeneratedDataInfo),
 }
 
-#[derive(Debug, PartialEq, Serialize)]
-pub struct BenfordData {
-    pub observed_distribution: [f64; 9],
-    pub expected_distribution: [f64; 9],
-    pub chi_square: f64,
-    pub p_value: f64,
-    pub mad: f64,
-    pub risk_level: String,
-    pub total_numbers: usize,
-    pub analysis_summary: String,
-}
-
-#[derive(Debug, PartialEq, Serialize)]
-pub struct ParetoData {
-    pub top_20_percent_contribution: f64,
-    pub pareto_ratio: f64,
-    pub concentration_index: f64,
-    pub risk_level: String,
-    pub total_items: usize,
-    pub analysis_summary: String,
-}
-
-#[derive(Debug, PartialEq, Serialize)]
-pub struct ZipfData {
-    pub zipf_coefficient: f64,
-    pub correlation_coefficient: f64,
-    pub deviation_score: f64,
-    pub risk_level: String,
-    pub total_items: usize,
-    pub analysis_summary: String,
-}
-
-#[derive(Debug, PartialEq, Serialize)]
-pub struct NormalData {
-    pub mean: f64,
-    pub std_dev: f64,
-    pub skewness: f64,
-    pub kurtosis: f64,
-    pub normality_test_p: f64,
-    pub risk_level: String,
-    pub total_numbers: usize,
-    pub analysis_summary: String,
-}
-
-#[derive(Debug, PartialEq, Serialize)]
-pub struct PoissonData {
-    pub lambda: f64,
-    pub variance_ratio: f64,
-    pub poisson_test_p: f64,
-    pub risk_level: String,
-    pub total_events: usize,
-    pub analysis_summary: String,
-}
-
 #[derive(Debug, PartialEq, Serialize)]
 pub struct IntegrationData {
     pub laws_analyzed: Vec<String>,
-    pub overall_risk: String,
+    pub overall_risk: RiskLevel,
     pub conflicting_results: Vec<String>,
     pub recommendations: Vec<String>,
     pub analysis_summary: String,
@@ -171,7 +131,7 @@ pub struct LawkitSpecificOptions {
     pub enable_outlier_detection: Option<bool>,
 
     // Benford-specific options
-    pub benford_digits: Option<String>, // "first", "second", "both"
+    pub benford_digits: Option<String>, // "first", "second", "first-two", ..., "both", "all"
     pub benford_base: Option<u32>,      // default 10
 
     // Pareto-specific options
@@ -181,6 +141,20 @@ pub struct LawkitSpecificOptions {
     // Zipf-specific options
     pub zipf_rank_limit: Option<usize>,
     pub zipf_frequency_cutoff: Option<f64>,
+    pub zipf_mandelbrot: Option<bool>, // also fit the Zipf–Mandelbrot distribution
+
+    // Power-law tail fit (pareto and zipf)
+    pub power_law_fit: Option<bool>,
+    pub power_law_bootstrap: Option<usize>, // default 100; 0 skips the p-value
+
+    // Inequality indices (pareto)
+    pub inequality_indices: Option<bool>,
+    pub inequality_bootstrap: Option<usize>, // default 200; 0 skips the Gini interval
+    pub atkinson_epsilons: Option<Vec<f64>>, // default [0.5, 1, 2]
+    pub top_share_percentiles: Option<Vec<f64>>, // default [1, 10, 20, 50]
+
+    // Comparison options (for compare subcommand)
+    pub compare_law: Option<String>, // "benf", "pareto", "zipf", "normal", "poisson"
 
     // Generation options (for generate subcommand)
     pub generate_count: Option<usize>,
@@ -200,8 +174,9 @@ pub struct LawkitSpecificOptions {
 #[derive(Debug, Clone, Default)]
 pub struct LawkitOptions {
     // Core options
-    pub ignore_keys_regex: Option<Regex>,
-    pub path_filter: Option<String>,
+    pub ignore_keys_regex: Option<Regex>, // Skip values under matching keys
+    pub include_keys_regex: Option<Regex>, // Only read values under matching keys
+    pub path_filter: Option<String>,      // JSONPath ("$.invoices[*].total") or dotted path
 
     // Output control
     pub output_format: Option<OutputFormat>,
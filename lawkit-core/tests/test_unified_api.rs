@@ -18,12 +18,12 @@ fn test_law_benford_analysis() {
     match &results[0] {
         LawkitResult::BenfordAnalysis(name, benford_data) => {
             assert_eq!(name, "benford_analysis");
-            assert_eq!(benford_data.observed_distribution.len(), 9);
+            assert_eq!(benford_data.digit_distribution.len(), 9);
             assert_eq!(benford_data.expected_distribution.len(), 9);
             assert!(benford_data.chi_square >= 0.0);
             assert!(benford_data.p_value >= 0.0 && benford_data.p_value <= 1.0);
-            assert!(benford_data.total_numbers > 0);
-            assert!(!benford_data.analysis_summary.is_empty());
+            assert!(benford_data.numbers_analyzed > 0);
+            assert!(!benford_data.verdict.is_empty());
         }
         _ => panic!("Expected BenfordAnalysis result"),
     }
@@ -39,13 +39,13 @@ fn test_law_pareto_analysis() {
     match &results[0] {
         LawkitResult::ParetoAnalysis(name, pareto_data) => {
             assert_eq!(name, "pareto_analysis");
-            assert!(pareto_data.top_20_percent_contribution > 0.0);
+            assert!(pareto_data.top_20_percent_share > 0.0);
             assert!(pareto_data.pareto_ratio > 0.0);
             assert!(pareto_data.concentration_index >= 0.0);
-            assert!(pareto_data.total_items > 0);
-            assert!(!pareto_data.analysis_summary.is_empty());
+            assert!(pareto_data.numbers_analyzed > 0);
+            assert!(!pareto_data.cumulative_distribution.is_empty());
             // Should be high contribution for compliant data
-            assert!(pareto_data.top_20_percent_contribution > 60.0);
+            assert!(pareto_data.top_20_percent_share > 60.0);
         }
         _ => panic!("Expected ParetoAnalysis result"),
     }
@@ -61,14 +61,14 @@ fn test_law_zipf_analysis() {
     match &results[0] {
         LawkitResult::ZipfAnalysis(name, zipf_data) => {
             assert_eq!(name, "zipf_analysis");
-            assert!(zipf_data.zipf_coefficient != 0.0);
+            assert!(zipf_data.zipf_exponent != 0.0);
             assert!(
                 zipf_data.correlation_coefficient >= -1.0
                     && zipf_data.correlation_coefficient <= 1.0
             );
-            assert!(zipf_data.deviation_score >= 0.0);
-            assert!(zipf_data.total_items > 0);
-            assert!(!zipf_data.analysis_summary.is_empty());
+            assert!(zipf_data.distribution_quality >= 0.0);
+            assert!(zipf_data.unique_items > 0);
+            assert!(zipf_data.total_observations > 0);
         }
         _ => panic!("Expected ZipfAnalysis result"),
     }
@@ -85,9 +85,10 @@ fn test_law_normal_analysis() {
         LawkitResult::NormalAnalysis(name, normal_data) => {
             assert_eq!(name, "normal_analysis");
             assert!(normal_data.std_dev > 0.0);
-            assert!(normal_data.normality_test_p >= 0.0 && normal_data.normality_test_p <= 1.0);
-            assert!(normal_data.total_numbers > 0);
-            assert!(!normal_data.analysis_summary.is_empty());
+            let shapiro_p = normal_data.shapiro_wilk_p_value.unwrap();
+            assert!((0.0..=1.0).contains(&shapiro_p));
+            assert!(normal_data.numbers_analyzed > 0);
+            assert!(normal_data.normality_score >= 0.0);
         }
         _ => panic!("Expected NormalAnalysis result"),
     }
@@ -105,9 +106,11 @@ fn test_law_poisson_analysis() {
             assert_eq!(name, "poisson_analysis");
             assert!(poisson_data.lambda > 0.0);
             assert!(poisson_data.variance_ratio > 0.0);
-            assert!(poisson_data.poisson_test_p >= 0.0 && poisson_data.poisson_test_p <= 1.0);
-            assert!(poisson_data.total_events > 0);
-            assert!(!poisson_data.analysis_summary.is_empty());
+            assert!(
+                poisson_data.chi_square_p_value >= 0.0 && poisson_data.chi_square_p_value <= 1.0
+            );
+            assert!(poisson_data.numbers_analyzed > 0);
+            assert!(!poisson_data.frequency_distribution.is_empty());
         }
         _ => panic!("Expected PoissonAnalysis result"),
     }
@@ -186,7 +189,20 @@ fn test_law_analyze_all() {
         LawkitResult::IntegrationAnalysis(name, integration_data) => {
             assert_eq!(name, "integration_analysis");
             assert!(!integration_data.laws_analyzed.is_empty());
-            assert!(!integration_data.overall_risk.is_empty());
+            // Overall risk is the most severe individual risk
+            let max_risk = results
+                .iter()
+                .filter_map(|r| match r {
+                    LawkitResult::BenfordAnalysis(_, d) => Some(d.risk_level),
+                    LawkitResult::ParetoAnalysis(_, d) => Some(d.risk_level),
+                    LawkitResult::ZipfAnalysis(_, d) => Some(d.risk_level),
+                    LawkitResult::NormalAnalysis(_, d) => Some(d.risk_level),
+                    LawkitResult::PoissonAnalysis(_, d) => Some(d.risk_level),
+                    _ => None,
+                })
+                .max()
+                .unwrap();
+            assert_eq!(integration_data.overall_risk, max_risk);
             assert!(!integration_data.recommendations.is_empty());
             assert!(!integration_data.analysis_summary.is_empty());
         }
@@ -219,7 +235,7 @@ fn test_benford_risk_levels() {
     match &results[0] {
         LawkitResult::BenfordAnalysis(_, benford_data) => {
             // This might be LOW or MEDIUM depending on exact data, but shouldn't be HIGH for compliant data
-            assert!(benford_data.risk_level == "LOW" || benford_data.risk_level == "MEDIUM");
+            assert!(benford_data.risk_level <= RiskLevel::Medium);
         }
         _ => panic!("Expected BenfordAnalysis result"),
     }
@@ -231,12 +247,34 @@ fn test_benford_risk_levels() {
     match &results[0] {
         LawkitResult::BenfordAnalysis(_, benford_data) => {
             // Non-compliant data should show higher risk
-            assert!(benford_data.risk_level == "MEDIUM" || benford_data.risk_level == "HIGH");
+            assert!(benford_data.risk_level >= RiskLevel::Medium);
         }
         _ => panic!("Expected BenfordAnalysis result"),
     }
 }
 
+#[test]
+fn test_results_match_law_modules() {
+    // law() and the CLI share the laws::* result structs, so the numbers are identical
+    let numbers: Vec<f64> = (1..=200).map(|i| (i * i) as f64 * 1.7).collect();
+    let data = json!(numbers);
+
+    let results = law("benford", &data, None).unwrap();
+    let expected = BenfordResult::new("benford_analysis".to_string(), &numbers).unwrap();
+    assert_eq!(benford_data(&results), &expected);
+
+    let results = law("normal", &data, None).unwrap();
+    let expected = NormalResult::new("normal_analysis".to_string(), &numbers).unwrap();
+    assert_eq!(
+        results[0],
+        LawkitResult::NormalAnalysis("normal_analysis".to_string(), expected)
+    );
+
+    let json = serde_json::to_value(&results[0]).unwrap();
+    assert!(json["NormalAnalysis"][1]["shapiro_wilk_p_value"].is_number());
+    assert!(json["NormalAnalysis"][1]["risk_level"].is_string());
+}
+
 #[test]
 fn test_pareto_principle_compliance() {
     // Test compliant data
@@ -246,7 +284,7 @@ fn test_pareto_principle_compliance() {
     match &results[0] {
         LawkitResult::ParetoAnalysis(_, pareto_data) => {
             // Should show strong Pareto principle (top 20% contributes significantly)
-            assert!(pareto_data.top_20_percent_contribution > 60.0);
+            assert!(pareto_data.top_20_percent_share > 60.0);
             assert!(pareto_data.concentration_index > 0.0);
         }
         _ => panic!("Expected ParetoAnalysis result"),
@@ -259,7 +297,7 @@ fn test_pareto_principle_compliance() {
     match &results[0] {
         LawkitResult::ParetoAnalysis(_, pareto_data) => {
             // Uniform data should not follow Pareto principle
-            assert!(pareto_data.top_20_percent_contribution < 60.0);
+            assert!(pareto_data.top_20_percent_share < 60.0);
         }
         _ => panic!("Expected ParetoAnalysis result"),
     }
@@ -269,17 +307,18 @@ fn test_pareto_principle_compliance() {
 fn test_normal_distribution_detection() {
     // Test normal data
     let normal_data = TestFixtures::normal_distribution_data();
-    let results = law("normal", &normal_data, None).unwrap();
+    let results = law("normal", &normal_data["normal_sample"], None).unwrap();
 
-    match &results[0] {
+    let normal_risk = match &results[0] {
         LawkitResult::NormalAnalysis(_, normal_analysis) => {
             // Should show signs of normality
             assert!(normal_analysis.skewness.abs() < 2.0); // Not too skewed
             assert!(normal_analysis.std_dev > 0.0);
-            assert!(normal_analysis.risk_level == "LOW" || normal_analysis.risk_level == "MEDIUM");
+            assert!(normal_analysis.risk_level < RiskLevel::Critical);
+            normal_analysis.risk_level
         }
         _ => panic!("Expected NormalAnalysis result"),
-    }
+    };
 
     // Test non-normal data
     let non_normal_data = TestFixtures::non_normal_distribution_data();
@@ -288,7 +327,8 @@ fn test_normal_distribution_detection() {
     match &results[0] {
         LawkitResult::NormalAnalysis(_, normal_analysis) => {
             // Skewed data should show deviation from normality
-            assert!(normal_analysis.risk_level == "MEDIUM" || normal_analysis.risk_level == "HIGH");
+            assert!(normal_analysis.risk_level >= RiskLevel::Medium);
+            assert!(normal_analysis.risk_level > normal_risk);
         }
         _ => panic!("Expected NormalAnalysis result"),
     }
@@ -306,9 +346,7 @@ fn test_poisson_distribution_detection() {
             assert!(poisson_analysis.lambda > 0.0);
             assert!(poisson_analysis.variance_ratio > 0.0);
             // For Poisson, variance should approximately equal mean
-            assert!(
-                poisson_analysis.risk_level == "LOW" || poisson_analysis.risk_level == "MEDIUM"
-            );
+            assert!(poisson_analysis.risk_level <= RiskLevel::Medium);
         }
         _ => panic!("Expected PoissonAnalysis result"),
     }
@@ -320,9 +358,7 @@ fn test_poisson_distribution_detection() {
     match &results[0] {
         LawkitResult::PoissonAnalysis(_, poisson_analysis) => {
             // High variance data should deviate from Poisson
-            assert!(
-                poisson_analysis.risk_level == "MEDIUM" || poisson_analysis.risk_level == "HIGH"
-            );
+            assert!(poisson_analysis.risk_level >= RiskLevel::Medium);
         }
         _ => panic!("Expected PoissonAnalysis result"),
     }
@@ -356,7 +392,7 @@ fn test_lawkit_specific_options() {
     // Should still work with options
     match &results[0] {
         LawkitResult::BenfordAnalysis(_, benford_data) => {
-            assert!(benford_data.total_numbers > 0);
+            assert!(benford_data.numbers_analyzed > 0);
         }
         _ => panic!("Expected BenfordAnalysis result"),
     }
@@ -383,7 +419,7 @@ fn test_benford_specific_options() {
     match &results[0] {
         LawkitResult::BenfordAnalysis(_, benford_data) => {
             // Should analyze first digits (9 digits: 1-9)
-            assert_eq!(benford_data.observed_distribution.len(), 9);
+            assert_eq!(benford_data.digit_distribution.len(), 9);
             assert_eq!(benford_data.expected_distribution.len(), 9);
         }
         _ => panic!("Expected BenfordAnalysis result"),
@@ -410,13 +446,789 @@ fn test_pareto_specific_options() {
     assert_eq!(results.len(), 1);
     match &results[0] {
         LawkitResult::ParetoAnalysis(_, pareto_data) => {
-            assert!(pareto_data.total_items > 0);
-            assert!(pareto_data.top_20_percent_contribution > 0.0);
+            assert!(pareto_data.numbers_analyzed > 0);
+            assert!(pareto_data.top_20_percent_share > 0.0);
+        }
+        _ => panic!("Expected ParetoAnalysis result"),
+    }
+}
+
+#[test]
+fn test_power_law_fit_option() {
+    let data = TestFixtures::pareto_compliant_data();
+
+    let results = law("pareto", &data, None).unwrap();
+    match &results[0] {
+        LawkitResult::ParetoAnalysis(_, pareto_data) => assert!(pareto_data.power_law.is_none()),
+        _ => panic!("Expected ParetoAnalysis result"),
+    }
+
+    let options = options_with(LawkitSpecificOptions {
+        power_law_bootstrap: Some(10),
+        ..Default::default()
+    });
+    let results = law("pareto", &data, Some(&options)).unwrap();
+    match &results[0] {
+        LawkitResult::ParetoAnalysis(_, pareto_data) => {
+            let fit = pareto_data.power_law.as_ref().unwrap();
+            assert!(fit.alpha > 1.0);
+            assert!(fit.tail_size >= 10);
+            assert_eq!(fit.bootstrap_samples, 10);
+            assert_eq!(fit.comparisons.len(), 3);
         }
         _ => panic!("Expected ParetoAnalysis result"),
     }
 }
 
+#[test]
+fn test_inequality_indices_option() {
+    let data = TestFixtures::pareto_compliant_data();
+
+    let options = options_with(LawkitSpecificOptions {
+        inequality_indices: Some(true),
+        inequality_bootstrap: Some(50),
+        top_share_percentiles: Some(vec![20.0]),
+        confidence_level: Some(0.9),
+        ..Default::default()
+    });
+    let results = law("pareto", &data, Some(&options)).unwrap();
+    match &results[0] {
+        LawkitResult::ParetoAnalysis(_, pareto_data) => {
+            let indices = pareto_data.inequality.as_ref().unwrap();
+            // concentration_index と同じジニ係数
+            assert!((indices.gini - pareto_data.concentration_index).abs() < 1e-12);
+            let interval = indices.gini_confidence_interval.as_ref().unwrap();
+            assert!((interval.level - 0.9).abs() < 1e-12);
+            assert_eq!(indices.atkinson.len(), 3);
+            assert_eq!(indices.top_shares.len(), 1);
+            assert!(indices.top_shares[0].share > 0.0);
+        }
+        _ => panic!("Expected ParetoAnalysis result"),
+    }
+}
+
+#[test]
+fn test_zipf_maximum_likelihood_fits() {
+    // 値 r が 1000/r 回ずつ現れる（s = 1 の Zipf 分布）
+    let numbers: Vec<Value> = (1..=20)
+        .flat_map(|r| std::iter::repeat(json!(r)).take(1000 / r))
+        .collect();
+    let data = json!(numbers);
+
+    let results = law("zipf", &data, None).unwrap();
+    match &results[0] {
+        LawkitResult::ZipfAnalysis(_, zipf_data) => {
+            assert_eq!(zipf_data.zipf_fit.confidence_interval.level, 0.95);
+            assert!(zipf_data.zipf_mandelbrot.is_none());
+        }
+        _ => panic!("Expected ZipfAnalysis result"),
+    }
+
+    let options = options_with(LawkitSpecificOptions {
+        zipf_mandelbrot: Some(true),
+        confidence_level: Some(0.9),
+        ..Default::default()
+    });
+    let results = law("zipf", &data, Some(&options)).unwrap();
+    match &results[0] {
+        LawkitResult::ZipfAnalysis(_, zipf_data) => {
+            let fit = &zipf_data.zipf_fit;
+            assert!((fit.exponent - 1.0).abs() < 0.02, "s = {}", fit.exponent);
+            assert!((fit.confidence_interval.level - 0.9).abs() < 1e-12);
+            assert!(fit.confidence_interval.lower < fit.exponent);
+            assert!(fit.exponent < fit.confidence_interval.upper);
+            let mandelbrot = zipf_data.zipf_mandelbrot.as_ref().unwrap();
+            assert!(mandelbrot.q > -1.0);
+            assert!(mandelbrot.log_likelihood >= fit.log_likelihood);
+        }
+        _ => panic!("Expected ZipfAnalysis result"),
+    }
+}
+
+#[test]
+fn test_law_compare_datasets() {
+    let baseline: Vec<f64> = (0..200).map(|i| ((i * 37) % 200) as f64 / 20.0).collect();
+    let shifted: Vec<f64> = baseline.iter().map(|v| v + 5.0).collect();
+    let data = json!({"before": baseline, "after": shifted});
+
+    let options = options_with(LawkitSpecificOptions {
+        compare_law: Some("normal".to_string()),
+        ..Default::default()
+    });
+    let results = law("compare", &data, Some(&options)).unwrap();
+    match &results[0] {
+        LawkitResult::ComparisonAnalysis(_, comparison) => {
+            assert_eq!(comparison.law, ComparedLaw::Normal);
+            // オブジェクトのキーは名前順
+            assert_eq!(comparison.dataset_a, "after");
+            assert_eq!(comparison.dataset_b, "before");
+            assert!(comparison.significant);
+            assert!(comparison.effect_sizes[0].value < -1.0);
+            assert_eq!(comparison.risk_level, RiskLevel::Critical);
+        }
+        _ => panic!("Expected ComparisonAnalysis result"),
+    }
+
+    // 配列 2 つなら "a" と "b"、同じデータなら差はない
+    let same = json!([baseline, baseline]);
+    let results = law("compare", &same, Some(&options)).unwrap();
+    match &results[0] {
+        LawkitResult::ComparisonAnalysis(_, comparison) => {
+            assert_eq!(comparison.dataset_a, "a");
+            assert!(!comparison.significant);
+            assert_eq!(comparison.risk_level, RiskLevel::Low);
+        }
+        _ => panic!("Expected ComparisonAnalysis result"),
+    }
+
+    assert!(law("compare", &data, None).is_err());
+    assert!(law("compare", &json!([baseline]), Some(&options)).is_err());
+}
+
+fn options_with(lawkit_options: LawkitSpecificOptions) -> LawkitOptions {
+    LawkitOptions {
+        lawkit_options: Some(lawkit_options),
+        ..Default::default()
+    }
+}
+
+fn benford_data(results: &[LawkitResult]) -> &BenfordResult {
+    match &results[0] {
+        LawkitResult::BenfordAnalysis(_, benford_data) => benford_data,
+        _ => panic!("Expected BenfordAnalysis result"),
+    }
+}
+
+/// Digits 1-9 with a mild excess of 9s, so the p-value sits between the usual cut-offs
+fn mildly_deviating_data() -> Value {
+    let mut numbers = Vec::new();
+    for (digit, count) in [
+        (1, 60),
+        (2, 35),
+        (3, 25),
+        (4, 19),
+        (5, 16),
+        (6, 13),
+        (7, 12),
+        (8, 10),
+        (9, 22),
+    ] {
+        for i in 0..count {
+            numbers.push(digit as f64 * 100.0 + i as f64);
+        }
+    }
+    json!(numbers)
+}
+
+#[test]
+fn test_significance_level_option() {
+    let data = mildly_deviating_data();
+    let p_value = benford_data(&law("benford", &data, None).unwrap()).p_value;
+    assert!(p_value > 0.001 && p_value < 0.5, "p = {p_value}");
+
+    let strict = options_with(LawkitSpecificOptions {
+        significance_level: Some(p_value / 4.0),
+        ..Default::default()
+    });
+    let lenient = options_with(LawkitSpecificOptions {
+        significance_level: Some((p_value * 2.0).min(0.99)),
+        ..Default::default()
+    });
+
+    let strict_results = law("benford", &data, Some(&strict)).unwrap();
+    let lenient_results = law("benford", &data, Some(&lenient)).unwrap();
+    assert_eq!(benford_data(&strict_results).risk_level, RiskLevel::Low);
+    assert_eq!(benford_data(&lenient_results).risk_level, RiskLevel::High);
+}
+
+#[test]
+fn test_significance_level_option_for_normal_and_poisson() {
+    let normal_sample = TestFixtures::normal_distribution_data()["normal_sample"].clone();
+    let poisson_sample = TestFixtures::poisson_distribution_data();
+    let strict = options_with(LawkitSpecificOptions {
+        significance_level: Some(1e-9),
+        ..Default::default()
+    });
+    let lenient = options_with(LawkitSpecificOptions {
+        significance_level: Some(0.99),
+        ..Default::default()
+    });
+
+    let normal_risk =
+        |options: &LawkitOptions| match &law("normal", &normal_sample, Some(options)).unwrap()[0] {
+            LawkitResult::NormalAnalysis(_, normal_data) => normal_data.risk_level,
+            _ => panic!("Expected NormalAnalysis result"),
+        };
+    assert!(normal_risk(&strict) < RiskLevel::High);
+    assert!(normal_risk(&lenient) >= RiskLevel::High);
+
+    let poisson_risk =
+        |options: &LawkitOptions| match &law("poisson", &poisson_sample, Some(options)).unwrap()[0]
+        {
+            LawkitResult::PoissonAnalysis(_, poisson_data) => poisson_data.risk_level,
+            _ => panic!("Expected PoissonAnalysis result"),
+        };
+    assert!(poisson_risk(&strict) < RiskLevel::High);
+    assert!(poisson_risk(&lenient) >= RiskLevel::High);
+
+    // confidence_level is the same setting expressed as 1 - α
+    let confident = options_with(LawkitSpecificOptions {
+        confidence_level: Some(0.01),
+        ..Default::default()
+    });
+    assert_eq!(normal_risk(&confident), normal_risk(&lenient));
+
+    // Without a significance level the risk is the shape-based one of PoissonResult::new
+    let event_counts = &poisson_sample["event_counts"];
+    let counts: Vec<f64> = event_counts
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|v| v.as_f64().unwrap())
+        .collect();
+    let shape_risk = laws::poisson::PoissonResult::new("poisson".to_string(), &counts)
+        .unwrap()
+        .risk_level;
+    let results = law("poisson", event_counts, None).unwrap();
+    let LawkitResult::PoissonAnalysis(_, poisson_data) = &results[0] else {
+        panic!("Expected PoissonAnalysis result");
+    };
+    assert_eq!(poisson_data.risk_level, shape_risk);
+}
+
+#[test]
+fn test_confidence_level_option() {
+    let data = mildly_deviating_data();
+    let p_value = benford_data(&law("benford", &data, None).unwrap()).p_value;
+
+    let options = options_with(LawkitSpecificOptions {
+        confidence_level: Some(1.0 - (p_value * 2.0).min(0.99)),
+        ..Default::default()
+    });
+    let results = law("benford", &data, Some(&options)).unwrap();
+    assert_eq!(benford_data(&results).risk_level, RiskLevel::High);
+
+    // An explicit significance level takes precedence
+    let options = options_with(LawkitSpecificOptions {
+        confidence_level: Some(1.0 - (p_value * 2.0).min(0.99)),
+        significance_level: Some(p_value / 4.0),
+        ..Default::default()
+    });
+    let results = law("benford", &data, Some(&options)).unwrap();
+    assert_eq!(benford_data(&results).risk_level, RiskLevel::Low);
+}
+
+#[test]
+fn test_risk_threshold_option() {
+    let data = mildly_deviating_data();
+    let p_value = benford_data(&law("benford", &data, None).unwrap()).p_value;
+
+    let flagged = options_with(LawkitSpecificOptions {
+        risk_threshold: Some(format!("{}", (p_value * 2.0).min(1.0))),
+        ..Default::default()
+    });
+    let results = law("benford", &data, Some(&flagged)).unwrap();
+    assert_eq!(benford_data(&results).risk_level, RiskLevel::Critical);
+
+    let not_flagged = options_with(LawkitSpecificOptions {
+        risk_threshold: Some(format!("{}", p_value / 2.0)),
+        ..Default::default()
+    });
+    let results = law("benford", &data, Some(&not_flagged)).unwrap();
+    assert_eq!(benford_data(&results).risk_level, RiskLevel::Low);
+
+    let invalid = options_with(LawkitSpecificOptions {
+        risk_threshold: Some("extreme".to_string()),
+        ..Default::default()
+    });
+    assert!(law("benford", &data, Some(&invalid)).is_err());
+}
+
+#[test]
+fn test_mad_conformity_threshold() {
+    let data = TestFixtures::benford_compliant_data();
+    let options = options_with(LawkitSpecificOptions {
+        risk_threshold: Some("mad".to_string()),
+        benford_digits: Some("first,second".to_string()),
+        ..Default::default()
+    });
+    let results = law("benford", &data, Some(&options)).unwrap();
+    let benford = benford_data(&results);
+
+    assert_eq!(
+        benford.conformity,
+        DigitTest::First.conformity(benford.mean_absolute_deviation)
+    );
+    for test in &benford.digit_tests {
+        assert_eq!(test.risk_level, test.conformity.risk_level());
+    }
+    let worst = benford.digit_tests.iter().map(|t| t.risk_level).max();
+    assert_eq!(Some(benford.risk_level), worst);
+}
+
+#[test]
+fn test_digit_z_statistics() {
+    let data = mildly_deviating_data();
+    let results = law("benford", &data, None).unwrap();
+    let benford = benford_data(&results);
+
+    assert_eq!(benford.z_statistics.len(), 9);
+    let nine = &benford.z_statistics[8];
+    assert_eq!(nine.bin, "9");
+    assert!(nine.z_statistic > 3.0);
+    assert!(nine.significant);
+
+    // A stricter significance level stops flagging the same digit
+    let strict = options_with(LawkitSpecificOptions {
+        significance_level: Some(1e-6),
+        ..Default::default()
+    });
+    let results = law("benford", &data, Some(&strict)).unwrap();
+    let strict_nine = &benford_data(&results).z_statistics[8];
+    assert_eq!(strict_nine.z_statistic, nine.z_statistic);
+    assert!(!strict_nine.significant);
+}
+
+#[test]
+fn test_benford_drill_down_points_to_lines() {
+    use lawkit_core::common::input::parse_text_records;
+    use lawkit_core::laws::benford::BenfordResult;
+
+    let mut text = String::new();
+    for i in 0..200 {
+        // Every fourth line starts with 9, far above Benford's 4.6%
+        let value = if i % 4 == 0 { 900 + i } else { 100 + i * 7 };
+        text.push_str(&format!("invoice {i}: {value}\n"));
+    }
+    let records = parse_text_records(&text).unwrap();
+    let numbers: Vec<f64> = records.iter().map(|r| r.value).collect();
+
+    let result = BenfordResult::new("lines".to_string(), &numbers)
+        .unwrap()
+        .with_drill_down(&records);
+    let nines = result
+        .drill_down
+        .iter()
+        .find(|d| d.test == DigitTest::First && d.bin == "9")
+        .expect("digit 9 should be flagged");
+
+    assert!(!nines.records.is_empty());
+    for record in &nines.records {
+        let line = record.source.line.unwrap();
+        assert_eq!(text.lines().nth(line - 1).unwrap(), record.text);
+        assert!(record.text.contains(&record.value.to_string()));
+    }
+}
+
+#[test]
+fn test_outliers_carry_csv_provenance() {
+    use lawkit_core::common::input::formats::csv::parse_csv_records;
+    use lawkit_core::common::outliers::detect_outliers_ensemble;
+    use lawkit_core::laws::normal::{detect_outliers, OutlierDetectionMethod};
+
+    let mut csv = String::from("id,amount\n");
+    for i in 1..=30 {
+        let amount = if i == 17 { 98765.0 } else { 100.0 + i as f64 };
+        csv.push_str(&format!("{i},{amount}\n"));
+    }
+    let records: Vec<_> = parse_csv_records(&csv)
+        .unwrap()
+        .into_iter()
+        .filter(|r| r.source.header.as_deref() == Some("amount"))
+        .collect();
+    let numbers: Vec<f64> = records.iter().map(|r| r.value).collect();
+
+    let result = detect_outliers(&numbers, OutlierDetectionMethod::ZScore)
+        .unwrap()
+        .with_records(&records);
+    let outlier = result
+        .outliers
+        .iter()
+        .find(|o| o.value == 98765.0)
+        .expect("98765 should be an outlier");
+    let source = &outlier.record.as_ref().unwrap().source;
+    assert_eq!(source.line, Some(18));
+    assert_eq!(source.column, Some(2));
+
+    let ensemble = detect_outliers_ensemble(&numbers)
+        .unwrap()
+        .with_records(&records);
+    for outlier in &ensemble.outliers {
+        assert_eq!(outlier.record.as_ref().unwrap().value, outlier.value);
+    }
+}
+
+#[test]
+fn test_min_sample_size_option() {
+    let data = TestFixtures::benford_compliant_data();
+    let options = options_with(LawkitSpecificOptions {
+        min_sample_size: Some(1000),
+        ..Default::default()
+    });
+
+    for subcommand in ["benford", "pareto", "zipf", "normal"] {
+        assert!(
+            law(subcommand, &data, None).is_ok(),
+            "{subcommand} should succeed without a minimum"
+        );
+        assert!(
+            law(subcommand, &data, Some(&options)).is_err(),
+            "{subcommand} should reject a sample below the minimum"
+        );
+    }
+
+    let results = law(
+        "validate",
+        &json!([1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11]),
+        None,
+    )
+    .unwrap();
+    let LawkitResult::ValidationResult(_, default_validation) = &results[0] else {
+        panic!("Expected ValidationResult");
+    };
+    assert!(default_validation.validation_passed);
+
+    let results = law(
+        "validate",
+        &json!([1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11]),
+        Some(&options),
+    )
+    .unwrap();
+    let LawkitResult::ValidationResult(_, strict_validation) = &results[0] else {
+        panic!("Expected ValidationResult");
+    };
+    assert!(!strict_validation.validation_passed);
+}
+
+#[test]
+fn test_analysis_threshold_option() {
+    let data = json!([5, 50, 500, 5000, 50000, 15, 150, 1500, 15000, 25]);
+    let options = options_with(LawkitSpecificOptions {
+        analysis_threshold: Some(100.0),
+        ..Default::default()
+    });
+
+    let all = law("benford", &data, None).unwrap();
+    let filtered = law("benford", &data, Some(&options)).unwrap();
+    assert_eq!(benford_data(&all).numbers_analyzed, 10);
+    assert_eq!(benford_data(&filtered).numbers_analyzed, 6);
+}
+
+#[test]
+fn test_outlier_detection_option() {
+    let data = json!([10, 11, 12, 10, 11, 12, 10, 11, 12, 10, 500]);
+
+    let findings =
+        |options: Option<&LawkitOptions>| match &law("diagnose", &data, options).unwrap()[0] {
+            LawkitResult::DiagnosticResult(_, diagnostic_data) => diagnostic_data.findings.clone(),
+            _ => panic!("Expected DiagnosticResult"),
+        };
+
+    let disabled = options_with(LawkitSpecificOptions {
+        enable_outlier_detection: Some(false),
+        ..Default::default()
+    });
+    assert!(findings(None).iter().any(|f| f.contains("outliers")));
+    assert!(!findings(Some(&disabled))
+        .iter()
+        .any(|f| f.contains("outliers")));
+
+    let enabled = options_with(LawkitSpecificOptions {
+        enable_outlier_detection: Some(true),
+        ..Default::default()
+    });
+    let results = law("normal", &data, Some(&enabled)).unwrap();
+    let LawkitResult::NormalAnalysis(_, normal_data) = &results[0] else {
+        panic!("Expected NormalAnalysis");
+    };
+    assert_eq!(normal_data.outliers_iqr.len(), 1);
+    assert_eq!(normal_data.outliers_iqr[0].1, 500.0);
+
+    let results = law("normal", &data, Some(&disabled)).unwrap();
+    let LawkitResult::NormalAnalysis(_, normal_data) = &results[0] else {
+        panic!("Expected NormalAnalysis");
+    };
+    assert!(normal_data.outliers_z_score.is_empty());
+    assert!(normal_data.outliers_modified_z.is_empty());
+    assert!(normal_data.outliers_iqr.is_empty());
+}
+
+#[test]
+fn test_benford_digit_tests_option() {
+    let data = TestFixtures::benford_compliant_data();
+    let default_results = law("benford", &data, None).unwrap();
+    assert!(benford_data(&default_results).digit_tests.is_empty());
+
+    let options = options_with(LawkitSpecificOptions {
+        benford_digits: Some("second,first-two,last-two".to_string()),
+        ..Default::default()
+    });
+    let results = law("benford", &data, Some(&options)).unwrap();
+    let benford = benford_data(&results);
+
+    let tests: Vec<DigitTest> = benford.digit_tests.iter().map(|t| t.test).collect();
+    assert_eq!(
+        tests,
+        vec![DigitTest::Second, DigitTest::FirstTwo, DigitTest::LastTwo]
+    );
+    for test in &benford.digit_tests {
+        assert_eq!(test.observed_distribution.len(), test.test.bin_count());
+        assert_eq!(test.expected_distribution.len(), test.test.bin_count());
+        assert!(test.p_value >= 0.0 && test.p_value <= 1.0);
+    }
+    // Headline first-digit numbers are unchanged
+    assert_eq!(
+        benford.chi_square,
+        benford_data(&default_results).chi_square
+    );
+    // The overall risk is the most severe requested test
+    let worst = benford.digit_tests.iter().map(|t| t.risk_level).max();
+    assert_eq!(Some(benford.risk_level), worst);
+}
+
+#[test]
+fn test_unsupported_benford_options_are_rejected() {
+    let data = TestFixtures::benford_compliant_data();
+
+    let third = options_with(LawkitSpecificOptions {
+        benford_digits: Some("third".to_string()),
+        ..Default::default()
+    });
+    assert!(law("benford", &data, Some(&third)).is_err());
+
+    let base16 = options_with(LawkitSpecificOptions {
+        benford_base: Some(16),
+        ..Default::default()
+    });
+    assert!(law("benford", &data, Some(&base16)).is_err());
+}
+
+#[test]
+fn test_pareto_ratio_option() {
+    let data = TestFixtures::pareto_compliant_data();
+    let pareto = |options: Option<&LawkitOptions>| match &law("pareto", &data, options).unwrap()[0]
+    {
+        LawkitResult::ParetoAnalysis(_, pareto_data) => (
+            pareto_data
+                .custom_percentiles
+                .as_ref()
+                .map_or(pareto_data.top_20_percent_share, |p| p[0].1),
+            pareto_data.numbers_analyzed,
+        ),
+        _ => panic!("Expected ParetoAnalysis result"),
+    };
+
+    let (default_share, default_items) = pareto(None);
+    let (top_10_share, _) = pareto(Some(&options_with(LawkitSpecificOptions {
+        pareto_ratio: Some(0.9),
+        ..Default::default()
+    })));
+    assert!(top_10_share < default_share);
+
+    let (_, limited_items) = pareto(Some(&options_with(LawkitSpecificOptions {
+        pareto_category_limit: Some(5),
+        ..Default::default()
+    })));
+    assert!(default_items > 5);
+    assert_eq!(limited_items, 5);
+}
+
+#[test]
+fn test_zipf_options() {
+    let data = json!([1, 1, 1, 1, 1, 1, 2, 2, 2, 2, 2, 3, 3, 3, 3, 4, 4, 4, 5, 5, 6, 7, 8]);
+    let zipf_items =
+        |options: Option<&LawkitOptions>| match &law("zipf", &data, options).unwrap()[0] {
+            LawkitResult::ZipfAnalysis(_, zipf_data) => zipf_data.unique_items,
+            _ => panic!("Expected ZipfAnalysis result"),
+        };
+
+    assert_eq!(zipf_items(None), 8);
+    assert_eq!(
+        zipf_items(Some(&options_with(LawkitSpecificOptions {
+            zipf_rank_limit: Some(6),
+            ..Default::default()
+        }))),
+        6
+    );
+    assert_eq!(
+        zipf_items(Some(&options_with(LawkitSpecificOptions {
+            zipf_frequency_cutoff: Some(2.0),
+            ..Default::default()
+        }))),
+        5
+    );
+}
+
+#[test]
+fn test_numeral_options() {
+    let data = json!(["１２３", "四五六", "789"]);
+    let count = |options: Option<&LawkitOptions>| {
+        benford_data(&law("benford", &data, options).unwrap()).numbers_analyzed
+    };
+
+    assert_eq!(count(None), 1);
+    assert_eq!(
+        count(Some(&options_with(LawkitSpecificOptions {
+            enable_japanese_numerals: Some(true),
+            ..Default::default()
+        }))),
+        3
+    );
+
+    let hindi = json!(["१२३", "४५६", "789"]);
+    let options = options_with(LawkitSpecificOptions {
+        enable_international_numerals: Some(true),
+        ..Default::default()
+    });
+    let results = law("benford", &hindi, Some(&options)).unwrap();
+    assert_eq!(benford_data(&results).numbers_analyzed, 3);
+}
+
+#[test]
+fn test_performance_options_preserve_results() {
+    let data = TestFixtures::benford_compliant_data();
+    let baseline = law("benford", &data, None).unwrap();
+
+    let parallel = LawkitOptions {
+        batch_size: Some(7),
+        lawkit_options: Some(LawkitSpecificOptions {
+            enable_parallel_processing: Some(true),
+            ..Default::default()
+        }),
+        ..Default::default()
+    };
+    assert_eq!(law("benford", &data, Some(&parallel)).unwrap(), baseline);
+
+    let batched = LawkitOptions {
+        use_memory_optimization: Some(true),
+        batch_size: Some(5),
+        ..Default::default()
+    };
+    assert_eq!(law("benford", &data, Some(&batched)).unwrap(), baseline);
+}
+
+#[test]
+fn test_memory_limit_option() {
+    let data = json!((1..=200_000).collect::<Vec<u32>>());
+    let options = options_with(LawkitSpecificOptions {
+        memory_limit_mb: Some(1),
+        ..Default::default()
+    });
+
+    assert!(law("benford", &data, None).is_ok());
+    assert!(law("benford", &data, Some(&options)).is_err());
+}
+
+#[test]
+fn test_key_and_path_selection_options() {
+    let data = TestFixtures::benford_compliant_data();
+    let all = benford_data(&law("benford", &data, None).unwrap()).numbers_analyzed;
+
+    let ignore = LawkitOptions {
+        ignore_keys_regex: Some(regex::Regex::new("^invoice").unwrap()),
+        ..Default::default()
+    };
+    let ignored = benford_data(&law("benford", &data, Some(&ignore)).unwrap()).numbers_analyzed;
+
+    let path = LawkitOptions {
+        path_filter: Some("invoice_amounts".to_string()),
+        ..Default::default()
+    };
+    let selected = benford_data(&law("benford", &data, Some(&path)).unwrap()).numbers_analyzed;
+
+    assert_eq!(selected, 27);
+    assert_eq!(ignored + selected, all);
+
+    let nested = json!({"invoices": [{"id": 1, "total": 120.5}, {"id": 2, "total": 340.0}]});
+    let path = LawkitOptions {
+        path_filter: Some("invoices.total".to_string()),
+        ..Default::default()
+    };
+    let results = law("diagnose", &nested, Some(&path)).unwrap();
+    let LawkitResult::DiagnosticResult(_, diagnostic_data) = &results[0] else {
+        panic!("Expected DiagnosticResult");
+    };
+    assert_eq!(diagnostic_data.findings[0], "Sample size: 2");
+}
+
+#[test]
+fn test_json_path_and_include_keys_options() {
+    let payload = json!({
+        "data": {
+            "invoices": [
+                {"id": 9001, "total": 120.5, "lines": [{"qty": 3, "amount": 40.5}]},
+                {"id": 9002, "total": "340", "lines": [{"qty": 1, "amount": 340}]}
+            ]
+        },
+        "meta": {"page": 1, "total": 2}
+    });
+    let sample_size =
+        |options: &LawkitOptions| match &law("diagnose", &payload, Some(options)).unwrap()[0] {
+            LawkitResult::DiagnosticResult(_, data) => data.findings[0].clone(),
+            _ => panic!("Expected DiagnosticResult"),
+        };
+
+    let totals = LawkitOptions {
+        path_filter: Some("$.data.invoices[*].total".to_string()),
+        ..Default::default()
+    };
+    assert_eq!(sample_size(&totals), "Sample size: 2");
+
+    let monetary = LawkitOptions {
+        include_keys_regex: Some(regex::Regex::new("^(total|amount)$").unwrap()),
+        ignore_keys_regex: Some(regex::Regex::new("^meta$").unwrap()),
+        ..Default::default()
+    };
+    assert_eq!(sample_size(&monetary), "Sample size: 4");
+
+    let descendant = LawkitOptions {
+        path_filter: Some("$..amount".to_string()),
+        ..Default::default()
+    };
+    assert_eq!(sample_size(&descendant), "Sample size: 2");
+
+    let invalid = LawkitOptions {
+        path_filter: Some("$.data[".to_string()),
+        ..Default::default()
+    };
+    assert!(law("benford", &payload, Some(&invalid)).is_err());
+}
+
+#[test]
+fn test_reporting_options() {
+    let data = TestFixtures::integration_analysis_data();
+
+    let recommendations = |options: Option<&LawkitOptions>| {
+        law("analyze", &data, options)
+            .unwrap()
+            .into_iter()
+            .find_map(|r| match r {
+                LawkitResult::IntegrationAnalysis(_, integration_data) => {
+                    Some(integration_data.recommendations)
+                }
+                _ => None,
+            })
+            .unwrap()
+    };
+    let hidden = LawkitOptions {
+        show_recommendations: Some(false),
+        ..Default::default()
+    };
+    assert!(!recommendations(None).is_empty());
+    assert!(recommendations(Some(&hidden)).is_empty());
+
+    let findings =
+        |options: Option<&LawkitOptions>| match &law("diagnose", &data, options).unwrap()[0] {
+            LawkitResult::DiagnosticResult(_, diagnostic_data) => diagnostic_data.findings.len(),
+            _ => panic!("Expected DiagnosticResult"),
+        };
+    let details = LawkitOptions {
+        show_details: Some(true),
+        ..Default::default()
+    };
+    assert!(findings(Some(&details)) > findings(None));
+}
+
 #[test]
 fn test_generation_options() {
     let config = json!({
@@ -454,6 +1266,29 @@ fn test_generation_options() {
     }
 }
 
+#[test]
+fn test_generation_options_fill_missing_config() {
+    let lawkit_options = LawkitSpecificOptions {
+        generate_count: Some(250),
+        generate_range_min: Some(10.0),
+        generate_range_max: Some(20.0),
+        generate_seed: Some(7),
+        ..Default::default()
+    };
+    let options = options_with(lawkit_options);
+
+    let generate =
+        || match &law("generate", &json!({"type": "benford"}), Some(&options)).unwrap()[0] {
+            LawkitResult::GeneratedData(_, generated_info) => generated_info.sample_data.clone(),
+            _ => panic!("Expected GeneratedData result"),
+        };
+
+    let first = generate();
+    assert_eq!(first.len(), 250);
+    assert!(first.iter().all(|&x| (10.0..=20.0).contains(&x)));
+    assert_eq!(first, generate());
+}
+
 // ============================================================================
 // OUTPUT FORMAT TESTS
 // ============================================================================
@@ -539,7 +1374,7 @@ fn test_small_sample_size() {
     assert!(result
         .unwrap_err()
         .to_string()
-        .contains("Insufficient data points"));
+        .contains("Insufficient data"));
 }
 
 #[test]
@@ -599,10 +1434,11 @@ fn test_comprehensive_analysis_workflow() {
 
 #[test]
 fn test_data_generation_and_analysis_cycle() {
-    // Generate Benford data
+    // Generate Benford data (seeded: a conforming sample still fails at p < 0.05 one run in twenty)
     let config = json!({
         "type": "benford",
-        "count": 100
+        "count": 100,
+        "seed": 42
     });
 
     let generation_results = law("generate", &config, None).unwrap();
@@ -620,8 +1456,8 @@ fn test_data_generation_and_analysis_cycle() {
     match &analysis_results[0] {
         LawkitResult::BenfordAnalysis(_, benford_data) => {
             // Generated Benford data should show low risk
-            assert!(benford_data.risk_level == "LOW" || benford_data.risk_level == "MEDIUM");
-            assert_eq!(benford_data.total_numbers, 100);
+            assert!(benford_data.risk_level <= RiskLevel::Medium);
+            assert_eq!(benford_data.numbers_analyzed, 100);
         }
         _ => panic!("Expected BenfordAnalysis result"),
     }
@@ -645,7 +1481,7 @@ fn test_large_dataset_performance() {
 
     match &results[0] {
         LawkitResult::BenfordAnalysis(_, benford_data) => {
-            assert_eq!(benford_data.total_numbers, 10000);
+            assert_eq!(benford_data.numbers_analyzed, 10000);
         }
         _ => panic!("Expected BenfordAnalysis result"),
     }
@@ -704,6 +1540,23 @@ fn test_parse_yaml() {
     assert_eq!(result["numbers"].as_array().unwrap().len(), 3);
 }
 
+#[test]
+fn test_select_values() {
+    let document =
+        parse_yaml("invoices:\n  - id: 1\n    total: 120.5\n  - id: 2\n    total: '340'\n")
+            .unwrap();
+    let selection = lawkit_core::common::input::KeySelection {
+        path: Some("$.invoices[*].total".parse().unwrap()),
+        ..Default::default()
+    };
+
+    assert_eq!(select_values(&document, &selection), json!([120.5, "340"]));
+    assert_eq!(
+        select_values(&document, &Default::default()),
+        json!([1, 120.5, 2, "340"])
+    );
+}
+
 #[test]
 fn test_parse_invalid_json() {
     let content = "invalid json {";
@@ -742,10 +1595,48 @@ fn test_format_output_all_formats() {
     }
 }
 
+#[test]
+fn test_law_to_string_applies_output_format() {
+    let data = TestFixtures::benford_compliant_data();
+    let results = law("benf", &data, None).unwrap();
+
+    let options = LawkitOptions {
+        output_format: Some(OutputFormat::Json),
+        ..Default::default()
+    };
+    let output = law_to_string("benf", &data, Some(&options)).unwrap();
+    assert_eq!(output, format_output(&results, OutputFormat::Json).unwrap());
+    assert!(serde_json::from_str::<Value>(&output).unwrap().is_array());
+
+    // Without output_format the lawkit format is used
+    let output = law_to_string("benf", &data, None).unwrap();
+    assert_eq!(
+        output,
+        format_output(&results, OutputFormat::Lawkit).unwrap()
+    );
+}
+
 // ============================================================================
 // EDGE CASES AND BOUNDARY TESTS
 // ============================================================================
 
+#[test]
+fn test_non_finite_strings_are_skipped() {
+    let mut data: Vec<Value> = vec![json!("NaN"), json!("inf"), json!("-infinity")];
+    data.extend((1..=40).map(|n| json!(n * 37)));
+    let data = Value::Array(data);
+
+    for subcommand in ["pareto", "zipf", "benf", "normal", "diagnose"] {
+        let results = law(subcommand, &data, None).unwrap();
+        assert!(!results.is_empty(), "{subcommand}");
+    }
+    let LawkitResult::ParetoAnalysis(_, pareto_data) = &law("pareto", &data, None).unwrap()[0]
+    else {
+        panic!("Expected ParetoAnalysis result");
+    };
+    assert_eq!(pareto_data.numbers_analyzed, 40);
+}
+
 #[test]
 fn test_single_value_data() {
     let single_value = json!([42.0]);
@@ -753,10 +1644,9 @@ fn test_single_value_data() {
     // Most analyses should fail or handle gracefully with single value
     let benford_result = law("benford", &single_value, None);
     // Should work but with limited data
-    if benford_result.is_ok() {
-        let results = benford_result.unwrap();
+    if let Ok(results) = benford_result {
         if let LawkitResult::BenfordAnalysis(_, benford_data) = &results[0] {
-            assert_eq!(benford_data.total_numbers, 1);
+            assert_eq!(benford_data.numbers_analyzed, 1);
         }
     }
 